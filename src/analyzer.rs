@@ -0,0 +1,944 @@
+use crate::badges::StaleBadge;
+use crate::ci::CiAdvice;
+use crate::codeowners::Problem;
+use crate::custom::Violation;
+use crate::deps::{DependencyProblem, ProblemKind};
+use crate::detector::Finding;
+use crate::docker::ContainerProblem;
+use crate::editorconfig::{self, Rule};
+use crate::freshness;
+use crate::git;
+use crate::links::DeadLink;
+use crate::naming;
+use crate::packages::ProblemKind as PackageKind;
+use crate::readme;
+use crate::scanner::{Issue, PotentialSecret, ScanResult};
+use crate::sops::UnencryptedFile;
+use crate::templates;
+use crate::terraform::TerraformProblem;
+
+/// Build the scan's issues from its findings and store them on `result`:
+/// what's reported, what suppressions silence, and what the baseline hides.
+pub fn analyze(result: &mut ScanResult) {
+    let (active, suppressed) = partition_unbaselined(result);
+    let (issues, baselined) = match &result.baseline {
+        Some(baseline) => baseline.partition(active),
+        None => (active, Vec::new()),
+    };
+    result.issues = issues;
+    result.suppressed = suppressed;
+    result.baselined = baselined;
+}
+
+/// Issues a single finding raises on its own, reported as soon as its
+/// detector finishes instead of after the whole scan. Findings whose issues
+/// depend on the rest of the scan (absences, counts, cross-checks) raise
+/// none here, and neither does anything the baseline may hide or blame will
+/// annotate: `analyze` reports those at the end.
+pub fn streamed(result: &ScanResult, finding: &Finding) -> Vec<Issue> {
+    if result.config.blame {
+        return Vec::new();
+    }
+    let mut issues = match finding {
+        Finding::Secret(s) => vec![secret_issue(s)],
+        Finding::Custom(v) => vec![custom_issue(v)],
+        Finding::DependencyProblem(p) => vec![dependency_issue(p)],
+        Finding::DeadLink(l) => vec![dead_link_issue(l)],
+        Finding::StaleBadge(b) => vec![stale_badge_issue(b)],
+        Finding::ContainerProblem(p) => vec![container_issue(p)],
+        Finding::UnencryptedFile(f) => vec![unencrypted_issue(f)],
+        Finding::CodeownersProblem(p) => vec![codeowners_problem_issue(p)],
+        Finding::TemplateProblem(p) => vec![template_issue(p)],
+        _ => return Vec::new(),
+    };
+    apply_config(&mut issues, result);
+    issues.retain(|i| {
+        result.suppressions.find(i).is_none()
+            && !result.baseline.as_ref().is_some_and(|b| b.knows(i))
+    });
+    issues
+}
+
+fn partition_unbaselined(result: &ScanResult) -> (Vec<Issue>, Vec<(Issue, String)>) {
+    let mut active = Vec::new();
+    let mut suppressed = Vec::new();
+    for issue in collect_unsuppressed(result) {
+        match result.suppressions.find(&issue) {
+            Some(rule) => {
+                let origin = rule.origin.clone();
+                suppressed.push((issue, origin));
+            }
+            None => active.push(issue),
+        }
+    }
+    (active, suppressed)
+}
+
+/// Every issue after config is applied, before suppressions.
+fn collect_unsuppressed(result: &ScanResult) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    if !result.has_git {
+        issues.push(Issue::error(
+            "not-git-repo",
+            "Not a Git repository (no .git directory)",
+        ));
+    }
+    if !result.has_readme {
+        issues.push(Issue::error("missing-readme", "Missing README.md"));
+    }
+    if !result.has_license {
+        issues.push(Issue::error("missing-license", "Missing LICENSE file"));
+    }
+    for l in &result.licenses {
+        match (&l.spdx, l.truncated) {
+            (None, _) => issues.push(
+                Issue::warning(
+                    "license-unrecognized",
+                    format!(
+                    "Unrecognized license text in {}: no known license or SPDX-License-Identifier",
+                    l.file
+                ),
+                )
+                .at(&l.file, None),
+            ),
+            (Some(spdx), true) => issues.push(
+                Issue::warning(
+                    "license-truncated",
+                    format!(
+                        "Truncated license text in {}: starts like {} but is incomplete",
+                        l.file, spdx
+                    ),
+                )
+                .at(&l.file, None),
+            ),
+            (Some(_), false) => {}
+        }
+    }
+    if !result.has_gitignore {
+        issues.push(Issue::warning("missing-gitignore", "Missing .gitignore"));
+    }
+    if !result.has_changelog {
+        issues.push(Issue::warning("missing-changelog", "Missing CHANGELOG"));
+    }
+    if !result.has_contributing {
+        issues.push(Issue::warning(
+            "missing-contributing",
+            "Missing CONTRIBUTING.md",
+        ));
+    }
+    if !result.has_code_of_conduct {
+        issues.push(Issue::warning(
+            "missing-code-of-conduct",
+            "Missing CODE_OF_CONDUCT.md",
+        ));
+    }
+    if !result.has_security {
+        issues.push(Issue::warning("missing-security", "Missing SECURITY.md"));
+    }
+    if !result.has_editorconfig {
+        issues.push(Issue::warning(
+            "missing-editorconfig",
+            "Missing .editorconfig",
+        ));
+    }
+    if result.ci_systems.is_empty() {
+        issues.push(Issue::warning("no-ci", "No CI/CD configuration detected"));
+    }
+    if result.languages.is_empty() {
+        issues.push(Issue::info("no-source", "No source code detected"));
+    }
+    if result.build_systems.is_empty() && result.has_source_code() {
+        issues.push(Issue::info("no-build-system", "No build system detected"));
+    }
+    if !result.has_tests && result.has_source_code() {
+        issues.push(Issue::info("no-tests", "No test directory detected"));
+    }
+    if result.lacks_coverage() {
+        issues.push(Issue::warning(
+            "no-coverage",
+            "Tests present but no coverage measurement configured",
+        ));
+    }
+    if result.lacks_dependency_updates() {
+        issues.push(Issue::warning(
+            "no-dependency-updates",
+            "Dependencies declared but no Dependabot or Renovate config",
+        ));
+    }
+    if let Some(ref h) = result.git_history {
+        let msg = format!(
+            "No commits in {} days (last: {})",
+            h.days_since_last_commit, h.last_commit_date
+        );
+        if h.days_since_last_commit > git::STALE_DAYS {
+            issues.push(Issue::warning("stale-repo", msg));
+        } else if h.days_since_last_commit > git::INACTIVE_DAYS {
+            issues.push(Issue::info("stale-repo", msg));
+        }
+    }
+    // Check for missing lock files when manifest exists
+    let lock_checks: &[(&str, &[&str], &str)] = &[
+        (
+            "package.json",
+            &["package-lock.json", "yarn.lock", "pnpm-lock.yaml"],
+            "npm/yarn/pnpm lock file",
+        ),
+        ("Pipfile", &["Pipfile.lock"], "Pipfile.lock"),
+        ("Gemfile", &["Gemfile.lock"], "Gemfile.lock"),
+        ("composer.json", &["composer.lock"], "composer.lock"),
+        ("go.mod", &["go.sum"], "go.sum"),
+        ("mix.exs", &["mix.lock"], "mix.lock"),
+    ];
+    for (manifest, locks, desc) in lock_checks {
+        if result.dependency_files.iter().any(|f| f == *manifest) {
+            let has_lock = locks
+                .iter()
+                .any(|l| result.dependency_files.iter().any(|f| f == *l));
+            if !has_lock {
+                issues.push(Issue::warning(
+                    "missing-lock-file",
+                    format!(
+                        "Missing {}: found {} but no lock file (reproducible builds)",
+                        desc, manifest
+                    ),
+                ));
+            }
+        }
+    }
+    // Cargo.lock check: only warn for binaries, not libraries
+    if result.dependency_files.iter().any(|f| f == "Cargo.toml") {
+        let has_lock = result.dependency_files.iter().any(|f| f == "Cargo.lock");
+        if !has_lock {
+            // Info-level since libraries often don't commit Cargo.lock
+            issues.push(Issue::info(
+                "missing-lock-file",
+                "No Cargo.lock found (consider committing for binary/app projects)",
+            ));
+        }
+    }
+    let max_age = result.config.lockfile_max_age_days();
+    for lock in &result.lockfiles {
+        if max_age == 0 || lock.days_since_update <= max_age {
+            continue;
+        }
+        let mut msg = format!(
+            "Stale lock file: {} untouched for {} (policy: {})",
+            lock.lockfile,
+            freshness::humanize_days(lock.days_since_update),
+            freshness::humanize_days(max_age)
+        );
+        if let Some(manifest) = lock
+            .manifest
+            .as_ref()
+            .filter(|_| lock.manifest_lead_days > 0)
+        {
+            msg.push_str(&format!(
+                "; {} changed {} after it",
+                manifest,
+                freshness::humanize_days(lock.manifest_lead_days)
+            ));
+        }
+        issues.push(Issue::warning("stale-lockfile", msg).at(&lock.lockfile, None));
+    }
+    for advice in &result.ci_advice {
+        match advice {
+            CiAdvice::MissingCache { file, build_system }
+                if result.build_systems.contains(build_system) =>
+            {
+                issues.push(
+                    Issue::info(
+                        "ci-missing-cache",
+                        format!(
+                            "No dependency cache for {} in {}",
+                            build_system.name(),
+                            file
+                        ),
+                    )
+                    .at(file, None),
+                );
+            }
+            CiAdvice::MissingCache { .. } => {}
+            CiAdvice::ArtifactRetention { file } => issues.push(
+                Issue::info(
+                    "ci-artifact-retention",
+                    format!("Artifacts uploaded without a retention period in {}", file),
+                )
+                .at(file, None),
+            ),
+        }
+    }
+    if let Some(ref h) = result.ci_health {
+        if h.is_red() {
+            issues.push(Issue::warning(
+                "ci-failing",
+                format!(
+                    "CI is failing on {} (latest run: {})",
+                    h.branch, h.last_conclusion
+                ),
+            ));
+        }
+        if h.is_flaky() {
+            issues.push(Issue::warning(
+                "ci-flaky",
+                format!(
+                    "Flaky CI on {}: {}% of {} runs passed, {} needed a re-run",
+                    h.branch,
+                    h.pass_rate(),
+                    h.runs,
+                    h.reruns
+                ),
+            ));
+        }
+    }
+    if let Some(ref r) = result.remote {
+        if r.archived {
+            issues.push(Issue::warning(
+                "repo-archived",
+                format!("Repository is archived on {} ({})", r.host_name(), r.slug),
+            ));
+        }
+        match r.protected {
+            Some(false) => issues.push(Issue::warning(
+                "branch-unprotected",
+                format!(
+                    "Default branch {} is not protected on {}",
+                    r.default_branch,
+                    r.host_name()
+                ),
+            )),
+            // Unknown when the token can't read approval settings
+            Some(true) if r.required_reviews == Some(0) => issues.push(Issue::info(
+                "no-required-reviews",
+                format!(
+                    "{} to {} merge without a required review",
+                    r.request_name(),
+                    r.default_branch
+                ),
+            )),
+            _ => {}
+        }
+        if let Some(open) = r.dependabot_alerts.filter(|n| *n > 0) {
+            let severe = r.severe_alerts.unwrap_or(0);
+            let message = format!(
+                "Open Dependabot alerts: {} ({} critical or high)",
+                open, severe
+            );
+            issues.push(if severe > 0 {
+                Issue::error("dependabot-alerts", message)
+            } else {
+                Issue::warning("dependabot-alerts", message)
+            });
+        }
+        if r.host == "github" && result.has_license && r.license.is_none() {
+            issues.push(Issue::info("license-undetected", 
+                "GitHub doesn't recognize the license; its license detection expects the unmodified text",
+            ));
+        }
+        if r.issue_templates == Some(0) {
+            issues.push(Issue::info(
+                "missing-gitlab-templates",
+                "No GitLab issue templates (.gitlab/issue_templates/ or group templates)",
+            ));
+        }
+        if r.merge_request_templates == Some(0) {
+            issues.push(Issue::info("missing-gitlab-templates", 
+                "No GitLab merge request templates (.gitlab/merge_request_templates/ or group templates)",
+            ));
+        }
+    }
+    if let Some(ref q) = result.readme {
+        if !q.missing_sections.is_empty() {
+            issues.push(
+                Issue::warning(
+                    "readme-incomplete",
+                    format!(
+                        "README is missing sections: {} (quality {}/100)",
+                        q.missing_sections.join(", "),
+                        q.score
+                    ),
+                )
+                .at(&q.file, None),
+            );
+        }
+        if q.is_short() {
+            issues.push(
+                Issue::warning(
+                    "readme-short",
+                    format!(
+                        "README is short: {} words (aim for at least {})",
+                        q.words,
+                        readme::MIN_WORDS
+                    ),
+                )
+                .at(&q.file, None),
+            );
+        }
+        for l in &q.broken_links {
+            issues.push(
+                Issue::warning(
+                    "readme-broken-link",
+                    format!("Broken link in {} at line {}: {}", q.file, l.line, l.target),
+                )
+                .at(&q.file, Some(l.line)),
+            );
+        }
+        for p in &q.placeholders {
+            issues.push(
+                Issue::warning(
+                    "readme-placeholder",
+                    format!("Placeholder in {} at line {}: {}", q.file, p.line, p.text),
+                )
+                .at(&q.file, Some(p.line)),
+            );
+        }
+        for m in &q.install_mismatches {
+            issues.push(
+                Issue::warning(
+                    "readme-install-mismatch",
+                    format!(
+                        "README install mismatch in {} at line {}: `{}` {}",
+                        q.file, m.line, m.command, m.reason
+                    ),
+                )
+                .at(&q.file, Some(m.line)),
+            );
+        }
+    }
+    if naming::diverge(&result.project_names) {
+        issues.push(Issue::warning(
+            "name-mismatch",
+            format!(
+                "Project name differs across sources: {}",
+                naming::describe(&result.project_names)
+            ),
+        ));
+    }
+    issues.extend(result.dependency_problems.iter().map(dependency_issue));
+    issues.extend(result.dead_links.iter().map(dead_link_issue));
+    for w in &result.workflows {
+        if w.write_all {
+            let reason = if w.permissions == "default" && w.job_permissions.is_empty() {
+                "no permissions block, so the repository default applies".to_string()
+            } else {
+                format!("permissions: {}", w.permissions_summary())
+            };
+            issues.push(
+                Issue::warning(
+                    "workflow-write-all",
+                    format!(
+                        "Workflow token can write everything in {}: {}",
+                        w.file, reason
+                    ),
+                )
+                .at(&w.file, w.write_all_line),
+            );
+        }
+        if w.push_all_branches && !w.publishes.is_empty() {
+            issues.push(
+                Issue::warning(
+                    "ci-publish-on-push",
+                    format!(
+                        "Workflow publishes on push to every branch in {}: {}",
+                        w.file,
+                        w.publishes.join(", ")
+                    ),
+                )
+                .at(&w.file, w.push_line),
+            );
+        }
+        for a in &w.unpinned_actions {
+            issues.push(
+                Issue::info(
+                    "workflow-unpinned-action",
+                    format!(
+                        "Unpinned action in {} at line {}: {} (not a commit SHA)",
+                        w.file, a.line, a.action
+                    ),
+                )
+                .at(&w.file, Some(a.line)),
+            );
+        }
+    }
+    issues.extend(result.stale_badges.iter().map(stale_badge_issue));
+    for k in &result.k8s_secrets {
+        let name = k
+            .name
+            .as_ref()
+            .map(|n| format!(" \"{}\"", n))
+            .unwrap_or_default();
+        issues.push(
+            Issue::warning(
+                "k8s-secret-manifest",
+                format!(
+                    "Kubernetes Secret{} with {} committed value(s) in {} at line {}",
+                    name,
+                    k.keys.len(),
+                    k.file,
+                    k.line
+                ),
+            )
+            .at(&k.file, Some(k.line)),
+        );
+    }
+    for w in &result.unbounded_workloads {
+        let name = w
+            .name
+            .as_ref()
+            .map(|n| format!(" \"{}\"", n))
+            .unwrap_or_default();
+        issues.push(
+            Issue::warning(
+                "k8s-missing-limits",
+                format!(
+                    "No resource limits for {} in {}{} ({} at line {})",
+                    w.containers.join(", "),
+                    w.kind,
+                    name,
+                    w.file,
+                    w.line
+                ),
+            )
+            .at(&w.file, Some(w.line)),
+        );
+    }
+    for c in result.helm_charts.iter().filter(|c| !c.has_schema) {
+        let file = if c.dir == "." {
+            "Chart.yaml".to_string()
+        } else {
+            format!("{}/Chart.yaml", c.dir)
+        };
+        issues.push(
+            Issue::warning(
+                "helm-missing-schema",
+                format!(
+                    "Helm chart {} has no values.schema.json to validate its values",
+                    c.name.as_deref().unwrap_or(&c.dir)
+                ),
+            )
+            .at(&file, None),
+        );
+    }
+    issues.extend(editorconfig_issues(result));
+    for p in &result.terraform_problems {
+        let issue = match p {
+            TerraformProblem::StateFile { file } => Issue::error("terraform-state-committed", format!(
+                "Terraform state committed: {} holds resource attributes, secrets included, in plaintext",
+                file
+            ))
+            .at(file, None),
+            TerraformProblem::MissingLockFile { dir } => Issue::warning("terraform-missing-lock", format!(
+                "No .terraform.lock.hcl in {}: provider versions and checksums aren't locked",
+                dir
+            )),
+            TerraformProblem::UnpinnedProviders { dir, providers } => Issue::warning("terraform-unpinned-provider", format!(
+                "Unpinned Terraform provider(s) in {}: {}",
+                dir,
+                providers.join(", ")
+            )),
+            TerraformProblem::SecretInTfvars { file, line, key } => Issue::error("terraform-tfvars-secret", format!(
+                "Terraform variable {} looks like a secret in {} at line {}",
+                key, file, line
+            ))
+            .at(file, Some(*line)),
+            TerraformProblem::NoFmtCheck => {
+                Issue::info("terraform-no-fmt-check", "Terraform code but no `terraform fmt -check` in CI or pre-commit")
+            }
+        };
+        issues.push(issue);
+    }
+    issues.extend(result.container_problems.iter().map(container_issue));
+    issues.extend(result.unencrypted_files.iter().map(unencrypted_issue));
+    issues.extend(
+        result
+            .codeowners_problems
+            .iter()
+            .map(codeowners_problem_issue),
+    );
+    issues.extend(result.template_problems.iter().map(template_issue));
+    if let Some(ref c) = result.codeowners_coverage {
+        if c.unowned_files > 0 {
+            let dirs: Vec<String> = c
+                .unowned_dirs
+                .iter()
+                .map(|d| match d.path.as_str() {
+                    "." => format!("root ({})", d.files),
+                    dir => format!("{}/ ({})", dir, d.files),
+                })
+                .collect();
+            issues.push(
+                Issue::warning(
+                    "codeowners-unowned",
+                    format!(
+                        "CODEOWNERS leaves {} of {} files ({}%) without an owner: {}",
+                        c.unowned_files,
+                        c.total_files,
+                        c.unowned_percent(),
+                        dirs.join(", ")
+                    ),
+                )
+                .at(&c.file, None),
+            );
+        }
+    }
+    let threshold = result.config.large_file_threshold_mb();
+    for f in &result.large_files {
+        issues.push(
+            Issue::warning(
+                "large-file",
+                format!("Large file detected (>{}MB): {}", threshold, f),
+            )
+            .at(f, None),
+        );
+    }
+    if let Some(first) = result.lfs.untracked.first() {
+        let setup = if result.lfs.tracked.is_empty() {
+            "without Git LFS"
+        } else {
+            "not tracked by Git LFS"
+        };
+        issues.push(Issue::warning(
+            "lfs-recommended",
+            format!(
+                "Large binary assets {}: {} file(s) over {}MB, e.g. {}",
+                setup,
+                result.lfs.untracked.len(),
+                result.config.large_file_threshold_mb(),
+                first
+            ),
+        ));
+    }
+    let endings = &result.line_endings;
+    // Files the .editorconfig check already reports for their endings
+    let inconsistent: Vec<_> = endings
+        .inconsistent
+        .iter()
+        .filter(|f| {
+            !result.editorconfig_violations.iter().any(|v| {
+                v.file == f.file && matches!(v.rule, Rule::MixedLineEndings | Rule::LineEnding)
+            })
+        })
+        .collect();
+    if let Some(first) = inconsistent.first() {
+        let mut kinds: Vec<&str> = Vec::new();
+        for f in &inconsistent {
+            if !kinds.contains(&f.ending.name()) {
+                kinds.push(f.ending.name());
+            }
+        }
+        let fix = if endings.normalized {
+            "run `git add --renormalize .`".to_string()
+        } else {
+            format!("add `{}` to .gitattributes", endings.text_rule())
+        };
+        issues.push(
+            Issue::warning("inconsistent-line-endings", format!(
+                "Inconsistent line endings: {} file(s) use {} endings in an otherwise {} repository, e.g. {}; {}",
+                inconsistent.len(),
+                kinds.join(" or "),
+                endings.dominant().name(),
+                first.file,
+                fix
+            ))
+            .at(&first.file, None),
+        );
+    }
+    if let Some(first) = endings.bom_files.first() {
+        issues.push(
+            Issue::info(
+                "utf8-bom",
+                format!(
+                    "UTF-8 byte order mark in {} file(s), e.g. {}",
+                    endings.bom_files.len(),
+                    first
+                ),
+            )
+            .at(first, None),
+        );
+    }
+    for p in &result.package_problems {
+        let root = p.root_file.as_deref().unwrap_or_default();
+        let issue = match p.kind {
+            PackageKind::MissingReadme => Issue::info(
+                "package-missing-readme",
+                format!(
+                    "Package {} has no README of its own (registries show the package's)",
+                    p.path
+                ),
+            ),
+            PackageKind::MissingLicense => Issue::warning(
+                "package-missing-license",
+                format!(
+                    "Package {} has no LICENSE of its own (the root one isn't published with it)",
+                    p.path
+                ),
+            ),
+            PackageKind::Duplicate => Issue::info(
+                "duplicate-root-file",
+                format!("Duplicate of the root {}: {}", root, p.path),
+            )
+            .at(&p.path, None),
+            PackageKind::Stale => Issue::info(
+                "stale-license-copy",
+                format!(
+                    "Stale copy of the root {}: {} has different text",
+                    root, p.path
+                ),
+            )
+            .at(&p.path, None),
+        };
+        issues.push(issue);
+    }
+    for b in &result.history_blobs {
+        issues.push(Issue::warning(
+            "history-large-file",
+            format!(
+                "Large file in git history ({:.1} MB, {}): {}",
+                b.size as f64 / (1024.0 * 1024.0),
+                if b.path_in_head {
+                    "since replaced"
+                } else {
+                    "since deleted"
+                },
+                b.path
+            ),
+        ));
+    }
+    issues.extend(result.custom_violations.iter().map(custom_issue));
+    issues.extend(result.potential_secrets.iter().map(secret_issue));
+    for issue in issues.iter_mut() {
+        issue.blame = result
+            .attributions
+            .iter()
+            .find(|a| Some(&a.file) == issue.file.as_ref() && a.line == issue.line)
+            .cloned();
+    }
+    apply_config(&mut issues, result);
+    issues
+}
+
+fn dependency_issue(p: &DependencyProblem) -> Issue {
+    let issue = match p.kind {
+        ProblemKind::Wildcard => {
+            let requirement = if p.spec.is_empty() {
+                "unpinned".to_string()
+            } else {
+                format!("\"{}\"", p.spec)
+            };
+            Issue::warning(
+                "dependency-wildcard",
+                format!(
+                    "Wildcard dependency in {}: {} {} accepts any future version",
+                    p.manifest, p.name, requirement
+                ),
+            )
+        }
+        ProblemKind::Git => Issue::warning(
+            "dependency-git",
+            format!(
+                "Git dependency in {}: {} from {} (not a registry release)",
+                p.manifest, p.name, p.spec
+            ),
+        ),
+        ProblemKind::Outdated => Issue::info(
+            "dependency-outdated",
+            format!(
+                "Outdated dependency in {}: {} \"{}\" is behind {}",
+                p.manifest,
+                p.name,
+                p.spec,
+                p.latest.as_deref().unwrap_or("a newer major version")
+            ),
+        ),
+    };
+    issue.at(&p.manifest, Some(p.line))
+}
+
+fn dead_link_issue(l: &DeadLink) -> Issue {
+    Issue::warning(
+        "dead-link",
+        format!(
+            "Dead link in {} at line {}: {} ({})",
+            l.file, l.line, l.target, l.reason
+        ),
+    )
+    .at(&l.file, Some(l.line))
+}
+
+fn stale_badge_issue(b: &StaleBadge) -> Issue {
+    Issue::warning(
+        "stale-badge",
+        format!("Stale badge in {} at line {}: {}", b.file, b.line, b.reason),
+    )
+    .at(&b.file, Some(b.line))
+}
+
+fn unencrypted_issue(f: &UnencryptedFile) -> Issue {
+    Issue::warning(
+        "sops-unencrypted",
+        format!(
+            "Unencrypted file matches .sops.yaml rule \"{}\": {}",
+            f.path_regex, f.file
+        ),
+    )
+    .at(&f.file, None)
+}
+
+fn codeowners_problem_issue(p: &Problem) -> Issue {
+    Issue::warning(
+        "codeowners-invalid",
+        format!(
+            "Bad CODEOWNERS rule in {} at line {}: {}",
+            p.file, p.line, p.message
+        ),
+    )
+    .at(&p.file, Some(p.line))
+}
+
+fn secret_issue(s: &PotentialSecret) -> Issue {
+    Issue::error(
+        &s.rule,
+        match s.key {
+            Some(ref key) => format!(
+                "Potential {} in {} at line {} ({})",
+                s.pattern, s.file, s.line, key
+            ),
+            None => format!("Potential {} in {} at line {}", s.pattern, s.file, s.line),
+        },
+    )
+    .at(&s.file, Some(s.line))
+}
+
+fn container_issue(p: &ContainerProblem) -> Issue {
+    let (issue, file, line) = match p {
+        ContainerProblem::MissingDockerignore { file } => (
+            Issue::warning(
+                "missing-dockerignore",
+                format!(
+                    "No .dockerignore for {}: .git and local files enter the build context",
+                    file
+                ),
+            ),
+            file,
+            None,
+        ),
+        ContainerProblem::LatestTag { file, line, image } => (
+            Issue::warning(
+                "docker-latest-tag",
+                format!("Unpinned image {} in {} at line {}", image, file, line),
+            ),
+            file,
+            Some(*line),
+        ),
+        ContainerProblem::RunsAsRoot { file } => (
+            Issue::warning(
+                "docker-root-user",
+                format!(
+                    "Container runs as root in {}: no non-root USER in the final stage",
+                    file
+                ),
+            ),
+            file,
+            None,
+        ),
+        ContainerProblem::AddInsteadOfCopy { file, line } => (
+            Issue::info(
+                "docker-add-instead-of-copy",
+                format!(
+                    "ADD used for local files in {} at line {} (COPY is safer)",
+                    file, line
+                ),
+            ),
+            file,
+            Some(*line),
+        ),
+        ContainerProblem::SecretInEnv { file, line, key } => (
+            Issue::error(
+                "docker-secret-env",
+                format!("Secret baked into {}: {} at line {}", file, key, line),
+            ),
+            file,
+            Some(*line),
+        ),
+    };
+    issue.at(file, line)
+}
+
+fn template_issue(p: &templates::Problem) -> Issue {
+    let message = match p.line {
+        Some(line) => format!("Broken template {} at line {}: {}", p.file, line, p.message),
+        None => format!("Broken template {}: {}", p.file, p.message),
+    };
+    Issue::warning("template-invalid", message).at(&p.file, p.line)
+}
+
+fn custom_issue(v: &Violation) -> Issue {
+    Issue {
+        message: v.message.clone(),
+        severity: v.severity,
+        file: v.file.clone(),
+        line: v.line,
+        blame: None,
+        check: v.rule.clone(),
+    }
+}
+
+/// Drop disabled checks (and, for a scoped scan, issues outside the change),
+/// then apply severity overrides from the config.
+fn apply_config(issues: &mut Vec<Issue>, result: &ScanResult) {
+    let config = &result.config;
+    // A scoped scan reports only what the change touched
+    if config.changed_files.is_some() {
+        issues.retain(|i| i.file.as_ref().is_some_and(|f| config.in_scope(f)));
+    }
+    issues.retain(|i| !config.is_disabled(&i.check));
+    for issue in issues.iter_mut() {
+        if let Some(severity) = config.severity_for(&issue.check) {
+            issue.severity = severity;
+        }
+    }
+}
+
+/// One info issue per file type breaking `.editorconfig`, counting the
+/// files behind each rule and pointing at the first offender.
+fn editorconfig_issues(result: &ScanResult) -> Vec<Issue> {
+    let mut types: Vec<(String, Vec<&editorconfig::Violation>)> = Vec::new();
+    for v in &result.editorconfig_violations {
+        let kind = editorconfig::file_type(&v.file);
+        match types.iter_mut().find(|(t, _)| *t == kind) {
+            Some((_, list)) => list.push(v),
+            None => types.push((kind, vec![v])),
+        }
+    }
+    types.sort_by(|a, b| a.0.cmp(&b.0));
+    types
+        .into_iter()
+        .map(|(kind, violations)| {
+            let mut files: Vec<&str> = violations.iter().map(|v| v.file.as_str()).collect();
+            files.dedup();
+            let rules: Vec<String> = [
+                Rule::MixedLineEndings,
+                Rule::LineEnding,
+                Rule::IndentStyle,
+                Rule::FinalNewline,
+                Rule::TrailingWhitespace,
+            ]
+            .iter()
+            .filter_map(|rule| {
+                let count = violations.iter().filter(|v| v.rule == *rule).count();
+                (count > 0).then(|| format!("{} in {}", rule.describe(), count))
+            })
+            .collect();
+            let first = violations[0];
+            Issue::info(
+                "editorconfig-violation",
+                format!(
+                    "EditorConfig not followed in {} {} file(s): {}",
+                    files.len(),
+                    kind,
+                    rules.join(", ")
+                ),
+            )
+            .at(&first.file, first.line)
+        })
+        .collect()
+}
@@ -0,0 +1,428 @@
+use serde::Serialize;
+
+/// A README badge pointing at something that no longer matches the repo.
+#[derive(Debug, Clone, Serialize)]
+pub struct StaleBadge {
+    pub file: String,
+    pub line: usize,
+    pub url: String,
+    pub reason: String,
+}
+
+/// Markers around the score badge `repomedic badge --write` maintains.
+pub const BLOCK_START: &str = "<!-- repomedic-badge:start -->";
+pub const BLOCK_END: &str = "<!-- repomedic-badge:end -->";
+
+/// `readme` with `snippet` between the badge markers: replacing what the
+/// markers held, or as a new block under the first `# ` heading (at the top
+/// when there is none).
+pub fn write_block(readme: &str, snippet: &str) -> String {
+    let block = format!("{}\n{}\n{}", BLOCK_START, snippet, BLOCK_END);
+    if let Some(start) = readme.find(BLOCK_START) {
+        if let Some(end) = readme[start..].find(BLOCK_END) {
+            let end = start + end + BLOCK_END.len();
+            return format!("{}{}{}", &readme[..start], block, &readme[end..]);
+        }
+    }
+    let mut offset = 0;
+    for line in readme.split_inclusive('\n') {
+        offset += line.len();
+        if line.starts_with("# ") {
+            let rest = readme[offset..].trim_start_matches('\n');
+            let newline = if line.ends_with('\n') { "" } else { "\n" };
+            return format!("{}{}\n{}\n\n{}", &readme[..offset], newline, block, rest);
+        }
+    }
+    format!("{}\n\n{}", block, readme)
+}
+
+/// Approximate width in pixels of `text` in 11px Verdana, the font
+/// shields.io badges use.
+fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '!' | '.' | ',' | ':' | ';' | '|' | '\'' => 3.5,
+            'f' | 't' | 'r' | 'I' | ' ' | '(' | ')' | '[' | ']' => 4.5,
+            'm' | 'w' | '%' => 10.5,
+            'M' | 'W' => 11.0,
+            'A'..='Z' => 7.5,
+            '0'..='9' => 7.0,
+            _ => 6.5,
+        })
+        .sum()
+}
+
+/// Hex fill for a shields.io color name.
+fn color_hex(color: &str) -> &'static str {
+    match color {
+        "brightgreen" => "#4c1",
+        "green" => "#97ca00",
+        "yellowgreen" => "#a4a61d",
+        "yellow" => "#dfb317",
+        "orange" => "#fe7d37",
+        "red" => "#e05d44",
+        "blue" => "#007ec6",
+        _ => "#9f9f9f",
+    }
+}
+
+/// A shields.io-style badge drawn locally: `label` on grey, `message` on
+/// `color` (a shields.io color name). `square` drops the rounded corners
+/// and gloss, like the `flat-square` style.
+pub fn svg(label: &str, message: &str, color: &str, square: bool) -> String {
+    const PADDING: f64 = 10.0;
+    let left = (text_width(label) + PADDING).round();
+    let right = (text_width(message) + PADDING).round();
+    let width = left + right;
+    let (radius, gloss) = if square {
+        ("0", String::new())
+    } else {
+        (
+            "3",
+            format!("<rect width=\"{}\" height=\"20\" fill=\"url(#s)\"/>", width),
+        )
+    };
+    let escape = |s: &str| {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    let (label, message) = (escape(label), escape(message));
+    // Text is drawn at 10x and scaled down, as shields.io does, for crisp
+    // rendering; the shadow copy sits one pixel lower
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+<title>{label}: {message}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="{radius}" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{left}" height="20" fill="#555"/><rect x="{left}" width="{right}" height="20" fill="{fill}"/>{gloss}</g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" text-rendering="geometricPrecision" font-size="110">
+<text x="{label_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)">{label}</text><text x="{label_x}" y="140" transform="scale(.1)">{label}</text>
+<text x="{message_x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)">{message}</text><text x="{message_x}" y="140" transform="scale(.1)">{message}</text>
+</g>
+</svg>
+"##,
+        width = width,
+        left = left,
+        right = right,
+        radius = radius,
+        gloss = gloss,
+        fill = color_hex(color),
+        label = label,
+        message = message,
+        label_x = left * 5.0,
+        message_x = (left + right / 2.0) * 10.0,
+    )
+}
+
+/// What a badge is checked against, gathered from the repository.
+#[derive(Debug, Default)]
+pub struct BadgeContext {
+    /// `owner/repo` of the GitHub origin remote.
+    pub slug: Option<String>,
+    /// Workflow file names under `.github/workflows` with their `name:`.
+    pub workflows: Vec<(String, Option<String>)>,
+    pub crate_name: Option<String>,
+    pub npm_name: Option<String>,
+}
+
+/// Image URLs in a Markdown or HTML line: `![alt](url)` and `<img src="url">`.
+pub fn image_urls(line: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = line;
+    while let Some(i) = rest.find("![") {
+        rest = &rest[i + 2..];
+        let Some(j) = rest.find("](") else {
+            break;
+        };
+        let after = &rest[j + 2..];
+        let end = after
+            .find(|c: char| c == ')' || c.is_whitespace())
+            .unwrap_or(after.len());
+        urls.push(&after[..end]);
+        rest = &after[end..];
+    }
+    let mut rest = line;
+    while let Some(i) = rest.find("src=\"") {
+        let after = &rest[i + 5..];
+        let end = after.find('"').unwrap_or(after.len());
+        urls.push(&after[..end]);
+        rest = &after[end..];
+    }
+    urls
+}
+
+/// Check every badge in a README.
+pub fn check(file: &str, content: &str, ctx: &BadgeContext) -> Vec<StaleBadge> {
+    let mut stale = Vec::new();
+    for (n, line) in content.lines().enumerate() {
+        for url in image_urls(line) {
+            if let Some(reason) = check_url(url, ctx) {
+                stale.push(StaleBadge {
+                    file: file.to_string(),
+                    line: n + 1,
+                    url: url.to_string(),
+                    reason,
+                });
+            }
+        }
+    }
+    stale
+}
+
+/// Why a badge URL is stale, if it is.
+fn check_url(url: &str, ctx: &BadgeContext) -> Option<String> {
+    let url = url.split(['?', '#']).next().unwrap_or(url);
+    let path = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let parts: Vec<&str> = path.split('/').filter(|p| !p.is_empty()).collect();
+    match parts.as_slice() {
+        ["travis-ci.org", ..] => {
+            Some("travis-ci.org has shut down; the badge no longer updates".into())
+        }
+        ["github.com", owner, repo, "actions", "workflows", file, "badge.svg"] => {
+            check_slug(owner, repo, ctx).or_else(|| check_workflow_file(file, ctx))
+        }
+        ["github.com", owner, repo, "workflows", name, "badge.svg"] => {
+            check_slug(owner, repo, ctx).or_else(|| check_workflow_name(&decode(name), ctx))
+        }
+        ["img.shields.io", "github", "actions", "workflow", "status", owner, repo, file, ..] => {
+            check_slug(owner, repo, ctx).or_else(|| check_workflow_file(file, ctx))
+        }
+        ["img.shields.io", "github", kind, owner, repo, ..] if is_repo_kind(kind) => {
+            check_slug(owner, repo, ctx)
+        }
+        ["img.shields.io", "github", "v", "release" | "tag", owner, repo, ..] => {
+            check_slug(owner, repo, ctx)
+        }
+        ["codecov.io", "gh" | "github", owner, repo, ..] => check_slug(owner, repo, ctx),
+        ["coveralls.io", "repos", "github", owner, repo, ..] => check_slug(owner, repo, ctx),
+        ["img.shields.io", "crates", _, name, ..] | ["docs.rs", name, "badge.svg"] => {
+            check_package("crates.io", name, ctx.crate_name.as_deref())
+        }
+        ["img.shields.io", "npm", _, scope, name, ..] if scope.starts_with('@') => check_package(
+            "npm",
+            &format!("{}/{}", scope, name),
+            ctx.npm_name.as_deref(),
+        ),
+        ["img.shields.io", "npm", _, name, ..] => {
+            check_package("npm", &decode(name), ctx.npm_name.as_deref())
+        }
+        ["badge.fury.io", "js", name] => check_package("npm", name, ctx.npm_name.as_deref()),
+        _ => None,
+    }
+}
+
+/// shields.io `github/<kind>/<owner>/<repo>` badges.
+fn is_repo_kind(kind: &str) -> bool {
+    matches!(
+        kind,
+        "license"
+            | "stars"
+            | "forks"
+            | "issues"
+            | "issues-pr"
+            | "last-commit"
+            | "contributors"
+            | "release"
+            | "repo-size"
+            | "commit-activity"
+            | "downloads"
+    )
+}
+
+fn check_slug(owner: &str, repo: &str, ctx: &BadgeContext) -> Option<String> {
+    let slug = ctx.slug.as_deref()?;
+    let badge = format!("{}/{}", owner, repo);
+    if badge.eq_ignore_ascii_case(slug) {
+        None
+    } else {
+        Some(format!("points at {}, but origin is {}", badge, slug))
+    }
+}
+
+fn check_workflow_file(file: &str, ctx: &BadgeContext) -> Option<String> {
+    if ctx.workflows.iter().any(|(f, _)| f == file) {
+        None
+    } else {
+        Some(format!(
+            "workflow {} does not exist in .github/workflows",
+            file
+        ))
+    }
+}
+
+fn check_workflow_name(name: &str, ctx: &BadgeContext) -> Option<String> {
+    if ctx
+        .workflows
+        .iter()
+        .any(|(f, n)| n.as_deref() == Some(name) || f.rsplit_once('.').map(|p| p.0) == Some(name))
+    {
+        None
+    } else {
+        Some(format!("no workflow is named \"{}\"", name))
+    }
+}
+
+fn check_package(registry: &str, name: &str, actual: Option<&str>) -> Option<String> {
+    let actual = actual?;
+    let name = [".svg", ".png"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
+    if name == actual {
+        None
+    } else {
+        Some(format!(
+            "{} badge is for {}, but the package is {}",
+            registry, name, actual
+        ))
+    }
+}
+
+/// Decode `%XX` escapes (workflow names and scoped npm packages).
+fn decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        if let (b'%', Some(b)) = (bytes[i], hex) {
+            out.push(b);
+            i += 3;
+            continue;
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// Top-level `name:` of a workflow file.
+pub fn workflow_name(content: &str) -> Option<String> {
+    content.lines().find_map(|l| {
+        let v = l.strip_prefix("name:")?.trim();
+        let v = v.trim_matches(|c| c == '"' || c == '\'');
+        (!v.is_empty()).then(|| v.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, image_urls, svg, workflow_name, write_block, BadgeContext};
+
+    fn context() -> BadgeContext {
+        BadgeContext {
+            slug: Some("acme/tool".to_string()),
+            workflows: vec![("ci.yml".to_string(), Some("CI Build".to_string()))],
+            crate_name: Some("acme-tool".to_string()),
+            npm_name: Some("@acme/tool".to_string()),
+        }
+    }
+
+    fn reasons(readme: &str) -> Vec<(usize, String)> {
+        check("README.md", readme, &context())
+            .into_iter()
+            .map(|b| (b.line, b.reason))
+            .collect()
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            image_urls(
+                "[![CI](https://a/ci.svg)](https://a) ![x](b.png \"t\") <img src=\"c.svg\">"
+            ),
+            ["https://a/ci.svg", "b.png", "c.svg"]
+        );
+    }
+
+    #[test]
+    fn current_badges() {
+        let readme = "\
+![CI](https://github.com/Acme/Tool/actions/workflows/ci.yml/badge.svg?branch=main)
+![CI](https://github.com/acme/tool/workflows/CI%20Build/badge.svg)
+![crate](https://img.shields.io/crates/v/acme-tool.svg)
+![npm](https://img.shields.io/npm/v/@acme/tool)
+![stars](https://img.shields.io/github/stars/acme/tool)
+";
+        assert!(reasons(readme).is_empty(), "{:?}", reasons(readme));
+    }
+
+    #[test]
+    fn stale_badges() {
+        let readme = "\
+![build](https://travis-ci.org/acme/tool.svg)
+![CI](https://github.com/acme/tool/actions/workflows/test.yml/badge.svg)
+![CI](https://github.com/acme/tool/workflows/Tests/badge.svg)
+![cov](https://codecov.io/gh/acme/old-tool/branch/main/graph/badge.svg)
+![docs](https://docs.rs/old_tool/badge.svg)
+![npm](https://badge.fury.io/js/tool.svg)
+";
+        assert_eq!(
+            reasons(readme),
+            [
+                (
+                    1,
+                    "travis-ci.org has shut down; the badge no longer updates".to_string()
+                ),
+                (
+                    2,
+                    "workflow test.yml does not exist in .github/workflows".to_string()
+                ),
+                (3, "no workflow is named \"Tests\"".to_string()),
+                (
+                    4,
+                    "points at acme/old-tool, but origin is acme/tool".to_string()
+                ),
+                (
+                    5,
+                    "crates.io badge is for old_tool, but the package is acme-tool".to_string()
+                ),
+                (
+                    6,
+                    "npm badge is for tool, but the package is @acme/tool".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn badge_block() {
+        let snippet = "![score](.repomedic/badge.svg)";
+        let written = write_block("# Tool\nIntro\n", snippet);
+        assert_eq!(
+            written,
+            "# Tool\n\n<!-- repomedic-badge:start -->\n![score](.repomedic/badge.svg)\n<!-- repomedic-badge:end -->\n\nIntro\n"
+        );
+        let rewritten = write_block(&written, "![score](new.svg)");
+        assert_eq!(rewritten.matches("repomedic-badge:start").count(), 1);
+        assert!(rewritten.contains("![score](new.svg)") && !rewritten.contains("badge.svg)"));
+        assert!(write_block("Intro\n", snippet).starts_with("<!-- repomedic-badge:start -->"));
+    }
+
+    #[test]
+    fn drawn_badges() {
+        let badge = svg("health", "<90>", "brightgreen", true);
+        assert!(badge.contains("aria-label=\"health: &lt;90&gt;\""));
+        assert!(badge.contains("fill=\"#4c1\""));
+        assert!(!badge.contains("url(#s)\"/>"));
+        assert!(svg("a", "b", "purple", false).contains("fill=\"#9f9f9f\""));
+    }
+
+    #[test]
+    fn workflow_names() {
+        assert_eq!(
+            workflow_name("name: \"CI\"\non: push\n").as_deref(),
+            Some("CI")
+        );
+        assert_eq!(
+            workflow_name("on: push\njobs:\n  a:\n    name: Job\n"),
+            None
+        );
+    }
+}
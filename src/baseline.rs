@@ -0,0 +1,240 @@
+use crate::migrate;
+use crate::report::{collect_baselined, collect_issues};
+use crate::scanner::{Issue, ScanError, ScanResult};
+use crate::suppress;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Default file written by `repomedic baseline`.
+pub const DEFAULT_FILE: &str = "repomedic-baseline.json";
+
+/// Schema version written into new baseline files.
+///
+/// 1: secrets fingerprinted as `potential-secret:<file>`.
+/// 2: secrets fingerprinted by their own rule, e.g. `secret-github-token:<file>`.
+pub const VERSION: u32 = 2;
+
+/// Findings accepted as known, so later scans only report new ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub issues: Vec<BaselineEntry>,
+    /// Entries past their `until` date, which no longer hide anything.
+    #[serde(skip)]
+    pub expired: Vec<BaselineEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub fingerprint: String,
+    pub severity: String,
+    pub message: String,
+    /// Last day the entry hides its issue, `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<String>,
+    /// Who owns fixing the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Why the issue is accepted for now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl Baseline {
+    /// Record every current issue (including ones an older baseline hides).
+    ///
+    /// `until`, `owner`, and `reason` carry over from the `previous`
+    /// baseline's entries, expired ones included, so re-recording doesn't
+    /// extend them.
+    pub fn record(result: &ScanResult, previous: Option<&Baseline>) -> Self {
+        let previous: Vec<&BaselineEntry> = previous
+            .iter()
+            .flat_map(|b| b.issues.iter().chain(&b.expired))
+            .collect();
+        let mut issues: Vec<BaselineEntry> = collect_issues(result)
+            .into_iter()
+            .chain(collect_baselined(result))
+            .map(|i| {
+                let fingerprint = fingerprint(&i);
+                let known = previous.iter().find(|e| e.fingerprint == fingerprint);
+                BaselineEntry {
+                    severity: i.severity.name().to_string(),
+                    message: i.message,
+                    until: known.and_then(|e| e.until.clone()),
+                    owner: known.and_then(|e| e.owner.clone()),
+                    reason: known.and_then(|e| e.reason.clone()),
+                    fingerprint,
+                }
+            })
+            .collect();
+        issues.sort_by(|a, b| a.fingerprint.cmp(&b.fingerprint));
+        Self {
+            version: VERSION,
+            issues,
+            expired: Vec::new(),
+        }
+    }
+
+    /// Load a baseline (or exported JSON report), migrating older schemas.
+    pub fn load(path: &Path) -> Result<Self, ScanError> {
+        let name = path.display().to_string();
+        let content = fs::read_to_string(path).map_err(|e| ScanError::io(name.clone(), &e))?;
+        let value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| ScanError::parse(name.clone(), e.to_string()))?;
+        let value =
+            migrate::migrate_baseline(value).map_err(|e| ScanError::parse(name.clone(), e))?;
+        let baseline: Baseline = serde_json::from_value(value)
+            .map_err(|e| ScanError::parse(name.clone(), e.to_string()))?;
+        if let Some(entry) = baseline
+            .issues
+            .iter()
+            .find(|e| e.until.as_deref().is_some_and(|d| !suppress::is_date(d)))
+        {
+            return Err(ScanError::parse(
+                name,
+                format!("{}: until is not a YYYY-MM-DD date", entry.fingerprint),
+            ));
+        }
+        Ok(baseline)
+    }
+
+    /// Move entries whose `until` date is before `today` to `expired`.
+    pub fn expire(&mut self, today: &str) {
+        let (expired, issues) = self
+            .issues
+            .drain(..)
+            .partition(|e| e.until.as_deref().is_some_and(|until| until < today));
+        self.issues = issues;
+        self.expired = expired;
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string());
+        fs::write(path, json + "\n")
+    }
+
+    /// Whether any entry shares the issue's fingerprint, i.e. `partition`
+    /// might count it as known.
+    pub fn knows(&self, issue: &Issue) -> bool {
+        let fingerprint = fingerprint(issue);
+        self.issues.iter().any(|e| e.fingerprint == fingerprint)
+    }
+
+    /// Split issues into (new, known).
+    ///
+    /// Matching is by fingerprint and count: if the baseline holds two
+    /// secrets in `.env`, a third one is new even though it looks alike.
+    pub fn partition(&self, issues: Vec<Issue>) -> (Vec<Issue>, Vec<Issue>) {
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+        for entry in &self.issues {
+            *remaining.entry(entry.fingerprint.as_str()).or_default() += 1;
+        }
+        let mut new = Vec::new();
+        let mut known = Vec::new();
+        for issue in issues {
+            match remaining.get_mut(fingerprint(&issue).as_str()) {
+                Some(n) if *n > 0 => {
+                    *n -= 1;
+                    known.push(issue);
+                }
+                _ => new.push(issue),
+            }
+        }
+        (new, known)
+    }
+}
+
+/// Identity of an issue that survives unrelated edits.
+///
+/// Line numbers are left out so a known secret stays known when lines are
+/// added above it.
+pub fn fingerprint(issue: &Issue) -> String {
+    let id = issue.id();
+    match &issue.file {
+        Some(file) => format!("{}:{}", id, file),
+        None => id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fingerprint, Baseline, BaselineEntry, VERSION};
+    use crate::scanner::Issue;
+
+    fn entry(fingerprint: &str, until: Option<&str>) -> BaselineEntry {
+        BaselineEntry {
+            fingerprint: fingerprint.to_string(),
+            severity: "error".to_string(),
+            message: String::new(),
+            until: until.map(String::from),
+            owner: None,
+            reason: None,
+        }
+    }
+
+    fn secret(file: &str, line: usize) -> Issue {
+        Issue::error(
+            "secret-github-token",
+            format!("Potential GitHub token in {} at line {}", file, line),
+        )
+        .at(file, Some(line))
+    }
+
+    #[test]
+    fn fingerprints_ignore_lines() {
+        assert_eq!(fingerprint(&secret(".env", 1)), "secret-github-token:.env");
+        assert_eq!(
+            fingerprint(&secret(".env", 9)),
+            fingerprint(&secret(".env", 1))
+        );
+        assert_eq!(
+            fingerprint(&Issue::info("no-ci", "No CI/CD configuration detected")),
+            "no-ci"
+        );
+    }
+
+    #[test]
+    fn partition_counts_matches() {
+        let baseline = Baseline {
+            version: VERSION,
+            issues: vec![entry("secret-github-token:.env", None)],
+            expired: Vec::new(),
+        };
+        let (new, known) = baseline.partition(vec![
+            secret(".env", 4),
+            secret(".env", 7),
+            secret("config.yml", 1),
+        ]);
+        assert_eq!(known.len(), 1);
+        assert_eq!(known[0].line, Some(4));
+        let new: Vec<_> = new.iter().map(|i| (i.file.as_deref(), i.line)).collect();
+        assert_eq!(
+            new,
+            [(Some(".env"), Some(7)), (Some("config.yml"), Some(1))]
+        );
+    }
+
+    #[test]
+    fn expiry() {
+        let mut baseline = Baseline {
+            version: VERSION,
+            issues: vec![
+                entry("a", Some("2024-01-31")),
+                entry("b", Some("2024-02-01")),
+                entry("c", None),
+            ],
+            expired: Vec::new(),
+        };
+        baseline.expire("2024-02-01");
+        let names = |entries: &[BaselineEntry]| {
+            entries
+                .iter()
+                .map(|e| e.fingerprint.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&baseline.issues), ["b", "c"]);
+        assert_eq!(names(&baseline.expired), ["a"]);
+    }
+}
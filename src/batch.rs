@@ -0,0 +1,263 @@
+use crate::report::{calculate_score, clean_path, score_grade};
+use crate::scanner::{is_ignored_dir, Issue, ScanError, ScanResult, Severity};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How deep below the parent directory repositories are looked for by default.
+pub const DEFAULT_DEPTH: usize = 3;
+
+/// Outcome of scanning one repository in a batch.
+#[derive(Debug, Serialize)]
+pub struct RepoSummary {
+    /// Path relative to the parent directory (`owner/repo` for `org scan`).
+    pub name: String,
+    pub score: u8,
+    pub grade: &'static str,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+    pub issues: Vec<Issue>,
+    /// Why the scan failed; the other fields are zero in that case.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_error: Option<String>,
+}
+
+impl RepoSummary {
+    pub fn from_result(name: String, result: &ScanResult) -> Self {
+        let issues = result.issues.clone();
+        let count = |s: Severity| issues.iter().filter(|i| i.severity == s).count();
+        let score = calculate_score(result);
+        RepoSummary {
+            name,
+            score,
+            grade: score_grade(score),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            issues,
+            scan_error: None,
+        }
+    }
+
+    pub fn failed(name: String, err: &ScanError) -> Self {
+        RepoSummary {
+            name,
+            score: 0,
+            grade: score_grade(0),
+            errors: 0,
+            warnings: 0,
+            info: 0,
+            issues: Vec::new(),
+            scan_error: Some(err.to_string()),
+        }
+    }
+}
+
+/// Git repositories under `root`, sorted by path.
+///
+/// A directory containing `.git` (a directory, or a file for worktrees and
+/// submodules) is a repository and is not descended into. Hidden and
+/// build-output directories are skipped, as in a regular scan.
+pub fn discover(root: &Path, max_depth: usize) -> Vec<PathBuf> {
+    let mut repos = Vec::new();
+    let mut pending = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = pending.pop() {
+        if dir.join(".git").exists() {
+            repos.push(dir);
+            continue;
+        }
+        if depth >= max_depth {
+            continue;
+        }
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            if is_dir && !name.starts_with('.') && !is_ignored_dir(&name) {
+                pending.push((entry.path(), depth + 1));
+            }
+        }
+    }
+    repos.sort();
+    repos
+}
+
+/// Display name of a repository relative to the parent directory.
+pub fn repo_name(root: &Path, repo: &Path) -> String {
+    match repo.strip_prefix(root) {
+        Ok(rel) if rel.as_os_str().is_empty() => ".".to_string(),
+        Ok(rel) => rel.to_string_lossy().replace('\\', "/"),
+        Err(_) => clean_path(repo),
+    }
+}
+
+/// Worst score first, then by name.
+pub fn sort_for_triage(summaries: &mut [RepoSummary]) {
+    summaries.sort_by(|a, b| {
+        a.scan_error
+            .is_none()
+            .cmp(&b.scan_error.is_none())
+            .then(a.score.cmp(&b.score))
+            .then(a.name.cmp(&b.name))
+    });
+}
+
+/// Best score first, then by name; failed scans last.
+pub fn sort_for_leaderboard(summaries: &mut [RepoSummary]) {
+    summaries.sort_by(|a, b| {
+        a.scan_error
+            .is_some()
+            .cmp(&b.scan_error.is_some())
+            .then(b.score.cmp(&a.score))
+            .then(a.name.cmp(&b.name))
+    });
+}
+
+pub fn print_table(summaries: &[RepoSummary], color: bool) {
+    let width = summaries
+        .iter()
+        .map(|s| s.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("REPOSITORY".len());
+    println!(
+        "{:width$}  {:>5}  {:5}  {:>6}  {:>8}  {:>4}",
+        "REPOSITORY",
+        "SCORE",
+        "GRADE",
+        "ERRORS",
+        "WARNINGS",
+        "INFO",
+        width = width
+    );
+    for s in summaries {
+        if let Some(ref e) = s.scan_error {
+            println!(
+                "{:width$}  {}",
+                s.name,
+                paint(&format!("scan failed: {}", e), "31", color),
+                width = width
+            );
+            continue;
+        }
+        let code = match s.score {
+            90..=100 => "32",
+            70..=89 => "33",
+            _ => "31",
+        };
+        println!(
+            "{:width$}  {}  {:5}  {:>6}  {:>8}  {:>4}",
+            s.name,
+            paint(&format!("{:>5}", s.score), code, color),
+            s.grade,
+            s.errors,
+            s.warnings,
+            s.info,
+            width = width
+        );
+    }
+    let scanned: Vec<&RepoSummary> = summaries
+        .iter()
+        .filter(|s| s.scan_error.is_none())
+        .collect();
+    if !scanned.is_empty() {
+        let avg = scanned.iter().map(|s| s.score as usize).sum::<usize>() / scanned.len();
+        println!(
+            "\n{} repositories, average score {}, {} with errors",
+            summaries.len(),
+            avg,
+            scanned.iter().filter(|s| s.errors > 0).count()
+        );
+    }
+}
+
+fn paint(s: &str, code: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, s)
+    } else {
+        s.to_string()
+    }
+}
+
+#[derive(Serialize)]
+struct BatchReport<'a> {
+    root: String,
+    repositories: &'a [RepoSummary],
+}
+
+pub fn to_json(root: &Path, summaries: &[RepoSummary]) -> String {
+    let report = BatchReport {
+        root: clean_path(root),
+        repositories: summaries,
+    };
+    serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// One row per repository.
+pub fn to_csv(summaries: &[RepoSummary]) -> String {
+    let mut out = String::from("repository,score,grade,errors,warnings,info,scan_error\n");
+    for s in summaries {
+        out.push_str(&format!(
+            "\"{}\",{},{},{},{},{},\"{}\"\n",
+            s.name.replace('"', "\"\""),
+            s.score,
+            s.grade,
+            s.errors,
+            s.warnings,
+            s.info,
+            s.scan_error.as_deref().unwrap_or("").replace('"', "\"\"")
+        ));
+    }
+    out
+}
+
+/// Score table followed by each repository's issues.
+pub fn to_markdown(root: &Path, summaries: &[RepoSummary]) -> String {
+    let mut r = format!(
+        "# Repository Health Overview\n\n**Path:** `{}`\n\n| Repository | Score | Grade | Errors | Warnings | Info |\n|------------|-------|-------|--------|----------|------|\n",
+        clean_path(root)
+    );
+    for s in summaries {
+        r.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            s.name, s.score, s.grade, s.errors, s.warnings, s.info
+        ));
+    }
+    for s in summaries {
+        r.push_str(&format!("\n## {}\n\n", s.name));
+        if let Some(ref e) = s.scan_error {
+            r.push_str(&format!("Scan failed: {}\n", e));
+        } else if s.issues.is_empty() {
+            r.push_str("No issues found.\n");
+        }
+        for issue in &s.issues {
+            r.push_str(&format!(
+                "- **{}** {}\n",
+                issue.severity.name(),
+                issue.message
+            ));
+        }
+    }
+    r
+}
+
+/// Export the batch results, format chosen by extension (.json, .csv, .md).
+pub fn export_to_file(root: &Path, summaries: &[RepoSummary], file: &Path) -> io::Result<()> {
+    let ext = file
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("json")
+        .to_lowercase();
+    let content = match ext.as_str() {
+        "csv" => to_csv(summaries),
+        "md" | "markdown" => to_markdown(root, summaries),
+        _ => to_json(root, summaries),
+    };
+    fs::write(file, content)?;
+    println!("Exported to: {}", clean_path(file));
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use crate::git::date_from_unix;
+use crate::scanner::Attribution;
+use std::path::Path;
+use std::process::Command;
+
+/// Attribute each `(file, line)` location. Locations git can't attribute
+/// (untracked files, uncommitted lines, no git) are left out.
+pub fn attribute(root: &Path, locations: &[(String, Option<usize>)]) -> Vec<Attribution> {
+    if !root.join(".git").exists() {
+        return Vec::new();
+    }
+    let mut out: Vec<Attribution> = Vec::new();
+    for (file, line) in locations {
+        if out.iter().any(|a| &a.file == file && a.line == *line) {
+            continue;
+        }
+        let found = match line {
+            Some(n) => blame_line(root, file, *n),
+            None => last_commit(root, file),
+        };
+        if let Some((author, date, commit)) = found {
+            out.push(Attribution {
+                file: file.clone(),
+                line: *line,
+                author,
+                date,
+                commit,
+            });
+        }
+    }
+    out
+}
+
+/// `git blame` for a single line.
+fn blame_line(root: &Path, file: &str, line: usize) -> Option<(String, String, String)> {
+    let range = format!("{},{}", line, line);
+    let out = git(root, &["blame", "--porcelain", "-L", &range, "--", file])?;
+    let mut lines = out.lines();
+    let commit = lines.next()?.split(' ').next()?.to_string();
+    // All zeros means the line is not committed yet
+    if commit.bytes().all(|b| b == b'0') {
+        return None;
+    }
+    let mut author = None;
+    let mut time = None;
+    for l in lines {
+        if let Some(a) = l.strip_prefix("author ") {
+            author = Some(a.to_string());
+        } else if let Some(t) = l.strip_prefix("author-time ") {
+            time = t.parse::<i64>().ok();
+        }
+    }
+    Some((
+        author?,
+        date_from_unix(time?),
+        commit[..commit.len().min(7)].to_string(),
+    ))
+}
+
+/// Last commit touching a file (for findings without a line).
+fn last_commit(root: &Path, file: &str) -> Option<(String, String, String)> {
+    let out = git(
+        root,
+        &[
+            "log",
+            "-1",
+            "--format=%an%x09%ad%x09%h",
+            "--date=short",
+            "--",
+            file,
+        ],
+    )?;
+    let mut parts = out.trim().splitn(3, '\t');
+    Some((
+        parts.next()?.to_string(),
+        parts.next()?.to_string(),
+        parts.next()?.to_string(),
+    ))
+}
+
+fn git(root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::attribute;
+    use crate::testutil::TempDir;
+    use std::fs;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "user.name=Ada", "-c", "user.email=ada@example.com"])
+            .args(args)
+            .env("GIT_AUTHOR_DATE", "2024-05-01T12:00:00Z")
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn attributes_committed_lines() {
+        let root = TempDir::new("blame");
+        assert!(attribute(&root, &[("a.txt".to_string(), None)]).is_empty());
+        git(&root, &["init", "-q"]);
+        fs::write(root.join("a.txt"), "one\ntwo\n").unwrap();
+        git(&root, &["add", "."]);
+        git(&root, &["commit", "-qm", "first"]);
+        // A third line that isn't committed yet, and an untracked file
+        fs::write(root.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        fs::write(root.join("new.txt"), "new\n").unwrap();
+
+        let found = attribute(
+            &root,
+            &[
+                ("a.txt".to_string(), Some(2)),
+                ("a.txt".to_string(), Some(2)),
+                ("a.txt".to_string(), Some(3)),
+                ("a.txt".to_string(), None),
+                ("new.txt".to_string(), None),
+            ],
+        );
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].line, Some(2));
+        assert_eq!(
+            (found[0].author.as_str(), found[0].date.as_str()),
+            ("Ada", "2024-05-01")
+        );
+        assert_eq!(found[0].commit.len(), 7);
+        assert_eq!(found[1].line, None);
+        assert_eq!(found[1].to_string(), found[0].to_string());
+    }
+}
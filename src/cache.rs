@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::UNIX_EPOCH;
+
+/// Cache file name, kept in the Git directory (`.git/repomedic-cache`), or
+/// at the root of a directory outside Git when `cache = true`.
+pub const CACHE_FILE: &str = ".repomedic-cache";
+
+/// Where the cache lives in the Git repository at `root`, so it never shows
+/// up as an untracked file. `None` outside a repository.
+pub fn git_location(root: &Path) -> Option<PathBuf> {
+    Some(git_dir(root)?.join("repomedic-cache"))
+}
+
+/// The Git directory of the repository at `root`: `.git`, or the directory
+/// a worktree's `.git` file points to. `None` outside a repository.
+pub fn git_dir(root: &Path) -> Option<PathBuf> {
+    let dot_git = root.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+    let pointer = fs::read_to_string(&dot_git).ok()?;
+    let dir = pointer.strip_prefix("gitdir:")?.trim();
+    Some(root.join(dir))
+}
+
+/// What a file looked like when a whole-tree pass last found nothing in it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Entry {
+    /// Modification time, nanoseconds since the Unix epoch.
+    mtime: u64,
+    size: u64,
+    /// FNV-1a hash of the text, so a touched but unchanged file still hits.
+    hash: u64,
+    /// Passes (`secrets`, custom content rules) that found nothing.
+    clean: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Stored {
+    version: String,
+    files: HashMap<String, Entry>,
+}
+
+/// Per-file results from earlier scans, used to skip files that haven't
+/// changed since a pass last found them clean. Files with findings are never
+/// cached, so they're always re-examined. The cache is dropped whenever the
+/// repomedic version changes, since rules change with it.
+#[derive(Debug)]
+pub struct FileCache {
+    path: PathBuf,
+    files: HashMap<String, Entry>,
+    /// Files confirmed or recorded this scan.
+    seen: HashSet<String>,
+    dirty: bool,
+    hits: usize,
+}
+
+impl FileCache {
+    /// Load the cache for `root` (see [`git_location`]); a missing,
+    /// unreadable, or outdated cache starts empty.
+    pub fn load(root: &Path) -> Self {
+        let path = git_location(root).unwrap_or_else(|| root.join(CACHE_FILE));
+        let files = fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| serde_json::from_str::<Stored>(&c).ok())
+            .filter(|s| s.version == env!("CARGO_PKG_VERSION"))
+            .map(|s| s.files)
+            .unwrap_or_default();
+        FileCache {
+            path,
+            files,
+            seen: HashSet::new(),
+            dirty: false,
+            hits: 0,
+        }
+    }
+
+    /// Whether `pass` found nothing in `rel` last time and the file is
+    /// unchanged: same size and mtime, or same size and content (`read` is
+    /// only called when the mtime differs).
+    pub fn is_clean(
+        &mut self,
+        root: &Path,
+        rel: &str,
+        pass: &str,
+        read: impl FnOnce() -> Option<Rc<String>>,
+    ) -> bool {
+        let Some((mtime, size)) = stat(&root.join(rel)) else {
+            return false;
+        };
+        let Some(entry) = self.files.get_mut(rel) else {
+            return false;
+        };
+        if entry.size != size || !entry.clean.iter().any(|p| p == pass) {
+            return false;
+        }
+        if entry.mtime != mtime {
+            if read().is_none_or(|content| hash(&content) != entry.hash) {
+                return false;
+            }
+            entry.mtime = mtime;
+            self.dirty = true;
+        }
+        self.seen.insert(rel.to_string());
+        self.hits += 1;
+        true
+    }
+
+    /// Record that `pass` found nothing in `rel`, whose text is `content`.
+    pub fn mark_clean(&mut self, root: &Path, rel: &str, pass: &str, content: &str) {
+        let Some((mtime, size)) = stat(&root.join(rel)) else {
+            return;
+        };
+        let hash = hash(content);
+        let entry = self.files.entry(rel.to_string()).or_insert(Entry {
+            mtime,
+            size,
+            hash,
+            clean: Vec::new(),
+        });
+        if entry.hash != hash || entry.size != size {
+            entry.clean.clear();
+        }
+        entry.mtime = mtime;
+        entry.size = size;
+        entry.hash = hash;
+        if !entry.clean.iter().any(|p| p == pass) {
+            entry.clean.push(pass.to_string());
+        }
+        self.seen.insert(rel.to_string());
+        self.dirty = true;
+    }
+
+    /// Passes skipped thanks to the cache this scan.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Write the cache back. Entries for files this scan didn't confirm are
+    /// dropped (deleted files, files that now have findings) unless `partial`,
+    /// as for a scan limited to some files. Write failures are ignored: the
+    /// cache only saves time.
+    pub fn save(mut self, partial: bool) {
+        if !partial {
+            let before = self.files.len();
+            self.files.retain(|rel, _| self.seen.contains(rel));
+            self.dirty |= self.files.len() != before;
+        }
+        if !self.dirty {
+            return;
+        }
+        let stored = Stored {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            files: self.files,
+        };
+        if let Ok(json) = serde_json::to_string(&stored) {
+            let _ = fs::write(&self.path, json);
+        }
+    }
+}
+
+fn stat(path: &Path) -> Option<(u64, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((mtime.as_nanos() as u64, meta.len()))
+}
+
+/// 64-bit FNV-1a.
+pub(crate) fn hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{git_location, FileCache, CACHE_FILE};
+    use crate::testutil::TempDir;
+    use std::fs;
+    use std::path::Path;
+    use std::rc::Rc;
+
+    fn clean(cache: &mut FileCache, root: &Path, rel: &str, pass: &str) -> bool {
+        let content = fs::read_to_string(root.join(rel)).ok().map(Rc::new);
+        cache.is_clean(root, rel, pass, || content)
+    }
+
+    #[test]
+    fn locations() {
+        let dir = TempDir::new("cache-loc");
+        assert_eq!(git_location(&dir), None);
+        fs::write(dir.join(".git"), "gitdir: ../main/.git/worktrees/wt\n").unwrap();
+        assert_eq!(
+            git_location(&dir),
+            Some(dir.join("../main/.git/worktrees/wt/repomedic-cache"))
+        );
+        fs::remove_file(dir.join(".git")).unwrap();
+        fs::create_dir(dir.join(".git")).unwrap();
+        assert_eq!(git_location(&dir), Some(dir.join(".git/repomedic-cache")));
+    }
+
+    #[test]
+    fn clean_files_survive_a_reload() {
+        let dir = TempDir::new("cache-hit");
+        fs::write(dir.join("a.txt"), "hello").unwrap();
+        fs::write(dir.join("b.txt"), "world").unwrap();
+        let mut cache = FileCache::load(&dir);
+        assert!(!clean(&mut cache, &dir, "a.txt", "secrets"));
+        cache.mark_clean(&dir, "a.txt", "secrets", "hello");
+        cache.mark_clean(&dir, "b.txt", "secrets", "world");
+        cache.save(false);
+        assert!(dir.join(CACHE_FILE).is_file());
+
+        let mut cache = FileCache::load(&dir);
+        assert!(clean(&mut cache, &dir, "a.txt", "secrets"));
+        assert!(!clean(&mut cache, &dir, "a.txt", "rules"));
+        // A changed file misses, and is dropped when the scan doesn't confirm it
+        fs::write(dir.join("b.txt"), "world!").unwrap();
+        assert!(!clean(&mut cache, &dir, "b.txt", "secrets"));
+        assert_eq!(cache.hits(), 1);
+        cache.save(false);
+
+        let mut cache = FileCache::load(&dir);
+        fs::write(dir.join("b.txt"), "world").unwrap();
+        assert!(!clean(&mut cache, &dir, "b.txt", "secrets"));
+        assert!(clean(&mut cache, &dir, "a.txt", "secrets"));
+    }
+}
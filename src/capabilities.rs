@@ -0,0 +1,140 @@
+use crate::detector::Registry;
+use crate::fix::{Template, FIXERS};
+use crate::plugins;
+use crate::rules;
+use serde::Serialize;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Output formats accepted by `--format`.
+pub const OUTPUT_FORMATS: &[&str] = &[
+    "console",
+    "json",
+    "ndjson",
+    "markdown",
+    "sarif",
+    "lsp-diagnostics",
+    "github",
+    "hook",
+];
+
+/// File extensions accepted by `export`.
+#[cfg(feature = "html")]
+pub const EXPORT_FORMATS: &[&str] = &[
+    "json",
+    "md",
+    "txt",
+    "csv",
+    "tsv",
+    "html",
+    "cdx.json",
+    "spdx.json",
+];
+#[cfg(not(feature = "html"))]
+pub const EXPORT_FORMATS: &[&str] = &["json", "md", "txt", "csv", "tsv", "cdx.json", "spdx.json"];
+
+/// What this build of repomedic supports, for wrapper tools.
+#[derive(Debug, Serialize)]
+pub struct Capabilities {
+    pub version: &'static str,
+    pub output_formats: &'static [&'static str],
+    pub export_formats: &'static [&'static str],
+    pub rules: Vec<RuleInfo>,
+    pub detectors: Vec<DetectorInfo>,
+    pub generators: Vec<&'static str>,
+    pub fixers: Vec<FixerInfo>,
+    pub features: Vec<FeatureInfo>,
+    /// External checks found on `PATH` (`repomedic-check-<name>`).
+    pub plugins: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RuleInfo {
+    pub code: &'static str,
+    pub id: &'static str,
+    pub category: &'static str,
+    pub description: &'static str,
+    pub level: &'static str,
+    pub fix: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DetectorInfo {
+    pub name: &'static str,
+    pub category: &'static str,
+    pub requires: &'static [&'static str],
+}
+
+#[derive(Debug, Serialize)]
+pub struct FixerInfo {
+    pub id: &'static str,
+    pub description: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeatureInfo {
+    pub name: &'static str,
+    pub enabled: bool,
+}
+
+impl Capabilities {
+    pub fn detect(registry: &Registry) -> Self {
+        Self {
+            version: VERSION,
+            output_formats: OUTPUT_FORMATS,
+            export_formats: EXPORT_FORMATS,
+            rules: rules::all()
+                .into_iter()
+                .map(|c| RuleInfo {
+                    code: c.code,
+                    id: c.id,
+                    category: c.category.name(),
+                    description: c.description,
+                    level: c.sarif_level(),
+                    fix: c.fix,
+                })
+                .collect(),
+            detectors: registry
+                .ordered()
+                .iter()
+                .map(|d| DetectorInfo {
+                    name: d.name(),
+                    category: d.category().name(),
+                    requires: d.requires(),
+                })
+                .collect(),
+            generators: Template::ALL.iter().map(|t| t.file_name()).collect(),
+            fixers: FIXERS
+                .iter()
+                .map(|(id, description)| FixerInfo { id, description })
+                .collect(),
+            plugins: plugins::discover().into_iter().map(|p| p.name).collect(),
+            features: vec![
+                FeatureInfo {
+                    name: "cli",
+                    enabled: cfg!(feature = "cli"),
+                },
+                FeatureInfo {
+                    name: "network",
+                    enabled: cfg!(feature = "network"),
+                },
+                FeatureInfo {
+                    name: "tui",
+                    enabled: cfg!(feature = "tui"),
+                },
+                FeatureInfo {
+                    name: "html",
+                    enabled: cfg!(feature = "html"),
+                },
+                FeatureInfo {
+                    name: "git-history",
+                    enabled: cfg!(feature = "git-history"),
+                },
+            ],
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
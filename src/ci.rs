@@ -0,0 +1,367 @@
+use crate::scanner::{BuildSystem, CISystem};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+/// A caching or artifact setting worth changing in a CI config.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum CiAdvice {
+    /// The config builds with `build_system` but never caches its dependencies.
+    MissingCache {
+        file: String,
+        build_system: BuildSystem,
+    },
+    /// Artifacts are uploaded without an expiry, so the provider default
+    /// (90 days on GitHub, 30 on GitLab) applies.
+    ArtifactRetention { file: String },
+}
+
+/// Commands that show a config builds with a tool, and settings that show
+/// it caches the tool's downloads. Matched against the lowercased config
+/// with quotes removed.
+const CACHE_RULES: &[(BuildSystem, &[&str], &[&str])] = &[
+    (
+        BuildSystem::Cargo,
+        &["cargo "],
+        &[
+            "rust-cache",
+            "setup-rust-toolchain",
+            ".cargo/registry",
+            "~/.cargo",
+            "sccache",
+        ],
+    ),
+    (
+        BuildSystem::Npm,
+        &["npm ci", "npm install"],
+        &["cache: npm", "/.npm", "node_modules"],
+    ),
+    (
+        BuildSystem::Yarn,
+        &["yarn install", "run: yarn"],
+        &["cache: yarn", "yarn-cache", ".yarn/cache", "node_modules"],
+    ),
+    (
+        BuildSystem::Pnpm,
+        &["pnpm install", "pnpm i "],
+        &["cache: pnpm", "pnpm-store", "node_modules"],
+    ),
+    (
+        BuildSystem::Pip,
+        &["pip install"],
+        &["cache: pip", ".cache/pip"],
+    ),
+    (
+        BuildSystem::Poetry,
+        &["poetry install"],
+        &["cache: poetry", "pypoetry", ".venv"],
+    ),
+    (
+        BuildSystem::Go,
+        &["go build", "go test", "go mod download"],
+        // setup-go caches modules by default since v4
+        &["setup-go", "go/pkg/mod", "go-build"],
+    ),
+    (BuildSystem::Maven, &["mvn "], &["cache: maven", ".m2"]),
+    (
+        BuildSystem::Gradle,
+        &["gradle"],
+        &[
+            "cache: gradle",
+            "gradle-build-action",
+            "setup-gradle",
+            ".gradle/caches",
+        ],
+    ),
+    (
+        BuildSystem::Bundler,
+        &["bundle install"],
+        &["bundler-cache: true", "vendor/bundle"],
+    ),
+    (
+        BuildSystem::Composer,
+        &["composer install"],
+        &["composer-cache", "composer/cache", "cache-files-dir"],
+    ),
+];
+
+/// Docker Compose files, scanned for secrets alongside the CI configs.
+pub const COMPOSE_FILES: &[&str] = &[
+    "docker-compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.override.yml",
+    "compose.yml",
+    "compose.yaml",
+];
+
+/// CI config files under `root` (relative paths), with the system they belong to.
+pub fn config_files(root: &Path) -> Vec<(String, CISystem)> {
+    let mut files = Vec::new();
+    if let Ok(entries) = fs::read_dir(root.join(".github/workflows")) {
+        let mut names: Vec<String> = entries
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|n| n.ends_with(".yml") || n.ends_with(".yaml"))
+            .collect();
+        names.sort();
+        for name in names {
+            files.push((
+                format!(".github/workflows/{}", name),
+                CISystem::GitHubActions,
+            ));
+        }
+    }
+    for (rel, ci) in [
+        (".gitlab-ci.yml", CISystem::GitLab),
+        (".circleci/config.yml", CISystem::CircleCI),
+        (".travis.yml", CISystem::Travis),
+        ("azure-pipelines.yml", CISystem::Azure),
+    ] {
+        if root.join(rel).is_file() {
+            files.push((rel.to_string(), ci));
+        }
+    }
+    files
+}
+
+/// Check one CI config for uncached builds and artifacts without retention.
+pub fn analyze(file: &str, ci: &CISystem, content: &str) -> Vec<CiAdvice> {
+    let text = content.to_lowercase().replace(['"', '\''], "");
+    let has = |markers: &[&str]| markers.iter().any(|m| text.contains(m));
+    // `cargo test` must not count as `go test`
+    let runs_any = |commands: &[&str]| {
+        commands.iter().any(|c| {
+            text.match_indices(c).any(|(i, _)| {
+                !text[..i]
+                    .chars()
+                    .next_back()
+                    .is_some_and(|p| p.is_alphanumeric() || p == '-' || p == '_')
+            })
+        })
+    };
+    // Outside GitHub Actions a single cache block usually covers every tool
+    let cached_anywhere = match ci {
+        CISystem::GitHubActions => false,
+        CISystem::GitLab | CISystem::Travis => text.contains("cache:"),
+        CISystem::CircleCI => text.contains("save_cache"),
+        CISystem::Azure => text.contains("cache@"),
+        CISystem::Jenkins => true,
+    };
+    let mut advice = Vec::new();
+    for (build_system, runs, caches) in CACHE_RULES {
+        if runs_any(runs) && !cached_anywhere && !has(caches) {
+            advice.push(CiAdvice::MissingCache {
+                file: file.to_string(),
+                build_system: build_system.clone(),
+            });
+        }
+    }
+    let unexpiring = match ci {
+        CISystem::GitHubActions => {
+            text.contains("actions/upload-artifact") && !text.contains("retention-days")
+        }
+        CISystem::GitLab => text.contains("artifacts:") && !text.contains("expire_in"),
+        _ => false,
+    };
+    if unexpiring {
+        advice.push(CiAdvice::ArtifactRetention {
+            file: file.to_string(),
+        });
+    }
+    advice
+}
+
+/// Dotted key path of a YAML line, e.g. `jobs.build.steps[2].env.TOKEN`.
+///
+/// Tracks block mappings, sequences, and block scalars (`run: |`) by
+/// indentation; flow collections and multi-document files are not parsed.
+/// Returns `None` for top-level lines outside any mapping key.
+pub fn yaml_key_path(lines: &[&str], target: usize) -> Option<String> {
+    struct Node {
+        col: usize,
+        segment: String,
+        item: Option<usize>,
+    }
+    let mut stack: Vec<Node> = Vec::new();
+    // Column of the key whose block scalar is being read
+    let mut block: Option<usize> = None;
+    for (i, raw) in lines.iter().enumerate().take(target + 1) {
+        let trimmed = raw.trim_start();
+        let col = raw.len() - trimmed.len();
+        if let Some(b) = block {
+            if trimmed.is_empty() || col > b {
+                if i == target {
+                    break;
+                }
+                continue;
+            }
+            block = None;
+        }
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let mut content = trimmed;
+        let mut key_col = col;
+        if trimmed == "-" || trimmed.starts_with("- ") {
+            let mut index = 0;
+            while let Some(top) = stack.last() {
+                if top.col < col || (top.col == col && top.item.is_none()) {
+                    break;
+                }
+                if top.col == col {
+                    index = top.item.map_or(0, |n| n + 1);
+                }
+                stack.pop();
+            }
+            stack.push(Node {
+                col,
+                segment: format!("[{}]", index),
+                item: Some(index),
+            });
+            content = trimmed[1..].trim_start();
+            key_col = col + (trimmed.len() - content.len());
+        } else {
+            while stack.last().is_some_and(|top| top.col >= col) {
+                stack.pop();
+            }
+        }
+        if let Some((key, value)) = yaml_key(content) {
+            stack.push(Node {
+                col: key_col,
+                segment: key.to_string(),
+                item: None,
+            });
+            if value.starts_with('|') || value.starts_with('>') {
+                block = Some(key_col);
+            }
+        }
+    }
+    if stack.is_empty() {
+        return None;
+    }
+    let mut path = String::new();
+    for node in &stack {
+        if !path.is_empty() && node.item.is_none() {
+            path.push('.');
+        }
+        path.push_str(&node.segment);
+    }
+    Some(path)
+}
+
+/// `key: value` split, for plain or quoted keys.
+pub(crate) fn yaml_key(content: &str) -> Option<(&str, &str)> {
+    let (key, value) = if let Some(rest) = content.strip_prefix(['"', '\'']) {
+        let quote = content.as_bytes()[0] as char;
+        let end = rest.find(quote)?;
+        (&rest[..end], rest[end + 1..].strip_prefix(':')?)
+    } else {
+        let end = content
+            .find(": ")
+            .or_else(|| content.strip_suffix(':').map(|k| k.len()))?;
+        let key = &content[..end];
+        if key.contains(" #") || key.starts_with(['{', '[', '&', '*', '!']) {
+            return None;
+        }
+        (key, &content[end + 1..])
+    };
+    Some((key.trim(), value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, yaml_key, yaml_key_path, CiAdvice};
+    use crate::scanner::{BuildSystem, CISystem};
+
+    fn uncached(ci: &CISystem, content: &str) -> Vec<BuildSystem> {
+        analyze("ci.yml", ci, content)
+            .into_iter()
+            .filter_map(|a| match a {
+                CiAdvice::MissingCache { build_system, .. } => Some(build_system),
+                CiAdvice::ArtifactRetention { .. } => None,
+            })
+            .collect()
+    }
+
+    fn retention(ci: &CISystem, content: &str) -> bool {
+        analyze("ci.yml", ci, content)
+            .iter()
+            .any(|a| matches!(a, CiAdvice::ArtifactRetention { .. }))
+    }
+
+    #[test]
+    fn missing_caches() {
+        let gha = &CISystem::GitHubActions;
+        assert_eq!(
+            uncached(gha, "steps:\n  - run: cargo test\n  - run: npm ci\n"),
+            [BuildSystem::Cargo, BuildSystem::Npm]
+        );
+        let cached = "steps:\n  - uses: Swatinem/rust-cache@v2\n  - uses: actions/setup-node@v4\n    with:\n      cache: 'npm'\n  - run: cargo test\n  - run: npm ci\n";
+        assert!(uncached(gha, cached).is_empty());
+        // A GitLab cache block covers every tool in the file
+        let gitlab = "cache:\n  paths: [target/]\ntest:\n  script: cargo test\n";
+        assert!(uncached(&CISystem::GitLab, gitlab).is_empty());
+        assert_eq!(
+            uncached(&CISystem::GitLab, "test:\n  script: cargo test\n"),
+            [BuildSystem::Cargo]
+        );
+    }
+
+    #[test]
+    fn artifact_retention() {
+        let gha = &CISystem::GitHubActions;
+        assert!(retention(gha, "- uses: actions/upload-artifact@v4\n"));
+        assert!(!retention(
+            gha,
+            "- uses: actions/upload-artifact@v4\n  with:\n    retention-days: 5\n"
+        ));
+        assert!(retention(
+            &CISystem::GitLab,
+            "build:\n  artifacts:\n    paths: [dist]\n"
+        ));
+        assert!(!retention(
+            &CISystem::GitLab,
+            "build:\n  artifacts:\n    expire_in: 1 week\n"
+        ));
+    }
+
+    #[test]
+    fn key_paths() {
+        let lines = [
+            "jobs:",
+            "  build:",
+            "    steps:",
+            "      - uses: actions/checkout@v4",
+            "      - name: Test",
+            "        run: |",
+            "          cargo test",
+            "        env:",
+            "          TOKEN: x",
+        ];
+        assert_eq!(yaml_key_path(&lines, 1).as_deref(), Some("jobs.build"));
+        assert_eq!(
+            yaml_key_path(&lines, 3).as_deref(),
+            Some("jobs.build.steps[0].uses")
+        );
+        assert_eq!(
+            yaml_key_path(&lines, 6).as_deref(),
+            Some("jobs.build.steps[1].run")
+        );
+        assert_eq!(
+            yaml_key_path(&lines, 8).as_deref(),
+            Some("jobs.build.steps[1].env.TOKEN")
+        );
+        assert_eq!(yaml_key_path(&["- a"], 0).as_deref(), Some("[0]"));
+        assert_eq!(yaml_key_path(&["plain"], 0), None);
+    }
+
+    #[test]
+    fn keys() {
+        assert_eq!(yaml_key("name: Build"), Some(("name", "Build")));
+        assert_eq!(yaml_key("\"on\": push"), Some(("on", "push")));
+        assert_eq!(yaml_key("steps:"), Some(("steps", "")));
+        assert_eq!(yaml_key("https://example.com"), None);
+        assert_eq!(yaml_key("{a: 1}"), None);
+    }
+}
@@ -0,0 +1,2610 @@
+//! The `repomedic` command line.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use repomedic::capabilities::Capabilities;
+use repomedic::config::{self, Branding, Config};
+use repomedic::custom::CustomRule;
+use repomedic::detector::Registry;
+use repomedic::events::{Event, EventWriter};
+use repomedic::gitignores::Template;
+use repomedic::license::Choice;
+use repomedic::locale::Locale;
+use repomedic::pipeline::Provider;
+use repomedic::profile::{self, Profile};
+#[cfg(feature = "tui")]
+use repomedic::tui;
+use repomedic::{
+    badges, baseline, batch, cache, evidence, fix, generator, git, github, history, hook, migrate,
+    remote, report, rules, scanner, selftest, suppress, watch,
+};
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const GITHUB_URL: &str = "https://github.com/Brutus1066/repomedic";
+
+/// Print colored banner, or the `[branding] banner` text in its place.
+fn print_banner(color: bool, branding: &Branding) {
+    if let Some(ref text) = branding.banner {
+        println!("{}\n", text);
+        return;
+    }
+    let cyan = if color { "\x1b[36m" } else { "" };
+    let green = if color { "\x1b[32m" } else { "" };
+    let reset = if color { "\x1b[0m" } else { "" };
+    println!(
+        "{}╔══════════════════════════════════════════════════════════════╗{}",
+        cyan, reset
+    );
+    println!(
+        "{}║  {}██████╗ ███████╗██████╗  ██████╗ ███╗   ███╗███████╗██████╗ {} ║{}",
+        cyan, green, cyan, reset
+    );
+    println!(
+        "{}║  {}██╔══██╗██╔════╝██╔══██╗██╔═══██╗████╗ ████║██╔════╝██╔══██╗{} ║{}",
+        cyan, green, cyan, reset
+    );
+    println!(
+        "{}║  {}██████╔╝█████╗  ██████╔╝██║   ██║██╔████╔██║█████╗  ██║  ██║{} ║{}",
+        cyan, green, cyan, reset
+    );
+    println!(
+        "{}║  {}██╔══██╗██╔══╝  ██╔═══╝ ██║   ██║██║╚██╔╝██║██╔══╝  ██║  ██║{} ║{}",
+        cyan, green, cyan, reset
+    );
+    println!(
+        "{}║  {}██║  ██║███████╗██║     ╚██████╔╝██║ ╚═╝ ██║███████╗██████╔╝{} ║{}",
+        cyan, green, cyan, reset
+    );
+    println!(
+        "{}║  {}╚═╝  ╚═╝╚══════╝╚═╝      ╚═════╝ ╚═╝     ╚═╝╚══════╝╚═════╝ {} ║{}",
+        cyan, green, cyan, reset
+    );
+    println!(
+        "{}╚══════════════════════════════════════════════════════════════╝{}",
+        cyan, reset
+    );
+    println!();
+}
+
+fn print_examples(color: bool, banner: bool, branding: &Branding) {
+    let cyan = if color { "\x1b[36m" } else { "" };
+    let green = if color { "\x1b[32m" } else { "" };
+    let yellow = if color { "\x1b[33m" } else { "" };
+    let reset = if color { "\x1b[0m" } else { "" };
+
+    if banner {
+        print_banner(color, branding);
+    }
+    println!(
+        r#"{}Usage Examples{}
+
+{}━━━ Quick Start ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}
+  repomedic                       {}Scan current directory{}
+  repomedic C:\Projects\MyApp     {}Scan specific path{}
+  repomedic doctor                {}Quick health check (one line){}
+  repomedic scan-all ~/code       {}Score every repo under a folder{}
+  repomedic scan --score          {}Show score with README badge{}
+  repomedic scan --score --explain {}Show points behind the score{}
+  repomedic scan --suggest        {}Show fix commands{}
+
+{}━━━ Export Results ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}
+  repomedic export report.json    {}Save as JSON{}
+  repomedic export report.md      {}Save as Markdown{}
+  repomedic export report.txt     {}Save as plain text{}
+  repomedic export report.csv     {}Save as CSV{}
+  repomedic export report.html    {}Save as standalone HTML{}
+  repomedic export sbom.cdx.json  {}CycloneDX SBOM (sbom.spdx.json: SPDX){}
+
+{}━━━ Generate Files ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}
+  repomedic init                  {}Generate all recommended files{}
+  repomedic init --author "Name"  {}Set LICENSE author{}
+  repomedic generate --readme     {}Generate only README.md{}
+  repomedic generate --dry-run    {}Preview without writing{}
+
+{}━━━ CI/CD Integration ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}
+  repomedic -q                    {}Quiet mode (exit code only){}
+  repomedic --fail-level warning  {}Strict mode (exit 2 on warn){}
+  repomedic -f json               {}JSON for pipelines{}
+  repomedic -f sarif              {}SARIF for GitHub Security{}
+  repomedic -f github             {}Annotations + job summary{}
+
+{}━━━ Exit Codes ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━{}
+  {}0{} = Clean      {}1{} = Error      {}2{} = Issues found
+
+{}{}{} {}
+"#,
+        cyan,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        cyan,
+        if branding.help_url.is_some() {
+            "Help:"
+        } else {
+            "GitHub:"
+        },
+        reset,
+        branding.url(GITHUB_URL)
+    );
+}
+
+fn print_help_detailed(color: bool, banner: bool, branding: &Branding) {
+    let cyan = if color { "\x1b[36m" } else { "" };
+    let green = if color { "\x1b[32m" } else { "" };
+    let yellow = if color { "\x1b[33m" } else { "" };
+    let reset = if color { "\x1b[0m" } else { "" };
+
+    let more_info = match (&branding.help_url, &branding.support) {
+        (None, None) => format!(
+            "  Website:  https://kindware.dev\n  GitHub:   {}\n  Author:   LazyFrog <support@kindware.dev>\n",
+            GITHUB_URL
+        ),
+        (url, support) => {
+            let mut lines = String::new();
+            if let Some(url) = url {
+                lines.push_str(&format!("  Help:     {}\n", url));
+            }
+            if let Some(support) = support {
+                lines.push_str(&format!("  Support:  {}\n", support));
+            }
+            lines
+        }
+    };
+
+    if banner {
+        print_banner(color, branding);
+    }
+    println!(
+        r#"{}RepoMedic v{}{} - Repository Health Scanner
+
+{}WHAT IT DOES:{}
+  Scans Git repositories for missing files, security issues, and
+  best practices. Generates health scores and fix suggestions.
+
+{}COMMANDS:{}
+  {}scan{}        Scan repository and show results (default)
+              --score    Show health score (0-100) with badge
+              --explain  With --score, show points per finding
+              --suggest  Show fix commands for each issue
+              --emit-script FILE  With --suggest, write them to fixes.sh/.ps1
+              --fixture  Compare repos in DIR against golden reports
+
+  {}scan-all{}    Scan every Git repository under PATH (score table)
+              --depth N      Directory levels to search (default: 3)
+              --export FILE  Aggregated results (.json, .csv, .md)
+
+  {}org scan{}    Clone and rank every repository of a GitHub org (ORG)
+              --include-forks, --include-archived, --limit N
+              --export FILE  Leaderboard (.json, .csv, .md)
+
+  {}doctor{}      Quick one-line health summary (--json-out FILE for agents)
+              Perfect for shell prompts or quick checks
+
+  {}export{}      Save results to file
+              Formats: .json, .md, .txt, .csv, .tsv, .html, .cdx.json, .spdx.json (SBOM)
+              Example: repomedic export report.json
+
+  {}init{}        Generate all recommended files at once
+              Creates: README, LICENSE, .gitignore, etc.
+
+  {}generate{}    Generate specific files
+              --readme, --license, --gitignore, etc.
+              --all      Generate all missing
+              --dry-run  Preview without writing (diffs for existing files)
+              --force    Overwrite existing files
+              --merge    Add missing .gitignore/.editorconfig sections
+              --templates DIR  Use DIR/<file>.hbs instead of built-ins
+
+  {}fix{}         Repair fixable issues (--dry-run, --interactive)
+
+  {}hook{}        Install a pre-commit hook (hook install, hook uninstall)
+
+  {}badge{}       Print a shields.io badge for the grade
+              --write  Keep it in the README between markers
+              --style, --link  Badge style and link target
+              --svg FILE  Draw the badge locally (no shields.io)
+
+  {}history{}     Record the score in .repomedic/history.jsonl, show the trend
+
+  {}report{}      Save full report to REPO_REPORT.md
+
+  {}baseline{}    Record current issues; later use --baseline FILE
+
+  {}detectors{}   List detectors (use --skip to disable)
+
+  {}rules{}       List checks (rules list) or explain one (rules explain RM001)
+
+  {}capabilities{} Supported formats, rules, fixers (--format json)
+
+  {}schema{}      JSON Schema of --format json reports
+
+  {}watch{}       Re-scan on file changes (watch --score for score only)
+
+  {}tui{}         Interactive dashboard: panes for score, issues, fixes, secrets
+
+  {}workflows{}   Table of workflow triggers, cron schedules, permissions
+
+  {}examples{}    Show usage examples with colors
+
+{}GLOBAL FLAGS:{}
+  {}-f, --format{}    Output: console, json, ndjson, markdown, sarif, lsp-diagnostics, github, hook
+  {}-q, --quiet{}     Exit code only (for scripts)
+  {}-v, --verbose{}   Show scan timing and stats
+  {}--no-color{}      Disable colored output
+  {}--fail-level{}    Exit 2 at: error (default), warning, info, none; 3 on secrets
+  {}--fail-on-warning{}  Same as --fail-level warning
+  {}--show-passed{}   List passed checks too
+  {}--evidence{}      Write redacted audit evidence to a directory
+  {}--skip{}          Skip detectors by name or category
+  {}--config{}        Config file (default: .repomedic.toml)
+  {}--self-test{}     Verify this build against bundled golden reports
+  {}--baseline{}      Only report issues not in a baseline file
+  {}--blame{}         Show who last changed each flagged file/line
+  {}--ci-health{}     Fetch CI pass rate/flakiness from GitHub (token)
+  {}--remote github{} Add branch protection, reviews, alerts to the score (or gitlab)
+  {}--staged{}        Only check and report staged files
+  {}--changed{}       Only changed files since --base REF (PR gates)
+  {}--check-links{}   Check relative links in Markdown docs (--check-http-links: URLs too)
+  {}--files-from{}    Only check and report paths listed in FILE (- for stdin)
+  {}--no-cache{}      Re-read every file (skip the scan cache)
+  {}--events jsonl{}  Progress events on stderr (--events-fd N: another fd)
+  {}--online{}        Check registries for newer major versions of dependencies
+  {}--ci{}, {}--local{}  Force CI defaults (plain output, [ci] config) or local ones
+  {}--no-banner{}     No banner above help and examples
+  {}--sample N{}      Read N files per content rule and estimate the rest
+  {}--offline{}       Use the cached policy instead of fetching it
+  {}--also{}          Also write FORMAT=FILE in the same run (repeatable)
+
+{}HEALTH SCORE:{}
+  A (90-100)  Excellent - Ready for production
+  B (80-89)   Good - Minor improvements needed
+  C (70-79)   Fair - Some issues to address
+  D (60-69)   Poor - Needs attention
+  F (<60)     Failing - Critical issues
+
+{}MORE INFO:{}
+{}"#,
+        cyan,
+        VERSION,
+        reset,
+        yellow,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        yellow,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        green,
+        reset,
+        yellow,
+        reset,
+        yellow,
+        reset,
+        more_info
+    );
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Console,
+    Json,
+    /// One JSON object per finding per line, then a summary line (scan-all
+    /// streams each repository as soon as it is scanned)
+    Ndjson,
+    Markdown,
+    Sarif,
+    /// LSP PublishDiagnostics JSON keyed by file URI (for editor integrations)
+    LspDiagnostics,
+    /// GitHub Actions annotations, plus a job summary when $GITHUB_STEP_SUMMARY is set
+    Github,
+    /// Terse file:line findings on staged lines only (`git diff --cached`), for pre-commit
+    Hook,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum EventsFormat {
+    /// One JSON object per line, tagged by `event`
+    Jsonl,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FailLevel {
+    /// Errors only (the default)
+    Error,
+    /// Errors and warnings
+    Warning,
+    /// Any issue
+    Info,
+    /// Never exit 2 or 3 for issues
+    None,
+}
+
+impl FailLevel {
+    /// The least severe issue that fails the run.
+    fn threshold(self) -> Option<scanner::Severity> {
+        match self {
+            FailLevel::Error => Some(scanner::Severity::Error),
+            FailLevel::Warning => Some(scanner::Severity::Warning),
+            FailLevel::Info => Some(scanner::Severity::Info),
+            FailLevel::None => None,
+        }
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "repomedic")]
+#[command(author = "LazyFrog <support@kindware.dev>")]
+#[command(version = VERSION)]
+#[command(about = "Local Git repository scanner and hygiene tool")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    /// Path to repository (default: current directory)
+    #[arg(global = true, default_value = ".")]
+    path: PathBuf,
+
+    /// Output format [default: console, or `format` from .repomedic.toml]
+    #[arg(long, short = 'f', value_enum, global = true)]
+    format: Option<OutputFormat>,
+
+    /// Quiet mode (exit code only, no output)
+    #[arg(long, short = 'q', global = true)]
+    quiet: bool,
+
+    /// Verbose mode (show scan stats, timing)
+    #[arg(long, short = 'v', global = true)]
+    verbose: bool,
+
+    /// Disable colored output
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Don't print the banner above help and examples
+    #[arg(long, global = true)]
+    no_banner: bool,
+
+    #[command(flatten)]
+    profile: ProfileArgs,
+
+    /// Least severe issue that exits 2 (3 when it's a secret)
+    #[arg(long, global = true, value_enum, value_name = "LEVEL")]
+    fail_level: Option<FailLevel>,
+
+    /// Same as --fail-level warning
+    #[arg(long, global = true, conflicts_with = "fail_level")]
+    fail_on_warning: bool,
+
+    /// Output only the numeric health score (0-100) for scripting
+    #[arg(long, global = true)]
+    score_only: bool,
+
+    /// Watch mode: continuously monitor repository health
+    #[arg(long, short = 'w', global = true)]
+    watch: bool,
+
+    /// Watch interval in seconds (default: 30)
+    #[arg(long, default_value = "30", global = true)]
+    interval: u64,
+
+    /// Shorthand for --format json (useful for CI/CD scripting)
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Also list passed checks (useful for audits and stakeholder reports)
+    #[arg(long, global = true)]
+    show_passed: bool,
+
+    /// Write a redacted evidence bundle for each finding into this directory
+    #[arg(long, global = true, value_name = "DIR")]
+    evidence: Option<PathBuf>,
+
+    /// Also write the report in another format to a file, e.g. json=report.json (repeatable)
+    #[arg(long, global = true, value_name = "FORMAT=FILE", value_parser = parse_also)]
+    also: Vec<(OutputFormat, PathBuf)>,
+
+    /// Config file (default: .repomedic.toml in the repository root)
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Large file threshold in MB (overrides config, default: 5)
+    #[arg(long, global = true, value_name = "MB")]
+    large_file_threshold: Option<u64>,
+
+    /// Read only N files per content rule and estimate the rest (secrets are always exhaustive)
+    #[arg(long, global = true, value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    sample: Option<u64>,
+
+    /// Skip detectors by name or category (comma-separated, see `detectors`)
+    #[arg(long, global = true, value_delimiter = ',', value_name = "NAME")]
+    skip: Vec<String>,
+
+    /// Only report issues not recorded in this baseline file (see `baseline`)
+    #[arg(long, global = true, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Attach git blame (last author and date) to file-located findings
+    #[arg(long, global = true)]
+    blame: bool,
+
+    /// Fetch CI pass rate and flakiness from the GitHub API (needs GITHUB_TOKEN or GH_TOKEN)
+    #[arg(long, global = true)]
+    ci_health: bool,
+
+    /// Read branch protection, required reviews, and more from the host's API (github: GITHUB_TOKEN or GH_TOKEN; gitlab: GITLAB_TOKEN)
+    #[arg(long, global = true, value_name = "HOST", value_parser = remote::HOSTS.to_vec())]
+    remote: Option<String>,
+
+    /// Check relative links in README and docs Markdown files
+    #[arg(long, global = true)]
+    check_links: bool,
+
+    /// Also request HTTP(S) links in Markdown docs (implies --check-links)
+    #[arg(long, global = true)]
+    check_http_links: bool,
+
+    /// Ask crates.io, npm, PyPI, and the Go proxy for newer major versions of dependencies
+    #[arg(long, global = true)]
+    online: bool,
+
+    /// Limit file-level checks and the report to staged files
+    #[arg(long, global = true, conflicts_with = "changed")]
+    staged: bool,
+
+    /// Limit file-level checks and the report to files changed since --base
+    #[arg(long, global = true, requires = "base")]
+    changed: bool,
+
+    /// Base ref for --changed (e.g. origin/main)
+    #[arg(long, global = true, value_name = "REF")]
+    base: Option<String>,
+
+    /// Limit file-level checks and the report to the paths listed in FILE
+    /// (one per line, or NUL-separated; `-` reads stdin)
+    #[arg(long, global = true, value_name = "FILE", conflicts_with_all = ["staged", "changed"])]
+    files_from: Option<PathBuf>,
+
+    /// Date and number format for exports (e.g. en-US, de-DE, iso; default: from LANG)
+    #[arg(long, global = true, value_name = "LOCALE", value_parser = Locale::parse)]
+    locale: Option<&'static Locale>,
+
+    /// Emit progress events (checks started/completed, findings) on stderr
+    #[arg(long, global = true, value_name = "FORMAT")]
+    events: Option<EventsFormat>,
+
+    /// Write progress events to this file descriptor (3 or above) instead (implies --events jsonl)
+    #[arg(long, global = true, value_name = "N")]
+    events_fd: Option<u32>,
+
+    /// Re-examine every file instead of skipping unchanged ones via the scan cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Use the cached copy of the config's policy URL instead of fetching it
+    #[arg(long, global = true)]
+    offline: bool,
+
+    /// Contents of --files-from, read once so rescans reuse it
+    #[arg(skip)]
+    file_list: Option<String>,
+
+    /// Run the bundled fixture repositories and compare against golden reports
+    #[arg(long)]
+    self_test: bool,
+}
+
+/// `--ci` and `--local`, taken before the subcommand or by the ones that
+/// scan. Not global, so `generate --ci <provider>` keeps its own meaning.
+#[derive(Args, Clone, Copy, Default)]
+struct ProfileArgs {
+    /// CI defaults: no color or banner, plus the config's [ci] fail level and format
+    #[arg(long, conflicts_with = "local")]
+    ci: bool,
+
+    /// Local defaults even when CI, GITHUB_ACTIONS, or GITLAB_CI is set
+    #[arg(long)]
+    local: bool,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Scan repository and print summary
+    Scan {
+        /// Show health score and badge
+        #[arg(long)]
+        score: bool,
+
+        /// With --score, list the points each finding and bonus contributed
+        #[arg(long, requires = "score")]
+        explain: bool,
+
+        /// Show fix suggestions with commands
+        #[arg(long)]
+        suggest: bool,
+
+        /// With --suggest, also write the fix commands to a script to review
+        /// and run (PowerShell for .ps1, sh otherwise)
+        #[arg(long, value_name = "FILE", requires = "suggest")]
+        emit_script: Option<PathBuf>,
+
+        /// Scan each repository in DIR and compare with its <name>.golden report
+        #[arg(long, value_name = "DIR")]
+        fixture: Option<PathBuf>,
+
+        #[command(flatten)]
+        profile: ProfileArgs,
+    },
+
+    /// Scan every Git repository under a parent directory and summarize scores
+    ScanAll {
+        /// How many directory levels below PATH to search for repositories
+        #[arg(long, default_value_t = batch::DEFAULT_DEPTH)]
+        depth: usize,
+
+        /// Write the aggregated results to a file (.json, .csv, .md)
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+
+        #[command(flatten)]
+        profile: ProfileArgs,
+    },
+
+    /// Clone and scan the repositories of a GitHub organization
+    Org {
+        #[command(subcommand)]
+        action: OrgAction,
+    },
+
+    /// One-line health check (great for shell prompts)
+    Doctor {
+        /// Also write score, grade, counts, and HEAD commit as JSON to this file
+        #[arg(long, value_name = "FILE")]
+        json_out: Option<PathBuf>,
+
+        #[command(flatten)]
+        profile: ProfileArgs,
+    },
+
+    /// Re-scan whenever files change and print the doctor line and what changed
+    Watch {
+        /// Print only the score on each change
+        #[arg(long)]
+        score: bool,
+
+        #[command(flatten)]
+        profile: ProfileArgs,
+    },
+
+    /// Interactive dashboard: score, issues, fixes, and secrets, with fixes one key away
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Table of GitHub Actions workflows: triggers, cron schedules, and permissions
+    Workflows,
+
+    /// Print a shields.io badge for the health grade (--write puts it in the README)
+    Badge {
+        /// shields.io style: flat, flat-square, plastic, for-the-badge, social
+        #[arg(long, value_parser = report::BADGE_STYLES.to_vec())]
+        style: Option<String>,
+
+        /// Make the badge a link to this URL
+        #[arg(long, value_name = "URL")]
+        link: Option<String>,
+
+        /// Write the badge into the README between repomedic-badge markers
+        #[arg(long)]
+        write: bool,
+
+        /// Also draw the badge to an SVG file, offline, and point the Markdown at it
+        #[arg(long, value_name = "FILE")]
+        svg: Option<PathBuf>,
+    },
+
+    /// Record this scan in .repomedic/history.jsonl and show the score trend
+    History {
+        /// Show the trend without recording a new scan
+        #[arg(long)]
+        no_record: bool,
+
+        /// Show only the last N scans
+        #[arg(long, value_name = "N")]
+        last: Option<usize>,
+    },
+
+    /// List available detectors with their category and prerequisites
+    Detectors,
+
+    /// List supported formats, rules, generators, fixers, and features
+    Capabilities,
+
+    /// Print the JSON Schema of --format json reports
+    Schema,
+
+    /// List checks or explain one (codes such as RM001, or IDs)
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+
+    /// Install or remove a git pre-commit hook that runs repomedic
+    Hook {
+        #[command(subcommand)]
+        action: HookAction,
+    },
+
+    /// Show usage examples
+    Examples,
+
+    /// Show detailed help and feature explanations
+    Info,
+
+    /// Export scan results to file (.json, .md, .txt, .csv, .tsv, .html)
+    Export {
+        /// Output file path (format detected from extension)
+        file: PathBuf,
+    },
+
+    /// Record current issues so later scans with --baseline only report new ones
+    Baseline {
+        /// Output file (default: repomedic-baseline.json in the repository root)
+        file: Option<PathBuf>,
+    },
+
+    /// Initialize a healthy repository (generate all recommended files)
+    Init {
+        /// Author name for LICENSE
+        #[arg(long)]
+        author: Option<String>,
+
+        /// License to generate: mit, apache-2.0, gpl-2.0, gpl-3.0, lgpl-2.1,
+        /// mpl-2.0, bsd-2-clause, bsd-3-clause, isc, unlicense, cc0-1.0
+        #[arg(long, value_name = "TYPE", value_parser = Choice::parse)]
+        license_type: Option<&'static Choice>,
+
+        /// Preview without writing files
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Generate REPO_REPORT.md
+    Report,
+
+    /// Repair fixable issues (missing files, .gitignore gaps, EOF newlines, committed .env)
+    Fix {
+        /// Preview fixes without changing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Confirm each fix before applying it
+        #[arg(long, short = 'i')]
+        interactive: bool,
+
+        /// Apply every fix without asking (including git index changes)
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Author name for LICENSE
+        #[arg(long)]
+        author: Option<String>,
+
+        /// License to generate: mit, apache-2.0, gpl-2.0, gpl-3.0, lgpl-2.1,
+        /// mpl-2.0, bsd-2-clause, bsd-3-clause, isc, unlicense, cc0-1.0
+        #[arg(long, value_name = "TYPE", value_parser = Choice::parse)]
+        license_type: Option<&'static Choice>,
+    },
+
+    /// Generate missing files (README, LICENSE, .gitignore, etc.)
+    Generate {
+        /// Generate README.md
+        #[arg(long)]
+        readme: bool,
+
+        /// Generate LICENSE (MIT unless --license-type is given)
+        #[arg(long)]
+        license: bool,
+
+        /// Generate .gitignore
+        #[arg(long)]
+        gitignore: bool,
+
+        /// Bundled github/gitignore templates to add to .gitignore besides
+        /// the detected languages' (implies --gitignore): Rust,Node,macOS
+        #[arg(
+            long,
+            value_name = "NAMES",
+            value_delimiter = ',',
+            value_parser = Template::parse
+        )]
+        gitignore_template: Vec<&'static Template>,
+
+        /// Generate CONTRIBUTING.md
+        #[arg(long)]
+        contributing: bool,
+
+        /// Generate CHANGELOG.md
+        #[arg(long)]
+        changelog: bool,
+
+        /// Generate .editorconfig
+        #[arg(long)]
+        editorconfig: bool,
+
+        /// Generate CODE_OF_CONDUCT.md
+        #[arg(long)]
+        codeofconduct: bool,
+
+        /// Generate SECURITY.md
+        #[arg(long)]
+        security: bool,
+
+        /// Generate bug report and feature request templates in .github/ISSUE_TEMPLATE
+        #[arg(long)]
+        issue_template: bool,
+
+        /// Generate .github/PULL_REQUEST_TEMPLATE.md
+        #[arg(long)]
+        pr_template: bool,
+
+        /// Generate a starter codecov.yml
+        #[arg(long)]
+        codecov: bool,
+
+        /// Generate .github/dependabot.yml for the detected ecosystems
+        #[arg(long)]
+        dependabot: bool,
+
+        /// Generate .gitattributes with Git LFS patterns for the detected asset types
+        #[arg(long)]
+        gitattributes: bool,
+
+        /// Generate .dockerignore for the detected languages
+        #[arg(long)]
+        dockerignore: bool,
+
+        /// Generate a starter CI pipeline for the detected build systems:
+        /// github, gitlab, circleci
+        #[arg(long, value_name = "PROVIDER", value_parser = Provider::parse)]
+        ci: Option<Provider>,
+
+        /// Generate all missing files
+        #[arg(long)]
+        all: bool,
+
+        /// Preview generation without writing files; existing files show a
+        /// diff of what --force would change
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Overwrite files that already exist
+        #[arg(long)]
+        force: bool,
+
+        /// Append only the missing sections to an existing .gitignore or .editorconfig
+        #[arg(long, conflicts_with = "force")]
+        merge: bool,
+
+        /// Directory of .hbs templates that replace the built-in files
+        /// (README.md.hbs, LICENSE.hbs, ...); default: `templates` in config
+        #[arg(long, value_name = "DIR")]
+        templates: Option<PathBuf>,
+
+        /// Author name for LICENSE
+        #[arg(long)]
+        author: Option<String>,
+
+        /// License to generate: mit, apache-2.0, gpl-2.0, gpl-3.0, lgpl-2.1,
+        /// mpl-2.0, bsd-2-clause, bsd-3-clause, isc, unlicense, cc0-1.0
+        #[arg(long, value_name = "TYPE", value_parser = Choice::parse)]
+        license_type: Option<&'static Choice>,
+    },
+}
+
+#[derive(Subcommand)]
+enum RulesAction {
+    /// List every check with its code, category, severity, and status
+    List,
+
+    /// Show a check's metadata, fix hint, and how to configure it
+    Explain {
+        /// Code (RM001), ID (missing-readme), or both (RM001-missing-readme)
+        rule: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum OrgAction {
+    /// Shallow-clone each repository to a temp dir, scan it, and rank the scores
+    Scan {
+        /// Organization (or user) login on GitHub
+        org: String,
+
+        /// Also scan forks
+        #[arg(long)]
+        include_forks: bool,
+
+        /// Also scan archived repositories
+        #[arg(long)]
+        include_archived: bool,
+
+        /// Scan at most N repositories (in name order)
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+
+        /// Write the leaderboard to a file (.json, .csv, .md)
+        #[arg(long, value_name = "FILE")]
+        export: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HookAction {
+    /// Write .git/hooks/pre-commit running `repomedic --quiet --fail-on-warning`
+    Install {
+        /// Add an entry to .pre-commit-config.yaml instead (pre-commit framework)
+        #[arg(long)]
+        framework: bool,
+
+        /// Replace an existing hook, keeping it as pre-commit.bak
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Remove the hook (restoring pre-commit.bak) and the framework entry
+    Uninstall,
+}
+
+/// Build the detector registry, honoring --skip.
+fn build_registry(cli: &Cli) -> Registry {
+    let mut registry = Registry::builtin();
+    registry.retain(|d| {
+        !cli.skip
+            .iter()
+            .any(|s| s == d.name() || s == d.category().name())
+    });
+    registry
+}
+
+/// Where --events / --events-fd progress goes, if anywhere.
+fn event_writer(cli: &Cli) -> Option<EventWriter> {
+    let out: Box<dyn std::io::Write> = match cli.events_fd {
+        Some(fd) => match events_file(fd) {
+            Ok(file) => Box::new(file),
+            Err(e) => {
+                eprintln!("Error: cannot write events to fd {}: {}", fd, e);
+                process::exit(1);
+            }
+        },
+        None => match cli.events {
+            Some(EventsFormat::Jsonl) => Box::new(std::io::stderr()),
+            None => return None,
+        },
+    };
+    Some(EventWriter::new(out))
+}
+
+/// Take over the descriptor passed with --events-fd.
+#[cfg(unix)]
+fn events_file(fd: u32) -> std::io::Result<std::fs::File> {
+    use std::mem::ManuallyDrop;
+    use std::os::unix::io::{FromRawFd, RawFd};
+    let fd = RawFd::try_from(fd).map_err(|_| std::io::ErrorKind::InvalidInput)?;
+    // Taking over a standard stream would close it when the scan ends
+    if fd <= 2 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "0-2 are the standard streams; use --events jsonl for stderr",
+        ));
+    }
+    // SAFETY: the caller hands the descriptor to us with --events-fd. It's
+    // only closed once fstat shows it is open.
+    let file = ManuallyDrop::new(unsafe { std::fs::File::from_raw_fd(fd) });
+    file.metadata()?;
+    Ok(ManuallyDrop::into_inner(file))
+}
+
+#[cfg(not(unix))]
+fn events_file(_fd: u32) -> std::io::Result<std::fs::File> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "--events-fd is only supported on Unix; use --events jsonl",
+    ))
+}
+
+/// Load the repository config and apply CLI overrides.
+fn load_config(cli: &Cli, path: &std::path::Path) -> Result<Config, scanner::ScanError> {
+    let mut config = match cli.config {
+        Some(ref file) => Config::load_file_with(file, path, cli.offline)?,
+        None => Config::load_with(path, cli.offline)?,
+    };
+    if active_profile(cli) == Profile::Ci && config.ci_format.is_some() {
+        config.format = config.ci_format.clone();
+    }
+    if let Some(ref level) = config.ci_fail_level {
+        if FailLevel::from_str(level, true).is_err() {
+            return Err(scanner::ScanError {
+                kind: scanner::ScanErrorKind::Parse,
+                path: config::CONFIG_FILES[0].to_string(),
+                message: format!(
+                    "[ci] fail_level must be error, warning, info, or none, not \"{}\"",
+                    level
+                ),
+            });
+        }
+    }
+    if let Some(mb) = cli.large_file_threshold {
+        config.large_file_threshold_mb = Some(mb);
+    }
+    if let Some(n) = cli.sample {
+        config.sample = Some(n as usize);
+    }
+    if cli.blame {
+        config.blame = true;
+    }
+    if cli.locale.is_some() {
+        config.locale = cli.locale;
+    }
+    if cli.no_cache {
+        config.cache = Some(false);
+    }
+    // On by default only where the cache can hide in the Git directory
+    if config.cache.is_none() {
+        config.cache = Some(cache::git_location(path).is_some());
+    }
+    if cli.ci_health {
+        config.ci_health = true;
+    }
+    if cli.remote.is_some() {
+        config.remote = cli.remote.clone();
+    }
+    if cli.check_links {
+        config.check_links = true;
+    }
+    if cli.check_http_links {
+        config.check_http_links = true;
+    }
+    if cli.online {
+        config.online = true;
+    }
+    if let Some(ref file) = cli.baseline {
+        let file = std::env::current_dir()
+            .map(|cwd| cwd.join(file))
+            .unwrap_or_else(|_| file.clone());
+        if !file.is_file() {
+            let err = std::io::Error::new(std::io::ErrorKind::NotFound, "baseline file not found");
+            return Err(scanner::ScanError::io(file.display().to_string(), &err));
+        }
+        config.baseline = Some(file.display().to_string());
+    }
+    // The hook format is always a staged-changes gate
+    let hook = matches!(cli.format, Some(OutputFormat::Hook))
+        || (cli.format.is_none() && config.format.as_deref() == Some("hook"));
+    let scope = if cli.staged || hook {
+        Some(("staged files".to_string(), git::staged_files(path)))
+    } else if cli.changed {
+        let base = cli.base.as_deref().unwrap_or_default();
+        Some((
+            format!("files changed since {}", base),
+            git::changed_files(path, base),
+        ))
+    } else {
+        let listed = cli.file_list.as_deref().map(git::listed_files);
+        listed.map(|files| ("listed files".to_string(), Some(files)))
+    };
+    if let Some((what, files)) = scope {
+        let err = std::io::Error::other(format!(
+            "could not list {} (not a git repository, or unknown ref)",
+            what
+        ));
+        let files = files.ok_or_else(|| scanner::ScanError::io(report::clean_path(path), &err))?;
+        config.changed_files = Some(files);
+    }
+    Ok(config)
+}
+
+/// Parse an `--also` value, `FORMAT=FILE`.
+fn parse_also(value: &str) -> Result<(OutputFormat, PathBuf), String> {
+    let (name, file) = value
+        .split_once('=')
+        .filter(|(_, file)| !file.is_empty())
+        .ok_or("expected FORMAT=FILE, e.g. json=report.json")?;
+    match OutputFormat::from_str(name, true)? {
+        OutputFormat::Hook => Err("hook output is only available with --format hook".to_string()),
+        format => Ok((format, PathBuf::from(file))),
+    }
+}
+
+/// The report `format` writes to an `--also` file; `console` is the plain
+/// text report.
+fn also_report(
+    format: OutputFormat,
+    result: &scanner::ScanResult,
+    path: &std::path::Path,
+) -> String {
+    match format {
+        OutputFormat::Console | OutputFormat::Hook => report::to_text(result, path),
+        OutputFormat::Json => report::to_json(result, path) + "\n",
+        OutputFormat::Ndjson => report::to_ndjson(result, path),
+        OutputFormat::Markdown => report::to_markdown(result, path),
+        OutputFormat::Sarif => report::to_sarif(result, path) + "\n",
+        OutputFormat::LspDiagnostics => report::to_lsp_diagnostics(result, path) + "\n",
+        OutputFormat::Github => report::to_github_annotations(result),
+    }
+}
+
+/// Resolve the output format: CLI flag, then config, then console.
+fn output_format(cli: &Cli, config: &Config) -> OutputFormat {
+    if let Some(f) = cli.format {
+        return f;
+    }
+    match config.format {
+        Some(ref name) => OutputFormat::from_str(name, true).unwrap_or_else(|_| {
+            eprintln!(
+                "Error: invalid format '{}' in config (expected console, json, ndjson, markdown, sarif, lsp-diagnostics, github, hook)",
+                name
+            );
+            process::exit(1);
+        }),
+        None => OutputFormat::Console,
+    }
+}
+
+/// Print the detector registry as a table.
+fn print_detectors(registry: &Registry) {
+    println!("{:14} {:10} REQUIRES", "DETECTOR", "CATEGORY");
+    for d in registry.ordered() {
+        let requires = if d.requires().is_empty() {
+            "-".to_string()
+        } else {
+            d.requires().join(", ")
+        };
+        println!("{:14} {:10} {}", d.name(), d.category().name(), requires);
+    }
+}
+
+/// Print every check with its effective severity and status under `config`.
+fn print_rules(config: &Config, json: bool) {
+    let checks = rules::all();
+    if json {
+        let mut list: Vec<_> = checks
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "code": c.code,
+                    "id": c.id,
+                    "category": c.category,
+                    "severity": config.severity_for(c.id).unwrap_or(c.severity),
+                    "enabled": !config.is_disabled(c.id),
+                    "description": c.description,
+                    "fix": c.fix,
+                })
+            })
+            .collect();
+        list.extend(config.custom_rules.iter().map(|r| {
+            serde_json::json!({
+                "code": null,
+                "id": r.id,
+                "custom": true,
+                "severity": config.severity_for(&r.id).unwrap_or(r.severity),
+                "enabled": !config.is_disabled(&r.id),
+                "description": r.description(),
+            })
+        }));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&list).unwrap_or_else(|_| "[]".to_string())
+        );
+        return;
+    }
+    println!(
+        "{:6} {:32} {:10} {:8} {:8} DESCRIPTION",
+        "CODE", "ID", "CATEGORY", "SEVERITY", "STATUS"
+    );
+    for c in &checks {
+        let severity = config.severity_for(c.id).unwrap_or(c.severity);
+        println!(
+            "{:6} {:32} {:10} {:8} {:8} {}",
+            c.code,
+            c.id,
+            c.category.name(),
+            severity.name(),
+            if config.is_disabled(c.id) {
+                "disabled"
+            } else {
+                "enabled"
+            },
+            c.description
+        );
+    }
+    for r in &config.custom_rules {
+        let severity = config.severity_for(&r.id).unwrap_or(r.severity);
+        println!(
+            "{:6} {:32} {:10} {:8} {:8} {}",
+            "-",
+            r.id,
+            "custom",
+            severity.name(),
+            if config.is_disabled(&r.id) {
+                "disabled"
+            } else {
+                "enabled"
+            },
+            r.description()
+        );
+    }
+}
+
+/// Print one check's metadata and how to configure it.
+fn print_rule_explanation(check: &rules::Check, config: &Config) {
+    println!("{}  {}", check.full_id(), check.description);
+    println!("\nCategory:  {}", check.category.name());
+    match config.severity_for(check.id) {
+        Some(s) if s != check.severity => println!(
+            "Severity:  {} (default {}, overridden in config)",
+            s.name(),
+            check.severity.name()
+        ),
+        _ => println!("Severity:  {}", check.severity.name()),
+    }
+    println!(
+        "Status:    {}",
+        if config.is_disabled(check.id) {
+            "disabled in config"
+        } else {
+            "enabled"
+        }
+    );
+    println!("Fix:       {}", check.fix);
+    println!("\nConfigure in .repomedic.toml:");
+    println!("  [checks]\n  disable = [\"{}\"]", check.id);
+    println!("\n  [severity]\n  {} = \"info\"", check.id);
+    println!(
+        "\nSuppress specific findings in {} (`{} [path[:line]]`) or with a\n`repomedic:ignore {}` comment on or above the flagged line.",
+        suppress::IGNORE_FILE,
+        check.id,
+        check.id
+    );
+}
+
+/// Print a custom rule from the config.
+fn print_custom_rule_explanation(rule: &CustomRule, config: &Config) {
+    println!("{}  {}", rule.id, rule.description());
+    println!(
+        "\nCustom rule from {}",
+        config.source.as_deref().unwrap_or(config::CONFIG_FILES[0])
+    );
+    match config.severity_for(&rule.id) {
+        Some(s) if s != rule.severity => println!(
+            "Severity:  {} (default {}, overridden in config)",
+            s.name(),
+            rule.severity.name()
+        ),
+        _ => println!("Severity:  {}", rule.severity.name()),
+    }
+    println!(
+        "Status:    {}",
+        if config.is_disabled(&rule.id) {
+            "disabled in config"
+        } else {
+            "enabled"
+        }
+    );
+}
+
+/// Print capabilities as plain text.
+fn print_capabilities(caps: &Capabilities) {
+    println!("repomedic {}", caps.version);
+    println!("\nOutput formats: {}", caps.output_formats.join(", "));
+    println!("Export formats: {}", caps.export_formats.join(", "));
+    println!("\nRules:");
+    for r in &caps.rules {
+        println!("  {:24} {:8} {}", r.id, r.level, r.description);
+    }
+    println!(
+        "\nDetectors: {}",
+        caps.detectors
+            .iter()
+            .map(|d| d.name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!("Generators: {}", caps.generators.join(", "));
+    println!(
+        "Fixers: {}",
+        caps.fixers
+            .iter()
+            .map(|f| f.id)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!(
+        "Features: {}",
+        caps.features
+            .iter()
+            .map(|f| format!("{}{}", f.name, if f.enabled { "" } else { " (off)" }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    if !caps.plugins.is_empty() {
+        println!("Plugins: {}", caps.plugins.join(", "));
+    }
+}
+
+/// Print fixture results and return the process exit code.
+fn run_self_test(results: std::io::Result<Vec<selftest::CaseResult>>, no_color: bool) -> i32 {
+    let results = match results {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error running self-test: {}", e);
+            return 1;
+        }
+    };
+    let use_color = report::use_color(no_color);
+    let (green, red, yellow, reset) = if use_color {
+        ("\x1b[32m", "\x1b[31m", "\x1b[33m", "\x1b[0m")
+    } else {
+        ("", "", "", "")
+    };
+    let mut failed = 0;
+    for case in &results {
+        match &case.outcome {
+            selftest::Outcome::Pass => println!("{}ok{}      {}", green, reset, case.name),
+            selftest::Outcome::Recorded(golden) => println!(
+                "{}recorded{} {} -> {}",
+                yellow,
+                reset,
+                case.name,
+                report::clean_path(golden)
+            ),
+            selftest::Outcome::Violation(msg) => {
+                println!("{}FAIL{}    {}: {}", red, reset, case.name, msg)
+            }
+            selftest::Outcome::Mismatch { expected, actual } => {
+                println!(
+                    "{}FAIL{}    {}: report differs from golden",
+                    red, reset, case.name
+                );
+                for line in expected
+                    .lines()
+                    .filter(|l| !actual.lines().any(|a| a == *l))
+                {
+                    println!("    - {}", line);
+                }
+                for line in actual
+                    .lines()
+                    .filter(|l| !expected.lines().any(|e| e == *l))
+                {
+                    println!("    + {}", line);
+                }
+            }
+        }
+        if !case.passed() {
+            failed += 1;
+        }
+    }
+    println!(
+        "\n{} fixture(s), {} passed, {} failed",
+        results.len(),
+        results.len() - failed,
+        failed
+    );
+    if failed > 0 {
+        2
+    } else {
+        0
+    }
+}
+
+/// Scan every repository under `root`; returns the process exit code.
+fn run_scan_all(
+    cli: &Cli,
+    root: &std::path::Path,
+    depth: usize,
+    export: Option<&std::path::Path>,
+) -> i32 {
+    let repos = batch::discover(root, depth);
+    if repos.is_empty() {
+        eprintln!(
+            "No Git repositories found under {} (depth {})",
+            report::clean_path(root),
+            depth
+        );
+        return 1;
+    }
+    let registry = build_registry(cli);
+    let stream = !cli.quiet && matches!(cli.format, Some(OutputFormat::Ndjson));
+    let mut summaries = Vec::new();
+    for repo in &repos {
+        let name = batch::repo_name(root, repo);
+        if cli.verbose {
+            eprintln!("Scanning {}", name);
+        }
+        summaries.push(scan_summary(cli, &registry, name, repo, repo, stream));
+    }
+    batch::sort_for_triage(&mut summaries);
+    finish_batch(cli, root, &summaries, export)
+}
+
+/// Scan one repository of a batch, streaming its findings as NDJSON under
+/// `label` when `stream` is set.
+fn scan_summary(
+    cli: &Cli,
+    registry: &Registry,
+    name: String,
+    repo: &std::path::Path,
+    label: &std::path::Path,
+    stream: bool,
+) -> batch::RepoSummary {
+    let scanned = load_config(cli, repo).and_then(|c| scanner::scan_with(repo, registry, c));
+    if stream {
+        let mut stdout = std::io::stdout();
+        let _ = match &scanned {
+            Ok(result) => report::stream_ndjson(result, label, &mut stdout),
+            Err(e) => report::write_ndjson(
+                &mut stdout,
+                &report::NdjsonRecord::Error {
+                    repository: &report::clean_path(label),
+                    message: &e.to_string(),
+                },
+            ),
+        };
+    }
+    match scanned {
+        Ok(result) => batch::RepoSummary::from_result(name, &result),
+        Err(e) => batch::RepoSummary::failed(name, &e),
+    }
+}
+
+/// Print and export sorted batch results and return the exit code.
+fn finish_batch(
+    cli: &Cli,
+    root: &std::path::Path,
+    summaries: &[batch::RepoSummary],
+    export: Option<&std::path::Path>,
+) -> i32 {
+    let stream = matches!(cli.format, Some(OutputFormat::Ndjson));
+    if !cli.quiet && !stream {
+        if cli.json || matches!(cli.format, Some(OutputFormat::Json)) {
+            println!("{}", batch::to_json(root, summaries));
+        } else if matches!(cli.format, Some(OutputFormat::Markdown)) {
+            print!("{}", batch::to_markdown(root, summaries));
+        } else {
+            batch::print_table(summaries, report::use_color(cli.no_color));
+        }
+    }
+    if let Some(file) = export {
+        if let Err(e) = batch::export_to_file(root, summaries, file) {
+            eprintln!("Error exporting: {}", e);
+            return 1;
+        }
+    }
+
+    // The [ci] policy comes from the parent folder's config, if any
+    let config = match load_config(cli, root) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return 1;
+        }
+    };
+    let code = summaries
+        .iter()
+        .map(|s| report::exit_code(&s.issues, fail_at(cli, &config)))
+        .max()
+        .unwrap_or(0);
+    if code == 0 && summaries.iter().any(|s| s.scan_error.is_some()) {
+        return 2;
+    }
+    code
+}
+
+impl Commands {
+    /// The `--ci` / `--local` flags of a subcommand that scans.
+    fn profile(&self) -> Option<ProfileArgs> {
+        match self {
+            Commands::Scan { profile, .. }
+            | Commands::ScanAll { profile, .. }
+            | Commands::Doctor { profile, .. }
+            | Commands::Watch { profile, .. } => Some(*profile),
+            _ => None,
+        }
+    }
+}
+
+/// `org scan`: list the organization's repositories, then clone, scan, and
+/// delete them one at a time.
+fn run_org_scan(cli: &Cli, scan: &OrgAction) -> i32 {
+    let OrgAction::Scan {
+        org,
+        include_forks,
+        include_archived,
+        limit,
+        export,
+    } = scan;
+    let token = github::token().unwrap_or_default();
+    let repos = match github::list_repos(org, &token) {
+        Ok(repos) => repos,
+        Err(e) => {
+            eprintln!("Error listing repositories of {}: {}", org, e);
+            return 1;
+        }
+    };
+    let repos: Vec<github::OrgRepo> = repos
+        .into_iter()
+        .filter(|r| *include_forks || !r.fork)
+        .filter(|r| *include_archived || !r.archived)
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    if repos.is_empty() {
+        eprintln!("No repositories to scan in {}", org);
+        return 1;
+    }
+    let workdir = std::env::temp_dir().join(format!("repomedic-org-{}", process::id()));
+    let registry = build_registry(cli);
+    let stream = !cli.quiet && matches!(cli.format, Some(OutputFormat::Ndjson));
+    let mut summaries = Vec::new();
+    for (i, repo) in repos.iter().enumerate() {
+        if !cli.quiet {
+            eprintln!("[{}/{}] {}", i + 1, repos.len(), repo.full_name);
+        }
+        let dest = workdir.join(repo.full_name.replace('/', "__"));
+        let label = PathBuf::from(format!("github.com/{}", repo.full_name));
+        let summary = match github::shallow_clone(&repo.clone_url, &dest, &token) {
+            Ok(()) => scan_summary(
+                cli,
+                &registry,
+                repo.full_name.clone(),
+                &dest,
+                &label,
+                stream,
+            ),
+            Err(e) => {
+                let err = scanner::ScanError::network(&repo.clone_url, e);
+                if stream {
+                    let _ = report::write_ndjson(
+                        &mut std::io::stdout(),
+                        &report::NdjsonRecord::Error {
+                            repository: &report::clean_path(&label),
+                            message: &err.to_string(),
+                        },
+                    );
+                }
+                batch::RepoSummary::failed(repo.full_name.clone(), &err)
+            }
+        };
+        summaries.push(summary);
+        let _ = std::fs::remove_dir_all(&dest);
+    }
+    let _ = std::fs::remove_dir_all(&workdir);
+    batch::sort_for_leaderboard(&mut summaries);
+    let root = PathBuf::from(format!("github.com/{}", org));
+    finish_batch(cli, &root, &summaries, export.as_deref())
+}
+
+/// `--ci` or `--local`, else detected from the environment.
+fn active_profile(cli: &Cli) -> Profile {
+    if cli.profile.ci {
+        Profile::Ci
+    } else if cli.profile.local {
+        Profile::Local
+    } else {
+        Profile::detect()
+    }
+}
+
+/// The least severe issue that fails the run, from `--fail-level` or
+/// `--fail-on-warning`, else the config's `[ci] fail_level` in CI. Flags on
+/// the command line always win over the config.
+fn fail_at(cli: &Cli, config: &Config) -> Option<scanner::Severity> {
+    if let Some(level) = cli.fail_level {
+        return level.threshold();
+    }
+    if cli.fail_on_warning {
+        return Some(scanner::Severity::Warning);
+    }
+    // `load_config` rejects a level that doesn't parse
+    let ci_level = config
+        .ci_fail_level
+        .as_deref()
+        .filter(|_| active_profile(cli) == Profile::Ci)
+        .and_then(|l| FailLevel::from_str(l, true).ok());
+    match ci_level {
+        Some(level) => level.threshold(),
+        None => Some(scanner::Severity::Error),
+    }
+}
+
+/// Exit with `report::exit_code` for the scan's issues, if it isn't 0.
+fn exit_for_issues(cli: &Cli, result: &scanner::ScanResult) {
+    let code = report::exit_code(&result.issues, fail_at(cli, &result.config));
+    if code != 0 {
+        process::exit(code);
+    }
+}
+
+/// Clear terminal screen (cross-platform)
+fn clear_screen() {
+    if cfg!(windows) {
+        // Windows: use ANSI escape (works in modern terminals)
+        print!("\x1b[2J\x1b[1;1H");
+    } else {
+        // Unix: ANSI escape
+        print!("\x1b[2J\x1b[1;1H");
+    }
+}
+
+/// Run continuous watch mode
+fn run_watch_mode(cli: &Cli, path: &std::path::Path) {
+    let use_color = report::use_color(cli.no_color);
+    let interval = Duration::from_secs(cli.interval);
+    let registry = build_registry(cli);
+
+    let cyan = if use_color { "\x1b[36m" } else { "" };
+    let green = if use_color { "\x1b[32m" } else { "" };
+    let yellow = if use_color { "\x1b[33m" } else { "" };
+    let reset = if use_color { "\x1b[0m" } else { "" };
+
+    println!("{}RepoMedic Watch Mode{}", cyan, reset);
+    println!("{}Monitoring:{} {}", green, reset, report::clean_path(path));
+    println!(
+        "{}Interval:{} {}s (Ctrl+C to stop)\n",
+        green, reset, cli.interval
+    );
+
+    loop {
+        let scanned = load_config(cli, path).and_then(|c| scanner::scan_with(path, &registry, c));
+        let result = match scanned {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("{}Error:{} {}", yellow, reset, e);
+                thread::sleep(interval);
+                continue;
+            }
+        };
+
+        // Get current timestamp
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let hours = (now % 86400) / 3600;
+        let minutes = (now % 3600) / 60;
+        let seconds = now % 60;
+
+        clear_screen();
+
+        // Print header
+        println!(
+            "{}╔═══════════════════════════════════════════════════════════════╗{}",
+            cyan, reset
+        );
+        println!(
+            "{}║  {}REPOMEDIC WATCH MODE{}                                          ║{}",
+            cyan, green, cyan, reset
+        );
+        println!(
+            "{}╚═══════════════════════════════════════════════════════════════╝{}",
+            cyan, reset
+        );
+        println!();
+
+        if cli.score_only {
+            let score = report::calculate_score(&result);
+            let grade = report::score_grade(score);
+            let score_color = if score >= 80 {
+                green
+            } else if score >= 60 {
+                yellow
+            } else {
+                "\x1b[31m"
+            };
+            println!(
+                "{}Score:{} {}{}{} ({})",
+                green, reset, score_color, score, reset, grade
+            );
+        } else {
+            report::print_summary(&result, false, cli.verbose, use_color);
+        }
+
+        println!();
+        println!(
+            "{}─────────────────────────────────────────────────────────────────{}",
+            cyan, reset
+        );
+        println!(
+            "{}Last update:{} {:02}:{:02}:{:02} UTC  {}│{}  {}Next refresh:{} {}s",
+            green, reset, hours, minutes, seconds, cyan, reset, green, reset, cli.interval
+        );
+        println!("{}Press Ctrl+C to stop{}", yellow, reset);
+
+        thread::sleep(interval);
+    }
+}
+
+/// `repomedic watch`: re-scan on file changes, printing one status line per
+/// scan plus the issues that appeared or were fixed since the last one.
+fn run_watch(cli: &Cli, path: &std::path::Path, score_only: bool) -> ! {
+    let use_color = report::use_color(cli.no_color);
+    let registry = build_registry(cli);
+    let dim = if use_color { "\x1b[2m" } else { "" };
+    let green = if use_color { "\x1b[32m" } else { "" };
+    let red = if use_color { "\x1b[31m" } else { "" };
+    let reset = if use_color { "\x1b[0m" } else { "" };
+
+    println!(
+        "{}Watching {} for changes (Ctrl+C to stop){}",
+        dim,
+        report::clean_path(path),
+        reset
+    );
+    let mut previous: Option<Vec<String>> = None;
+    let mut fingerprint = watch::fingerprint(path);
+    loop {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let stamp = format!(
+            "{:02}:{:02}:{:02}",
+            (now % 86400) / 3600,
+            (now % 3600) / 60,
+            now % 60
+        );
+        match load_config(cli, path).and_then(|c| scanner::scan_with(path, &registry, c)) {
+            Ok(result) => {
+                if score_only {
+                    let score = report::calculate_score(&result);
+                    println!("{}{}{} {}", dim, stamp, reset, score);
+                } else {
+                    let issues: Vec<String> = result
+                        .issues
+                        .clone()
+                        .into_iter()
+                        .map(|i| i.message)
+                        .collect();
+                    println!(
+                        "{}{}{} {}",
+                        dim,
+                        stamp,
+                        reset,
+                        report::doctor_line(&result, use_color)
+                    );
+                    if let Some(ref before) = previous {
+                        for fixed in before.iter().filter(|m| !issues.contains(m)) {
+                            println!("  {}- {}{}", green, fixed, reset);
+                        }
+                        for new in issues.iter().filter(|m| !before.contains(m)) {
+                            println!("  {}+ {}{}", red, new, reset);
+                        }
+                    }
+                    previous = Some(issues);
+                }
+            }
+            Err(e) => eprintln!("{}{}{} Error: {}", dim, stamp, reset, e),
+        }
+        fingerprint = watch::wait_for_change(path, fingerprint);
+    }
+}
+
+/// `repomedic tui`: interactive dashboard. Returns the exit code.
+#[cfg(feature = "tui")]
+fn run_tui(cli: &Cli, path: &std::path::Path) -> i32 {
+    let author = match load_config(cli, path) {
+        Ok(c) => c.author,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return 1;
+        }
+    };
+    let registry = build_registry(cli);
+    let color = report::use_color(cli.no_color);
+    let rescan = || load_config(cli, path).and_then(|c| scanner::scan_with(path, &registry, c));
+    match tui::run(path, author, color, rescan) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            1
+        }
+    }
+}
+
+pub fn main() {
+    let mut cli = Cli::parse();
+    // A subcommand's own --ci or --local wins over one before it
+    if let Some(profile) = cli.command.as_ref().and_then(Commands::profile) {
+        if profile.ci || profile.local {
+            cli.profile = profile;
+        }
+    }
+    if active_profile(&cli) == Profile::Ci {
+        cli.no_color = true;
+        cli.no_banner = true;
+    }
+    if let Some(ref source) = cli.files_from {
+        let list = if source.as_os_str() == "-" {
+            let mut list = String::new();
+            std::io::Read::read_to_string(&mut std::io::stdin(), &mut list).map(|_| list)
+        } else {
+            std::fs::read_to_string(source)
+        };
+        match list {
+            Ok(list) => cli.file_list = Some(list),
+            Err(e) => {
+                eprintln!("Error: cannot read file list '{}': {}", source.display(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    if cli.self_test {
+        process::exit(run_self_test(selftest::run_builtin(), cli.no_color));
+    }
+    if let Some(Commands::Scan {
+        fixture: Some(ref dir),
+        ..
+    }) = cli.command
+    {
+        process::exit(run_self_test(selftest::run_dir(dir), cli.no_color));
+    }
+
+    let path = match cli.path.canonicalize() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("Error: cannot access path '{}': {}", cli.path.display(), e);
+            process::exit(1);
+        }
+    };
+
+    if !path.is_dir() {
+        eprintln!("Error: '{}' is not a directory", report::clean_path(&path));
+        process::exit(1);
+    }
+
+    // Handle --watch mode: continuous monitoring loop
+    if cli.watch {
+        run_watch_mode(&cli, &path);
+        return;
+    }
+
+    if let Some(Commands::Detectors) = cli.command {
+        print_detectors(&build_registry(&cli));
+        return;
+    }
+
+    if let Some(Commands::Capabilities) = cli.command {
+        let caps = Capabilities::detect(&build_registry(&cli));
+        if cli.json || matches!(cli.format, Some(OutputFormat::Json)) {
+            println!("{}", caps.to_json());
+        } else {
+            print_capabilities(&caps);
+        }
+        return;
+    }
+
+    if let Some(Commands::Schema) = cli.command {
+        print!("{}", migrate::REPORT_SCHEMA);
+        return;
+    }
+
+    if let Some(Commands::Hook { ref action }) = cli.command {
+        let outcome = match action {
+            HookAction::Install {
+                framework: true, ..
+            } => hook::install_framework(&path),
+            HookAction::Install { force, .. } => hook::install(&path, *force),
+            HookAction::Uninstall => hook::uninstall(&path),
+        };
+        if let Err(e) = outcome {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    if let Some(Commands::Watch { score, .. }) = cli.command {
+        run_watch(&cli, &path, score);
+    }
+
+    #[cfg(feature = "tui")]
+    if let Some(Commands::Tui) = cli.command {
+        process::exit(run_tui(&cli, &path));
+    }
+
+    if let Some(Commands::ScanAll {
+        depth, ref export, ..
+    }) = cli.command
+    {
+        process::exit(run_scan_all(&cli, &path, depth, export.as_deref()));
+    }
+
+    if let Some(Commands::Org {
+        action: ref scan @ OrgAction::Scan { .. },
+    }) = cli.command
+    {
+        process::exit(run_org_scan(&cli, scan));
+    }
+
+    let mut config = match load_config(&cli, &path) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            process::exit(1);
+        }
+    };
+
+    if let Some(Commands::Rules { ref action }) = cli.command {
+        let json = cli.json || matches!(cli.format, Some(OutputFormat::Json));
+        match action {
+            RulesAction::List => print_rules(&config, json),
+            RulesAction::Explain { rule } => {
+                let custom = config.custom_rules.iter().find(|r| r.id == *rule);
+                match (rules::find(rule), custom) {
+                    (Some(check), _) => print_rule_explanation(&check, &config),
+                    (None, Some(r)) => print_custom_rule_explanation(r, &config),
+                    (None, None) => {
+                        eprintln!(
+                            "Error: unknown rule '{}' (see `repomedic rules list`)",
+                            rule
+                        );
+                        process::exit(1);
+                    }
+                }
+            }
+        }
+        return;
+    }
+
+    let format = output_format(&cli, &config);
+    // Only the Markdown, HTML, and JSON reports show lines per language
+    let no_lines = |f: &OutputFormat| {
+        matches!(
+            f,
+            OutputFormat::Console
+                | OutputFormat::Sarif
+                | OutputFormat::LspDiagnostics
+                | OutputFormat::Hook
+        )
+    };
+    config.skip_line_counts = matches!(
+        cli.command,
+        None | Some(Commands::Scan {
+            score: false,
+            suggest: false,
+            ..
+        })
+    ) && no_lines(&format)
+        && !cli.json
+        && cli.evidence.is_none()
+        && cli.also.iter().all(|(f, _)| no_lines(f));
+
+    // NDJSON on stdout is written as the scan produces it: each finding as
+    // soon as it's known, the summary once the scan is done
+    let live_ndjson = matches!(format, OutputFormat::Ndjson)
+        && matches!(
+            cli.command,
+            None | Some(Commands::Scan {
+                score: false,
+                suggest: false,
+                ..
+            })
+        )
+        && !cli.quiet
+        && !cli.score_only
+        && !cli.json;
+    let repository = report::clean_path(&path);
+    let mut events = event_writer(&cli);
+    let scanned = {
+        let mut scanned_at = String::new();
+        let mut stdout = std::io::stdout();
+        let mut observe = |event: Event| {
+            if let Some(ref mut writer) = events {
+                writer.emit(&event);
+            }
+            if !live_ndjson {
+                return;
+            }
+            match event {
+                Event::ScanStarted { scanned_at: at, .. } => scanned_at = at.to_string(),
+                Event::Finding { issue } => {
+                    // A closed pipe only stops the stream
+                    let record = report::ndjson_finding(&repository, &scanned_at, issue);
+                    let _ = report::write_ndjson(&mut stdout, &record);
+                }
+                _ => {}
+            }
+        };
+        scanner::scan_observed(&path, &build_registry(&cli), config, &mut observe)
+    };
+    let result = match scanned {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error scanning repository: {}", e);
+            process::exit(1);
+        }
+    };
+    if let Some(ref mut writer) = events {
+        let score = report::calculate_score(&result);
+        writer.finish(
+            &result.issues.clone(),
+            score,
+            report::score_grade(score),
+            result.scan_stats.scan_duration_ms,
+        );
+    }
+
+    // Handle --evidence: write the audit bundle before any output mode exits
+    if let Some(ref dir) = cli.evidence {
+        match evidence::write_bundle(&result, &path, dir) {
+            Ok(n) if !cli.quiet => eprintln!(
+                "Evidence written to: {} ({} file(s))",
+                report::clean_path(dir),
+                n
+            ),
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error writing evidence: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // Handle --also: machine-readable copies next to whatever is printed
+    for (format, file) in &cli.also {
+        if let Err(e) = std::fs::write(file, also_report(*format, &result, &path)) {
+            eprintln!("Error writing {}: {}", report::clean_path(file), e);
+            process::exit(1);
+        }
+        if !cli.quiet {
+            eprintln!("Report written to: {}", report::clean_path(file));
+        }
+    }
+
+    // Handle --score-only: just print the number and exit
+    if cli.score_only {
+        let score = report::calculate_score(&result);
+        println!("{}", score);
+        exit_for_issues(&cli, &result);
+        return;
+    }
+
+    // Handle --json shorthand: output JSON and exit (history prints its own)
+    if cli.json && !matches!(cli.command, Some(Commands::History { .. })) {
+        if !cli.quiet {
+            println!("{}", report::to_json(&result, &path));
+        }
+        exit_for_issues(&cli, &result);
+        return;
+    }
+
+    let use_color = report::use_color(cli.no_color);
+
+    match cli.command {
+        None
+        | Some(Commands::Scan {
+            score: false,
+            suggest: false,
+            ..
+        }) => {
+            match format {
+                OutputFormat::Console => {
+                    report::print_summary(&result, cli.quiet, cli.verbose, use_color);
+                    if cli.show_passed && !cli.quiet {
+                        report::print_passed(&result, use_color);
+                    }
+                    let chosen = cli.format.is_some() || result.config.format.is_some();
+                    if active_profile(&cli) == Profile::Ci
+                        && profile::provider() == Some("github")
+                        && !chosen
+                        && !cli.quiet
+                    {
+                        eprintln!("Hint: --format github (or [ci] format = \"github\") adds annotations and a job summary");
+                    }
+                }
+                OutputFormat::Json => {
+                    if !cli.quiet {
+                        println!("{}", report::to_json(&result, &path));
+                    }
+                }
+                OutputFormat::Ndjson => {
+                    if live_ndjson {
+                        // The findings went out during the scan
+                        let summary = report::ndjson_summary(&result, &repository);
+                        let _ = report::write_ndjson(&mut std::io::stdout(), &summary);
+                    }
+                }
+                OutputFormat::Markdown => {
+                    if !cli.quiet {
+                        print!("{}", report::to_markdown(&result, &path));
+                    }
+                }
+                OutputFormat::Sarif => {
+                    if !cli.quiet {
+                        println!("{}", report::to_sarif(&result, &path));
+                    }
+                }
+                OutputFormat::LspDiagnostics => {
+                    if !cli.quiet {
+                        println!("{}", report::to_lsp_diagnostics(&result, &path));
+                    }
+                }
+                OutputFormat::Github => {
+                    if !cli.quiet {
+                        print!("{}", report::to_github_annotations(&result));
+                    }
+                    if let Err(e) = report::append_github_summary(&result, &path) {
+                        eprintln!("Warning: could not write job summary: {}", e);
+                    }
+                }
+                OutputFormat::Hook => {
+                    let staged = result.config.changed_files.as_deref().unwrap_or_default();
+                    // Exit status reflects only the staged change
+                    let issues = report::staged_issues(&result, staged);
+                    if !cli.quiet {
+                        print!("{}", report::to_hook(&issues));
+                    }
+                    let code = report::exit_code(&issues, fail_at(&cli, &result.config));
+                    if code != 0 {
+                        process::exit(code);
+                    }
+                    return;
+                }
+            }
+
+            exit_for_issues(&cli, &result);
+        }
+        Some(Commands::Scan {
+            score,
+            explain,
+            suggest,
+            ref emit_script,
+            ..
+        }) => {
+            if explain {
+                report::print_score_explanation(&result, use_color);
+            }
+            if score {
+                report::print_score(&result, use_color);
+            }
+            if suggest {
+                report::print_suggestions(&result, use_color);
+            }
+            if let Some(file) = emit_script {
+                match report::write_suggestions_script(&result, &path, file) {
+                    Ok(n) => println!("Wrote {} command(s) to {}", n, report::clean_path(file)),
+                    Err(e) => {
+                        eprintln!("Error writing {}: {}", report::clean_path(file), e);
+                        process::exit(1);
+                    }
+                }
+            }
+            exit_for_issues(&cli, &result);
+        }
+        Some(Commands::Doctor { ref json_out, .. }) => {
+            report::print_doctor(&result, use_color);
+            if let Some(file) = json_out {
+                let status = report::DoctorStatus::new(&result, &path);
+                let json = serde_json::to_string(&status).unwrap_or_else(|_| "{}".to_string());
+                if let Err(e) = std::fs::write(file, json + "\n") {
+                    eprintln!("Error writing {}: {}", report::clean_path(file), e);
+                    process::exit(1);
+                }
+            }
+            exit_for_issues(&cli, &result);
+        }
+        Some(
+            Commands::Detectors
+            | Commands::Capabilities
+            | Commands::Schema
+            | Commands::ScanAll { .. }
+            | Commands::Org { .. }
+            | Commands::Watch { .. }
+            | Commands::Hook { .. }
+            | Commands::Rules { .. },
+        ) => {
+            unreachable!("handled before scanning")
+        }
+        #[cfg(feature = "tui")]
+        Some(Commands::Tui) => unreachable!("handled before scanning"),
+        Some(Commands::Workflows) => {
+            if cli.json || matches!(cli.format, Some(OutputFormat::Json)) {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&result.workflows).unwrap_or_default()
+                );
+            } else {
+                report::print_workflows(&result, use_color);
+            }
+        }
+        Some(Commands::Badge {
+            ref style,
+            ref link,
+            write,
+            ref svg,
+        }) => {
+            let score = report::calculate_score(&result);
+            let image = match svg {
+                Some(file) => {
+                    let square = matches!(style.as_deref(), Some("flat-square" | "for-the-badge"));
+                    let drawn = badges::svg(
+                        "RepoMedic",
+                        &report::badge_message(score),
+                        report::badge_color(score),
+                        square,
+                    );
+                    if let Err(e) = std::fs::write(file, drawn) {
+                        eprintln!("Error writing {}: {}", report::clean_path(file), e);
+                        process::exit(1);
+                    }
+                    eprintln!("Badge written to: {}", report::clean_path(file));
+                    file.to_string_lossy().replace('\\', "/")
+                }
+                None => report::badge_url(score, style.as_deref()),
+            };
+            let snippet = report::badge_markdown(&image, link.as_deref());
+            println!("{}", snippet);
+            if !write {
+                return;
+            }
+            let file = result
+                .readme
+                .as_ref()
+                .map_or("README.md".to_string(), |r| r.file.clone());
+            let readme = match std::fs::read_to_string(path.join(&file)) {
+                Ok(text) => text,
+                Err(_) => {
+                    eprintln!("No README to update; run 'repomedic generate --readme' first");
+                    process::exit(1);
+                }
+            };
+            let updated = badges::write_block(&readme, &snippet);
+            if updated == readme {
+                eprintln!("{} badge is up to date.", file);
+            } else if let Err(e) = std::fs::write(path.join(&file), updated) {
+                eprintln!("Error writing {}: {}", file, e);
+                process::exit(1);
+            } else {
+                eprintln!("Updated the badge in {}.", file);
+            }
+        }
+        Some(Commands::History { no_record, last }) => {
+            if !no_record {
+                let entry = history::Entry::record(&path, &result);
+                if let Err(e) = history::append(&path, &entry) {
+                    eprintln!("Error writing {}: {}", history::HISTORY_FILE, e);
+                    process::exit(1);
+                }
+            }
+            let mut entries = match history::load(&path) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    eprintln!("Error reading {}: {}", history::HISTORY_FILE, e);
+                    process::exit(1);
+                }
+            };
+            if let Some(n) = last {
+                entries.drain(..entries.len().saturating_sub(n));
+            }
+            if cli.json || matches!(cli.format, Some(OutputFormat::Json)) {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&entries).unwrap_or_default()
+                );
+            } else if entries.is_empty() {
+                println!("No scans recorded yet (run `repomedic history`).");
+            } else {
+                print!("{}", history::render(&entries, result.config.locale()));
+            }
+        }
+        Some(Commands::Examples) => {
+            let branding = &result.config.branding;
+            print_examples(use_color, !cli.no_banner && !branding.hide_banner, branding);
+        }
+        Some(Commands::Info) => {
+            let branding = &result.config.branding;
+            print_help_detailed(use_color, !cli.no_banner && !branding.hide_banner, branding);
+        }
+        Some(Commands::Export { file }) => {
+            if let Err(e) = report::export_to_file(&result, &path, &file) {
+                eprintln!("Error exporting: {}", e);
+                process::exit(1);
+            }
+        }
+        Some(Commands::Baseline { file }) => {
+            let file = file.unwrap_or_else(|| path.join(baseline::DEFAULT_FILE));
+            // Keep the annotations of the file being replaced
+            let previous = baseline::Baseline::load(&file).ok();
+            let recorded = baseline::Baseline::record(&result, previous.as_ref());
+            if let Err(e) = recorded.save(&file) {
+                eprintln!("Error writing baseline: {}", e);
+                process::exit(1);
+            }
+            println!(
+                "Baseline written to: {} ({} issue(s))",
+                report::clean_path(&file),
+                recorded.issues.len()
+            );
+        }
+        Some(Commands::Init {
+            author,
+            license_type,
+            dry_run,
+        }) => {
+            let author = author.or_else(|| result.config.author.clone());
+            let license = license_type.or(result.config.license_type);
+            let opts = generator::GenerateOptions {
+                dry_run,
+                license,
+                color: use_color,
+                templates: result.config.templates.as_ref().map(|dir| {
+                    generator::Overrides::new(
+                        path.join(dir),
+                        &path,
+                        &result,
+                        author.as_deref(),
+                        license,
+                    )
+                }),
+                ..generator::GenerateOptions::default()
+            };
+            println!("Initializing healthy repository...\n");
+            if let Err(e) = generator::generate_all(&path, &result, author.as_deref(), &opts) {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+            println!("\nRun 'repomedic scan --score' to check your new health score!");
+        }
+        Some(Commands::Fix {
+            dry_run,
+            interactive,
+            yes,
+            author,
+            license_type,
+        }) => {
+            let opts = fix::FixOptions {
+                dry_run,
+                interactive,
+                yes,
+                author: author.or_else(|| result.config.author.clone()),
+                license: license_type.or(result.config.license_type),
+                color: use_color,
+            };
+            let fixes = fix::plan(&path, &result);
+            if fixes.is_empty() {
+                println!("Nothing to fix.");
+                return;
+            }
+            match fix::apply(&path, &result, &fixes, &opts) {
+                Ok(n) if !dry_run => println!("\n{} of {} fix(es) applied.", n, fixes.len()),
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("Error applying fixes: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Report) => {
+            if let Err(e) = report::write(&result, &path) {
+                eprintln!("Error writing report: {}", e);
+                process::exit(1);
+            }
+        }
+        Some(Commands::Generate {
+            readme,
+            license,
+            gitignore,
+            gitignore_template,
+            contributing,
+            changelog,
+            editorconfig,
+            codeofconduct,
+            security,
+            issue_template,
+            pr_template,
+            codecov,
+            dependabot,
+            gitattributes,
+            dockerignore,
+            ci,
+            all,
+            dry_run,
+            force,
+            merge,
+            templates,
+            author,
+            license_type,
+        }) => {
+            let gitignore = gitignore || !gitignore_template.is_empty();
+            let author = author.or_else(|| result.config.author.clone());
+            let license_choice = license_type.or(result.config.license_type);
+            let templates =
+                templates.or_else(|| result.config.templates.as_ref().map(|d| path.join(d)));
+            let opts = generator::GenerateOptions {
+                dry_run,
+                force,
+                merge,
+                color: use_color,
+                license: license_choice,
+                gitignore_templates: gitignore_template,
+                templates: templates.map(|dir| {
+                    generator::Overrides::new(
+                        dir,
+                        &path,
+                        &result,
+                        author.as_deref(),
+                        license_choice,
+                    )
+                }),
+            };
+            let author_ref = author.as_deref();
+            let none_specified = !readme
+                && !license
+                && !gitignore
+                && !contributing
+                && !changelog
+                && !editorconfig
+                && !codeofconduct
+                && !security
+                && !issue_template
+                && !pr_template
+                && !codecov
+                && !dependabot
+                && !gitattributes
+                && !dockerignore
+                && ci.is_none();
+
+            if all || none_specified {
+                if let Err(e) = generator::generate_all(&path, &result, author_ref, &opts) {
+                    eprintln!("Error generating files: {}", e);
+                    process::exit(1);
+                }
+            } else {
+                if readme {
+                    if let Err(e) = generator::generate_readme(&path, &result, &opts) {
+                        eprintln!("Error generating README: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if license {
+                    if let Err(e) = generator::generate_license(&path, author_ref, &opts) {
+                        eprintln!("Error generating LICENSE: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if gitignore {
+                    if let Err(e) = generator::generate_gitignore(&path, &result, &opts) {
+                        eprintln!("Error generating .gitignore: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if contributing {
+                    if let Err(e) = generator::generate_contributing(&path, &opts) {
+                        eprintln!("Error generating CONTRIBUTING.md: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if changelog {
+                    if let Err(e) = generator::generate_changelog(&path, &opts) {
+                        eprintln!("Error generating CHANGELOG.md: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if editorconfig {
+                    if let Err(e) = generator::generate_editorconfig(&path, &result, &opts) {
+                        eprintln!("Error generating .editorconfig: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if codeofconduct {
+                    if let Err(e) = generator::generate_code_of_conduct(&path, &opts) {
+                        eprintln!("Error generating CODE_OF_CONDUCT.md: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if security {
+                    if let Err(e) = generator::generate_security(&path, &opts) {
+                        eprintln!("Error generating SECURITY.md: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if issue_template {
+                    if let Err(e) = generator::generate_issue_templates(&path, &opts) {
+                        eprintln!("Error generating issue templates: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if pr_template {
+                    if let Err(e) = generator::generate_pr_template(&path, &opts) {
+                        eprintln!("Error generating PULL_REQUEST_TEMPLATE.md: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if codecov {
+                    if let Err(e) = generator::generate_codecov(&path, &opts) {
+                        eprintln!("Error generating codecov.yml: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if dependabot {
+                    if let Err(e) = generator::generate_dependabot(&path, &result, &opts) {
+                        eprintln!("Error generating .github/dependabot.yml: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if gitattributes {
+                    if let Err(e) = generator::generate_gitattributes(&path, &result, &opts) {
+                        eprintln!("Error generating .gitattributes: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if dockerignore {
+                    if let Err(e) = generator::generate_dockerignore(&path, &result, &opts) {
+                        eprintln!("Error generating .dockerignore: {}", e);
+                        process::exit(1);
+                    }
+                }
+                if let Some(provider) = ci {
+                    if let Err(e) = generator::generate_ci(&path, &result, provider, &opts) {
+                        eprintln!("Error generating {}: {}", provider.path(), e);
+                        process::exit(1);
+                    }
+                }
+            }
+        }
+    }
+}
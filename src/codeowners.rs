@@ -0,0 +1,378 @@
+use crate::glob::glob_match;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Where GitHub looks for CODEOWNERS, in the order it checks them.
+pub const FILES: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// How many uncovered directories the report lists.
+pub const TOP_DIRS: usize = 5;
+
+/// One `pattern @owner...` line.
+#[derive(Debug, Clone)]
+struct Rule {
+    /// 1-based line number.
+    line: usize,
+    glob: String,
+    /// Patterns ending in `/` match directories only.
+    dir_only: bool,
+    /// Patterns containing a slash match the full path, others any name.
+    anchored: bool,
+    /// A rule without owners explicitly leaves its paths unowned.
+    owners: Vec<String>,
+}
+
+/// A CODEOWNERS line GitHub would skip or that has no effect.
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    pub file: String,
+    pub line: usize,
+    pub message: String,
+}
+
+/// Parsed CODEOWNERS rules. As on GitHub, the last matching rule wins.
+#[derive(Debug, Clone, Default)]
+pub struct Codeowners {
+    rules: Vec<Rule>,
+    /// Lines skipped for unsupported syntax, with the reason.
+    skipped: Vec<(usize, String)>,
+}
+
+impl Codeowners {
+    pub fn parse(content: &str) -> Self {
+        let mut owners = Codeowners::default();
+        for (n, raw) in content.lines().enumerate() {
+            let line = raw.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // Comments may also follow a rule
+            let line = match line.find(" #").or_else(|| line.find("\t#")) {
+                Some(i) => &line[..i],
+                None => line,
+            };
+            let mut parts = line.split_whitespace();
+            let Some(pattern) = parts.next() else {
+                continue;
+            };
+            if let Some(reason) = unsupported_syntax(pattern) {
+                owners.skipped.push((n + 1, reason));
+                continue;
+            }
+            let (dir_only, pattern) = match pattern.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, pattern),
+            };
+            owners.rules.push(Rule {
+                line: n + 1,
+                glob: pattern.trim_start_matches('/').to_string(),
+                dir_only,
+                anchored: pattern.contains('/'),
+                owners: parts.map(String::from).collect(),
+            });
+        }
+        owners
+    }
+
+    /// Problems in the rules of `file`: unsupported syntax, owners that
+    /// aren't `@user`, `@org/team`, or an email, patterns matching none of
+    /// `files` (root-relative) and nothing on disk, and patterns repeated
+    /// from an earlier line, which the later line overrides.
+    pub fn validate(&self, file: &str, root: &Path, files: &[&str]) -> Vec<Problem> {
+        let mut problems: Vec<(usize, String)> = self.skipped.clone();
+        let mut seen: HashMap<(bool, &str, bool), &Rule> = HashMap::new();
+        for rule in &self.rules {
+            for owner in rule.owners.iter().filter(|o| !is_valid_owner(o)) {
+                problems.push((
+                    rule.line,
+                    format!(
+                        "owner `{}` is not a @user, @org/team, or email address",
+                        owner
+                    ),
+                ));
+            }
+            let literal = !rule.glob.contains(['*', '?']);
+            // The walk skips hidden files, so globs into them can't be checked
+            let checkable = literal || !(rule.glob.starts_with('.') || rule.glob.contains("/."));
+            if checkable
+                && !files.iter().any(|f| rule.matches(f))
+                && !(literal && root.join(&rule.glob).exists())
+            {
+                problems.push((
+                    rule.line,
+                    format!("pattern `{}` matches no files", rule.pattern()),
+                ));
+            }
+            let key = (rule.anchored, rule.glob.as_str(), rule.dir_only);
+            if let Some(earlier) = seen.insert(key, rule) {
+                let message = if same_owners(&earlier.owners, &rule.owners) {
+                    format!("duplicates line {}", earlier.line)
+                } else {
+                    format!(
+                        "assigns different owners than line {}, which it overrides",
+                        earlier.line
+                    )
+                };
+                problems.push((rule.line, format!("`{}` {}", rule.pattern(), message)));
+            }
+        }
+        problems.sort_by_key(|(line, _)| *line);
+        problems
+            .into_iter()
+            .map(|(line, message)| Problem {
+                file: file.to_string(),
+                line,
+                message,
+            })
+            .collect()
+    }
+
+    /// Whether the last rule matching `rel` (root-relative, `/`-separated)
+    /// assigns owners.
+    pub fn is_owned(&self, rel: &str) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|r| r.matches(rel))
+            .is_some_and(|r| !r.owners.is_empty())
+    }
+}
+
+impl Rule {
+    /// The pattern as written, for messages.
+    fn pattern(&self) -> String {
+        let lead = if self.anchored && !self.glob.contains('/') {
+            "/"
+        } else {
+            ""
+        };
+        let trail = if self.dir_only { "/" } else { "" };
+        format!("{}{}{}", lead, self.glob, trail)
+    }
+
+    /// A pattern matches a file or any directory above it, except that a
+    /// trailing `*` (`docs/*`) covers only the directory's direct files.
+    fn matches(&self, rel: &str) -> bool {
+        let mut prefixes: Vec<(&str, bool)> = rel
+            .match_indices('/')
+            .map(|(i, _)| (&rel[..i], true))
+            .collect();
+        prefixes.push((rel, false));
+        prefixes.into_iter().any(|(path, is_dir)| {
+            if (self.dir_only && !is_dir) || (is_dir && self.glob.ends_with('*')) {
+                return false;
+            }
+            let subject = if self.anchored {
+                path
+            } else {
+                path.rsplit('/').next().unwrap_or(path)
+            };
+            glob_match(self.glob.as_bytes(), subject.as_bytes())
+        })
+    }
+}
+
+/// Gitignore syntax GitHub doesn't support in CODEOWNERS, which makes it
+/// skip the line.
+fn unsupported_syntax(pattern: &str) -> Option<String> {
+    let reason = if pattern.starts_with('!') {
+        "negated patterns (`!`) are not supported"
+    } else if pattern.starts_with("\\#") {
+        "escaping `#` with `\\` is not supported"
+    } else if pattern.contains('[') {
+        "character ranges (`[ ]`) are not supported"
+    } else if pattern.starts_with('@') {
+        "line starts with an owner; the pattern is missing"
+    } else {
+        return None;
+    };
+    Some(reason.to_string())
+}
+
+/// `@user`, `@org/team`, or an email address.
+fn is_valid_owner(owner: &str) -> bool {
+    let handle = |name: &str| {
+        !name.is_empty()
+            && name.len() <= 39
+            && !name.starts_with('-')
+            && !name.ends_with('-')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+    if let Some(name) = owner.strip_prefix('@') {
+        return match name.split_once('/') {
+            Some((org, team)) => {
+                handle(org)
+                    && !team.is_empty()
+                    && team
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            }
+            None => handle(name),
+        };
+    }
+    match owner.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+        }
+        None => false,
+    }
+}
+
+fn same_owners(a: &[String], b: &[String]) -> bool {
+    let normalize = |owners: &[String]| {
+        let mut v: Vec<String> = owners.iter().map(|o| o.to_lowercase()).collect();
+        v.sort();
+        v
+    };
+    normalize(a) == normalize(b)
+}
+
+/// How much of the tree CODEOWNERS assigns to someone.
+#[derive(Debug, Clone, Serialize)]
+pub struct Coverage {
+    /// The CODEOWNERS file in effect.
+    pub file: String,
+    pub total_files: usize,
+    pub unowned_files: usize,
+    /// Top-level directories with the most unowned files, most first
+    /// (`.` for files at the root).
+    pub unowned_dirs: Vec<UnownedDir>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UnownedDir {
+    pub path: String,
+    pub files: usize,
+}
+
+impl Coverage {
+    /// Measure `files` (root-relative paths) against the rules in `file`.
+    pub fn measure<'a>(
+        file: &str,
+        owners: &Codeowners,
+        files: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let mut total_files = 0;
+        let mut by_dir: HashMap<&str, usize> = HashMap::new();
+        for rel in files {
+            total_files += 1;
+            if !owners.is_owned(rel) {
+                let dir = rel.split_once('/').map_or(".", |(top, _)| top);
+                *by_dir.entry(dir).or_default() += 1;
+            }
+        }
+        let mut unowned_dirs: Vec<UnownedDir> = by_dir
+            .into_iter()
+            .map(|(path, files)| UnownedDir {
+                path: path.to_string(),
+                files,
+            })
+            .collect();
+        unowned_dirs.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.path.cmp(&b.path)));
+        let unowned_files = unowned_dirs.iter().map(|d| d.files).sum();
+        unowned_dirs.truncate(TOP_DIRS);
+        Coverage {
+            file: file.to_string(),
+            total_files,
+            unowned_files,
+            unowned_dirs,
+        }
+    }
+
+    /// Share of files without an owner, rounded to a whole percent.
+    pub fn unowned_percent(&self) -> usize {
+        if self.total_files == 0 {
+            return 0;
+        }
+        let percent = (self.unowned_files * 100 + self.total_files / 2) / self.total_files;
+        // A handful of unowned files in a large tree still shows as 1%
+        percent.max(usize::from(self.unowned_files > 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_valid_owner, Codeowners, Coverage};
+    use std::path::Path;
+
+    fn messages(content: &str, files: &[&str]) -> Vec<(usize, String)> {
+        Codeowners::parse(content)
+            .validate(".github/CODEOWNERS", Path::new("/nonexistent"), files)
+            .into_iter()
+            .map(|p| (p.line, p.message))
+            .collect()
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        let owners =
+            Codeowners::parse("* @org/all\n/docs/ @org/docs # writers\n*.md\nsrc/*  @dev\n");
+        assert!(owners.is_owned("Cargo.toml"));
+        assert!(owners.is_owned("docs/guide/intro.txt"));
+        assert!(!owners.is_owned("docs/intro.md"));
+        assert!(owners.is_owned("src/main.rs"));
+        // A trailing `*` covers only direct files
+        let nested = Codeowners::parse("src/* @dev\n");
+        assert!(!nested.is_owned("src/bin/tool.rs"));
+    }
+
+    #[test]
+    fn owners() {
+        assert!(is_valid_owner("@octocat"));
+        assert!(is_valid_owner("@my-org/core.team"));
+        assert!(is_valid_owner("dev@example.com"));
+        assert!(!is_valid_owner("octocat"));
+        assert!(!is_valid_owner("@-bad"));
+        assert!(!is_valid_owner("@org/"));
+        assert!(!is_valid_owner("dev@localhost"));
+    }
+
+    #[test]
+    fn validation_problems() {
+        let problems = messages(
+            "!vendor/ @a\n*.rs @a\n/missing.txt @a\n*.rs @b\n[ab].c @a\n*.rs team\n",
+            &["src/main.rs"],
+        );
+        assert_eq!(
+            problems,
+            [
+                (1, "negated patterns (`!`) are not supported".to_string()),
+                (3, "pattern `/missing.txt` matches no files".to_string()),
+                (
+                    4,
+                    "`*.rs` assigns different owners than line 2, which it overrides".to_string()
+                ),
+                (5, "character ranges (`[ ]`) are not supported".to_string()),
+                (
+                    6,
+                    "owner `team` is not a @user, @org/team, or email address".to_string()
+                ),
+                (
+                    6,
+                    "`*.rs` assigns different owners than line 4, which it overrides".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            messages("*.rs @a @b\n*.rs @B @a\n", &["lib.rs"]),
+            [(2, "`*.rs` duplicates line 1".to_string())]
+        );
+    }
+
+    #[test]
+    fn coverage() {
+        let owners = Codeowners::parse("/src/ @dev\n");
+        let files = ["src/a.rs", "docs/a.md", "docs/b.md", "README.md"];
+        let coverage = Coverage::measure("CODEOWNERS", &owners, files.into_iter());
+        assert_eq!(coverage.total_files, 4);
+        assert_eq!(coverage.unowned_files, 3);
+        assert_eq!(coverage.unowned_dirs[0].path, "docs");
+        assert_eq!(coverage.unowned_dirs[1].path, ".");
+        assert_eq!(coverage.unowned_percent(), 75);
+    }
+}
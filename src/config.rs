@@ -0,0 +1,857 @@
+use crate::custom::{self, CustomRule, RuleBuilder};
+use crate::freshness;
+use crate::git::ChangedFile;
+use crate::license::Choice;
+use crate::locale::Locale;
+use crate::policy::{self, Origin};
+use crate::regex::Regex;
+use crate::remote;
+use crate::report::{check_matches, SCORE_CATEGORIES};
+use crate::rules;
+use crate::scanner::{ScanError, Severity};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Config file names looked up in the repository root, in order.
+pub const CONFIG_FILES: &[&str] = &[".repomedic.toml", "repomedic.toml"];
+
+/// Default large-file threshold in megabytes.
+pub const DEFAULT_LARGE_FILE_MB: u64 = 5;
+
+/// Settings loaded from `.repomedic.toml`.
+///
+/// ```toml
+/// policy = "https://internal.example.com/repomedic/policy.yaml"
+/// format = "json"
+/// author = "Jane Doe"
+/// license_type = "apache-2.0"
+/// templates = "company-templates"
+/// large_file_threshold_mb = 10
+/// lockfile_max_age_days = 180
+/// baseline = "repomedic-baseline.json"
+/// blame = true
+/// ci_health = true
+/// remote = "github"
+/// check_links = true
+/// check_http_links = true
+/// online = true
+/// cache = false
+/// locale = "de-DE"
+///
+/// [ci]
+/// fail_level = "warning"
+/// format = "github"
+///
+/// [branding]
+/// banner = false
+/// footer = "Scanned by Acme RepoCheck"
+/// help_url = "https://wiki.acme.internal/repocheck"
+/// support = "#repo-health on chat"
+///
+/// [checks]
+/// disable = ["missing-changelog"]
+///
+/// [severity]
+/// missing-security = "info"
+///
+/// [weights]
+/// security = 2
+/// documentation = 0.5
+///
+/// [rules.catalog-info]
+/// require_file = "catalog-info.yaml"
+/// severity = "error"
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// File the config was loaded from (relative to the root).
+    pub source: Option<String>,
+    /// Shared organization config this one is layered over: an HTTPS URL
+    /// or a path relative to the config file. Keys set here win.
+    pub policy: Option<String>,
+    /// Let `policy` be a plain `http://` URL.
+    pub allow_http_policy: bool,
+    /// Where the policy was read from, once loaded.
+    pub policy_origin: Option<Origin>,
+    /// Default output format (console, json, markdown, sarif, github, hook).
+    pub format: Option<String>,
+    /// Default author for generated LICENSE files.
+    pub author: Option<String>,
+    /// Default license for generated LICENSE files.
+    pub license_type: Option<&'static Choice>,
+    /// Directory of `.hbs` templates that replace the built-in generated
+    /// files (relative to the root).
+    pub templates: Option<String>,
+    /// Date and number conventions for human-readable exports (default:
+    /// from `LC_ALL`, `LC_TIME`, or `LANG`).
+    pub locale: Option<&'static Locale>,
+    /// `--fail-level` for CI jobs (`[ci] fail_level`): error, warning, info,
+    /// or none.
+    pub ci_fail_level: Option<String>,
+    /// Output format for CI jobs (`[ci] format`), ahead of `format`.
+    pub ci_format: Option<String>,
+    pub large_file_threshold_mb: Option<u64>,
+    /// Days a lock file may go unchanged before it's stale (0 disables).
+    pub lockfile_max_age_days: Option<u64>,
+    /// Attach `git blame` authorship to file-located findings.
+    pub blame: bool,
+    /// Fetch workflow run history from the GitHub API (needs a token).
+    pub ci_health: bool,
+    /// Hosting provider to read repository settings from (`github` or
+    /// `gitlab`; needs a token).
+    pub remote: Option<String>,
+    /// Check relative links in README and docs Markdown files.
+    pub check_links: bool,
+    /// Also request HTTP links (implies `check_links`).
+    pub check_http_links: bool,
+    /// Ask package registries whether dependencies are a major version behind.
+    pub online: bool,
+    /// Skip unchanged files already found clean, via a cache in `.git` (or
+    /// `.repomedic-cache` at the root outside Git). Unset means off; the CLI
+    /// turns it on in Git repositories unless `cache = false` or `--no-cache`.
+    pub cache: Option<bool>,
+    /// Baseline file of known issues (relative to the root).
+    pub baseline: Option<String>,
+    /// Check IDs to drop from the report entirely.
+    pub disabled_checks: Vec<String>,
+    /// Per-check severity overrides, keyed by check ID.
+    pub severity_overrides: Vec<(String, Severity)>,
+    /// Score category multipliers, keyed by `SCORE_CATEGORIES` name.
+    pub score_weights: Vec<(String, f64)>,
+    /// Organization-specific rules from `[rules.<id>]` sections.
+    pub custom_rules: Vec<CustomRule>,
+    /// Banner, footer, and help links from `[branding]`.
+    pub branding: Branding,
+    /// Files read per expensive content check, chosen by `--sample` (CLI
+    /// only). Secret scanning always reads every file.
+    pub sample: Option<usize>,
+    /// Files a `--staged` or `--changed` scan is limited to (CLI only).
+    pub changed_files: Option<Vec<ChangedFile>>,
+    /// Leave line counts out of the language breakdown when the output
+    /// doesn't show them (CLI only).
+    pub skip_line_counts: bool,
+}
+
+/// Text an internal distribution can swap in for RepoMedic's own, so
+/// users are pointed at internal support instead of the public project.
+#[derive(Debug, Clone, Default)]
+pub struct Branding {
+    /// `banner = false` hides the startup banner.
+    pub hide_banner: bool,
+    /// Replaces the ASCII-art banner (`banner = "..."`).
+    pub banner: Option<String>,
+    /// Replaces the "Generated by RepoMedic" line in exported reports.
+    pub footer: Option<String>,
+    /// Replaces the project links in help text and report footers.
+    pub help_url: Option<String>,
+    /// Where to get help, shown under `--help-all` (a channel or address).
+    pub support: Option<String>,
+}
+
+impl Branding {
+    /// `help_url`, else `default`.
+    pub fn url<'a>(&'a self, default: &'a str) -> &'a str {
+        self.help_url.as_deref().unwrap_or(default)
+    }
+}
+
+impl Config {
+    /// Load the first config file found in `root`, or defaults if none exists.
+    pub fn load(root: &Path) -> Result<Config, ScanError> {
+        Config::load_with(root, false)
+    }
+
+    /// Like `load`; `offline` reads a policy URL's cached copy instead of
+    /// fetching it.
+    pub fn load_with(root: &Path, offline: bool) -> Result<Config, ScanError> {
+        for name in CONFIG_FILES {
+            match fs::read_to_string(root.join(name)) {
+                Ok(content) => return Config::layered(name, &content, root, root, offline),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(ScanError::io(*name, &e)),
+            }
+        }
+        Ok(Config::default())
+    }
+
+    /// Load a config from an explicit file path.
+    pub fn load_file(path: &Path) -> Result<Config, ScanError> {
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Config::load_file_with(path, dir, false)
+    }
+
+    /// Like `load_file`, caching a policy URL under `root`.
+    pub fn load_file_with(path: &Path, root: &Path, offline: bool) -> Result<Config, ScanError> {
+        let name = path.display().to_string();
+        let content = fs::read_to_string(path).map_err(|e| ScanError::io(name.clone(), &e))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+        Config::layered(&name, &content, dir, root, offline)
+    }
+
+    /// Parse the config in `content`, read from `name`, over its policy.
+    /// The policy is validated on its own first so errors name its lines.
+    fn layered(
+        name: &str,
+        content: &str,
+        base: &Path,
+        root: &Path,
+        offline: bool,
+    ) -> Result<Config, ScanError> {
+        let own = parse_toml(content).map_err(|e| ScanError::parse(name, e))?;
+        let mut config =
+            Config::from_entries(own.clone()).map_err(|e| ScanError::parse(name, e))?;
+        if let Some(location) = config.policy.clone() {
+            let (origin, text) =
+                policy::load(root, base, &location, config.allow_http_policy, offline)?;
+            let shared = policy::entries(&location, &text)
+                .and_then(|e| Config::from_entries(e.clone()).map(|_| e))
+                .map_err(|e| ScanError::parse(location.as_str(), e))?;
+            config = Config::from_entries(shared.into_iter().chain(own).collect())
+                .map_err(|e| ScanError::parse(name, e))?;
+            config.policy_origin = Some(origin);
+        }
+        config.source = Some(name.to_string());
+        Ok(config)
+    }
+
+    /// Parse config text (a small TOML subset).
+    pub fn parse(content: &str) -> Result<Config, String> {
+        Config::from_entries(parse_toml(content)?)
+    }
+
+    /// Build a config from parsed entries; later entries override earlier
+    /// ones.
+    fn from_entries(entries: Vec<(usize, String, String, Value)>) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut builders: Vec<RuleBuilder> = Vec::new();
+        for (line, section, key, value) in entries {
+            let at = |msg: &str| format!("line {}: {}", line, msg);
+            if let Some(id) = section
+                .strip_prefix(custom::SECTION)
+                .and_then(|s| s.strip_prefix('.'))
+            {
+                let builder = match builders.iter_mut().position(|b| b.id == id) {
+                    Some(i) => &mut builders[i],
+                    None => {
+                        let valid = !id.is_empty()
+                            && id
+                                .chars()
+                                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+                        if !valid {
+                            return Err(at(&format!(
+                                "invalid rule ID '{}' (use lowercase letters, digits, and -)",
+                                id
+                            )));
+                        }
+                        if rules::find(id).is_some() {
+                            return Err(at(&format!("rule ID '{}' is a built-in check", id)));
+                        }
+                        builders.push(RuleBuilder {
+                            id: id.to_string(),
+                            line,
+                            ..Default::default()
+                        });
+                        builders.last_mut().expect("just pushed")
+                    }
+                };
+                let text = || {
+                    value
+                        .as_str()
+                        .ok_or(at(&format!("{} must be a string", key)))
+                };
+                let regex = |pattern: String| {
+                    Regex::new(&pattern).ok_or(at(&format!("unsupported regex '{}'", pattern)))
+                };
+                match key.as_str() {
+                    "severity" => {
+                        builder.severity = Some(
+                            parse_severity(&text()?)
+                                .ok_or(at("severity must be error, warning, or info"))?,
+                        )
+                    }
+                    "message" => builder.message = Some(text()?),
+                    "require_file" => builder.require_file = Some(text()?),
+                    "forbid_file" => builder.forbid_file = Some(text()?),
+                    "require_content" => builder.require_content = Some(regex(text()?)?),
+                    "forbid_content" => builder.forbid_content = Some(regex(text()?)?),
+                    "file" => builder.file = Some(text()?),
+                    "files" => builder.files = Some(text()?),
+                    k => return Err(at(&format!("unknown key '{}.{}'", section, k))),
+                }
+                continue;
+            }
+            match (section.as_str(), key.as_str()) {
+                ("", "policy") => {
+                    config.policy = Some(value.as_str().ok_or(at("policy must be a string"))?)
+                }
+                ("", "allow_http_policy") => {
+                    config.allow_http_policy = value
+                        .as_bool()
+                        .ok_or(at("allow_http_policy must be true or false"))?
+                }
+                ("", "format") => {
+                    config.format = Some(value.as_str().ok_or(at("format must be a string"))?)
+                }
+                ("", "author") => {
+                    config.author = Some(value.as_str().ok_or(at("author must be a string"))?)
+                }
+                ("", "license_type") => {
+                    let name = value.as_str().ok_or(at("license_type must be a string"))?;
+                    config.license_type = Some(Choice::parse(&name).map_err(|e| at(&e))?)
+                }
+                ("", "templates") => {
+                    config.templates = Some(value.as_str().ok_or(at("templates must be a string"))?)
+                }
+                ("", "locale") => {
+                    let tag = value.as_str().ok_or(at("locale must be a string"))?;
+                    config.locale = Some(Locale::parse(&tag).map_err(|e| at(&e))?)
+                }
+                ("", "lockfile_max_age_days") => {
+                    config.lockfile_max_age_days = Some(
+                        value
+                            .as_int()
+                            .ok_or(at("lockfile_max_age_days must be an integer"))?,
+                    )
+                }
+                ("", "large_file_threshold_mb") => {
+                    config.large_file_threshold_mb = Some(
+                        value
+                            .as_int()
+                            .ok_or(at("large_file_threshold_mb must be an integer"))?,
+                    )
+                }
+                ("", "blame") => {
+                    config.blame = value.as_bool().ok_or(at("blame must be true or false"))?
+                }
+                ("", "ci_health") => {
+                    config.ci_health = value
+                        .as_bool()
+                        .ok_or(at("ci_health must be true or false"))?
+                }
+                ("", "remote") => {
+                    let name = value.as_str().ok_or(at("remote must be a string"))?;
+                    if !remote::HOSTS.contains(&name.as_str()) {
+                        return Err(at(&format!(
+                            "unknown remote '{}' (expected: {})",
+                            name,
+                            remote::HOSTS.join(", ")
+                        )));
+                    }
+                    config.remote = Some(name)
+                }
+                ("", "check_links") => {
+                    config.check_links = value
+                        .as_bool()
+                        .ok_or(at("check_links must be true or false"))?
+                }
+                ("", "check_http_links") => {
+                    config.check_http_links = value
+                        .as_bool()
+                        .ok_or(at("check_http_links must be true or false"))?
+                }
+                ("", "online") => {
+                    config.online = value.as_bool().ok_or(at("online must be true or false"))?
+                }
+                ("", "cache") => {
+                    config.cache = Some(value.as_bool().ok_or(at("cache must be true or false"))?)
+                }
+                ("", "baseline") => {
+                    config.baseline = Some(value.as_str().ok_or(at("baseline must be a string"))?)
+                }
+                ("ci", "fail_level") => {
+                    let level = value.as_str().ok_or(at("fail_level must be a string"))?;
+                    if !["error", "warning", "info", "none"].contains(&level.as_str()) {
+                        return Err(at("fail_level must be error, warning, info, or none"));
+                    }
+                    config.ci_fail_level = Some(level);
+                }
+                ("ci", "format") => {
+                    config.ci_format = Some(value.as_str().ok_or(at("format must be a string"))?)
+                }
+                ("branding", "banner") => match value.as_bool() {
+                    Some(show) => config.branding.hide_banner = !show,
+                    None => {
+                        let text = value
+                            .as_str()
+                            .ok_or(at("banner must be a string or false"))?;
+                        config.branding.banner = Some(text);
+                    }
+                },
+                ("branding", "footer") => {
+                    config.branding.footer =
+                        Some(value.as_str().ok_or(at("footer must be a string"))?)
+                }
+                ("branding", "help_url") => {
+                    config.branding.help_url =
+                        Some(value.as_str().ok_or(at("help_url must be a string"))?)
+                }
+                ("branding", "support") => {
+                    config.branding.support =
+                        Some(value.as_str().ok_or(at("support must be a string"))?)
+                }
+                ("checks", "disable") => {
+                    config.disabled_checks = value
+                        .as_list()
+                        .ok_or(at("disable must be a list of strings"))?
+                }
+                ("severity", id) => {
+                    let level = value.as_str().ok_or(at("severity must be a string"))?;
+                    let severity = parse_severity(&level)
+                        .ok_or(at("severity must be error, warning, or info"))?;
+                    config.severity_overrides.push((id.to_string(), severity));
+                }
+                ("weights", category) => {
+                    if !SCORE_CATEGORIES.iter().any(|(c, _)| *c == category) {
+                        return Err(at(&format!("unknown score category '{}'", category)));
+                    }
+                    let weight = match value {
+                        Value::Int(i) => Some(i as f64),
+                        Value::Str(ref s) => s.parse::<f64>().ok(),
+                        _ => None,
+                    }
+                    .filter(|w| w.is_finite() && *w >= 0.0)
+                    .ok_or(at("weight must be a non-negative number"))?;
+                    config.score_weights.push((category.to_string(), weight));
+                }
+                (s, k) => {
+                    let name = if s.is_empty() {
+                        k.to_string()
+                    } else {
+                        format!("{}.{}", s, k)
+                    };
+                    return Err(at(&format!("unknown key '{}'", name)));
+                }
+            }
+        }
+        for builder in builders {
+            config.custom_rules.push(builder.build()?);
+        }
+        Ok(config)
+    }
+
+    pub fn large_file_threshold_mb(&self) -> u64 {
+        self.large_file_threshold_mb
+            .unwrap_or(DEFAULT_LARGE_FILE_MB)
+    }
+
+    pub fn lockfile_max_age_days(&self) -> u64 {
+        self.lockfile_max_age_days
+            .unwrap_or(freshness::DEFAULT_MAX_AGE_DAYS)
+    }
+
+    /// Locale for human-readable exports: configured, else from the environment.
+    pub fn locale(&self) -> &'static Locale {
+        self.locale.unwrap_or_else(Locale::from_env)
+    }
+
+    /// Multiplier for a score category (1 unless configured).
+    pub fn score_weight(&self, category: &str) -> f64 {
+        self.score_weights
+            .iter()
+            .rev()
+            .find(|(c, _)| c == category)
+            .map_or(1.0, |(_, w)| *w)
+    }
+
+    /// Whether file-level checks look at `rel`: every file, unless the scan
+    /// is limited to a change.
+    pub fn in_scope(&self, rel: &str) -> bool {
+        self.changed_files
+            .as_ref()
+            .is_none_or(|files| files.iter().any(|f| f.path == rel))
+    }
+
+    pub fn is_disabled(&self, id: &str) -> bool {
+        self.disabled_checks.iter().any(|d| check_matches(d, id))
+    }
+
+    /// Severity override for `id`; an exact entry beats a family one.
+    pub fn severity_for(&self, id: &str) -> Option<Severity> {
+        let overrides = &self.severity_overrides;
+        overrides
+            .iter()
+            .find(|(k, _)| k == id)
+            .or_else(|| overrides.iter().find(|(k, _)| check_matches(k, id)))
+            .map(|(_, s)| *s)
+    }
+}
+
+pub fn parse_severity(s: &str) -> Option<Severity> {
+    match s.to_lowercase().as_str() {
+        "error" => Some(Severity::Error),
+        "warning" | "warn" => Some(Severity::Warning),
+        "info" | "note" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+    List(Vec<Value>),
+    Table(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<String> {
+        match self {
+            Value::Str(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<u64> {
+        match self {
+            Value::Int(i) if *i >= 0 => Some(*i as u64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Look up a key in an inline table.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Table(items) => items.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<Vec<String>> {
+        match self {
+            Value::List(items) => items.iter().map(|v| v.as_str()).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// A parsed TOML document: `(line, section, key, value)` entries plus every
+/// section header seen (including empty ones such as a bare `[workspace]`).
+#[derive(Debug, Clone, Default)]
+pub struct TomlDoc {
+    pub sections: Vec<String>,
+    pub entries: Vec<(usize, String, String, Value)>,
+}
+
+impl TomlDoc {
+    /// Parse the TOML subset RepoMedic needs: `[section]` and `[[array]]`
+    /// headers and `key = value` pairs with strings (including `"""`
+    /// multi-line strings), integers, booleans, inline tables, and
+    /// (possibly multi-line) arrays.
+    pub fn parse(content: &str) -> Result<TomlDoc, String> {
+        let mut doc = TomlDoc::default();
+        let mut section = String::new();
+        let mut lines = content.lines().enumerate();
+        while let Some((n, raw)) = lines.next() {
+            let line_no = n + 1;
+            let mut line = strip_comment(raw).trim().to_string();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with('[') {
+                let name = line
+                    .strip_prefix("[[")
+                    .and_then(|l| l.strip_suffix("]]"))
+                    .or_else(|| line.strip_prefix('[').and_then(|l| l.strip_suffix(']')))
+                    .ok_or(format!("line {}: invalid section header", line_no))?;
+                section = name.trim().to_string();
+                if !doc.sections.contains(&section) {
+                    doc.sections.push(section.clone());
+                }
+                continue;
+            }
+            let Some(eq) = line.find('=') else {
+                return Err(format!("line {}: expected key = value", line_no));
+            };
+            let key = unquote_key(line[..eq].trim());
+            if key.is_empty() {
+                return Err(format!("line {}: missing key", line_no));
+            }
+            let rest = line[eq + 1..].trim_start().to_string();
+            if let Some(body) = rest.strip_prefix("\"\"\"") {
+                // Multi-line basic string: collect raw lines until the closing quotes.
+                let mut text = body.to_string();
+                while !text.contains("\"\"\"") {
+                    let Some((_, next)) = lines.next() else {
+                        return Err(format!("line {}: unterminated string", line_no));
+                    };
+                    text.push('\n');
+                    text.push_str(next);
+                }
+                let end = text.find("\"\"\"").unwrap_or(text.len());
+                let value = Value::Str(unescape(text[..end].trim_start_matches('\n')));
+                doc.entries.push((line_no, section.clone(), key, value));
+                continue;
+            }
+            // Arrays and inline tables may span several lines.
+            if rest.starts_with('[') || rest.starts_with('{') {
+                while !balanced(&line[eq + 1..]) {
+                    let Some((_, next)) = lines.next() else {
+                        return Err(format!("line {}: unterminated array", line_no));
+                    };
+                    line.push(' ');
+                    line.push_str(strip_comment(next).trim());
+                }
+            }
+            let value = parse_value(line[eq + 1..].trim())
+                .map_err(|e| format!("line {}: {}", line_no, e))?;
+            doc.entries.push((line_no, section.clone(), key, value));
+        }
+        Ok(doc)
+    }
+
+    pub fn has_section(&self, name: &str) -> bool {
+        self.sections.iter().any(|s| s == name)
+    }
+
+    /// First value for `key` in `section` (use "" for top-level keys).
+    pub fn get(&self, section: &str, key: &str) -> Option<&Value> {
+        self.entries
+            .iter()
+            .find(|(_, s, k, _)| s == section && k == key)
+            .map(|(_, _, _, v)| v)
+    }
+}
+
+/// Parse TOML into `(line, section, key, value)` entries.
+pub fn parse_toml(content: &str) -> Result<Vec<(usize, String, String, Value)>, String> {
+    TomlDoc::parse(content).map(|doc| doc.entries)
+}
+
+fn unquote_key(key: &str) -> String {
+    key.trim_matches(|c| c == '"' || c == '\'').to_string()
+}
+
+/// Drop a trailing `#` comment that is not inside a string.
+fn strip_comment(line: &str) -> &str {
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match quote {
+            Some('"') if escaped => escaped = false,
+            Some('"') if c == '\\' => escaped = true,
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '#' => return &line[..i],
+            None => {}
+        }
+    }
+    line
+}
+
+fn balanced(s: &str) -> bool {
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    for c in s.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '[' || c == '{' => depth += 1,
+            None if c == ']' || c == '}' => depth -= 1,
+            None => {}
+        }
+    }
+    depth <= 0
+}
+
+fn parse_value(s: &str) -> Result<Value, String> {
+    if let Some(rest) = s.strip_prefix('"') {
+        let end = rest.rfind('"').ok_or("unterminated string")?;
+        return Ok(Value::Str(unescape(&rest[..end])));
+    }
+    if let Some(rest) = s.strip_prefix('\'') {
+        let end = rest.rfind('\'').ok_or("unterminated string")?;
+        return Ok(Value::Str(rest[..end].to_string()));
+    }
+    if let Some(inner) = s.strip_prefix('[') {
+        let inner = inner.strip_suffix(']').ok_or("unterminated array")?;
+        let mut items = Vec::new();
+        for part in split_items(inner) {
+            let part = part.trim();
+            if !part.is_empty() {
+                items.push(parse_value(part)?);
+            }
+        }
+        return Ok(Value::List(items));
+    }
+    if let Some(inner) = s.strip_prefix('{') {
+        let inner = inner.strip_suffix('}').ok_or("unterminated inline table")?;
+        let mut items = Vec::new();
+        for part in split_items(inner) {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            let eq = part
+                .find('=')
+                .ok_or("expected key = value in inline table")?;
+            items.push((
+                unquote_key(part[..eq].trim()),
+                parse_value(part[eq + 1..].trim())?,
+            ));
+        }
+        return Ok(Value::Table(items));
+    }
+    match s {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+    if let Ok(i) = s.replace('_', "").parse::<i64>() {
+        return Ok(Value::Int(i));
+    }
+    // Floats and dates are kept as raw text; nothing we read needs them typed.
+    if s.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+') {
+        return Ok(Value::Str(s.to_string()));
+    }
+    Err(format!("invalid value '{}'", s))
+}
+
+/// Split array or inline-table contents on top-level commas.
+fn split_items(s: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    for c in s.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '[' || c == '{' => depth += 1,
+            None if c == ']' || c == '}' => depth -= 1,
+            None if c == ',' && depth == 0 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            None => {}
+        }
+        current.push(c);
+    }
+    items.push(current);
+    items
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, TomlDoc, Value};
+    use crate::scanner::Severity;
+
+    #[test]
+    fn toml_values() {
+        let doc = TomlDoc::parse(
+            "name = \"demo\" # trailing\ncount = 1_000\non = true\nurl = 'a#b'\n\
+             [package]\ntags = [\n  \"a\", # first\n  \"b\",\n]\ndep = { version = \"1\", optional = true }\n",
+        )
+        .expect("document should parse");
+        assert_eq!(doc.get("", "name"), Some(&Value::Str("demo".into())));
+        assert_eq!(doc.get("", "count"), Some(&Value::Int(1000)));
+        assert_eq!(doc.get("", "on"), Some(&Value::Bool(true)));
+        assert_eq!(doc.get("", "url"), Some(&Value::Str("a#b".into())));
+        assert_eq!(
+            doc.get("package", "tags").and_then(Value::as_list),
+            Some(vec!["a".to_string(), "b".to_string()])
+        );
+        let dep = doc.get("package", "dep").expect("inline table");
+        assert_eq!(dep.get("optional"), Some(&Value::Bool(true)));
+        assert!(doc.has_section("package"));
+    }
+
+    #[test]
+    fn toml_multiline_string_and_lines() {
+        let doc = TomlDoc::parse("[a]\ntext = \"\"\"\none\ntwo\"\"\"\nafter = \"x\\ty\"\n")
+            .expect("document should parse");
+        assert_eq!(doc.get("a", "text"), Some(&Value::Str("one\ntwo".into())));
+        assert_eq!(doc.get("a", "after"), Some(&Value::Str("x\ty".into())));
+        assert_eq!(doc.entries[1].0, 5);
+    }
+
+    #[test]
+    fn toml_errors() {
+        assert_eq!(
+            TomlDoc::parse("[a\n").unwrap_err(),
+            "line 1: invalid section header"
+        );
+        assert_eq!(
+            TomlDoc::parse("key\n").unwrap_err(),
+            "line 1: expected key = value"
+        );
+        assert_eq!(
+            TomlDoc::parse("k = [1,\n").unwrap_err(),
+            "line 1: unterminated array"
+        );
+        assert_eq!(
+            TomlDoc::parse("k = nope\n").unwrap_err(),
+            "line 1: invalid value 'nope'"
+        );
+    }
+
+    #[test]
+    fn config_keys() {
+        let config = Config::parse(
+            "large_file_threshold_mb = 20\ncache = false\n\
+             [checks]\ndisable = [\"missing-changelog\"]\n\
+             [severity]\nmissing-license = \"warning\"\n\
+             [weights]\nsecurity = 2\n",
+        )
+        .expect("config should parse");
+        assert_eq!(config.large_file_threshold_mb(), 20);
+        assert_eq!(config.cache, Some(false));
+        assert!(config.is_disabled("missing-changelog"));
+        assert!(!config.is_disabled("missing-readme"));
+        assert_eq!(
+            config.severity_for("missing-license"),
+            Some(Severity::Warning)
+        );
+        assert_eq!(config.score_weight("security"), 2.0);
+        assert_eq!(config.score_weight("ci"), 1.0);
+    }
+
+    #[test]
+    fn config_errors_name_the_line() {
+        assert_eq!(
+            Config::parse("colour = \"red\"\n").unwrap_err(),
+            "line 1: unknown key 'colour'"
+        );
+        assert_eq!(
+            Config::parse("\n[weights]\nstyle = 1\n").unwrap_err(),
+            "line 3: unknown score category 'style'"
+        );
+        assert_eq!(
+            Config::parse("[severity]\nmissing-readme = \"loud\"\n").unwrap_err(),
+            "line 2: severity must be error, warning, or info"
+        );
+        assert_eq!(
+            Config::parse("cache = \"yes\"\n").unwrap_err(),
+            "line 1: cache must be true or false"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Bytes inspected when classifying a file.
+const SNIFF_LEN: usize = 8000;
+
+/// How a file's bytes should be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentType {
+    /// UTF-8 (or ASCII) text, with or without a BOM.
+    Text,
+    Utf16Le,
+    Utf16Be,
+    Binary,
+}
+
+/// Classify content from its leading bytes.
+///
+/// A BOM wins outright. Without one, NUL bytes mean binary unless they
+/// fall on every other byte, which is how BOM-less UTF-16 text looks.
+pub fn sniff(bytes: &[u8]) -> ContentType {
+    let head = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if head.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return ContentType::Text;
+    }
+    if head.starts_with(&[0xFF, 0xFE]) {
+        return ContentType::Utf16Le;
+    }
+    if head.starts_with(&[0xFE, 0xFF]) {
+        return ContentType::Utf16Be;
+    }
+    if !head.contains(&0) {
+        return ContentType::Text;
+    }
+    let pairs = head.len() / 2;
+    if pairs > 0 {
+        let even_nul = head.iter().step_by(2).filter(|b| **b == 0).count();
+        let odd_nul = head.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+        if odd_nul * 10 >= pairs * 9 && even_nul == 0 {
+            return ContentType::Utf16Le;
+        }
+        if even_nul * 10 >= pairs * 9 && odd_nul == 0 {
+            return ContentType::Utf16Be;
+        }
+    }
+    ContentType::Binary
+}
+
+/// Decode file bytes as text. Returns `None` for binary content.
+pub fn decode(bytes: &[u8]) -> io::Result<Option<String>> {
+    let invalid = |what: &str| io::Error::new(io::ErrorKind::InvalidData, what.to_string());
+    match sniff(bytes) {
+        ContentType::Binary => Ok(None),
+        ContentType::Text => {
+            let body = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]).unwrap_or(bytes);
+            String::from_utf8(body.to_vec())
+                .map(Some)
+                .map_err(|_| invalid("stream did not contain valid UTF-8"))
+        }
+        ty @ (ContentType::Utf16Le | ContentType::Utf16Be) => {
+            let body = bytes
+                .strip_prefix(&[0xFF, 0xFE])
+                .or_else(|| bytes.strip_prefix(&[0xFE, 0xFF]))
+                .unwrap_or(bytes);
+            let units: Vec<u16> = body
+                .chunks_exact(2)
+                .map(|c| match ty {
+                    ContentType::Utf16Le => u16::from_le_bytes([c[0], c[1]]),
+                    _ => u16::from_be_bytes([c[0], c[1]]),
+                })
+                .collect();
+            String::from_utf16(&units)
+                .map(Some)
+                .map_err(|_| invalid("stream did not contain valid UTF-16"))
+        }
+    }
+}
+
+/// Read a file as text, skipping binaries after the first few kilobytes.
+pub fn read_text(path: &Path) -> io::Result<Option<String>> {
+    let mut file = File::open(path)?;
+    let mut bytes = Vec::new();
+    file.by_ref()
+        .take(SNIFF_LEN as u64)
+        .read_to_end(&mut bytes)?;
+    if sniff(&bytes) == ContentType::Binary {
+        return Ok(None);
+    }
+    file.read_to_end(&mut bytes)?;
+    decode(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, sniff, ContentType};
+
+    fn utf16(text: &str, big_endian: bool) -> Vec<u8> {
+        text.encode_utf16()
+            .flat_map(|u| {
+                if big_endian {
+                    u.to_be_bytes()
+                } else {
+                    u.to_le_bytes()
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sniffing() {
+        assert_eq!(sniff(b"plain text"), ContentType::Text);
+        assert_eq!(sniff(b""), ContentType::Text);
+        assert_eq!(sniff(b"\xEF\xBB\xBFwith bom"), ContentType::Text);
+        assert_eq!(sniff(&utf16("no bom", false)), ContentType::Utf16Le);
+        assert_eq!(sniff(&utf16("no bom", true)), ContentType::Utf16Be);
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), ContentType::Binary);
+    }
+
+    #[test]
+    fn decoding() {
+        assert_eq!(decode(b"\xEF\xBB\xBFhi").unwrap().as_deref(), Some("hi"));
+        let mut le = vec![0xFF, 0xFE];
+        le.extend(utf16("héllo", false));
+        assert_eq!(decode(&le).unwrap().as_deref(), Some("héllo"));
+        let mut be = vec![0xFE, 0xFF];
+        be.extend(utf16("héllo", true));
+        assert_eq!(decode(&be).unwrap().as_deref(), Some("héllo"));
+        assert_eq!(decode(b"\0\x01\x02\0\0\0").unwrap(), None);
+        assert!(decode(b"\xC3\x28").is_err());
+    }
+}
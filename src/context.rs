@@ -0,0 +1,196 @@
+use crate::cache::FileCache;
+use crate::config::TomlDoc;
+use crate::content;
+use crate::scanner::{ScanError, ScanResult};
+use std::cell::{Cell, OnceCell, Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::rc::Rc;
+
+/// State shared by every detector during one scan.
+///
+/// File contents and parsed manifests are read at most once and memoized, so
+/// detectors can ask for `Cargo.toml` or `package.json` freely. Read and
+/// parse failures are queued as scan warnings (once per file) instead of
+/// being reported by each detector.
+pub struct ScanContext<'a> {
+    /// Repository root being scanned.
+    pub root: &'a Path,
+    result: RefCell<ScanResult>,
+    files: RefCell<HashMap<String, Option<Rc<String>>>>,
+    package_json: OnceCell<Option<Rc<serde_json::Value>>>,
+    cargo_toml: OnceCell<Option<Rc<TomlDoc>>>,
+    walked: RefCell<Rc<Vec<(String, u64)>>>,
+    warnings: RefCell<Vec<ScanError>>,
+    reads: Cell<usize>,
+    binary: Cell<usize>,
+    cache: RefCell<Option<FileCache>>,
+}
+
+impl<'a> ScanContext<'a> {
+    pub fn new(root: &'a Path, result: ScanResult) -> Self {
+        let cache = (result.config.cache == Some(true)).then(|| FileCache::load(root));
+        Self {
+            root,
+            result: RefCell::new(result),
+            files: RefCell::new(HashMap::new()),
+            package_json: OnceCell::new(),
+            cargo_toml: OnceCell::new(),
+            walked: RefCell::new(Rc::new(Vec::new())),
+            warnings: RefCell::new(Vec::new()),
+            reads: Cell::new(0),
+            binary: Cell::new(0),
+            cache: RefCell::new(cache),
+        }
+    }
+
+    /// Results merged from detectors that already ran.
+    pub fn result(&self) -> Ref<'_, ScanResult> {
+        self.result.borrow()
+    }
+
+    pub(crate) fn result_mut(&self) -> RefMut<'_, ScanResult> {
+        self.result.borrow_mut()
+    }
+
+    pub fn into_result(self) -> ScanResult {
+        self.result.into_inner()
+    }
+
+    /// Text content of a file relative to the root, read at most once.
+    ///
+    /// Missing and binary files are silently `None`; UTF-16 text is decoded.
+    /// Other failures queue a warning.
+    pub fn read(&self, rel: &str) -> Option<Rc<String>> {
+        if let Some(cached) = self.files.borrow().get(rel) {
+            return cached.clone();
+        }
+        let content = self.load(rel);
+        self.files
+            .borrow_mut()
+            .insert(rel.to_string(), content.clone());
+        content
+    }
+
+    /// Like `read`, but a file not already cached is not kept afterwards.
+    ///
+    /// For whole-tree passes that look at each file once.
+    pub fn read_uncached(&self, rel: &str) -> Option<Rc<String>> {
+        if let Some(cached) = self.files.borrow().get(rel) {
+            return cached.clone();
+        }
+        self.load(rel)
+    }
+
+    fn load(&self, rel: &str) -> Option<Rc<String>> {
+        self.reads.set(self.reads.get() + 1);
+        match content::read_text(&self.root.join(rel)) {
+            Ok(Some(c)) => Some(Rc::new(c)),
+            Ok(None) => {
+                self.binary.set(self.binary.get() + 1);
+                None
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => {
+                self.warn(ScanError::io(rel, &e));
+                None
+            }
+        }
+    }
+
+    /// Whether the whole-tree `pass` can skip `rel`: it found nothing there
+    /// last scan and the file hasn't changed since. Always false without
+    /// the cache.
+    pub fn cached_clean(&self, rel: &str, pass: &str) -> bool {
+        match self.cache.borrow_mut().as_mut() {
+            Some(cache) => cache.is_clean(self.root, rel, pass, || self.read_uncached(rel)),
+            None => false,
+        }
+    }
+
+    /// Record that `pass` found nothing in `rel` (text `content`), so the
+    /// next scan can skip it while it's unchanged.
+    pub fn mark_clean(&self, rel: &str, pass: &str, content: &str) {
+        if let Some(cache) = self.cache.borrow_mut().as_mut() {
+            cache.mark_clean(self.root, rel, pass, content);
+        }
+    }
+
+    /// Write the cache back, returning how many passes it let skip a file.
+    pub(crate) fn save_cache(&self) -> usize {
+        let partial = self.result.borrow().config.changed_files.is_some();
+        let Some(cache) = self.cache.borrow_mut().take() else {
+            return 0;
+        };
+        let hits = cache.hits();
+        cache.save(partial);
+        hits
+    }
+
+    /// Parsed root `package.json`, if present and valid.
+    pub fn package_json(&self) -> Option<Rc<serde_json::Value>> {
+        self.package_json
+            .get_or_init(|| {
+                let content = self.read("package.json")?;
+                match serde_json::from_str(&content) {
+                    Ok(v) => Some(Rc::new(v)),
+                    Err(e) => {
+                        self.warn(ScanError::parse("package.json", e.to_string()));
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Parsed root `Cargo.toml`, if present and valid.
+    pub fn cargo_toml(&self) -> Option<Rc<TomlDoc>> {
+        self.cargo_toml
+            .get_or_init(|| {
+                let content = self.read("Cargo.toml")?;
+                match TomlDoc::parse(&content) {
+                    Ok(doc) => Some(Rc::new(doc)),
+                    Err(e) => {
+                        self.warn(ScanError::parse("Cargo.toml", e));
+                        None
+                    }
+                }
+            })
+            .clone()
+    }
+
+    /// Files seen by the tree walk (root-relative path, size in bytes).
+    ///
+    /// Empty until the `files` detector has run.
+    pub fn walked_files(&self) -> Rc<Vec<(String, u64)>> {
+        self.walked.borrow().clone()
+    }
+
+    pub(crate) fn set_walked_files(&self, files: Vec<(String, u64)>) {
+        *self.walked.borrow_mut() = Rc::new(files);
+    }
+
+    /// Whether file-level checks should look at `rel` (see `Config::in_scope`).
+    pub fn in_scope(&self, rel: &str) -> bool {
+        self.result.borrow().config.in_scope(rel)
+    }
+
+    /// Number of files actually read from disk.
+    pub fn files_read(&self) -> usize {
+        self.reads.get()
+    }
+
+    /// Number of files skipped because they sniffed as binary.
+    pub fn binary_skipped(&self) -> usize {
+        self.binary.get()
+    }
+
+    pub fn warn(&self, warning: ScanError) {
+        self.warnings.borrow_mut().push(warning);
+    }
+
+    pub(crate) fn take_warnings(&self) -> Vec<ScanError> {
+        std::mem::take(&mut *self.warnings.borrow_mut())
+    }
+}
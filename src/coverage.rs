@@ -0,0 +1,136 @@
+use crate::badges;
+use serde_json::Value;
+
+/// Coverage tool config files at the repository root.
+pub const CONFIG_FILES: &[&str] = &[
+    "codecov.yml",
+    ".codecov.yml",
+    ".github/codecov.yml",
+    ".coveralls.yml",
+    "tarpaulin.toml",
+    ".tarpaulin.toml",
+    ".nycrc",
+    ".nycrc.json",
+    ".nycrc.yml",
+    ".c8rc.json",
+    ".coveragerc",
+];
+
+/// Jest configs that may set `coverageThreshold`.
+pub const JEST_CONFIGS: &[&str] = &[
+    "jest.config.js",
+    "jest.config.cjs",
+    "jest.config.mjs",
+    "jest.config.ts",
+    "jest.config.json",
+];
+
+/// CI steps that measure or upload coverage, with the tool named in reports.
+/// Matched against the lowercased config.
+const CI_MARKERS: &[(&str, &str)] = &[
+    ("codecov/codecov-action", "codecov"),
+    ("codecov.io/bash", "codecov"),
+    ("coverallsapp/github-action", "coveralls"),
+    ("cargo tarpaulin", "tarpaulin"),
+    ("cargo llvm-cov", "llvm-cov"),
+    ("grcov", "grcov"),
+    ("--coverage", "coverage flag"),
+    ("pytest-cov", "pytest-cov"),
+    ("--cov", "pytest-cov"),
+    ("coverage run", "coverage.py"),
+    ("-coverprofile", "go test -coverprofile"),
+    ("jacoco", "jacoco"),
+];
+
+/// Coverage tools a CI config runs, deduplicated, in `CI_MARKERS` order.
+pub fn ci_tools(content: &str) -> Vec<&'static str> {
+    let text = content.to_lowercase();
+    let mut tools: Vec<&'static str> = Vec::new();
+    for (marker, tool) in CI_MARKERS {
+        if text.contains(marker) && !tools.contains(tool) {
+            tools.push(tool);
+        }
+    }
+    tools
+}
+
+/// Whether `package.json` configures coverage: a Jest `coverageThreshold`
+/// or an `nyc` / `c8` section.
+pub fn package_json_configures(pkg: &Value) -> bool {
+    pkg.get("jest")
+        .and_then(|j| j.get("coverageThreshold"))
+        .is_some()
+        || pkg.get("nyc").is_some()
+        || pkg.get("c8").is_some()
+}
+
+/// Whether a Python config (`pyproject.toml`, `setup.cfg`, `tox.ini`)
+/// has a coverage.py section.
+pub fn python_config_configures(content: &str) -> bool {
+    content.lines().any(|l| {
+        let l = l.trim();
+        l.starts_with("[tool.coverage") || l.starts_with("[coverage:")
+    })
+}
+
+/// Whether a README line shows a coverage badge.
+pub fn has_badge(content: &str) -> bool {
+    content.lines().any(|line| {
+        badges::image_urls(line).iter().any(|url| {
+            let url = url.to_lowercase();
+            url.contains("codecov.io")
+                || url.contains("coveralls.io")
+                || (url.contains("img.shields.io") && url.contains("coverage"))
+                || url.contains("coverage.svg")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ci_tools, has_badge, package_json_configures, python_config_configures};
+    use serde_json::json;
+
+    #[test]
+    fn ci_steps() {
+        let ci = "steps:\n  - run: cargo tarpaulin --out xml\n  - uses: codecov/codecov-action@v4\n  - run: bash <(curl -s https://codecov.io/bash)\n";
+        assert_eq!(ci_tools(ci), ["codecov", "tarpaulin"]);
+        assert_eq!(ci_tools("run: pytest --cov=src"), ["pytest-cov"]);
+        assert!(ci_tools("run: cargo test").is_empty());
+    }
+
+    #[test]
+    fn package_json() {
+        assert!(package_json_configures(&json!({
+            "jest": { "coverageThreshold": { "global": { "lines": 80 } } }
+        })));
+        assert!(package_json_configures(&json!({ "nyc": {} })));
+        assert!(!package_json_configures(
+            &json!({ "jest": { "verbose": true } })
+        ));
+    }
+
+    #[test]
+    fn python_configs() {
+        assert!(python_config_configures(
+            "[tool.coverage.run]\nbranch = true\n"
+        ));
+        assert!(python_config_configures(
+            "[metadata]\n\n[coverage:report]\n"
+        ));
+        assert!(!python_config_configures("[tool.pytest.ini_options]\n"));
+    }
+
+    #[test]
+    fn badges() {
+        assert!(has_badge(
+            "[![codecov](https://codecov.io/gh/a/b/branch/main/graph/badge.svg)](https://codecov.io/gh/a/b)"
+        ));
+        assert!(has_badge(
+            "<img src=\"https://img.shields.io/badge/Coverage-91%25-green\">"
+        ));
+        assert!(!has_badge(
+            "![CI](https://github.com/a/b/actions/workflows/ci.yml/badge.svg)\nSee [coverage](https://codecov.io/gh/a/b)."
+        ));
+    }
+}
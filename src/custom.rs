@@ -0,0 +1,261 @@
+use crate::regex::Regex;
+use crate::scanner::Severity;
+use serde::Serialize;
+
+/// Section prefix for custom rules in `.repomedic.toml` (`[rules.<id>]`).
+pub const SECTION: &str = "rules";
+
+/// An organization-specific rule defined in the config.
+///
+/// ```toml
+/// [rules.catalog-info]
+/// require_file = "catalog-info.yaml"
+/// severity = "error"
+///
+/// [rules.readme-support]
+/// file = "README.md"
+/// require_content = "^## Support"
+///
+/// [rules.no-internal-hosts]
+/// forbid_content = "corp\\.example\\.com"
+/// files = "*.yml"
+/// ```
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    /// Check ID, used like a built-in one in config, suppressions, and SARIF.
+    pub id: String,
+    pub severity: Severity,
+    /// Replaces the default issue message.
+    pub message: Option<String>,
+    pub kind: RuleKind,
+}
+
+/// What a custom rule checks. Paths are relative to the root; globs use `*`
+/// for any run of characters, as in `.repomedicignore`.
+#[derive(Debug, Clone)]
+pub enum RuleKind {
+    /// A file matching the path or glob must exist.
+    RequireFile(String),
+    /// No file may match the glob.
+    ForbidFile(String),
+    /// `file` must exist and have a line matching `pattern`.
+    RequireContent { file: String, pattern: Regex },
+    /// No line of a file matching `files` (every text file when unset) may
+    /// match `pattern`.
+    ForbidContent {
+        files: Option<String>,
+        pattern: Regex,
+    },
+}
+
+impl CustomRule {
+    /// One-line summary for `rules list`.
+    pub fn description(&self) -> String {
+        match &self.kind {
+            RuleKind::RequireFile(p) => format!("Requires {}", p),
+            RuleKind::ForbidFile(p) => format!("Forbids files matching {}", p),
+            RuleKind::RequireContent { file, pattern } => {
+                format!("Requires /{}/ in {}", pattern.as_str(), file)
+            }
+            RuleKind::ForbidContent { files, pattern } => format!(
+                "Forbids /{}/ in {}",
+                pattern.as_str(),
+                files.as_deref().unwrap_or("any file")
+            ),
+        }
+    }
+}
+
+/// A custom rule violation found by the scan.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub rule: String,
+    pub severity: Severity,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl Violation {
+    /// A violation of `rule`, worded by its `message` when one is configured.
+    pub fn new(
+        rule: &CustomRule,
+        default: String,
+        file: Option<&str>,
+        line: Option<usize>,
+    ) -> Self {
+        let message = match (&rule.message, file, line) {
+            (None, ..) => default,
+            (Some(m), Some(f), Some(l)) => format!("{} in {} at line {}", m, f, l),
+            (Some(m), Some(f), None) if !matches!(rule.kind, RuleKind::RequireContent { .. }) => {
+                format!("{}: {}", m, f)
+            }
+            (Some(m), ..) => m.clone(),
+        };
+        Violation {
+            rule: rule.id.clone(),
+            severity: rule.severity,
+            message,
+            file: file.map(String::from),
+            line,
+        }
+    }
+}
+
+/// Builds a `CustomRule` from the keys of one `[rules.<id>]` section.
+#[derive(Debug, Default)]
+pub(crate) struct RuleBuilder {
+    pub id: String,
+    /// Line of the section's first key, for error messages.
+    pub line: usize,
+    pub severity: Option<Severity>,
+    pub message: Option<String>,
+    pub require_file: Option<String>,
+    pub forbid_file: Option<String>,
+    pub require_content: Option<Regex>,
+    pub forbid_content: Option<Regex>,
+    pub file: Option<String>,
+    pub files: Option<String>,
+}
+
+impl RuleBuilder {
+    pub fn build(self) -> Result<CustomRule, String> {
+        let at = |msg: &str| format!("line {}: rule '{}' {}", self.line, self.id, msg);
+        let kinds = [
+            self.require_file.is_some(),
+            self.forbid_file.is_some(),
+            self.require_content.is_some(),
+            self.forbid_content.is_some(),
+        ];
+        if kinds.iter().filter(|k| **k).count() != 1 {
+            return Err(at(
+                "needs exactly one of require_file, forbid_file, require_content, forbid_content",
+            ));
+        }
+        let kind = if let Some(p) = self.require_file {
+            RuleKind::RequireFile(p)
+        } else if let Some(p) = self.forbid_file {
+            RuleKind::ForbidFile(p)
+        } else if let Some(pattern) = self.require_content {
+            let file = self.file.ok_or(at("needs `file` for require_content"))?;
+            RuleKind::RequireContent { file, pattern }
+        } else {
+            let pattern = self.forbid_content.ok_or(at("is empty"))?;
+            RuleKind::ForbidContent {
+                files: self.files,
+                pattern,
+            }
+        };
+        Ok(CustomRule {
+            id: self.id,
+            severity: self.severity.unwrap_or(Severity::Warning),
+            message: self.message,
+            kind,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RuleBuilder, RuleKind, Violation};
+    use crate::regex::Regex;
+    use crate::scanner::Severity;
+
+    fn builder(id: &str) -> RuleBuilder {
+        RuleBuilder {
+            id: id.to_string(),
+            line: 3,
+            ..RuleBuilder::default()
+        }
+    }
+
+    #[test]
+    fn build_rules() {
+        let rule = RuleBuilder {
+            require_file: Some("catalog-info.yaml".to_string()),
+            ..builder("catalog")
+        }
+        .build()
+        .unwrap();
+        assert_eq!(rule.severity, Severity::Warning);
+        assert_eq!(rule.description(), "Requires catalog-info.yaml");
+        let rule = RuleBuilder {
+            forbid_content: Regex::new("corp\\.example\\.com"),
+            files: Some("*.yml".to_string()),
+            severity: Some(Severity::Error),
+            ..builder("hosts")
+        }
+        .build()
+        .unwrap();
+        assert!(matches!(rule.kind, RuleKind::ForbidContent { .. }));
+        assert_eq!(
+            rule.description(),
+            "Forbids /corp\\.example\\.com/ in *.yml"
+        );
+    }
+
+    #[test]
+    fn invalid_rules() {
+        assert_eq!(
+            builder("empty").build().unwrap_err(),
+            "line 3: rule 'empty' needs exactly one of require_file, forbid_file, require_content, forbid_content"
+        );
+        let both = RuleBuilder {
+            require_file: Some("a".to_string()),
+            forbid_file: Some("b".to_string()),
+            ..builder("both")
+        };
+        assert!(both.build().is_err());
+        let no_file = RuleBuilder {
+            require_content: Regex::new("^## Support"),
+            ..builder("support")
+        };
+        assert_eq!(
+            no_file.build().unwrap_err(),
+            "line 3: rule 'support' needs `file` for require_content"
+        );
+    }
+
+    #[test]
+    fn violation_messages() {
+        let forbid = RuleBuilder {
+            forbid_file: Some("*.pem".to_string()),
+            message: Some("Keys belong in the vault".to_string()),
+            ..builder("no-keys")
+        }
+        .build()
+        .unwrap();
+        let default = || "default".to_string();
+        assert_eq!(
+            Violation::new(&forbid, default(), Some("a.pem"), None).message,
+            "Keys belong in the vault: a.pem"
+        );
+        assert_eq!(
+            Violation::new(&forbid, default(), Some("a.pem"), Some(2)).message,
+            "Keys belong in the vault in a.pem at line 2"
+        );
+        let require = RuleBuilder {
+            require_content: Regex::new("^## Support"),
+            file: Some("README.md".to_string()),
+            message: Some("Add a support section".to_string()),
+            ..builder("support")
+        }
+        .build()
+        .unwrap();
+        let violation = Violation::new(&require, default(), Some("README.md"), None);
+        assert_eq!(violation.message, "Add a support section");
+        assert_eq!(violation.rule, "support");
+        let unworded = RuleBuilder {
+            require_file: Some("a".to_string()),
+            ..builder("plain")
+        }
+        .build()
+        .unwrap();
+        assert_eq!(
+            Violation::new(&unworded, default(), None, None).message,
+            "default"
+        );
+    }
+}
@@ -0,0 +1,706 @@
+use crate::config::{TomlDoc, Value};
+use serde::Serialize;
+#[cfg(feature = "network")]
+use std::process::Command;
+#[cfg(feature = "network")]
+use std::thread;
+
+/// Parallel registry requests made by `outdated`.
+#[cfg(feature = "network")]
+const REGISTRY_WORKERS: usize = 8;
+
+/// Seconds to wait for each registry response.
+#[cfg(feature = "network")]
+const REGISTRY_TIMEOUT_SECS: u64 = 15;
+
+/// Package ecosystem of a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Pip,
+    Go,
+}
+
+/// Where a dependency comes from, as its manifest declares it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// A registry release matching this requirement (empty when unpinned).
+    Registry(String),
+    /// A git repository, or for Go an untagged commit (pseudo-version).
+    Git(String),
+    /// A local path, workspace member, or alias; not checked.
+    Local,
+}
+
+/// A dependency declared in a root manifest.
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub manifest: &'static str,
+    pub line: usize,
+    /// Name in the registry (after Cargo `package = "..."` renames).
+    pub name: String,
+    pub source: Source,
+}
+
+impl Dependency {
+    /// Registry the dependency resolves against, from its manifest.
+    #[cfg(feature = "network")]
+    fn ecosystem(&self) -> Ecosystem {
+        match self.manifest {
+            "Cargo.toml" => Ecosystem::Cargo,
+            "package.json" => Ecosystem::Npm,
+            "requirements.txt" => Ecosystem::Pip,
+            _ => Ecosystem::Go,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// Accepts any future version: `*`, `latest`, `>=1.0`, or no pin.
+    Wildcard,
+    /// Fetched from git instead of a registry release.
+    Git,
+    /// A newer major version is published (only with `--online`).
+    Outdated,
+}
+
+/// A dependency declaration that lets builds drift or bypass the registry.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyProblem {
+    pub manifest: String,
+    pub line: usize,
+    pub name: String,
+    /// Requirement or git source as written.
+    pub spec: String,
+    pub kind: ProblemKind,
+    /// Newest release, for `outdated`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest: Option<String>,
+}
+
+/// Dependencies declared in the root Cargo.toml, package.json,
+/// requirements.txt, and go.mod, read with `read`.
+pub fn declared(read: &dyn Fn(&str) -> Option<String>) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    if let Some(doc) = read("Cargo.toml").and_then(|c| TomlDoc::parse(&c).ok()) {
+        deps.extend(cargo(&doc));
+    }
+    if let Some(content) = read("package.json") {
+        deps.extend(npm(&content));
+    }
+    if let Some(content) = read("requirements.txt") {
+        deps.extend(pip(&content));
+    }
+    if let Some(content) = read("go.mod") {
+        deps.extend(go(&content));
+    }
+    deps
+}
+
+fn is_dependency_table(section: &str) -> bool {
+    ["dependencies", "dev-dependencies", "build-dependencies"]
+        .iter()
+        .any(|t| section == *t || section.ends_with(&format!(".{}", t)))
+}
+
+fn cargo(doc: &TomlDoc) -> Vec<Dependency> {
+    // (table, name, first line, keys) in declaration order
+    type Declaration = (String, String, usize, Vec<(String, Value)>);
+    let mut found: Vec<Declaration> = Vec::new();
+    for (line, section, key, value) in &doc.entries {
+        let (table, name, fields) = if is_dependency_table(section) {
+            match key.split_once('.') {
+                Some((name, field)) => (section.as_str(), name, vec![(field, value)]),
+                None => match value {
+                    Value::Table(items) => (
+                        section.as_str(),
+                        key.as_str(),
+                        items.iter().map(|(k, v)| (k.as_str(), v)).collect(),
+                    ),
+                    _ => (section.as_str(), key.as_str(), vec![("version", value)]),
+                },
+            }
+        } else {
+            match section.rsplit_once('.') {
+                Some((table, name)) if is_dependency_table(table) => {
+                    (table, name, vec![(key.as_str(), value)])
+                }
+                _ => continue,
+            }
+        };
+        let fields = fields.into_iter().map(|(k, v)| (k.to_string(), v.clone()));
+        match found
+            .iter_mut()
+            .find(|(t, n, _, _)| t == table && n == name)
+        {
+            Some(entry) => entry.3.extend(fields),
+            None => found.push((table.to_string(), name.to_string(), *line, fields.collect())),
+        }
+    }
+    found
+        .into_iter()
+        .map(|(_, name, line, fields)| {
+            let field = |k: &str| {
+                fields
+                    .iter()
+                    .find(|(f, _)| f == k)
+                    .and_then(|(_, v)| v.as_str())
+            };
+            let source = if let Some(url) = field("git") {
+                let pin = ["rev", "tag", "branch"]
+                    .iter()
+                    .find_map(|k| Some(format!(" ({} {})", k, field(k)?)))
+                    .unwrap_or_default();
+                Source::Git(url + &pin)
+            } else if field("path").is_some() || fields.iter().any(|(f, _)| f == "workspace") {
+                Source::Local
+            } else {
+                field("version").map_or(Source::Local, Source::Registry)
+            };
+            Dependency {
+                manifest: "Cargo.toml",
+                line,
+                name: field("package").unwrap_or(name),
+                source,
+            }
+        })
+        .collect()
+}
+
+fn npm(content: &str) -> Vec<Dependency> {
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = content.lines().collect();
+    let mut deps = Vec::new();
+    // peerDependencies are deliberately wide, so they're left out
+    for table in ["dependencies", "devDependencies", "optionalDependencies"] {
+        let Some(entries) = package.get(table).and_then(|t| t.as_object()) else {
+            continue;
+        };
+        let start = lines
+            .iter()
+            .position(|l| l.contains(&format!("\"{}\"", table)))
+            .unwrap_or(0);
+        for (name, spec) in entries {
+            let Some(spec) = spec.as_str() else {
+                continue;
+            };
+            let quoted = format!("\"{}\"", name);
+            let line = lines
+                .iter()
+                .skip(start)
+                .position(|l| l.trim_start().starts_with(&quoted))
+                .map_or(1, |i| start + i + 1);
+            deps.push(Dependency {
+                manifest: "package.json",
+                line,
+                name: name.clone(),
+                source: npm_source(spec),
+            });
+        }
+    }
+    deps
+}
+
+fn npm_source(spec: &str) -> Source {
+    const GIT: &[&str] = &[
+        "git+",
+        "git://",
+        "github:",
+        "gitlab:",
+        "bitbucket:",
+        "gist:",
+    ];
+    const LOCAL: &[&str] = &["file:", "link:", "workspace:", "npm:", "portal:", "patch:"];
+    let spec = spec.trim();
+    if GIT.iter().any(|p| spec.starts_with(p))
+        || (spec.starts_with("http") && spec.contains(".git"))
+    {
+        Source::Git(spec.to_string())
+    } else if LOCAL.iter().any(|p| spec.starts_with(p))
+        || spec.starts_with("http")
+        || spec.starts_with(['.', '/'])
+        || spec.starts_with("~/")
+    {
+        Source::Local
+    } else if spec.contains('/') {
+        // GitHub shorthand: `user/repo#ref`
+        Source::Git(spec.to_string())
+    } else {
+        Source::Registry(spec.to_string())
+    }
+}
+
+fn pip(content: &str) -> Vec<Dependency> {
+    let mut deps = Vec::new();
+    for (n, raw) in content.lines().enumerate() {
+        let line = raw.split(" #").next().unwrap_or(raw);
+        let line = line.trim().trim_end_matches('\\').trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (name, source) = if let Some(url) = line
+            .strip_prefix("-e ")
+            .or_else(|| line.strip_prefix("--editable "))
+            .or_else(|| line.starts_with("git+").then_some(line))
+        {
+            let url = url.trim();
+            if !url.starts_with("git+") {
+                continue;
+            }
+            let name = url
+                .split("#egg=")
+                .nth(1)
+                .or_else(|| url.trim_end_matches(".git").rsplit('/').next())
+                .unwrap_or(url);
+            (name.to_string(), Source::Git(url.to_string()))
+        } else if line.starts_with('-') {
+            // -r, -c, --index-url, and other options
+            continue;
+        } else if let Some((name, url)) = line.split_once(" @ ") {
+            let url = url.split(';').next().unwrap_or(url).trim();
+            let source = if url.starts_with("git+") {
+                Source::Git(url.to_string())
+            } else {
+                Source::Local
+            };
+            (name.trim().to_string(), source)
+        } else {
+            let end = line
+                .find(|c: char| !(c.is_alphanumeric() || "-_.".contains(c)))
+                .unwrap_or(line.len());
+            let rest = &line[end..];
+            let rest = match rest.strip_prefix('[') {
+                Some(r) => r.split_once(']').map_or("", |(_, after)| after),
+                None => rest,
+            };
+            // Drop environment markers and per-requirement options (`--hash`)
+            let spec = rest.split(';').next().unwrap_or("");
+            let spec = spec.split(" --").next().unwrap_or("").replace(' ', "");
+            (line[..end].to_string(), Source::Registry(spec))
+        };
+        deps.push(Dependency {
+            manifest: "requirements.txt",
+            line: n + 1,
+            name,
+            source,
+        });
+    }
+    deps
+}
+
+fn go(content: &str) -> Vec<Dependency> {
+    go_requires(content)
+        .into_iter()
+        // Indirect requirements are pinned by go mod tidy, not by hand
+        .filter(|(_, _, _, indirect)| !indirect)
+        .map(|(line, path, version, _)| {
+            let source = if is_pseudo_version(version) {
+                Source::Git(version.to_string())
+            } else {
+                Source::Registry(version.to_string())
+            };
+            Dependency {
+                manifest: "go.mod",
+                line,
+                name: path.to_string(),
+                source,
+            }
+        })
+        .collect()
+}
+
+/// `require` lines of a go.mod as `(line, module path, version, indirect)`.
+pub fn go_requires(content: &str) -> Vec<(usize, &str, &str, bool)> {
+    let mut requires = Vec::new();
+    let mut in_block = false;
+    for (n, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        let spec = if in_block {
+            if line.starts_with(')') {
+                in_block = false;
+                continue;
+            }
+            line
+        } else if let Some(rest) = line.strip_prefix("require") {
+            let rest = rest.trim();
+            if rest.starts_with('(') {
+                in_block = true;
+                continue;
+            }
+            rest
+        } else {
+            continue;
+        };
+        let mut parts = spec.split_whitespace();
+        let (Some(path), Some(version)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        if path.starts_with("//") {
+            continue;
+        }
+        requires.push((n + 1, path, version, spec.contains("// indirect")));
+    }
+    requires
+}
+
+/// `v0.0.0-20240101120000-abcdef123456`: a commit with no release tag.
+fn is_pseudo_version(version: &str) -> bool {
+    let parts: Vec<&str> = version.split(['-', '.']).collect();
+    parts.windows(2).any(|w| {
+        w[0].len() == 14
+            && w[0].bytes().all(|b| b.is_ascii_digit())
+            && w[1].len() == 12
+            && w[1].bytes().all(|b| b.is_ascii_hexdigit())
+    })
+}
+
+/// Whether a version requirement admits every future release: empty, `*`,
+/// `x`, `latest`, or only lower bounds (`>=1.0`). With `||` alternatives,
+/// one unbounded alternative is enough.
+pub fn accepts_any(requirement: &str) -> bool {
+    requirement.split("||").any(|alternative| {
+        // Rejoin operators written apart from their version: `>= 1.0`
+        let mut comparators: Vec<String> = Vec::new();
+        for token in alternative.split([',', ' ']).filter(|t| !t.is_empty()) {
+            match comparators.last_mut() {
+                Some(last) if last.chars().all(|c| "<>=!~^".contains(c)) => last.push_str(token),
+                _ => comparators.push(token.to_string()),
+            }
+        }
+        comparators
+            .iter()
+            .all(|c| matches!(c.as_str(), "*" | "x" | "X" | "latest") || c.starts_with(['>', '!']))
+    })
+}
+
+/// Wildcard and git dependencies.
+pub fn check(deps: &[Dependency]) -> Vec<DependencyProblem> {
+    deps.iter()
+        .filter_map(|d| {
+            let (kind, spec) = match &d.source {
+                Source::Registry(req) if accepts_any(req) => (ProblemKind::Wildcard, req),
+                Source::Git(url) => (ProblemKind::Git, url),
+                _ => return None,
+            };
+            Some(DependencyProblem {
+                manifest: d.manifest.to_string(),
+                line: d.line,
+                name: d.name.clone(),
+                spec: spec.clone(),
+                kind,
+                latest: None,
+            })
+        })
+        .collect()
+}
+
+/// `(major, minor)` of the first version in `s`, with the minor zeroed
+/// unless the major is 0 (Cargo and npm treat each `0.y` as breaking).
+#[cfg(feature = "network")]
+fn breaking_version(s: &str) -> Option<(u64, u64)> {
+    let start = s.find(|c: char| c.is_ascii_digit())?;
+    let mut numbers = s[start..]
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|n| n.parse::<u64>().ok());
+    let major = numbers.next()??;
+    let minor = numbers.next().flatten().unwrap_or(0);
+    Some((major, if major == 0 { minor } else { 0 }))
+}
+
+/// Registry dependencies with a newer breaking release, plus the packages
+/// whose registry could not be reached, with the reason.
+#[cfg(feature = "network")]
+pub fn outdated(deps: &[Dependency]) -> (Vec<DependencyProblem>, Vec<(String, String)>) {
+    let pinned: Vec<(&Dependency, &str)> = deps
+        .iter()
+        .filter_map(|d| match &d.source {
+            Source::Registry(req) if !accepts_any(req) => Some((d, req.as_str())),
+            _ => None,
+        })
+        .collect();
+    let chunk = pinned.len().div_ceil(REGISTRY_WORKERS).max(1);
+    let answers: Vec<_> = thread::scope(|s| {
+        let handles: Vec<_> = pinned
+            .chunks(chunk)
+            .map(|batch| {
+                s.spawn(move || {
+                    batch
+                        .iter()
+                        .map(|(d, req)| (*d, *req, latest(d)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    });
+    let mut problems = Vec::new();
+    let mut failures = Vec::new();
+    for (d, req, answer) in answers {
+        match answer {
+            Ok(Some(latest)) => {
+                let newer = match (breaking_version(req), breaking_version(&latest)) {
+                    (Some(current), Some(newest)) => newest > current,
+                    _ => false,
+                };
+                if newer {
+                    problems.push(DependencyProblem {
+                        manifest: d.manifest.to_string(),
+                        line: d.line,
+                        name: d.name.clone(),
+                        spec: req.to_string(),
+                        kind: ProblemKind::Outdated,
+                        latest: Some(latest),
+                    });
+                }
+            }
+            Ok(None) => {}
+            Err(e) => failures.push((d.name.clone(), e)),
+        }
+    }
+    (problems, failures)
+}
+
+#[cfg(not(feature = "network"))]
+pub fn outdated(_deps: &[Dependency]) -> (Vec<DependencyProblem>, Vec<(String, String)>) {
+    (Vec::new(), Vec::new())
+}
+
+/// Newest release of `dep` in its registry; `None` when it isn't published
+/// there (private packages).
+#[cfg(feature = "network")]
+fn latest(dep: &Dependency) -> Result<Option<String>, String> {
+    let name = &dep.name;
+    let field = |url: String, path: &[&str]| -> Result<Option<String>, String> {
+        Ok(fetch(&url)?.and_then(|v| {
+            path.iter()
+                .try_fold(&v, |v, key| v.get(key))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        }))
+    };
+    match dep.ecosystem() {
+        Ecosystem::Cargo => field(
+            format!("https://crates.io/api/v1/crates/{}", name),
+            &["crate", "max_stable_version"],
+        ),
+        Ecosystem::Npm => field(
+            format!(
+                "https://registry.npmjs.org/{}/latest",
+                name.replace('/', "%2F")
+            ),
+            &["version"],
+        ),
+        Ecosystem::Pip => field(
+            format!("https://pypi.org/pypi/{}/json", name),
+            &["info", "version"],
+        ),
+        Ecosystem::Go => latest_go_major(name),
+    }
+}
+
+/// Go puts the major version in the module path (`example.com/m/v2`), so a
+/// newer major is a separate module: probe `/v{N+1}`, `/v{N+2}`, ... on the
+/// module proxy.
+#[cfg(feature = "network")]
+fn latest_go_major(path: &str) -> Result<Option<String>, String> {
+    if path.starts_with("gopkg.in/") {
+        return Ok(None);
+    }
+    let (base, major) = match path.rsplit_once("/v") {
+        Some((base, n)) if n.parse::<u64>().is_ok_and(|n| n >= 2) => (base, n.parse().unwrap_or(2)),
+        _ => (path, 1),
+    };
+    // The proxy escapes capitals as `!` plus the lowercase letter
+    let escaped: String = base
+        .chars()
+        .flat_map(|c| {
+            if c.is_ascii_uppercase() {
+                vec!['!', c.to_ascii_lowercase()]
+            } else {
+                vec![c]
+            }
+        })
+        .collect();
+    let mut newest = None;
+    for next in major + 1..major + 6 {
+        let url = format!("https://proxy.golang.org/{}/v{}/@latest", escaped, next);
+        match fetch(&url)? {
+            Some(info) => newest = info["Version"].as_str().map(String::from),
+            None => break,
+        }
+    }
+    Ok(newest)
+}
+
+/// GET `url` with `curl` and parse the JSON body; `None` for 404 and 410.
+#[cfg(feature = "network")]
+fn fetch(url: &str) -> Result<Option<serde_json::Value>, String> {
+    let output = Command::new("curl")
+        .args([
+            "-sS",
+            "-L",
+            "-A",
+            "repomedic (https://github.com/Brutus1066/repomedic)",
+        ])
+        .args(["--max-time", &REGISTRY_TIMEOUT_SECS.to_string()])
+        .args(["-w", "\n%{http_code}"])
+        .arg(url)
+        .output()
+        .map_err(|e| format!("could not run curl: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, code) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    match code.trim().parse::<u16>().unwrap_or(0) {
+        200..=299 => serde_json::from_str(body)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", url, e)),
+        404 | 410 => Ok(None),
+        0 => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+        code => Err(format!("{}: HTTP {}", url, code)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{accepts_any, check, declared, go_requires, Dependency, ProblemKind, Source};
+
+    fn manifests(files: &[(&str, &str)]) -> Vec<Dependency> {
+        declared(&|path| {
+            files
+                .iter()
+                .find(|(p, _)| *p == path)
+                .map(|(_, c)| c.to_string())
+        })
+    }
+
+    fn sources(deps: &[Dependency]) -> Vec<(&str, &Source)> {
+        deps.iter().map(|d| (d.name.as_str(), &d.source)).collect()
+    }
+
+    #[test]
+    fn cargo_tables() {
+        let deps = manifests(&[(
+            "Cargo.toml",
+            "[package]\nname = \"demo\"\n\n[dependencies]\nserde = \"1\"\nfoo = { git = \"https://example.com/foo\", tag = \"v1\" }\nlocal = { path = \"../local\" }\nrenamed = { package = \"real\", version = \"*\" }\n\n[dev-dependencies.bar]\nversion = \"0.2\"\n",
+        )]);
+        assert_eq!(
+            sources(&deps),
+            [
+                ("serde", &Source::Registry("1".to_string())),
+                (
+                    "foo",
+                    &Source::Git("https://example.com/foo (tag v1)".to_string())
+                ),
+                ("local", &Source::Local),
+                ("real", &Source::Registry("*".to_string())),
+                ("bar", &Source::Registry("0.2".to_string())),
+            ]
+        );
+        assert_eq!(deps[0].line, 5);
+    }
+
+    #[test]
+    fn npm_specs() {
+        let deps = manifests(&[(
+            "package.json",
+            "{\n  \"dependencies\": {\n    \"left-pad\": \"^1.3.0\",\n    \"fork\": \"user/repo#main\",\n    \"util\": \"file:../util\"\n  },\n  \"devDependencies\": {\n    \"jest\": \"latest\"\n  }\n}\n",
+        )]);
+        assert_eq!(
+            sources(&deps),
+            [
+                ("fork", &Source::Git("user/repo#main".to_string())),
+                ("left-pad", &Source::Registry("^1.3.0".to_string())),
+                ("util", &Source::Local),
+                ("jest", &Source::Registry("latest".to_string())),
+            ]
+        );
+        let jest = deps.iter().find(|d| d.name == "jest").unwrap();
+        assert_eq!(jest.line, 8);
+    }
+
+    #[test]
+    fn requirements_txt() {
+        let deps = manifests(&[(
+            "requirements.txt",
+            "# pinned\nrequests[security] >= 2.0 ; python_version > '3'\n-r dev.txt\nflask==2.3.0 --hash=sha256:abc\n-e git+https://example.com/lib.git#egg=lib\npkg @ https://example.com/pkg.whl\n",
+        )]);
+        assert_eq!(
+            sources(&deps),
+            [
+                ("requests", &Source::Registry(">=2.0".to_string())),
+                ("flask", &Source::Registry("==2.3.0".to_string())),
+                (
+                    "lib",
+                    &Source::Git("git+https://example.com/lib.git#egg=lib".to_string())
+                ),
+                ("pkg", &Source::Local),
+            ]
+        );
+        assert_eq!(deps[1].line, 4);
+    }
+
+    #[test]
+    fn go_mod() {
+        let content = "module example.com/m\n\nrequire example.com/a v1.2.0\n\nrequire (\n\texample.com/b v0.0.0-20240101120000-abcdef123456\n\texample.com/c v1.0.0 // indirect\n)\n";
+        assert_eq!(
+            go_requires(content),
+            [
+                (3, "example.com/a", "v1.2.0", false),
+                (
+                    6,
+                    "example.com/b",
+                    "v0.0.0-20240101120000-abcdef123456",
+                    false
+                ),
+                (7, "example.com/c", "v1.0.0", true),
+            ]
+        );
+        let deps = manifests(&[("go.mod", content)]);
+        assert_eq!(
+            sources(&deps),
+            [
+                ("example.com/a", &Source::Registry("v1.2.0".to_string())),
+                (
+                    "example.com/b",
+                    &Source::Git("v0.0.0-20240101120000-abcdef123456".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unbounded_requirements() {
+        for req in ["", "*", "x", "latest", ">=1.0", ">= 1.0", "^1.0 || *"] {
+            assert!(accepts_any(req), "{:?}", req);
+        }
+        for req in ["1", "^1.2", "~1.2", ">=1.0, <2", "==2.3.0", ">= 1.0 < 2"] {
+            assert!(!accepts_any(req), "{:?}", req);
+        }
+    }
+
+    #[test]
+    fn problems() {
+        let deps = manifests(&[(
+            "Cargo.toml",
+            "[dependencies]\nany = \"*\"\npinned = \"1.0\"\nfork = { git = \"https://example.com/fork\" }\n",
+        )]);
+        let problems: Vec<_> = check(&deps)
+            .into_iter()
+            .map(|p| (p.name, p.kind, p.line))
+            .collect();
+        assert_eq!(
+            problems,
+            [
+                ("any".to_string(), ProblemKind::Wildcard, 2),
+                ("fork".to_string(), ProblemKind::Git, 4),
+            ]
+        );
+    }
+}
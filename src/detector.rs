@@ -0,0 +1,415 @@
+use crate::badges::StaleBadge;
+use crate::ci::CiAdvice;
+use crate::codeowners::{Coverage, Problem};
+use crate::context::ScanContext;
+use crate::custom::Violation;
+use crate::deps::DependencyProblem;
+use crate::docker::ContainerProblem;
+use crate::editorconfig;
+use crate::freshness::LockFreshness;
+use crate::git::{GitHistory, HistoryBlob};
+use crate::github::CiHealth;
+use crate::iac::{HelmChart, K8sSecret, UnboundedWorkload};
+use crate::indent::Indent;
+use crate::lfs::LfsStatus;
+use crate::license::License;
+use crate::line_endings::LineEndings;
+use crate::links::DeadLink;
+use crate::naming::NameSource;
+use crate::packages::PackageProblem;
+use crate::readme::ReadmeQuality;
+use crate::remote::RemoteInfo;
+use crate::sample::Estimate;
+use crate::scanner::{
+    self, BuildSystem, CISystem, Language, LanguageFiles, PotentialSecret, ScanError, ScanResult,
+};
+use crate::sops::UnencryptedFile;
+use crate::suppress::Rule;
+use crate::templates;
+use crate::terraform::TerraformProblem;
+use crate::workflows::Workflow;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    Documentation,
+    Community,
+    Ci,
+    Project,
+    Files,
+    Security,
+}
+
+impl Category {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::Documentation => "documentation",
+            Category::Community => "community",
+            Category::Ci => "ci",
+            Category::Project => "project",
+            Category::Files => "files",
+            Category::Security => "security",
+        }
+    }
+}
+
+/// Repository properties a detector can report as present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flag {
+    Git,
+    Readme,
+    License,
+    Gitignore,
+    Editorconfig,
+    Gitattributes,
+    Changelog,
+    Contributing,
+    CodeOfConduct,
+    Security,
+    Codeowners,
+    Funding,
+    IssueTemplate,
+    PrTemplate,
+    Tests,
+    Docs,
+    CoverageBadge,
+}
+
+/// A single fact reported by a detector, merged into the `ScanResult`.
+#[derive(Debug, Clone)]
+pub enum Finding {
+    Flag(Flag),
+    Ci(CISystem),
+    CiAdvice(CiAdvice),
+    CiHealth(CiHealth),
+    /// Repository settings from the hosting provider.
+    Remote(RemoteInfo),
+    Language(Language),
+    /// Files, lines, and bytes seen for a language.
+    LanguageFiles(LanguageFiles),
+    BuildSystem(BuildSystem),
+    DependencyFile(String),
+    LinterConfig(String),
+    /// A coverage config file, or a CI config measuring coverage.
+    CoverageConfig(String),
+    Workspace(String),
+    /// Git LFS patterns and the binary assets they should cover.
+    Lfs(LfsStatus),
+    /// Line endings and byte order marks across text files.
+    LineEndings(LineEndings),
+    /// Dominant indentation per source extension.
+    Indentation(Vec<Indent>),
+    /// A file breaking a `.editorconfig` setting.
+    EditorconfigViolation(editorconfig::Violation),
+    /// A package without its own README or LICENSE, or a root copy.
+    PackageProblem(PackageProblem),
+    History(GitHistory),
+    LargeFile(String),
+    /// A large file version that's only in git history.
+    HistoryBlob(HistoryBlob),
+    Secret(PotentialSecret),
+    K8sSecret(K8sSecret),
+    /// A YAML file holding Kubernetes resources.
+    K8sManifest(String),
+    UnboundedWorkload(UnboundedWorkload),
+    HelmChart(HelmChart),
+    /// A directory holding `.tf` files.
+    TerraformModule(String),
+    TerraformProblem(TerraformProblem),
+    /// A Dockerfile or Compose file.
+    ContainerFile(String),
+    ContainerProblem(ContainerProblem),
+    /// A SOPS- or age-encrypted file.
+    EncryptedFile(String),
+    UnencryptedFile(UnencryptedFile),
+    /// A license file and the license it was identified as.
+    License(License),
+    CodeownersCoverage(Coverage),
+    /// A CODEOWNERS line GitHub skips or that has no effect.
+    CodeownersProblem(Problem),
+    /// A broken issue form or issue/PR template.
+    TemplateProblem(templates::Problem),
+    /// A custom rule from the config failed.
+    Custom(Violation),
+    /// How a sampled check would scale up to every file.
+    Sample(Estimate),
+    StaleBadge(StaleBadge),
+    /// Triggers and token permissions of a GitHub Actions workflow.
+    Workflow(Workflow),
+    /// When a lock file and its manifest last changed.
+    LockFreshness(LockFreshness),
+    /// The Dependabot or Renovate config.
+    DependencyUpdates(String),
+    /// A wildcard, git, or outdated dependency.
+    DependencyProblem(DependencyProblem),
+    /// A Markdown link to a missing file or an unreachable URL.
+    DeadLink(DeadLink),
+    /// Completeness of the README.
+    Readme(ReadmeQuality),
+    /// The project name as one source gives it.
+    ProjectName(NameSource),
+    Warning(ScanError),
+    /// Inline suppression found next to a flagged line.
+    Suppress(Rule),
+    Walked {
+        files: usize,
+        dirs: usize,
+    },
+}
+
+/// A pluggable repository check.
+///
+/// Detectors declare prerequisites by name through `requires`; a detector is
+/// skipped when any prerequisite did not run or when `applies` is false for
+/// the results gathered so far.
+pub trait Detector {
+    fn name(&self) -> &'static str;
+    fn category(&self) -> Category;
+    fn requires(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn applies(&self, _result: &ScanResult) -> bool {
+        true
+    }
+    fn run(&self, ctx: &ScanContext) -> Vec<Finding>;
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectorTiming {
+    pub name: String,
+    pub category: Category,
+    pub findings: usize,
+    pub duration_us: u64,
+}
+
+/// Ordered collection of detectors used by a scan.
+#[derive(Default)]
+pub struct Registry {
+    detectors: Vec<Box<dyn Detector>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with every built-in detector.
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+        for d in scanner::builtin_detectors() {
+            registry.register(d);
+        }
+        registry
+    }
+
+    pub fn register(&mut self, detector: Box<dyn Detector>) {
+        self.detectors.push(detector);
+    }
+
+    /// Keep only detectors matching the predicate.
+    pub fn retain(&mut self, mut keep: impl FnMut(&dyn Detector) -> bool) {
+        self.detectors.retain(|d| keep(d.as_ref()));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Detector> {
+        self.detectors.iter().map(|d| d.as_ref())
+    }
+
+    /// Detectors ordered so every detector comes after its prerequisites.
+    ///
+    /// Registration order is kept where possible. Detectors whose
+    /// prerequisites can never be satisfied (unknown name or a cycle) are
+    /// appended last and will be skipped at run time.
+    pub fn ordered(&self) -> Vec<&dyn Detector> {
+        let mut ordered: Vec<&dyn Detector> = Vec::with_capacity(self.detectors.len());
+        let mut pending: Vec<&dyn Detector> = self.iter().collect();
+        while !pending.is_empty() {
+            let before = pending.len();
+            pending.retain(|d| {
+                let ready = d
+                    .requires()
+                    .iter()
+                    .all(|r| ordered.iter().any(|o| o.name() == *r));
+                if ready {
+                    ordered.push(*d);
+                }
+                !ready
+            });
+            if pending.len() == before {
+                break;
+            }
+        }
+        ordered.extend(pending);
+        ordered
+    }
+}
+
+/// Merge a finding into the scan result.
+pub fn apply(result: &mut ScanResult, finding: Finding) {
+    match finding {
+        Finding::Flag(flag) => {
+            let field = match flag {
+                Flag::Git => &mut result.has_git,
+                Flag::Readme => &mut result.has_readme,
+                Flag::License => &mut result.has_license,
+                Flag::Gitignore => &mut result.has_gitignore,
+                Flag::Editorconfig => &mut result.has_editorconfig,
+                Flag::Gitattributes => &mut result.has_gitattributes,
+                Flag::Changelog => &mut result.has_changelog,
+                Flag::Contributing => &mut result.has_contributing,
+                Flag::CodeOfConduct => &mut result.has_code_of_conduct,
+                Flag::Security => &mut result.has_security,
+                Flag::Codeowners => &mut result.has_codeowners,
+                Flag::Funding => &mut result.has_funding,
+                Flag::IssueTemplate => &mut result.has_issue_template,
+                Flag::PrTemplate => &mut result.has_pr_template,
+                Flag::Tests => &mut result.has_tests,
+                Flag::Docs => &mut result.has_docs,
+                Flag::CoverageBadge => &mut result.has_coverage_badge,
+            };
+            *field = true;
+        }
+        Finding::Ci(ci) => {
+            if !result.ci_systems.contains(&ci) {
+                result.ci_systems.push(ci);
+            }
+        }
+        Finding::CiAdvice(a) => result.ci_advice.push(a),
+        Finding::CiHealth(h) => result.ci_health = Some(h),
+        Finding::Remote(r) => result.remote = Some(r),
+        Finding::Language(l) => {
+            if !result.languages.contains(&l) {
+                result.languages.push(l);
+            }
+        }
+        Finding::LanguageFiles(files) => {
+            match result
+                .language_files
+                .iter_mut()
+                .find(|l| l.language == files.language)
+            {
+                Some(l) => {
+                    l.files += files.files;
+                    l.lines += files.lines;
+                    l.bytes += files.bytes;
+                }
+                None => result.language_files.push(files),
+            }
+        }
+        Finding::BuildSystem(b) => {
+            if !result.build_systems.contains(&b) {
+                result.build_systems.push(b);
+            }
+        }
+        Finding::DependencyFile(f) => {
+            if !result.dependency_files.contains(&f) {
+                result.dependency_files.push(f);
+            }
+        }
+        Finding::LinterConfig(c) => result.linter_configs.push(c),
+        Finding::CoverageConfig(c) => result.coverage_configs.push(c),
+        Finding::History(h) => result.git_history = Some(h),
+        Finding::Workspace(w) => {
+            result.is_monorepo = true;
+            result.workspace_type = Some(w);
+        }
+        Finding::LargeFile(f) => result.large_files.push(f),
+        Finding::HistoryBlob(b) => result.history_blobs.push(b),
+        Finding::Secret(s) => result.potential_secrets.push(s),
+        Finding::K8sSecret(s) => result.k8s_secrets.push(s),
+        Finding::K8sManifest(f) => result.k8s_manifests.push(f),
+        Finding::UnboundedWorkload(w) => result.unbounded_workloads.push(w),
+        Finding::HelmChart(c) => result.helm_charts.push(c),
+        Finding::TerraformModule(d) => result.terraform_modules.push(d),
+        Finding::TerraformProblem(p) => result.terraform_problems.push(p),
+        Finding::ContainerFile(f) => result.container_files.push(f),
+        Finding::ContainerProblem(p) => result.container_problems.push(p),
+        Finding::EncryptedFile(f) => result.encrypted_files.push(f),
+        Finding::UnencryptedFile(f) => result.unencrypted_files.push(f),
+        Finding::License(l) => result.licenses.push(l),
+        Finding::CodeownersCoverage(c) => result.codeowners_coverage = Some(c),
+        Finding::CodeownersProblem(p) => result.codeowners_problems.push(p),
+        Finding::TemplateProblem(p) => result.template_problems.push(p),
+        Finding::Custom(v) => result.custom_violations.push(v),
+        Finding::Sample(e) => result.sampling.push(e),
+        Finding::PackageProblem(p) => result.package_problems.push(p),
+        Finding::Lfs(s) => result.lfs = s,
+        Finding::LineEndings(s) => result.line_endings = s,
+        Finding::Indentation(i) => result.indentation = i,
+        Finding::EditorconfigViolation(v) => result.editorconfig_violations.push(v),
+        Finding::StaleBadge(b) => result.stale_badges.push(b),
+        Finding::Readme(q) => result.readme = Some(q),
+        Finding::ProjectName(n) => result.project_names.push(n),
+        Finding::DeadLink(l) => result.dead_links.push(l),
+        Finding::LockFreshness(f) => result.lockfiles.push(f),
+        Finding::DependencyUpdates(f) => result.dependency_updates = Some(f),
+        Finding::DependencyProblem(p) => result.dependency_problems.push(p),
+        Finding::Workflow(w) => result.workflows.push(w),
+        Finding::Warning(w) => result.scan_warnings.push(w),
+        Finding::Suppress(r) => result.suppressions.push(r),
+        Finding::Walked { files, dirs } => {
+            result.scan_stats.files_scanned += files;
+            result.scan_stats.dirs_traversed += dirs;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Category, Detector, Finding, Registry};
+    use crate::context::ScanContext;
+
+    struct Stub(&'static str, &'static [&'static str]);
+
+    impl Detector for Stub {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        fn category(&self) -> Category {
+            Category::Project
+        }
+
+        fn requires(&self) -> &'static [&'static str] {
+            self.1
+        }
+
+        fn run(&self, _ctx: &ScanContext) -> Vec<Finding> {
+            Vec::new()
+        }
+    }
+
+    fn names(registry: &Registry) -> Vec<&'static str> {
+        registry.ordered().iter().map(|d| d.name()).collect()
+    }
+
+    #[test]
+    fn prerequisites_run_first() {
+        let mut registry = Registry::new();
+        registry.register(Box::new(Stub("report", &["files", "git"])));
+        registry.register(Box::new(Stub("files", &[])));
+        registry.register(Box::new(Stub("orphan", &["missing"])));
+        registry.register(Box::new(Stub("git", &["files"])));
+        assert_eq!(names(&registry), ["files", "git", "report", "orphan"]);
+        registry.retain(|d| d.name() != "git");
+        assert_eq!(names(&registry), ["files", "report", "orphan"]);
+    }
+
+    #[test]
+    fn builtin_prerequisites_resolve() {
+        let registry = Registry::builtin();
+        let ordered = registry.ordered();
+        assert_eq!(ordered.len(), registry.iter().count());
+        for (i, d) in ordered.iter().enumerate() {
+            for r in d.requires() {
+                assert!(
+                    ordered[..i].iter().any(|o| o.name() == *r),
+                    "{} before {}",
+                    r,
+                    d.name()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+/// Unchanged lines shown around each change.
+const CONTEXT: usize = 3;
+
+/// Line pairs above which the diff gives up on aligning lines and replaces
+/// the whole file instead.
+const MAX_CELLS: usize = 4_000_000;
+
+/// One line of a diff: kept, removed from `old`, or added in `new`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// The lines of `old` and `new` aligned by their longest common
+/// subsequence.
+pub fn lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    if a.len().saturating_mul(b.len()) > MAX_CELLS {
+        return a
+            .into_iter()
+            .map(Line::Removed)
+            .chain(b.into_iter().map(Line::Added))
+            .collect();
+    }
+    // lcs[i][j]: common lines of a[i..] and b[j..]
+    let width = b.len() + 1;
+    let mut lcs = vec![0u32; (a.len() + 1) * width];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i * width + j] = if a[i] == b[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push(Line::Same(a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * width + j] >= lcs[i * width + j + 1] {
+            out.push(Line::Removed(a[i]));
+            i += 1;
+        } else {
+            out.push(Line::Added(b[j]));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().copied().map(Line::Removed));
+    out.extend(b[j..].iter().copied().map(Line::Added));
+    out
+}
+
+/// A unified diff from `old` to `new` with `--- name` / `+++ name`
+/// headers, or an empty string when they have the same lines. `color`
+/// paints removals red, additions green, and hunk headers cyan.
+pub fn unified(old: &str, new: &str, name: &str, color: bool) -> String {
+    let diff = lines(old, new);
+    let changed: Vec<usize> = (0..diff.len())
+        .filter(|&k| !matches!(diff[k], Line::Same(_)))
+        .collect();
+    if changed.is_empty() {
+        return String::new();
+    }
+    let paint = |text: String, code: &str| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    };
+    let mut out = format!("--- {}\n+++ {} (generated)\n", name, name);
+    let mut k = 0;
+    while k < changed.len() {
+        // Changes closer than two contexts apart share a hunk
+        let start = changed[k].saturating_sub(CONTEXT);
+        let mut last = changed[k];
+        while k + 1 < changed.len() && changed[k + 1] - last <= 2 * CONTEXT + 1 {
+            k += 1;
+            last = changed[k];
+        }
+        let end = (last + CONTEXT + 1).min(diff.len());
+        k += 1;
+
+        let before = &diff[..start];
+        let old_start = before
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_start = before
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        let hunk = &diff[start..end];
+        let old_len = hunk.iter().filter(|l| !matches!(l, Line::Added(_))).count();
+        let new_len = hunk
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        // An empty range is numbered by the line before it; `,1` is implied
+        let range = |start: usize, len: usize| match len {
+            0 => format!("{},0", start),
+            1 => (start + 1).to_string(),
+            _ => format!("{},{}", start + 1, len),
+        };
+        out.push_str(&paint(
+            format!(
+                "@@ -{} +{} @@",
+                range(old_start, old_len),
+                range(new_start, new_len)
+            ),
+            "36",
+        ));
+        out.push('\n');
+        for line in hunk {
+            let text = match line {
+                Line::Same(s) => format!(" {}", s),
+                Line::Removed(s) => paint(format!("-{}", s), "31"),
+                Line::Added(s) => paint(format!("+{}", s), "32"),
+            };
+            out.push_str(&text);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lines, unified, Line};
+
+    #[test]
+    fn aligns_common_lines() {
+        assert_eq!(
+            lines("a\nb\nc\n", "a\nx\nc\nd\n"),
+            [
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Added("x"),
+                Line::Same("c"),
+                Line::Added("d"),
+            ]
+        );
+        assert_eq!(lines("", "a\n"), [Line::Added("a")]);
+    }
+
+    #[test]
+    fn identical_text_has_no_diff() {
+        assert_eq!(unified("a\nb\n", "a\nb\n", "f", false), "");
+    }
+
+    #[test]
+    fn hunk_headers() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n";
+        let new = old.replacen("2\n", "two\n", 1).replacen("15\n", "", 1);
+        assert_eq!(
+            unified(old, &new, ".gitignore", false),
+            "--- .gitignore\n+++ .gitignore (generated)\n\
+             @@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n\
+             @@ -12,5 +12,4 @@\n 12\n 13\n 14\n-15\n 16\n"
+        );
+        assert_eq!(
+            unified("", "new\n", "f", false),
+            "--- f\n+++ f (generated)\n@@ -0,0 +1 @@\n+new\n"
+        );
+    }
+
+    #[test]
+    fn nearby_changes_share_a_hunk() {
+        let old = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let new = "A\nb\nc\nd\ne\nf\ng\nH\n";
+        let diff = unified(old, new, "f", false);
+        assert_eq!(diff.matches("@@ -").count(), 1);
+        assert!(diff.contains("@@ -1,8 +1,8 @@"));
+    }
+
+    #[test]
+    fn color() {
+        let diff = unified("a\n", "b\n", "f", true);
+        assert!(diff.contains("\x1b[31m-a\x1b[0m"));
+        assert!(diff.contains("\x1b[32m+b\x1b[0m"));
+        assert!(diff.contains("\x1b[36m@@ -1 +1 @@\x1b[0m"));
+    }
+}
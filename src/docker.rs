@@ -0,0 +1,440 @@
+use serde::Serialize;
+
+/// Compose file names Docker Compose picks up, plus `docker-compose.*.yml`
+/// overrides.
+const COMPOSE_FILES: &[&str] = &[
+    "compose.yaml",
+    "compose.yml",
+    "docker-compose.yaml",
+    "docker-compose.yml",
+];
+
+/// Words in an `ENV`, `ARG`, or Compose `environment` key that mark its
+/// value as a credential.
+const SECRET_KEYS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "SECRET",
+    "TOKEN",
+    "API_KEY",
+    "APIKEY",
+    "ACCESS_KEY",
+    "PRIVATE_KEY",
+    "CREDENTIALS",
+];
+
+/// Archive extensions `ADD` unpacks, a legitimate reason not to use `COPY`.
+const ARCHIVES: &[&str] = &[".tar", ".tar.gz", ".tgz", ".tar.bz2", ".tar.xz", ".txz"];
+
+/// A Dockerfile or Compose file setting worth changing.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum ContainerProblem {
+    /// No `.dockerignore` next to the Dockerfile or at the root, so `.git`
+    /// and local files are sent with every build.
+    MissingDockerignore { file: String },
+    /// A base or service image on `latest` or without a tag.
+    LatestTag {
+        file: String,
+        line: usize,
+        image: String,
+    },
+    /// The final stage never switches to a non-root `USER`.
+    RunsAsRoot { file: String },
+    /// `ADD` of local files, where `COPY` does the same without fetching
+    /// URLs or unpacking archives.
+    AddInsteadOfCopy { file: String, line: usize },
+    /// A credential-looking key with a literal value, baked into the image
+    /// or the Compose file.
+    SecretInEnv {
+        file: String,
+        line: usize,
+        key: String,
+    },
+}
+
+impl ContainerProblem {
+    pub fn file(&self) -> &str {
+        match self {
+            ContainerProblem::MissingDockerignore { file }
+            | ContainerProblem::LatestTag { file, .. }
+            | ContainerProblem::RunsAsRoot { file }
+            | ContainerProblem::AddInsteadOfCopy { file, .. }
+            | ContainerProblem::SecretInEnv { file, .. } => file,
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ContainerProblem::LatestTag { line, .. }
+            | ContainerProblem::AddInsteadOfCopy { line, .. }
+            | ContainerProblem::SecretInEnv { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+}
+
+/// `Dockerfile`, `Dockerfile.dev`, `api.Dockerfile`, and the like.
+pub fn is_dockerfile(rel: &str) -> bool {
+    let name = rel.rsplit('/').next().unwrap_or(rel);
+    let lower = name.to_lowercase();
+    lower == "dockerfile" || lower.starts_with("dockerfile.") || lower.ends_with(".dockerfile")
+}
+
+/// `compose.yaml`, `docker-compose.yml`, and `docker-compose.<env>.yml`.
+pub fn is_compose_file(rel: &str) -> bool {
+    let name = rel.rsplit('/').next().unwrap_or(rel);
+    COMPOSE_FILES.contains(&name)
+        || (name.starts_with("docker-compose.")
+            && (name.ends_with(".yml") || name.ends_with(".yaml")))
+}
+
+/// Whether a Dockerfile has a `.dockerignore` that applies: next to it (the
+/// usual build context), at the root, or as `<Dockerfile>.dockerignore`.
+pub fn has_dockerignore(rel: &str, exists: impl Fn(&str) -> bool) -> bool {
+    let dir = rel.rsplit_once('/').map_or("", |(d, _)| d);
+    let beside = if dir.is_empty() {
+        ".dockerignore".to_string()
+    } else {
+        format!("{}/.dockerignore", dir)
+    };
+    exists(&beside) || exists(".dockerignore") || exists(&format!("{}.dockerignore", rel))
+}
+
+/// Instructions of a Dockerfile with their starting line, continuation
+/// lines joined and comments dropped.
+fn instructions(content: &str) -> Vec<(usize, String)> {
+    let mut out = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') || (trimmed.is_empty() && current.is_none()) {
+            continue;
+        }
+        let (start, mut text) = current.take().unwrap_or((i + 1, String::new()));
+        let (body, continued) = match trimmed.strip_suffix('\\') {
+            Some(body) => (body, true),
+            None => (trimmed, false),
+        };
+        if !text.is_empty() {
+            text.push(' ');
+        }
+        text.push_str(body.trim());
+        if continued {
+            current = Some((start, text));
+        } else {
+            out.push((start, text));
+        }
+    }
+    if let Some(last) = current {
+        out.push(last);
+    }
+    out
+}
+
+/// Check one Dockerfile for unpinned base images, a root final stage, `ADD`
+/// of local files, and credentials in `ENV` or `ARG` defaults.
+pub fn analyze_dockerfile(file: &str, content: &str) -> Vec<ContainerProblem> {
+    let mut problems = Vec::new();
+    // Named stages with the USER and non-root base they end with
+    let mut stages: Vec<(String, Option<String>, bool)> = Vec::new();
+    let mut current_stage: Option<usize> = None;
+    let mut user: Option<String> = None;
+    let mut nonroot_base = false;
+    let mut saw_from = false;
+    for (line, text) in instructions(content) {
+        let (keyword, args) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+        let args = args.trim();
+        match keyword.to_uppercase().as_str() {
+            "FROM" => {
+                saw_from = true;
+                if let Some(i) = current_stage.take() {
+                    stages[i].1 = user.take();
+                    stages[i].2 = nonroot_base;
+                }
+                let words: Vec<&str> = args
+                    .split_whitespace()
+                    .filter(|w| !w.starts_with("--"))
+                    .collect();
+                let Some(image) = words.first() else {
+                    continue;
+                };
+                // Building on an earlier stage keeps its USER and pins nothing new
+                match stages.iter().find(|s| s.0.eq_ignore_ascii_case(image)) {
+                    Some((_, stage_user, stage_nonroot)) => {
+                        user = stage_user.clone();
+                        nonroot_base = *stage_nonroot;
+                    }
+                    None => {
+                        user = None;
+                        nonroot_base = *image == "scratch" || image.contains("nonroot");
+                        if is_unpinned(image) {
+                            problems.push(ContainerProblem::LatestTag {
+                                file: file.to_string(),
+                                line,
+                                image: image.to_string(),
+                            });
+                        }
+                    }
+                }
+                if let [_, alias, name] = words.as_slice() {
+                    if alias.eq_ignore_ascii_case("as") {
+                        stages.push((name.to_lowercase(), None, false));
+                        current_stage = Some(stages.len() - 1);
+                    }
+                }
+            }
+            "USER" => user = Some(args.to_string()),
+            "ADD" => {
+                let sources: Vec<&str> = args
+                    .split_whitespace()
+                    .filter(|w| !w.starts_with("--"))
+                    .collect();
+                let sources = &sources[..sources.len().saturating_sub(1)];
+                let needs_add = sources.iter().any(|s| {
+                    let s = s.trim_matches(|c| c == '"' || c == '[' || c == ']' || c == ',');
+                    s.contains("://")
+                        || s.starts_with("git@")
+                        || ARCHIVES.iter().any(|a| s.ends_with(a))
+                });
+                if !sources.is_empty() && !needs_add {
+                    problems.push(ContainerProblem::AddInsteadOfCopy {
+                        file: file.to_string(),
+                        line,
+                    });
+                }
+            }
+            "ENV" | "ARG" => {
+                for (key, value) in assignments(args) {
+                    if is_secret_key(&key) && is_literal(&value) {
+                        problems.push(ContainerProblem::SecretInEnv {
+                            file: file.to_string(),
+                            line,
+                            key,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let root = match user.as_deref() {
+        Some(u) => {
+            let name = u.split(':').next().unwrap_or(u).trim();
+            name == "root" || name == "0"
+        }
+        None => !nonroot_base,
+    };
+    if saw_from && root {
+        problems.push(ContainerProblem::RunsAsRoot {
+            file: file.to_string(),
+        });
+    }
+    problems
+}
+
+/// `KEY=value` pairs of an `ENV` or `ARG` line; the legacy `ENV KEY value`
+/// form is one pair.
+fn assignments(args: &str) -> Vec<(String, String)> {
+    let unquote = |v: &str| v.trim_matches(|c| c == '"' || c == '\'').to_string();
+    if !args.contains('=')
+        || args
+            .split_whitespace()
+            .next()
+            .is_some_and(|w| !w.contains('='))
+    {
+        return match args.split_once(char::is_whitespace) {
+            Some((key, value)) => vec![(key.to_string(), unquote(value.trim()))],
+            None => Vec::new(),
+        };
+    }
+    args.split_whitespace()
+        .filter_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            Some((key.to_string(), unquote(value)))
+        })
+        .collect()
+}
+
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    let upper = key.to_uppercase();
+    SECRET_KEYS.iter().any(|k| upper.contains(k))
+        // Names of files or variables holding the secret are fine
+        && !["_FILE", "_PATH", "_ENV", "_VAR", "_NAME"].iter().any(|s| upper.ends_with(s))
+}
+
+/// A value typed into the file rather than left empty or taken from a
+/// build argument or the environment.
+pub(crate) fn is_literal(value: &str) -> bool {
+    let value = value.trim();
+    !value.is_empty() && !value.contains('$') && !value.starts_with('<')
+}
+
+/// `latest` or no tag (a digest pins it); images built from variables are
+/// skipped.
+fn is_unpinned(image: &str) -> bool {
+    if image.contains('$') || image == "scratch" || image.contains('@') {
+        return false;
+    }
+    let name = image.rsplit('/').next().unwrap_or(image);
+    match name.split_once(':') {
+        Some((_, tag)) => tag == "latest",
+        None => true,
+    }
+}
+
+/// Check one Compose file for unpinned service images and credentials in
+/// `environment` blocks.
+pub fn analyze_compose(file: &str, content: &str) -> Vec<ContainerProblem> {
+    let mut problems = Vec::new();
+    // Indentation of the `environment:` key whose entries we're in
+    let mut environment: Option<usize> = None;
+    for (i, raw) in content.lines().enumerate() {
+        let line = i + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = raw.len() - raw.trim_start().len();
+        if environment.is_some_and(|at| indent <= at) {
+            environment = None;
+        }
+        if let Some(image) = trimmed.strip_prefix("image:") {
+            let image = image.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !image.is_empty() && is_unpinned(image) {
+                problems.push(ContainerProblem::LatestTag {
+                    file: file.to_string(),
+                    line,
+                    image: image.to_string(),
+                });
+            }
+        } else if trimmed == "environment:" {
+            environment = Some(indent);
+        } else if environment.is_some() {
+            // `- KEY=value` in list form, `KEY: value` in map form
+            let entry = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+            let entry = entry.trim_matches(|c| c == '"' || c == '\'');
+            let pair = if trimmed.starts_with("- ") {
+                entry.split_once('=')
+            } else {
+                entry.split_once(':')
+            };
+            let Some((key, value)) = pair else {
+                continue;
+            };
+            let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+            if is_secret_key(key.trim()) && is_literal(value) {
+                problems.push(ContainerProblem::SecretInEnv {
+                    file: file.to_string(),
+                    line,
+                    key: key.trim().to_string(),
+                });
+            }
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        analyze_compose, analyze_dockerfile, has_dockerignore, is_compose_file, is_dockerfile,
+        is_unpinned, ContainerProblem,
+    };
+
+    /// `(kind, line)` for each problem, in order.
+    fn kinds(problems: &[ContainerProblem]) -> Vec<(&'static str, Option<usize>)> {
+        problems
+            .iter()
+            .map(|p| {
+                let kind = match p {
+                    ContainerProblem::MissingDockerignore { .. } => "dockerignore",
+                    ContainerProblem::LatestTag { .. } => "latest",
+                    ContainerProblem::RunsAsRoot { .. } => "root",
+                    ContainerProblem::AddInsteadOfCopy { .. } => "add",
+                    ContainerProblem::SecretInEnv { .. } => "secret",
+                };
+                (kind, p.line())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn file_names() {
+        assert!(is_dockerfile("Dockerfile"));
+        assert!(is_dockerfile("build/api.Dockerfile"));
+        assert!(is_dockerfile("Dockerfile.dev"));
+        assert!(!is_dockerfile("docs/dockerfiles.md"));
+        assert!(is_compose_file("deploy/docker-compose.prod.yml"));
+        assert!(is_compose_file("compose.yaml"));
+        assert!(!is_compose_file("compose.json"));
+    }
+
+    #[test]
+    fn dockerignore_locations() {
+        assert!(has_dockerignore("api/Dockerfile", |p| p == "api/.dockerignore"));
+        assert!(has_dockerignore("api/Dockerfile", |p| p == ".dockerignore"));
+        assert!(has_dockerignore("api/Dockerfile", |p| p == "api/Dockerfile.dockerignore"));
+        assert!(!has_dockerignore("api/Dockerfile", |_| false));
+    }
+
+    #[test]
+    fn image_tags() {
+        assert!(is_unpinned("node"));
+        assert!(is_unpinned("node:latest"));
+        assert!(is_unpinned("registry:5000/team/app"));
+        assert!(!is_unpinned("registry:5000/team/app:1.2"));
+        assert!(!is_unpinned("node@sha256:abc"));
+        assert!(!is_unpinned("${BASE_IMAGE}"));
+        assert!(!is_unpinned("scratch"));
+    }
+
+    #[test]
+    fn dockerfile_problems() {
+        let dockerfile = "FROM node:latest AS build\n\
+                          ADD package.json /app/\n\
+                          ADD https://example.com/tool.tgz /tmp/\n\
+                          ADD vendor.tar.gz /opt/\n\
+                          ENV API_TOKEN=abc123 \\\n    NODE_ENV=production\n\
+                          ARG DB_PASSWORD\n\
+                          ENV SECRET_FILE=/run/secrets/key\n\
+                          FROM build\n\
+                          USER node\n";
+        assert_eq!(
+            kinds(&analyze_dockerfile("Dockerfile", dockerfile)),
+            [("latest", Some(1)), ("add", Some(2)), ("secret", Some(5))]
+        );
+    }
+
+    #[test]
+    fn final_stage_user() {
+        let root =
+            "FROM rust:1.80 AS build\nUSER builder\nFROM debian:12\nCOPY --from=build /app /app\n";
+        assert_eq!(
+            kinds(&analyze_dockerfile("Dockerfile", root)),
+            [("root", None)]
+        );
+        let explicit = "FROM alpine:3.20\nUSER 0:0\n";
+        assert_eq!(
+            kinds(&analyze_dockerfile("Dockerfile", explicit)),
+            [("root", None)]
+        );
+        let distroless = "FROM gcr.io/distroless/static:nonroot\n";
+        assert!(analyze_dockerfile("Dockerfile", distroless).is_empty());
+        let inherited = "FROM alpine:3.20 AS base\nUSER app\nFROM base\nRUN true\n";
+        assert!(analyze_dockerfile("Dockerfile", inherited).is_empty());
+    }
+
+    #[test]
+    fn compose_problems() {
+        let compose = "services:\n  db:\n    image: postgres\n    environment:\n      POSTGRES_PASSWORD: example\n      POSTGRES_USER: app\n  api:\n    image: \"app:1.0\"\n    environment:\n      - API_KEY=${API_KEY}\n      - JWT_SECRET=dev-secret\n    ports:\n      - \"8080:80\"\n";
+        assert_eq!(
+            kinds(&analyze_compose("compose.yaml", compose)),
+            [
+                ("latest", Some(3)),
+                ("secret", Some(5)),
+                ("secret", Some(11))
+            ]
+        );
+    }
+}
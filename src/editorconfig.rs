@@ -0,0 +1,358 @@
+use crate::glob::glob_match;
+use serde::Serialize;
+
+/// A property `.editorconfig` sets that a file breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    /// Both CRLF and LF line endings in one file.
+    MixedLineEndings,
+    /// Line endings other than `end_of_line`.
+    LineEnding,
+    /// Lines indented with tabs where `indent_style = space`, or the other
+    /// way round.
+    IndentStyle,
+    /// No final newline where `insert_final_newline = true`.
+    FinalNewline,
+    /// Trailing spaces or tabs where `trim_trailing_whitespace = true`.
+    TrailingWhitespace,
+}
+
+impl Rule {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Rule::MixedLineEndings => "mixed line endings",
+            Rule::LineEnding => "wrong line endings",
+            Rule::IndentStyle => "wrong indent style",
+            Rule::FinalNewline => "no final newline",
+            Rule::TrailingWhitespace => "trailing whitespace",
+        }
+    }
+}
+
+/// One file breaking one rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct Violation {
+    pub file: String,
+    pub rule: Rule,
+    /// First offending line, for line-level rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Offending lines (1 for whole-file rules).
+    pub count: usize,
+}
+
+/// The properties that apply to one file; `None` when unset.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Properties {
+    pub indent_style: Option<String>,
+    pub end_of_line: Option<String>,
+    pub insert_final_newline: Option<bool>,
+    pub trim_trailing_whitespace: Option<bool>,
+}
+
+impl Properties {
+    fn set(&mut self, key: &str, value: &str) {
+        let value = value.to_lowercase();
+        let flag = match value.as_str() {
+            "true" => Some(true),
+            "false" => Some(false),
+            _ => None,
+        };
+        let text = (value != "unset").then(|| value.clone());
+        match key {
+            "indent_style" => self.indent_style = text,
+            "end_of_line" => self.end_of_line = text,
+            "insert_final_newline" => self.insert_final_newline = flag,
+            "trim_trailing_whitespace" => self.trim_trailing_whitespace = flag,
+            _ => {}
+        }
+    }
+
+    /// Whether any checked property is set.
+    pub fn any(&self) -> bool {
+        *self != Properties::default()
+    }
+}
+
+/// A `[glob]` section: its globs with braces expanded, and its properties.
+#[derive(Debug)]
+struct Section {
+    globs: Vec<String>,
+    pairs: Vec<(String, String)>,
+}
+
+/// A parsed `.editorconfig`: sections in file order, later ones winning.
+#[derive(Debug, Default)]
+pub struct EditorConfig {
+    sections: Vec<Section>,
+}
+
+impl EditorConfig {
+    pub fn parse(content: &str) -> Self {
+        let mut config = EditorConfig::default();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                config.sections.push(Section {
+                    globs: expand_braces(glob),
+                    pairs: Vec::new(),
+                });
+            } else if let (Some((key, value)), Some(section)) =
+                (line.split_once('='), config.sections.last_mut())
+            {
+                section
+                    .pairs
+                    .push((key.trim().to_lowercase(), value.trim().to_string()));
+            }
+        }
+        config
+    }
+
+    /// Properties for `rel` (relative to the `.editorconfig` directory).
+    pub fn properties(&self, rel: &str) -> Properties {
+        let mut props = Properties::default();
+        for section in &self.sections {
+            if section.globs.iter().any(|g| matches(g, rel)) {
+                for (key, value) in &section.pairs {
+                    props.set(key, value);
+                }
+            }
+        }
+        props
+    }
+}
+
+/// EditorConfig globs: without a `/` a pattern matches the file name at
+/// any depth, with one it's relative to the `.editorconfig` directory.
+fn matches(glob: &str, rel: &str) -> bool {
+    if glob.contains('/') {
+        let glob = glob.trim_start_matches('/');
+        glob_match(glob.as_bytes(), rel.as_bytes())
+    } else {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        glob_match(glob.as_bytes(), name.as_bytes())
+    }
+}
+
+/// `*.{js,ts}` as `*.js` and `*.ts`; nested and repeated groups expand too.
+fn expand_braces(glob: &str) -> Vec<String> {
+    let Some(open) = glob.find('{') else {
+        return vec![glob.to_string()];
+    };
+    let mut depth = 0;
+    let mut close = None;
+    for (i, c) in glob[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    close = Some(open + i);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    let Some(close) = close else {
+        return vec![glob.to_string()];
+    };
+    let (head, body, tail) = (&glob[..open], &glob[open + 1..close], &glob[close + 1..]);
+    // Split the group's alternatives on top-level commas
+    let mut alternatives = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in body.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    alternatives.push(&body[start..]);
+    alternatives
+        .into_iter()
+        .flat_map(|alt| expand_braces(&format!("{}{}{}", head, alt, tail)))
+        .collect()
+}
+
+/// Check one file's text against its properties.
+pub fn check(file: &str, content: &str, props: &Properties) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    let mut add = |rule: Rule, line: Option<usize>, count: usize| {
+        if count > 0 {
+            violations.push(Violation {
+                file: file.to_string(),
+                rule,
+                line,
+                count,
+            });
+        }
+    };
+
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+    if crlf > 0 && lf > 0 {
+        add(Rule::MixedLineEndings, None, 1);
+    } else {
+        let wrong = match props.end_of_line.as_deref() {
+            Some("lf") => crlf,
+            Some("crlf") => lf,
+            _ => 0,
+        };
+        add(Rule::LineEnding, None, usize::from(wrong > 0));
+    }
+
+    let indent_char = match props.indent_style.as_deref() {
+        Some("space") => Some('\t'),
+        Some("tab") => Some(' '),
+        _ => None,
+    };
+    let (mut indent_first, mut indent_count) = (None, 0);
+    let (mut trailing_first, mut trailing_count) = (None, 0);
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.strip_suffix('\r').unwrap_or(raw);
+        if let Some(wrong) = indent_char {
+            let leading: String = line
+                .chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect();
+            // Tab-indented files align with a few spaces after the tabs, and
+            // block comments continue one space in
+            let offends = match wrong {
+                '\t' => leading.contains('\t'),
+                _ => {
+                    leading.starts_with("  ")
+                        && !line.trim_start().starts_with('*')
+                        && leading.len() < line.len()
+                }
+            };
+            if offends {
+                indent_first.get_or_insert(i + 1);
+                indent_count += 1;
+            }
+        }
+        if props.trim_trailing_whitespace == Some(true)
+            && (line.ends_with(' ') || line.ends_with('\t'))
+        {
+            trailing_first.get_or_insert(i + 1);
+            trailing_count += 1;
+        }
+    }
+    add(Rule::IndentStyle, indent_first, indent_count);
+    add(Rule::TrailingWhitespace, trailing_first, trailing_count);
+
+    if props.insert_final_newline == Some(true) && !content.is_empty() && !content.ends_with('\n') {
+        add(Rule::FinalNewline, None, 1);
+    }
+    violations
+}
+
+/// The file type violations are grouped by: `.rs`, or the whole name for
+/// files without an extension (`Makefile`).
+pub fn file_type(rel: &str) -> String {
+    let name = rel.rsplit('/').next().unwrap_or(rel);
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => format!(".{}", ext.to_lowercase()),
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, expand_braces, file_type, EditorConfig, Rule};
+
+    const CONFIG: &str = "root = true\n\n[*]\nend_of_line = lf\ninsert_final_newline = true\n\n\
+                          [*.{rs,toml}]\nindent_style = space\ntrim_trailing_whitespace = true\n\n\
+                          [Makefile]\nindent_style = tab\n\n[docs/**.md]\ntrim_trailing_whitespace = false\n";
+
+    fn rules(file: &str, content: &str) -> Vec<(Rule, Option<usize>, usize)> {
+        let config = EditorConfig::parse(CONFIG);
+        check(file, content, &config.properties(file))
+            .into_iter()
+            .map(|v| (v.rule, v.line, v.count))
+            .collect()
+    }
+
+    #[test]
+    fn braces_expand() {
+        assert_eq!(expand_braces("*.{js,ts}"), ["*.js", "*.ts"]);
+        assert_eq!(expand_braces("{a,b{1,2}}.c"), ["a.c", "b1.c", "b2.c"]);
+        assert_eq!(expand_braces("{a,b}/{c,d}").len(), 4);
+        assert_eq!(expand_braces("open{"), ["open{"]);
+    }
+
+    #[test]
+    fn sections_apply_in_order() {
+        let config = EditorConfig::parse(CONFIG);
+        let rs = config.properties("src/main.rs");
+        assert_eq!(rs.indent_style.as_deref(), Some("space"));
+        assert_eq!(rs.end_of_line.as_deref(), Some("lf"));
+        assert_eq!(
+            config.properties("Makefile").indent_style.as_deref(),
+            Some("tab")
+        );
+        assert_eq!(
+            config
+                .properties("docs/guide/a.md")
+                .trim_trailing_whitespace,
+            Some(false)
+        );
+        assert!(!EditorConfig::parse("[*.py]\nindent_style = unset\n")
+            .properties("a.py")
+            .any());
+    }
+
+    #[test]
+    fn clean_file_passes() {
+        assert!(rules("src/lib.rs", "fn main() {\n    let x = 1;\n}\n").is_empty());
+    }
+
+    #[test]
+    fn line_level_rules_count_lines() {
+        assert_eq!(
+            rules("src/lib.rs", "fn a() {\n\tx();  \n\ty();\n}\n"),
+            [
+                (Rule::IndentStyle, Some(2), 2),
+                (Rule::TrailingWhitespace, Some(2), 1),
+            ]
+        );
+        // Tab indentation may align with spaces after the tabs
+        assert!(rules("Makefile", "all:\n\techo hi\n\t  continued\n").is_empty());
+        assert_eq!(
+            rules("Makefile", "all:\n    echo hi\n"),
+            [(Rule::IndentStyle, Some(2), 1)]
+        );
+    }
+
+    #[test]
+    fn whole_file_rules() {
+        assert_eq!(
+            rules("a.txt", "one\r\ntwo\r\n"),
+            [(Rule::LineEnding, None, 1)]
+        );
+        assert_eq!(
+            rules("a.txt", "one\r\ntwo\n"),
+            [(Rule::MixedLineEndings, None, 1)]
+        );
+        assert_eq!(
+            rules("a.txt", "no newline"),
+            [(Rule::FinalNewline, None, 1)]
+        );
+        assert!(rules("a.txt", "").is_empty());
+    }
+
+    #[test]
+    fn file_types() {
+        assert_eq!(file_type("src/Main.RS"), ".rs");
+        assert_eq!(file_type("Makefile"), "Makefile");
+        assert_eq!(file_type("config/.env"), ".env");
+    }
+}
@@ -0,0 +1,81 @@
+use crate::detector::Category;
+use crate::scanner::{Issue, Severity};
+use serde::Serialize;
+use std::io::Write;
+
+/// Progress of a scan, for GUIs and bots embedding the CLI.
+///
+/// Check events are emitted live as each detector runs. A `finding` event
+/// follows its detector's `check_completed` when the issue stands on its
+/// own (a secret, a dead link), or comes once the report is assembled when
+/// it depends on the whole scan (a missing file). `scan_completed` is last.
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum Event<'a> {
+    ScanStarted {
+        path: &'a str,
+        /// The scan's `scanned_at` timestamp.
+        scanned_at: &'a str,
+        checks: usize,
+    },
+    CheckStarted {
+        check: &'a str,
+        category: Category,
+    },
+    CheckCompleted {
+        check: &'a str,
+        category: Category,
+        findings: usize,
+        duration_us: u64,
+    },
+    /// Prerequisites missing or not applicable to this repository.
+    CheckSkipped {
+        check: &'a str,
+        category: Category,
+    },
+    Finding {
+        #[serde(flatten)]
+        issue: &'a Issue,
+    },
+    ScanCompleted {
+        score: u8,
+        grade: &'a str,
+        errors: usize,
+        warnings: usize,
+        info: usize,
+        duration_ms: u64,
+    },
+}
+
+/// Writes events as JSON lines, flushed one at a time so readers see them
+/// as they happen. Write errors are ignored: a reader going away shouldn't
+/// fail the scan.
+pub struct EventWriter {
+    out: Box<dyn Write>,
+}
+
+impl EventWriter {
+    pub fn new(out: Box<dyn Write>) -> Self {
+        EventWriter { out }
+    }
+
+    pub fn emit(&mut self, event: &Event) {
+        if let Ok(line) = serde_json::to_string(event) {
+            let _ = writeln!(self.out, "{}", line);
+            let _ = self.out.flush();
+        }
+    }
+
+    /// `scan_completed`, once every `finding` has been emitted.
+    pub fn finish(&mut self, issues: &[Issue], score: u8, grade: &str, duration_ms: u64) {
+        let count = |s: Severity| issues.iter().filter(|i| i.severity == s).count();
+        self.emit(&Event::ScanCompleted {
+            score,
+            grade,
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+            duration_ms,
+        });
+    }
+}
@@ -0,0 +1,212 @@
+use crate::content;
+use crate::report::{clean_path, markdown_footer, to_json};
+use crate::scanner::{ScanResult, Severity};
+use crate::secrets;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Lines of context captured around a matched line.
+const CONTEXT_LINES: usize = 2;
+
+/// Mask anything that looks like a credential, keeping a short prefix so
+/// auditors can still tell which kind of value matched.
+pub fn redact_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut token = String::new();
+    let flush = |token: &mut String, out: &mut String| {
+        if token.chars().count() >= 8 {
+            let keep: String = token.chars().take(4).collect();
+            out.push_str(&keep);
+            out.push_str(&"*".repeat(token.chars().count() - 4));
+        } else {
+            out.push_str(token);
+        }
+        token.clear();
+    };
+    for c in line.chars() {
+        if c.is_whitespace() || matches!(c, '=' | ':' | '"' | '\'' | ',' | ';') {
+            flush(&mut token, &mut out);
+            out.push(c);
+        } else {
+            token.push(c);
+        }
+    }
+    flush(&mut token, &mut out);
+    out
+}
+
+/// Extract redacted context around a 1-based line number. Secret proofs
+/// mask every line; other findings only lines a secret rule matches, so
+/// snippets such as an unpinned `uses:` stay readable.
+fn line_context(file: &Path, line: usize, mask_all: bool) -> Option<String> {
+    let content = content::read_text(file).ok()??;
+    let lines: Vec<&str> = content.lines().collect();
+    let start = line.saturating_sub(CONTEXT_LINES + 1);
+    let end = (line + CONTEXT_LINES).min(lines.len());
+    let mut out = String::new();
+    for (i, l) in lines.iter().enumerate().take(end).skip(start) {
+        let marker = if i + 1 == line { ">" } else { " " };
+        let shown = if mask_all || !secrets::scan_line(l).is_empty() {
+            redact_line(l)
+        } else {
+            l.to_string()
+        };
+        out.push_str(&format!("{} {:4} | {}\n", marker, i + 1, shown));
+    }
+    Some(out)
+}
+
+/// Write an evidence bundle for every finding into `dir`.
+///
+/// The bundle contains `report.json`, an `INDEX.md` listing each finding,
+/// and one redacted proof file per finding that has local evidence: the
+/// matched lines for secrets, the file size for large files, and the
+/// snippet around the line for other findings tied to one (unpinned
+/// actions, workflow permissions, ...).
+/// Returns the number of evidence files written.
+pub fn write_bundle(result: &ScanResult, path: &Path, dir: &Path) -> io::Result<usize> {
+    fs::create_dir_all(dir)?;
+    fs::write(dir.join("report.json"), to_json(result, path))?;
+
+    let mut index = String::new();
+    index.push_str("# RepoMedic Evidence Bundle\n\n");
+    index.push_str(&format!("**Path:** `{}`\n\n", clean_path(path)));
+    index
+        .push_str("| # | Severity | Finding | Evidence |\n|---|----------|---------|----------|\n");
+
+    let mut written = 0;
+    for (n, s) in result.potential_secrets.iter().enumerate() {
+        let name = format!("secret-{:03}.txt", n + 1);
+        let context = line_context(&path.join(&s.file), s.line, true)
+            .unwrap_or_else(|| "(file no longer readable)\n".to_string());
+        let body = format!(
+            "Finding: Potential {}\nFile:    {}\nLine:    {}\n\n{}",
+            s.pattern, s.file, s.line, context
+        );
+        fs::write(dir.join(&name), body)?;
+        written += 1;
+        index.push_str(&format!(
+            "| S{} | error | Potential {} in {} at line {} | [{}]({}) |\n",
+            n + 1,
+            s.pattern,
+            s.file,
+            s.line,
+            name,
+            name
+        ));
+    }
+    for (n, f) in result.large_files.iter().enumerate() {
+        let name = format!("large-file-{:03}.txt", n + 1);
+        let size = fs::metadata(path.join(f)).map(|m| m.len()).unwrap_or(0);
+        let body = format!(
+            "Finding: Large file detected (>{}MB)\nFile:    {}\nSize:    {} bytes\n",
+            result.config.large_file_threshold_mb(),
+            f,
+            size
+        );
+        fs::write(dir.join(&name), body)?;
+        written += 1;
+        index.push_str(&format!(
+            "| L{} | warning | Large file: {} | [{}]({}) |\n",
+            n + 1,
+            f,
+            name,
+            name
+        ));
+    }
+
+    let located = result
+        .issues
+        .iter()
+        .filter_map(|i| Some((i, i.file.as_deref()?, i.line?)))
+        // Secrets already have their proof above
+        .filter(|(_, file, line)| {
+            !result
+                .potential_secrets
+                .iter()
+                .any(|s| s.file == *file && s.line == *line)
+        });
+    for (n, (issue, file, line)) in located.enumerate() {
+        let name = format!("finding-{:03}.txt", n + 1);
+        let context = line_context(&path.join(file), line, false)
+            .unwrap_or_else(|| "(file no longer readable)\n".to_string());
+        let body = format!(
+            "Finding: {}\nFile:    {}\nLine:    {}\n\n{}",
+            issue.message, file, line, context
+        );
+        fs::write(dir.join(&name), body)?;
+        written += 1;
+        index.push_str(&format!(
+            "| F{} | {} | {} | [{}]({}) |\n",
+            n + 1,
+            issue.severity.name(),
+            issue.message,
+            name,
+            name
+        ));
+    }
+
+    index.push_str("\n## All Findings\n\n");
+    for issue in &result.issues {
+        let sev = match issue.severity {
+            Severity::Error => "ERROR",
+            Severity::Warning => "WARN",
+            Severity::Info => "INFO",
+        };
+        index.push_str(&format!("- [{}] {}\n", sev, issue.message));
+    }
+    index.push_str(&format!(
+        "\n---\n*{}*\n",
+        markdown_footer(&result.config.branding)
+    ));
+    fs::write(dir.join("INDEX.md"), index)?;
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{redact_line, write_bundle};
+    use crate::scanner;
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    #[test]
+    fn redaction_keeps_a_prefix() {
+        assert_eq!(
+            redact_line("DB_PASSWORD=\"abcdefghij\" id: 42"),
+            "DB_P*******=\"abcd******\" id: 42"
+        );
+    }
+
+    #[test]
+    fn workflow_findings_carry_snippets() {
+        let base = TempDir::new("evidence");
+        let root = base.join("repo");
+        fs::create_dir_all(root.join(".github/workflows")).unwrap();
+        fs::write(
+            root.join(".github/workflows/ci.yml"),
+            "on: pull_request\npermissions:\n  contents: read\njobs:\n  test:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n",
+        )
+        .unwrap();
+        let result = scanner::scan(&root).unwrap();
+        let dir = base.join("bundle");
+        assert!(write_bundle(&result, &root, &dir).unwrap() >= 1);
+        let index = fs::read_to_string(dir.join("INDEX.md")).unwrap();
+        let row = index
+            .lines()
+            .find(|l| l.contains("Unpinned action"))
+            .unwrap();
+        let name = row
+            .rsplit('(')
+            .next()
+            .unwrap()
+            .trim_end_matches(" |")
+            .trim_end_matches(')');
+        let proof = fs::read_to_string(dir.join(name)).unwrap();
+        assert!(proof.starts_with("Finding: Unpinned action in .github/workflows/ci.yml at line 8"));
+        assert!(proof.contains(">    8 |       - uses: actions/checkout@v4\n"));
+        assert!(proof.contains("     7 |     steps:\n"));
+    }
+}
@@ -0,0 +1,391 @@
+use crate::content::{self, ContentType};
+use crate::generator::{self, GenerateOptions, Overrides};
+use crate::license::Choice;
+use crate::report::clean_path;
+use crate::scanner::ScanResult;
+use crate::templates;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::process::Command;
+
+/// Text files at the root that should end with a newline.
+const TEXT_EXTENSIONS: &[&str] = &["md", "txt", "toml", "yml", "yaml", "json", "cfg", "ini"];
+const TEXT_NAMES: &[&str] = &[
+    ".gitignore",
+    ".gitattributes",
+    ".editorconfig",
+    "LICENSE",
+    "CODEOWNERS",
+    "Makefile",
+    "Dockerfile",
+];
+
+/// Environment files that must never be committed.
+const ENV_FILES: &[&str] = &[".env", ".env.local", ".env.development", ".env.production"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Template {
+    Readme,
+    License,
+    Gitignore,
+    Contributing,
+    Changelog,
+    Editorconfig,
+    CodeOfConduct,
+    Security,
+    IssueTemplates,
+    PullRequest,
+    Codecov,
+    Dependabot,
+    Gitattributes,
+    Dockerignore,
+}
+
+/// Kinds of repair `fix` can make, with a short description.
+pub const FIXERS: &[(&str, &str)] = &[
+    ("generate", "Generate missing community and config files"),
+    (
+        "append-gitignore",
+        "Append recommended patterns to .gitignore",
+    ),
+    ("final-newline", "Add a trailing newline to text files"),
+    (
+        "untrack-env",
+        "Remove committed .env files from the git index",
+    ),
+];
+
+impl Template {
+    pub const ALL: [Template; 14] = [
+        Template::Readme,
+        Template::License,
+        Template::Gitignore,
+        Template::Contributing,
+        Template::Changelog,
+        Template::Editorconfig,
+        Template::CodeOfConduct,
+        Template::Security,
+        Template::IssueTemplates,
+        Template::PullRequest,
+        Template::Codecov,
+        Template::Dependabot,
+        Template::Gitattributes,
+        Template::Dockerignore,
+    ];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            Template::Readme => "README.md",
+            Template::License => "LICENSE",
+            Template::Gitignore => ".gitignore",
+            Template::Contributing => "CONTRIBUTING.md",
+            Template::Changelog => "CHANGELOG.md",
+            Template::Editorconfig => ".editorconfig",
+            Template::CodeOfConduct => "CODE_OF_CONDUCT.md",
+            Template::Security => "SECURITY.md",
+            Template::IssueTemplates => templates::ISSUE_DIR,
+            Template::PullRequest => templates::PR_FILES[0],
+            Template::Codecov => "codecov.yml",
+            Template::Dependabot => ".github/dependabot.yml",
+            Template::Gitattributes => ".gitattributes",
+            Template::Dockerignore => ".dockerignore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixKind {
+    /// Generate a missing file from its template.
+    Generate(Template),
+    /// Append recommended patterns to an existing .gitignore.
+    AppendGitignore(Vec<String>),
+    /// Add a trailing newline to a text file.
+    FinalNewline(String),
+    /// Remove a committed file from the git index (keeps it on disk).
+    Untrack(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Fix {
+    pub kind: FixKind,
+    pub description: String,
+}
+
+impl Fix {
+    /// Fixes that touch git state always ask before running.
+    pub fn needs_confirmation(&self) -> bool {
+        matches!(self.kind, FixKind::Untrack(_))
+    }
+}
+
+#[derive(Default)]
+pub struct FixOptions {
+    pub dry_run: bool,
+    /// Ask before every fix, not just destructive ones.
+    pub interactive: bool,
+    /// Answer yes to every confirmation prompt.
+    pub yes: bool,
+    pub author: Option<String>,
+    /// License to generate when LICENSE is missing (default MIT).
+    pub license: Option<&'static Choice>,
+    /// Color the diffs a dry run prints for files it would change.
+    pub color: bool,
+}
+
+/// Work out every fix applicable to the scanned repository.
+pub fn plan(path: &Path, result: &ScanResult) -> Vec<Fix> {
+    let mut fixes = Vec::new();
+
+    let missing = [
+        (result.has_readme, Template::Readme),
+        (result.has_license, Template::License),
+        (result.has_gitignore, Template::Gitignore),
+        (result.has_contributing, Template::Contributing),
+        (result.has_changelog, Template::Changelog),
+        (result.has_editorconfig, Template::Editorconfig),
+        (result.has_code_of_conduct, Template::CodeOfConduct),
+        (result.has_security, Template::Security),
+        (result.has_issue_template, Template::IssueTemplates),
+        (result.has_pr_template, Template::PullRequest),
+        (!result.lacks_coverage(), Template::Codecov),
+        (!result.lacks_dependency_updates(), Template::Dependabot),
+        (
+            result.lfs.untracked.is_empty() && !result.lacks_line_ending_normalization(),
+            Template::Gitattributes,
+        ),
+        (!result.lacks_dockerignore(), Template::Dockerignore),
+    ];
+    for (present, template) in missing {
+        if !present {
+            fixes.push(Fix {
+                kind: FixKind::Generate(template),
+                description: format!("Generate {}", template.file_name()),
+            });
+        }
+    }
+
+    let tracked_env = tracked_env_files(path);
+    if result.has_gitignore {
+        let patterns = missing_gitignore_patterns(path, result, !tracked_env.is_empty());
+        if !patterns.is_empty() {
+            fixes.push(Fix {
+                description: format!("Append {} to .gitignore", patterns.join(", ")),
+                kind: FixKind::AppendGitignore(patterns),
+            });
+        }
+    }
+
+    for name in files_missing_final_newline(path) {
+        fixes.push(Fix {
+            description: format!("Add newline at end of {}", name),
+            kind: FixKind::FinalNewline(name),
+        });
+    }
+
+    for name in tracked_env {
+        fixes.push(Fix {
+            description: format!("Remove committed {} from the git index", name),
+            kind: FixKind::Untrack(name),
+        });
+    }
+
+    fixes
+}
+
+/// Apply planned fixes. Returns the number of fixes applied.
+pub fn apply(
+    path: &Path,
+    result: &ScanResult,
+    fixes: &[Fix],
+    opts: &FixOptions,
+) -> io::Result<usize> {
+    let gen_opts = GenerateOptions {
+        dry_run: opts.dry_run,
+        license: opts.license,
+        color: opts.color,
+        templates: result.config.templates.as_ref().map(|dir| {
+            Overrides::new(
+                path.join(dir),
+                path,
+                result,
+                opts.author.as_deref(),
+                opts.license,
+            )
+        }),
+        ..GenerateOptions::default()
+    };
+    let mut applied = 0;
+    for fix in fixes {
+        if opts.dry_run {
+            println!("[dry-run] Would fix: {}", fix.description);
+            if let FixKind::Generate(template) = fix.kind {
+                generate(path, result, template, opts.author.as_deref(), &gen_opts)?;
+            }
+            continue;
+        }
+        if (opts.interactive || fix.needs_confirmation())
+            && !opts.yes
+            && !confirm(&fix.description)?
+        {
+            println!("Skipped: {}", fix.description);
+            continue;
+        }
+        match &fix.kind {
+            FixKind::Generate(template) => {
+                generate(path, result, *template, opts.author.as_deref(), &gen_opts)?
+            }
+            FixKind::AppendGitignore(patterns) => {
+                let file = path.join(".gitignore");
+                let mut content = fs::read_to_string(&file)?;
+                if !content.is_empty() && !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push_str("\n# Added by repomedic fix\n");
+                for p in patterns {
+                    content.push_str(p);
+                    content.push('\n');
+                }
+                fs::write(&file, content)?;
+                println!("Fixed: {}", fix.description);
+            }
+            FixKind::FinalNewline(name) => {
+                let file = path.join(name);
+                let mut content = fs::read(&file)?;
+                content.push(b'\n');
+                fs::write(&file, content)?;
+                println!("Fixed: {}", fix.description);
+            }
+            FixKind::Untrack(name) => {
+                let status = Command::new("git")
+                    .arg("-C")
+                    .arg(path)
+                    .args(["rm", "--cached", "--quiet", "--"])
+                    .arg(name)
+                    .status()?;
+                if !status.success() {
+                    return Err(io::Error::other(format!(
+                        "git rm --cached {} failed in {}",
+                        name,
+                        clean_path(path)
+                    )));
+                }
+                println!("Fixed: {} (file kept on disk)", fix.description);
+            }
+        }
+        applied += 1;
+    }
+    Ok(applied)
+}
+
+fn generate(
+    path: &Path,
+    result: &ScanResult,
+    template: Template,
+    author: Option<&str>,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
+    match template {
+        Template::Readme => generator::generate_readme(path, result, opts),
+        Template::License => generator::generate_license(path, author, opts),
+        Template::Gitignore => generator::generate_gitignore(path, result, opts),
+        Template::Contributing => generator::generate_contributing(path, opts),
+        Template::Changelog => generator::generate_changelog(path, opts),
+        Template::Editorconfig => generator::generate_editorconfig(path, result, opts),
+        Template::CodeOfConduct => generator::generate_code_of_conduct(path, opts),
+        Template::Security => generator::generate_security(path, opts),
+        Template::IssueTemplates => generator::generate_issue_templates(path, opts),
+        Template::PullRequest => generator::generate_pr_template(path, opts),
+        Template::Codecov => generator::generate_codecov(path, opts),
+        Template::Dependabot => generator::generate_dependabot(path, result, opts),
+        Template::Gitattributes => generator::generate_gitattributes(path, result, opts),
+        Template::Dockerignore => generator::generate_dockerignore(path, result, opts),
+    }
+}
+
+fn confirm(description: &str) -> io::Result<bool> {
+    print!("{}? [y/N] ", description);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Recommended patterns for the detected languages not already in .gitignore.
+fn missing_gitignore_patterns(path: &Path, result: &ScanResult, need_env: bool) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path.join(".gitignore")) else {
+        return Vec::new();
+    };
+    let existing: Vec<&str> = content
+        .lines()
+        .map(|l| l.trim().trim_start_matches('/'))
+        .collect();
+    let mut wanted: Vec<&str> = Vec::new();
+    for lang in &result.languages {
+        if let Some((_, patterns)) = generator::gitignore_section(lang) {
+            wanted.extend(patterns.iter());
+        }
+    }
+    if need_env || ENV_FILES.iter().any(|f| path.join(f).is_file()) {
+        wanted.push(".env");
+    }
+    let mut missing: Vec<String> = Vec::new();
+    for p in wanted {
+        let bare = p.trim_end_matches('/');
+        let covered = existing
+            .iter()
+            .any(|e| *e == p || e.trim_end_matches('/') == bare);
+        if !covered && !missing.iter().any(|m| m == p) {
+            missing.push(p.to_string());
+        }
+    }
+    missing
+}
+
+fn files_missing_final_newline(path: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(path) else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let ext = name.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+        let is_text = TEXT_NAMES.contains(&name.as_str())
+            || (TEXT_EXTENSIONS.contains(&ext) && !name.starts_with('.'));
+        if !is_text || !entry.path().is_file() {
+            continue;
+        }
+        if let Ok(content) = fs::read(entry.path()) {
+            // A single newline byte would corrupt UTF-16 files.
+            let text = content::sniff(&content) == ContentType::Text;
+            if text && !content.is_empty() && !content.ends_with(b"\n") {
+                names.push(name);
+            }
+        }
+    }
+    names.sort();
+    names
+}
+
+/// Environment files currently tracked by git.
+fn tracked_env_files(path: &Path) -> Vec<String> {
+    if !path.join(".git").exists() {
+        return Vec::new();
+    }
+    let Ok(output) = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["ls-files", "--"])
+        .args(ENV_FILES)
+        .output()
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.to_string())
+        .collect()
+}
@@ -0,0 +1,134 @@
+use crate::git::date_from_unix;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default days a lock file may go without updates before it's stale.
+pub const DEFAULT_MAX_AGE_DAYS: u64 = 365;
+
+/// Lock files at the root and the manifest each one pins.
+pub const LOCKFILES: &[(&str, &str)] = &[
+    ("Cargo.lock", "Cargo.toml"),
+    ("package-lock.json", "package.json"),
+    ("yarn.lock", "package.json"),
+    ("pnpm-lock.yaml", "package.json"),
+    ("poetry.lock", "pyproject.toml"),
+    ("Pipfile.lock", "Pipfile"),
+    ("Gemfile.lock", "Gemfile"),
+    ("composer.lock", "composer.json"),
+    ("go.sum", "go.mod"),
+    ("mix.lock", "mix.exs"),
+];
+
+/// When a lock file and its manifest last changed.
+#[derive(Debug, Clone, Serialize)]
+pub struct LockFreshness {
+    pub lockfile: String,
+    /// Last change, `YYYY-MM-DD`.
+    pub updated: String,
+    pub days_since_update: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest_updated: Option<String>,
+    /// Days the manifest changed after the lock file (0 if not later).
+    pub manifest_lead_days: u64,
+    /// Where the dates come from: `git` (last commit) or `mtime`.
+    pub source: &'static str,
+}
+
+impl LockFreshness {
+    /// Date lock files under `root`. Committed files use their last commit,
+    /// since checkouts reset modification times; others use the mtime.
+    pub fn measure(root: &Path) -> Vec<Self> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs() as i64);
+        let mut found = Vec::new();
+        for (lockfile, manifest) in LOCKFILES {
+            let Some((updated, source)) = last_change(root, lockfile) else {
+                continue;
+            };
+            let manifest_updated = last_change(root, manifest).map(|(t, _)| t);
+            found.push(LockFreshness {
+                lockfile: lockfile.to_string(),
+                updated: date_from_unix(updated),
+                days_since_update: ((now - updated).max(0) / 86_400) as u64,
+                manifest: manifest_updated.map(|_| manifest.to_string()),
+                manifest_updated: manifest_updated.map(date_from_unix),
+                manifest_lead_days: manifest_updated
+                    .map_or(0, |m| ((m - updated).max(0) / 86_400) as u64),
+                source,
+            });
+        }
+        found
+    }
+}
+
+/// Unix time `rel` last changed, and the source of that time.
+fn last_change(root: &Path, rel: &str) -> Option<(i64, &'static str)> {
+    let meta = fs::metadata(root.join(rel)).ok()?;
+    let committed = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["log", "-1", "--format=%ct", "--", rel])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .and_then(|o| String::from_utf8_lossy(&o.stdout).trim().parse().ok());
+    if let Some(time) = committed {
+        return Some((time, "git"));
+    }
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((mtime.as_secs() as i64, "mtime"))
+}
+
+/// `14 months`, `3 days`, or `2 years`.
+pub fn humanize_days(days: u64) -> String {
+    let (n, unit) = match days {
+        0..=59 => (days, "day"),
+        60..=729 => (days / 30, "month"),
+        _ => (days / 365, "year"),
+    };
+    format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{humanize_days, LockFreshness};
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    #[test]
+    fn humanized_ages() {
+        assert_eq!(humanize_days(1), "1 day");
+        assert_eq!(humanize_days(59), "59 days");
+        assert_eq!(humanize_days(60), "2 months");
+        assert_eq!(humanize_days(429), "14 months");
+        assert_eq!(humanize_days(730), "2 years");
+    }
+
+    #[test]
+    fn uncommitted_lockfiles_use_mtime() {
+        let dir = TempDir::new("fresh");
+        fs::write(dir.join("Cargo.lock"), "").unwrap();
+        fs::write(dir.join("Cargo.toml"), "").unwrap();
+        fs::write(dir.join("yarn.lock"), "").unwrap();
+        let found = LockFreshness::measure(&dir);
+        let summary: Vec<_> = found
+            .iter()
+            .map(|f| (f.lockfile.as_str(), f.manifest.as_deref(), f.source))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("Cargo.lock", Some("Cargo.toml"), "mtime"),
+                ("yarn.lock", None, "mtime"),
+            ]
+        );
+        assert_eq!(found[0].days_since_update, 0);
+        assert_eq!(found[0].manifest_lead_days, 0);
+    }
+}
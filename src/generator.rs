@@ -1,8 +1,19 @@
+use crate::cache;
+use crate::diff;
+use crate::docker;
+use crate::gitignores::{self, Template};
+use crate::hbs::{self, Value};
+use crate::indent::{Level, Style};
+use crate::install;
+use crate::lfs;
+use crate::license::{self, Choice};
+use crate::pipeline::{self, Provider};
 use crate::report::clean_path;
-use crate::scanner::{Language, ScanResult};
+use crate::scanner::{BuildSystem, CISystem, Language, ScanResult};
+use crate::templates;
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Get current year from system time.
@@ -18,10 +29,151 @@ fn current_year() -> u32 {
 #[derive(Default)]
 pub struct GenerateOptions {
     pub dry_run: bool,
+    /// Overwrite files that already exist.
+    pub force: bool,
+    /// Append only the missing sections to an existing `.gitignore` or
+    /// `.editorconfig` instead of skipping it.
+    pub merge: bool,
+    /// Color the diffs a dry run prints for existing files.
+    pub color: bool,
+    /// License for LICENSE and the README's License section (default MIT).
+    pub license: Option<&'static Choice>,
+    /// `.gitignore` templates to include besides the detected languages';
+    /// when given, they replace the default OS and editor patterns.
+    pub gitignore_templates: Vec<&'static Template>,
+    /// Directory of `.hbs` files that replace the built-in content.
+    pub templates: Option<Overrides>,
+}
+
+impl GenerateOptions {
+    fn license(&self) -> &'static Choice {
+        self.license.unwrap_or(license::DEFAULT_CHOICE)
+    }
+
+    /// The content for `name` (relative to the repository root): the
+    /// override template `<name>.hbs` rendered when there is one,
+    /// `built_in` otherwise.
+    fn content(&self, name: &str, built_in: String) -> io::Result<String> {
+        let Some(overrides) = &self.templates else {
+            return Ok(built_in);
+        };
+        let file = overrides.dir.join(format!("{}.hbs", name));
+        let template = match fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(built_in),
+            Err(e) => return Err(e),
+        };
+        hbs::render(&template, &overrides.vars).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", clean_path(&file), e),
+            )
+        })
+    }
+}
+
+/// Organization templates for `generate --templates` or the `templates`
+/// config key: `README.md.hbs`, `LICENSE.hbs`, `.github/dependabot.yml.hbs`
+/// and so on, named after the file they replace.
+#[derive(Debug, Clone)]
+pub struct Overrides {
+    pub dir: PathBuf,
+    vars: Vec<(&'static str, Value)>,
+}
+
+impl Overrides {
+    /// Templates in `dir`, filled in from the scan of the repository at
+    /// `path`. `license` defaults like `GenerateOptions::license`.
+    pub fn new(
+        dir: PathBuf,
+        path: &Path,
+        result: &ScanResult,
+        author: Option<&str>,
+        license: Option<&'static Choice>,
+    ) -> Overrides {
+        let license = license.unwrap_or(license::DEFAULT_CHOICE);
+        let project = Project::read(path);
+        let str = |s: &str| Value::Str(s.to_string());
+        let languages: Vec<String> = result.languages.iter().map(|l| l.name().into()).collect();
+        let vars = vec![
+            ("name", str(&project.name)),
+            (
+                "description",
+                str(project.description.as_deref().unwrap_or("")),
+            ),
+            ("version", str(project.version.as_deref().unwrap_or(""))),
+            ("author", str(author.unwrap_or("Author"))),
+            ("year", Value::Str(current_year().to_string())),
+            (
+                "language",
+                str(languages.first().map_or("", |l| l.as_str())),
+            ),
+            ("languages", Value::List(languages)),
+            (
+                "build_systems",
+                Value::List(
+                    result
+                        .build_systems
+                        .iter()
+                        .map(|b| b.name().to_string())
+                        .collect(),
+                ),
+            ),
+            ("license", str(license.spdx)),
+            ("license_name", str(license.name)),
+        ];
+        Overrides { dir, vars }
+    }
+}
+
+/// Whether to leave an existing `output` alone, printing why. `--force`
+/// overwrites it, and a dry run goes on to show what `--force` would change.
+fn keep_existing(output: &Path, name: &str, opts: &GenerateOptions) -> bool {
+    if !output.exists() || opts.force {
+        return false;
+    }
+    if opts.dry_run {
+        println!("{} already exists; with --force:", name);
+        return false;
+    }
+    println!("{} already exists, skipping.", name);
+    true
+}
+
+/// Write `existing` plus `additions` back to `output`, for `--merge`.
+fn append_missing(
+    output: &Path,
+    name: &str,
+    existing: &str,
+    additions: &str,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
+    if additions.is_empty() {
+        println!("{} already has every generated section, skipping.", name);
+        return Ok(());
+    }
+    let mut merged = existing.to_string();
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    merged.push('\n');
+    merged.push_str(additions.trim_end());
+    merged.push('\n');
+    write_file(output, &merged, opts)
 }
 
 fn write_file(path: &Path, content: &str, opts: &GenerateOptions) -> io::Result<()> {
     if opts.dry_run {
+        if let Ok(existing) = fs::read_to_string(path) {
+            let diff = diff::unified(&existing, content, &clean_path(path), opts.color);
+            if diff.is_empty() {
+                println!("[dry-run] Unchanged: {}", clean_path(path));
+            } else {
+                println!("[dry-run] Would overwrite: {}", clean_path(path));
+                print!("{}", diff);
+            }
+            return Ok(());
+        }
         println!("[dry-run] Would write: {}", clean_path(path));
         println!("--- content preview ---");
         let lines: Vec<_> = content.lines().collect();
@@ -47,19 +199,20 @@ fn write_file(path: &Path, content: &str, opts: &GenerateOptions) -> io::Result<
 
 pub fn generate_readme(path: &Path, result: &ScanResult, opts: &GenerateOptions) -> io::Result<()> {
     let output = path.join("README.md");
-    if output.exists() && !opts.dry_run {
-        println!("README.md already exists, skipping.");
+    if keep_existing(&output, "README.md", opts) {
         return Ok(());
     }
 
-    let project_name = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("Project");
-
+    let project = Project::read(path);
     let mut content = String::new();
-    content.push_str(&format!("# {}\n\n", project_name));
-    content.push_str("A brief description of the project.\n\n");
+    content.push_str(&format!("# {}\n\n", project.name));
+    match &project.description {
+        Some(description) => content.push_str(&format!("{}\n\n", description)),
+        None => content.push_str("A brief description of the project.\n\n"),
+    }
+    if let Some(version) = &project.version {
+        content.push_str(&format!("Current version: {}\n\n", version));
+    }
 
     if !result.languages.is_empty() {
         content.push_str("## Requirements\n\n");
@@ -71,161 +224,380 @@ pub fn generate_readme(path: &Path, result: &ScanResult, opts: &GenerateOptions)
                 Language::Go => content.push_str("- Go 1.21+\n"),
                 Language::Java => content.push_str("- Java 17+\n"),
                 Language::CSharp => content.push_str("- .NET 8.0+\n"),
+                Language::Hcl => content.push_str("- Terraform 1.5+ (or OpenTofu)\n"),
+                Language::Dockerfile => content.push_str("- Docker\n"),
                 _ => {}
             }
         }
         content.push('\n');
     }
 
+    let commands = dev_commands(path, result, &project);
+    let block = |section: Section| {
+        let lines: Vec<&str> = commands
+            .iter()
+            .filter(|(s, _)| *s == section)
+            .map(|(_, c)| c.as_str())
+            .collect();
+        lines.join("\n")
+    };
+    let install = block(Section::Install);
     content.push_str("## Installation\n\n");
-    content.push_str("```sh\n# Add installation instructions\n```\n\n");
+    if install.is_empty() {
+        content.push_str("```sh\n# Add installation instructions\n```\n\n");
+    } else {
+        content.push_str(&format!("```sh\n{}\n```\n\n", install));
+    }
+    for (section, heading) in [(Section::Build, "Building"), (Section::Test, "Testing")] {
+        let lines = block(section);
+        if !lines.is_empty() {
+            content.push_str(&format!("## {}\n\n```sh\n{}\n```\n\n", heading, lines));
+        }
+    }
 
     content.push_str("## Usage\n\n");
     content.push_str("```sh\n# Add usage examples\n```\n\n");
 
     content.push_str("## License\n\n");
-    content.push_str("MIT License\n");
+    content.push_str(opts.license().name);
+    content.push('\n');
 
+    let content = opts.content("README.md", content)?;
     write_file(&output, &content, opts)
 }
 
+/// Name, description, and version from the root manifests (Cargo.toml,
+/// package.json, pyproject.toml, go.mod, composer.json), falling back to
+/// the directory name.
+struct Project {
+    name: String,
+    description: Option<String>,
+    version: Option<String>,
+}
+
+impl Project {
+    fn read(path: &Path) -> Project {
+        let packages = install::packages(&|f| fs::read_to_string(path.join(f)).ok());
+        // Go module paths end in the name; composer names are vendor/name
+        let name = packages
+            .iter()
+            .find_map(|p| p.name.as_deref())
+            .map(|n| n.rsplit('/').next().unwrap_or(n).to_string())
+            .unwrap_or_else(|| {
+                path.file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("Project")
+                    .to_string()
+            });
+        Project {
+            name,
+            description: packages.iter().find_map(|p| p.description.clone()),
+            version: packages.iter().find_map(|p| p.version.clone()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    Install,
+    Build,
+    Test,
+}
+
+/// Install, build, and test commands for the detected build systems, in
+/// the order a README lists them. Yarn and pnpm replace npm, and npm
+/// scripts are only mentioned when `package.json` defines them.
+fn dev_commands(path: &Path, result: &ScanResult, project: &Project) -> Vec<(Section, String)> {
+    let has = |b: BuildSystem| result.build_systems.contains(&b);
+    let file = |f: &str| path.join(f).is_file();
+    let mut commands: Vec<(Section, String)> = Vec::new();
+    let mut add = |section: Section, command: &str| commands.push((section, command.to_string()));
+    if has(BuildSystem::Cargo) {
+        if file("src/main.rs") {
+            add(Section::Install, "cargo install --path .");
+        } else {
+            add(Section::Install, &format!("cargo add {}", project.name));
+        }
+        add(Section::Build, "cargo build --release");
+        add(Section::Test, "cargo test");
+    }
+    let node = if has(BuildSystem::Pnpm) {
+        Some("pnpm")
+    } else if has(BuildSystem::Yarn) {
+        Some("yarn")
+    } else if has(BuildSystem::Npm) {
+        Some("npm")
+    } else {
+        None
+    };
+    if let Some(tool) = node {
+        let scripts = fs::read_to_string(path.join("package.json"))
+            .ok()
+            .and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok())
+            .map(|p| p["scripts"].clone())
+            .unwrap_or_default();
+        add(Section::Install, &format!("{} install", tool));
+        if scripts.get("build").is_some() {
+            let run = if tool == "npm" { "npm run" } else { tool };
+            add(Section::Build, &format!("{} build", run));
+        }
+        if scripts.get("test").is_some() {
+            add(Section::Test, &format!("{} test", tool));
+        }
+    }
+    let poetry =
+        fs::read_to_string(path.join("pyproject.toml")).is_ok_and(|p| p.contains("[tool.poetry"));
+    if poetry {
+        add(Section::Install, "poetry install");
+        add(Section::Build, "poetry build");
+        add(Section::Test, "poetry run pytest");
+    } else if has(BuildSystem::Pip) || has(BuildSystem::Poetry) {
+        if file("pyproject.toml") || file("setup.py") {
+            add(Section::Install, "pip install -e .");
+        } else if file("requirements.txt") {
+            add(Section::Install, "pip install -r requirements.txt");
+        }
+        add(Section::Test, "pytest");
+    }
+    if has(BuildSystem::Go) {
+        add(Section::Install, "go install ./...");
+        add(Section::Build, "go build ./...");
+        add(Section::Test, "go test ./...");
+    }
+    if has(BuildSystem::Maven) {
+        add(Section::Install, "mvn install");
+        add(Section::Build, "mvn package");
+        add(Section::Test, "mvn test");
+    } else if has(BuildSystem::Gradle) {
+        let gradle = if file("gradlew") {
+            "./gradlew"
+        } else {
+            "gradle"
+        };
+        add(Section::Build, &format!("{} build", gradle));
+        add(Section::Test, &format!("{} test", gradle));
+    }
+    if has(BuildSystem::Msbuild) {
+        add(Section::Install, "dotnet restore");
+        add(Section::Build, "dotnet build");
+        add(Section::Test, "dotnet test");
+    }
+    if has(BuildSystem::Bundler) {
+        add(Section::Install, "bundle install");
+        add(Section::Test, "bundle exec rake test");
+    }
+    if has(BuildSystem::Composer) {
+        add(Section::Install, "composer install");
+        add(Section::Test, "vendor/bin/phpunit");
+    }
+    if has(BuildSystem::Mix) {
+        add(Section::Install, "mix deps.get");
+        add(Section::Build, "mix compile");
+        add(Section::Test, "mix test");
+    }
+    if has(BuildSystem::Cabal) {
+        add(Section::Build, "cabal build");
+        add(Section::Test, "cabal test");
+    } else if has(BuildSystem::Stack) {
+        add(Section::Build, "stack build");
+        add(Section::Test, "stack test");
+    }
+    if has(BuildSystem::Zig) {
+        add(Section::Build, "zig build");
+        add(Section::Test, "zig build test");
+    }
+    if has(BuildSystem::Nimble) {
+        add(Section::Install, "nimble install");
+        add(Section::Test, "nimble test");
+    }
+    // CMake projects often keep a Makefile too; CMake drives the build
+    if has(BuildSystem::Cmake) {
+        add(Section::Build, "cmake -B build && cmake --build build");
+        add(Section::Test, "ctest --test-dir build");
+    } else if has(BuildSystem::Make) {
+        add(Section::Build, "make");
+        add(Section::Test, "make test");
+    }
+    commands
+}
+
 pub fn generate_license(
     path: &Path,
     author: Option<&str>,
     opts: &GenerateOptions,
 ) -> io::Result<()> {
     let output = path.join("LICENSE");
-    if output.exists() && !opts.dry_run {
-        println!("LICENSE already exists, skipping.");
+    if keep_existing(&output, "LICENSE", opts) {
         return Ok(());
     }
 
-    let year = current_year();
     let holder = author.unwrap_or("Author");
-
-    let content = format!(
-        r#"MIT License
-
-Copyright (c) {} {}
-
-Permission is hereby granted, free of charge, to any person obtaining a copy
-of this software and associated documentation files (the "Software"), to deal
-in the Software without restriction, including without limitation the rights
-to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
-copies of the Software, and to permit persons to whom the Software is
-furnished to do so, subject to the following conditions:
-
-The above copyright notice and this permission notice shall be included in all
-copies or substantial portions of the Software.
-
-THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
-IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
-FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
-AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
-LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
-OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
-SOFTWARE.
-"#,
-        year, holder
-    );
-
+    let content = opts.content("LICENSE", opts.license().render(current_year(), holder))?;
     write_file(&output, &content, opts)
 }
 
+/// Recommended .gitignore header and patterns for a language.
+pub fn gitignore_section(lang: &Language) -> Option<(&'static str, &'static [&'static str])> {
+    let section: (&str, &[&str]) = match lang {
+        Language::Rust => ("Rust", &["target/"]),
+        Language::Python => (
+            "Python",
+            &[
+                "__pycache__/",
+                "*.py[cod]",
+                "*.egg-info/",
+                "dist/",
+                "build/",
+                "venv/",
+                ".venv/",
+                ".env",
+            ],
+        ),
+        Language::JavaScript | Language::TypeScript | Language::Vue | Language::Svelte => (
+            "Node.js",
+            &["node_modules/", "dist/", "build/", ".env", "*.log"],
+        ),
+        Language::Go => ("Go", &["bin/", "*.exe"]),
+        Language::Java | Language::Kotlin | Language::Scala => {
+            ("Java/JVM", &["target/", "build/", "*.class", "*.jar"])
+        }
+        Language::CSharp | Language::FSharp => (".NET", &["bin/", "obj/", "*.user", "*.suo"]),
+        Language::Cpp | Language::C => ("C/C++", &["build/", "*.o", "*.a", "*.so", "*.dylib"]),
+        Language::Hcl => ("Terraform", &["*.tfstate", "*.tfstate.*", ".terraform/"]),
+        _ => return None,
+    };
+    Some(section)
+}
+
 pub fn generate_gitignore(
     path: &Path,
     result: &ScanResult,
     opts: &GenerateOptions,
 ) -> io::Result<()> {
     let output = path.join(".gitignore");
-    if output.exists() && !opts.dry_run {
-        println!(".gitignore already exists, skipping.");
+    if !opts.merge && keep_existing(&output, ".gitignore", opts) {
         return Ok(());
     }
 
     let mut content = String::new();
 
-    content.push_str("# OS\n");
-    content.push_str(".DS_Store\n");
-    content.push_str("Thumbs.db\n");
-    content.push_str("*.swp\n");
-    content.push_str("*~\n\n");
+    if opts.gitignore_templates.is_empty() {
+        content.push_str("# OS\n");
+        content.push_str(".DS_Store\n");
+        content.push_str("Thumbs.db\n");
+        content.push_str("*.swp\n");
+        content.push_str("*~\n\n");
+
+        content.push_str("# IDE\n");
+        content.push_str(".idea/\n");
+        content.push_str(".vscode/\n");
+        content.push_str("*.iml\n\n");
+    }
 
-    content.push_str("# IDE\n");
-    content.push_str(".idea/\n");
-    content.push_str(".vscode/\n");
-    content.push_str("*.iml\n\n");
+    let templates = gitignores::select(
+        &result.languages,
+        &result.build_systems,
+        &opts.gitignore_templates,
+    );
+    for template in &templates {
+        content.push_str(&format!("### {} ###\n", template.name));
+        content.push_str(template.text.trim_end());
+        content.push_str("\n\n");
+    }
 
+    // Patterns `fix` checks for that the templates leave out (Go's bin/,
+    // Maven's target/ when only the Java template applies).
+    let listed: Vec<&str> = content
+        .lines()
+        .map(|l| l.trim().trim_start_matches('/').trim_end_matches('/'))
+        .collect();
+    let mut extra: Vec<&str> = Vec::new();
     for lang in &result.languages {
-        match lang {
-            Language::Rust => {
-                content.push_str("# Rust\n");
-                content.push_str("target/\n\n");
-            }
-            Language::Python => {
-                content.push_str("# Python\n");
-                content.push_str("__pycache__/\n");
-                content.push_str("*.py[cod]\n");
-                content.push_str("*.egg-info/\n");
-                content.push_str("dist/\n");
-                content.push_str("build/\n");
-                content.push_str("venv/\n");
-                content.push_str(".venv/\n");
-                content.push_str(".env\n\n");
-            }
-            Language::JavaScript | Language::TypeScript => {
-                content.push_str("# Node.js\n");
-                content.push_str("node_modules/\n");
-                content.push_str("dist/\n");
-                content.push_str("build/\n");
-                content.push_str(".env\n");
-                content.push_str("*.log\n\n");
+        if let Some((_, patterns)) = gitignore_section(lang) {
+            for p in patterns.iter() {
+                if !listed.contains(&p.trim_end_matches('/')) && !extra.contains(p) {
+                    extra.push(p);
+                }
             }
-            Language::Go => {
-                content.push_str("# Go\n");
-                content.push_str("bin/\n");
-                content.push_str("*.exe\n\n");
-            }
-            Language::Java | Language::Kotlin | Language::Scala => {
-                content.push_str("# Java/JVM\n");
-                content.push_str("target/\n");
-                content.push_str("build/\n");
-                content.push_str("*.class\n");
-                content.push_str("*.jar\n\n");
-            }
-            Language::CSharp => {
-                content.push_str("# .NET\n");
-                content.push_str("bin/\n");
-                content.push_str("obj/\n");
-                content.push_str("*.user\n");
-                content.push_str("*.suo\n\n");
-            }
-            Language::Cpp | Language::C => {
-                content.push_str("# C/C++\n");
-                content.push_str("build/\n");
-                content.push_str("*.o\n");
-                content.push_str("*.a\n");
-                content.push_str("*.so\n");
-                content.push_str("*.dylib\n\n");
-            }
-            _ => {}
         }
     }
+    if !extra.is_empty() {
+        content.push_str("### Build output ###\n");
+        for p in extra {
+            content.push_str(p);
+            content.push('\n');
+        }
+        content.push('\n');
+    }
 
-    if result.languages.is_empty() {
+    if templates.is_empty() {
         content.push_str("# Build\n");
         content.push_str("build/\n");
         content.push_str("dist/\n");
         content.push_str("out/\n\n");
     }
 
-    write_file(&output, &content, opts)
+    // Where the scan cache goes outside Git, with `cache = true`
+    content.push_str("# repomedic\n");
+    content.push_str(&format!("{}\n", cache::CACHE_FILE));
+
+    let content = opts.content(".gitignore", content)?;
+    match fs::read_to_string(&output) {
+        Ok(existing) if opts.merge => {
+            let additions = gitignore_additions(&existing, &content);
+            append_missing(&output, ".gitignore", &existing, &additions, opts)
+        }
+        _ => write_file(&output, &content, opts),
+    }
+}
+
+/// The patterns of a generated `.gitignore` that `existing` lacks, under
+/// the comments that introduce them and their template's `### Name ###`.
+fn gitignore_additions(existing: &str, generated: &str) -> String {
+    let bare = |l: &str| {
+        l.trim()
+            .trim_start_matches('/')
+            .trim_end_matches('/')
+            .to_string()
+    };
+    let listed: Vec<String> = existing.lines().map(bare).collect();
+    let mut out = String::new();
+    let mut title: Option<&str> = None;
+    for block in generated.split("\n\n") {
+        let mut comments: Vec<&str> = Vec::new();
+        let mut missing: Vec<&str> = Vec::new();
+        for line in block.lines().map(str::trim) {
+            if line.starts_with("### ") && line.ends_with(" ###") {
+                title = Some(line);
+            } else if line.starts_with('#') {
+                if missing.is_empty() {
+                    comments.push(line);
+                }
+            } else if !line.is_empty() && !listed.contains(&bare(line)) && !missing.contains(&line)
+            {
+                missing.push(line);
+            }
+        }
+        if missing.is_empty() {
+            continue;
+        }
+        // Each template's title goes in once, before its first addition
+        if let Some(t) = title.take() {
+            out.push_str(t);
+            out.push('\n');
+        }
+        for line in comments.into_iter().chain(missing) {
+            out.push_str(line);
+            out.push('\n');
+        }
+        out.push('\n');
+    }
+    out
 }
 
 pub fn generate_contributing(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
     let output = path.join("CONTRIBUTING.md");
-    if output.exists() && !opts.dry_run {
-        println!("CONTRIBUTING.md already exists, skipping.");
+    if keep_existing(&output, "CONTRIBUTING.md", opts) {
         return Ok(());
     }
 
@@ -268,13 +640,13 @@ By contributing, you agree that your contributions will be licensed under the sa
         project_name
     );
 
+    let content = opts.content("CONTRIBUTING.md", content)?;
     write_file(&output, &content, opts)
 }
 
 pub fn generate_changelog(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
     let output = path.join("CHANGELOG.md");
-    if output.exists() && !opts.dry_run {
-        println!("CHANGELOG.md already exists, skipping.");
+    if keep_existing(&output, "CHANGELOG.md", opts) {
         return Ok(());
     }
 
@@ -296,46 +668,267 @@ and this project adheres to [Semantic Versioning](https://semver.org/spec/v2.0.0
 ### Removed
 "#;
 
-    write_file(&output, content, opts)
+    let content = opts.content("CHANGELOG.md", content.to_string())?;
+    write_file(&output, &content, opts)
 }
 
-pub fn generate_editorconfig(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
+/// Indentation an extension gets when the code doesn't show one.
+const DEFAULT_INDENT: &[(&str, Level)] = &[
+    ("yml", (Style::Space, Some(2))),
+    ("yaml", (Style::Space, Some(2))),
+    ("json", (Style::Space, Some(2))),
+];
+
+/// Write `.editorconfig`, with `[*]` set to the indentation most surveyed
+/// files use and a section for each group of extensions that differ.
+pub fn generate_editorconfig(
+    path: &Path,
+    result: &ScanResult,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
     let output = path.join(".editorconfig");
-    if output.exists() && !opts.dry_run {
-        println!(".editorconfig already exists, skipping.");
+    if !opts.merge && keep_existing(&output, ".editorconfig", opts) {
         return Ok(());
     }
 
-    let content = r#"root = true
+    let mut indents: Vec<(String, Level, usize)> = result
+        .indentation
+        .iter()
+        .map(|i| (i.extension.clone(), (i.style, i.size), i.files))
+        .collect();
+    for (ext, level) in DEFAULT_INDENT {
+        if !indents.iter().any(|(e, ..)| e == ext) {
+            indents.push((ext.to_string(), *level, 0));
+        }
+    }
 
-[*]
-indent_style = space
-indent_size = 4
-end_of_line = lf
-charset = utf-8
-trim_trailing_whitespace = true
-insert_final_newline = true
+    // The default is whatever the most files agree on, else 4 spaces
+    let mut totals: Vec<(Level, usize)> = Vec::new();
+    for (_, level, files) in &indents {
+        match totals.iter_mut().find(|(l, _)| l == level) {
+            Some((_, n)) => *n += files,
+            None => totals.push((*level, *files)),
+        }
+    }
+    let default = totals
+        .iter()
+        .filter(|(_, n)| *n > 0)
+        .max_by_key(|(_, n)| *n)
+        .map(|(l, _)| *l)
+        .unwrap_or((Style::Space, Some(4)));
+
+    let mut content = String::from("root = true\n\n[*]\n");
+    content.push_str(&indent_lines(default));
+    content.push_str("end_of_line = lf\n");
+    content.push_str("charset = utf-8\n");
+    content.push_str("trim_trailing_whitespace = true\n");
+    content.push_str("insert_final_newline = true\n\n");
+    content.push_str("[*.md]\n");
+    content.push_str("trim_trailing_whitespace = false\n\n");
+
+    let mut groups: Vec<(Level, Vec<&str>)> = Vec::new();
+    for (ext, level, _) in &indents {
+        if *level == default {
+            continue;
+        }
+        match groups.iter_mut().find(|(l, _)| l == level) {
+            Some((_, exts)) => exts.push(ext),
+            None => groups.push((*level, vec![ext])),
+        }
+    }
+    for (level, mut exts) in groups {
+        exts.sort_unstable();
+        match exts.as_slice() {
+            [ext] => content.push_str(&format!("[*.{}]\n", ext)),
+            _ => content.push_str(&format!("[*.{{{}}}]\n", exts.join(","))),
+        }
+        content.push_str(&indent_lines(level));
+        content.push('\n');
+    }
+
+    content.push_str("[Makefile]\n");
+    content.push_str("indent_style = tab\n");
 
-[*.md]
-trim_trailing_whitespace = false
+    let content = opts.content(".editorconfig", content)?;
+    match fs::read_to_string(&output) {
+        Ok(existing) if opts.merge => {
+            let additions = editorconfig_additions(&existing, &content);
+            append_missing(&output, ".editorconfig", &existing, &additions, opts)
+        }
+        _ => write_file(&output, &content, opts),
+    }
+}
 
-[*.{yml,yaml}]
-indent_size = 2
+/// The `[glob]` sections of a generated `.editorconfig` whose header
+/// `existing` doesn't have.
+fn editorconfig_additions(existing: &str, generated: &str) -> String {
+    let headers: Vec<&str> = existing
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with('['))
+        .collect();
+    generated
+        .split("\n\n")
+        .filter(|block| {
+            let header = block.lines().next().unwrap_or("").trim();
+            header.starts_with('[') && !headers.contains(&header)
+        })
+        .map(|block| format!("{}\n\n", block.trim_end()))
+        .collect()
+}
 
-[*.json]
-indent_size = 2
+fn indent_lines((style, size): Level) -> String {
+    match (style, size) {
+        (Style::Tab, _) => "indent_style = tab\n".to_string(),
+        (Style::Space, size) => format!(
+            "indent_style = space\nindent_size = {}\n",
+            size.unwrap_or(4)
+        ),
+    }
+}
 
-[Makefile]
-indent_style = tab
-"#;
+/// Write `.gitattributes` with line-ending normalization and Git LFS
+/// patterns for the binary asset types in the tree, or add what's missing
+/// to an existing file. Normalization goes first so later patterns can
+/// override it.
+pub fn generate_gitattributes(
+    path: &Path,
+    result: &ScanResult,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
+    let output = path.join(".gitattributes");
+    let lfs_lines = lfs::attribute_lines(&result.lfs.asset_extensions, &result.lfs.tracked);
+    let normalize = format!(
+        "# Normalize line endings\n{}\n",
+        result.line_endings.attribute_lines()
+    );
+    let renormalize = result.lacks_line_ending_normalization();
+    let content = match fs::read_to_string(&output) {
+        Ok(_) if lfs_lines.is_empty() && !renormalize => {
+            println!(".gitattributes already tracks every asset type, skipping.");
+            return Ok(());
+        }
+        Ok(existing) => {
+            let mut content = if renormalize {
+                format!("{}{}", normalize, existing)
+            } else {
+                existing
+            };
+            if !lfs_lines.is_empty() {
+                if !content.is_empty() && !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push('\n');
+                content.push_str(&lfs_lines);
+            }
+            content
+        }
+        Err(_) => format!("{}{}", normalize, lfs_lines),
+    };
+    write_file(&output, &format!("{}\n", content.trim_end()), opts)?;
+    if renormalize {
+        println!(
+            "Run `git add --renormalize .` to convert the line endings of files already committed."
+        );
+    }
+    if !lfs_lines.is_empty() {
+        println!("Run `git lfs install`, then `git lfs migrate import --everything --include=\"<pattern>\"` to move files already committed.");
+    }
+    Ok(())
+}
+
+/// Build artifacts and dependency caches per build system, beyond what the
+/// language's `.gitignore` section covers.
+fn dockerignore_section(build: &BuildSystem) -> Option<(&'static str, &'static [&'static str])> {
+    let section: (&str, &[&str]) = match build {
+        BuildSystem::Gradle => ("Gradle", &[".gradle/", "build/"]),
+        BuildSystem::Yarn => ("Yarn", &[".yarn/cache/", ".pnp.*"]),
+        BuildSystem::Pnpm => ("pnpm", &[".pnpm-store/"]),
+        BuildSystem::Poetry | BuildSystem::Pip => ("Python tools", &[".tox/", ".pytest_cache/"]),
+        BuildSystem::Cmake => ("CMake", &["build/", "CMakeFiles/", "CMakeCache.txt"]),
+        BuildSystem::Bundler => ("Bundler", &[".bundle/", "vendor/bundle/"]),
+        BuildSystem::Composer => ("Composer", &["vendor/"]),
+        BuildSystem::Mix => ("Mix", &["_build/", "deps/"]),
+        BuildSystem::Cabal => ("Cabal", &["dist-newstyle/"]),
+        BuildSystem::Stack => ("Stack", &[".stack-work/"]),
+        BuildSystem::Zig => ("Zig", &["zig-out/", ".zig-cache/", "zig-cache/"]),
+        BuildSystem::Nimble => ("Nimble", &["nimcache/"]),
+        _ => return None,
+    };
+    Some(section)
+}
+
+/// A `.dockerignore` keeping version control directories, local env files
+/// and keys, editor files, and the detected stack's build output out of the
+/// build context. Skipped when there's no Dockerfile to use it.
+pub fn generate_dockerignore(
+    path: &Path,
+    result: &ScanResult,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
+    if !result
+        .container_files
+        .iter()
+        .any(|f| docker::is_dockerfile(f))
+    {
+        println!("No Dockerfile found, skipping .dockerignore.");
+        return Ok(());
+    }
+    let output = path.join(".dockerignore");
+    if !opts.merge && keep_existing(&output, ".dockerignore", opts) {
+        return Ok(());
+    }
+
+    let mut content = String::from("# Version control\n.git\n.gitignore\n.hg\n.svn\n\n");
+    content.push_str("# Local env files and keys\n.env\n.env.*\n*.pem\n*.key\n\n");
+    content.push_str("# Editors and OS\n.idea\n.vscode\n*.swp\n.DS_Store\n\n");
+
+    let sections = result
+        .languages
+        .iter()
+        .filter_map(gitignore_section)
+        .chain(result.build_systems.iter().filter_map(dockerignore_section));
+    // .dockerignore matches from the context root, so directories that
+    // appear anywhere get a `**/` prefix
+    let mut listed: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut named: Vec<&str> = Vec::new();
+    for (name, patterns) in sections {
+        if named.contains(&name) {
+            continue;
+        }
+        named.push(name);
+        let mut lines = Vec::new();
+        for p in patterns.iter() {
+            let bare = p.trim_end_matches('/');
+            let nested = bare.starts_with('*') || bare == "__pycache__" || bare == "node_modules";
+            let pattern = if nested {
+                format!("**/{}", bare)
+            } else {
+                bare.to_string()
+            };
+            if !listed.contains(&pattern) {
+                listed.push(pattern.clone());
+                lines.push(pattern);
+            }
+        }
+        if !lines.is_empty() {
+            content.push_str(&format!("# {}\n{}\n\n", name, lines.join("\n")));
+        }
+    }
 
-    write_file(&output, content, opts)
+    let content = opts.content(".dockerignore", format!("{}\n", content.trim_end()))?;
+    match fs::read_to_string(&output) {
+        Ok(existing) if opts.merge => {
+            let additions = gitignore_additions(&existing, &content);
+            append_missing(&output, ".dockerignore", &existing, &additions, opts)
+        }
+        _ => write_file(&output, &content, opts),
+    }
 }
 
 pub fn generate_code_of_conduct(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
     let output = path.join("CODE_OF_CONDUCT.md");
-    if output.exists() && !opts.dry_run {
-        println!("CODE_OF_CONDUCT.md already exists, skipping.");
+    if keep_existing(&output, "CODE_OF_CONDUCT.md", opts) {
         return Ok(());
     }
 
@@ -381,13 +974,13 @@ This Code of Conduct is adapted from the [Contributor Covenant](https://www.cont
 version 2.0.
 "#;
 
-    write_file(&output, content, opts)
+    let content = opts.content("CODE_OF_CONDUCT.md", content.to_string())?;
+    write_file(&output, &content, opts)
 }
 
 pub fn generate_security(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
     let output = path.join("SECURITY.md");
-    if output.exists() && !opts.dry_run {
-        println!("SECURITY.md already exists, skipping.");
+    if keep_existing(&output, "SECURITY.md", opts) {
         return Ok(());
     }
 
@@ -411,7 +1004,249 @@ If you discover a security vulnerability, please report it responsibly:
 We take security seriously and will respond promptly to valid reports.
 "#;
 
-    write_file(&output, content, opts)
+    let content = opts.content("SECURITY.md", content.to_string())?;
+    write_file(&output, &content, opts)
+}
+
+/// Issue templates written to `.github/ISSUE_TEMPLATE/`.
+const ISSUE_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "bug_report.md",
+        r#"---
+name: Bug report
+about: Report something that isn't working
+title: ""
+labels: bug
+assignees: ""
+---
+
+## Description
+
+A clear description of the bug.
+
+## Steps to Reproduce
+
+1.
+2.
+3.
+
+## Expected Behavior
+
+What you expected to happen.
+
+## Actual Behavior
+
+What happened instead, including any error output.
+
+## Environment
+
+- OS:
+- Version:
+"#,
+    ),
+    (
+        "feature_request.md",
+        r#"---
+name: Feature request
+about: Suggest an idea for this project
+title: ""
+labels: enhancement
+assignees: ""
+---
+
+## Problem
+
+What problem would this solve? For example: "I'm always frustrated when..."
+
+## Proposed Solution
+
+What you would like to happen.
+
+## Alternatives Considered
+
+Other solutions or workarounds you've tried.
+"#,
+    ),
+];
+
+/// Write a bug report and a feature request template to
+/// `.github/ISSUE_TEMPLATE/`, skipping any that already exist.
+pub fn generate_issue_templates(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
+    let dir = path.join(templates::ISSUE_DIR);
+    for (name, content) in ISSUE_TEMPLATES {
+        let output = dir.join(name);
+        let rel = format!("{}/{}", templates::ISSUE_DIR, name);
+        if keep_existing(&output, &rel, opts) {
+            continue;
+        }
+        if !opts.dry_run {
+            fs::create_dir_all(&dir)?;
+        }
+        write_file(&output, &opts.content(&rel, content.to_string())?, opts)?;
+    }
+    Ok(())
+}
+
+pub fn generate_pr_template(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
+    let output = path.join(templates::PR_FILES[0]);
+    if keep_existing(&output, templates::PR_FILES[0], opts) {
+        return Ok(());
+    }
+
+    let content = r#"## Summary
+
+What this change does and why.
+
+## Related Issues
+
+Closes #
+
+## Checklist
+
+- [ ] Tests added or updated
+- [ ] Documentation updated
+- [ ] CHANGELOG updated, if user-facing
+"#;
+
+    if let Some(dir) = output.parent().filter(|_| !opts.dry_run) {
+        fs::create_dir_all(dir)?;
+    }
+    let content = opts.content(templates::PR_FILES[0], content.to_string())?;
+    write_file(&output, &content, opts)
+}
+
+pub fn generate_codecov(path: &Path, opts: &GenerateOptions) -> io::Result<()> {
+    let output = path.join("codecov.yml");
+    if keep_existing(&output, "codecov.yml", opts) {
+        return Ok(());
+    }
+
+    let content = r#"# Codecov settings: https://docs.codecov.com/docs/codecov-yaml
+coverage:
+  precision: 2
+  round: down
+  range: "60...90"
+  status:
+    project:
+      default:
+        target: auto
+        threshold: 1%
+    patch:
+      default:
+        target: 80%
+
+comment:
+  layout: "diff, flags, files"
+  require_changes: true
+
+ignore:
+  - "tests/**"
+  - "examples/**"
+"#;
+
+    let content = opts.content("codecov.yml", content.to_string())?;
+    write_file(&output, &content, opts)
+}
+
+/// Dependabot ecosystem for a build system. npm covers Yarn and pnpm, and
+/// pip covers Poetry.
+fn dependabot_ecosystem(build_system: &BuildSystem) -> Option<&'static str> {
+    match build_system {
+        BuildSystem::Cargo => Some("cargo"),
+        BuildSystem::Npm | BuildSystem::Yarn | BuildSystem::Pnpm => Some("npm"),
+        BuildSystem::Pip | BuildSystem::Poetry => Some("pip"),
+        BuildSystem::Go => Some("gomod"),
+        BuildSystem::Maven => Some("maven"),
+        BuildSystem::Gradle => Some("gradle"),
+        BuildSystem::Msbuild => Some("nuget"),
+        BuildSystem::Bundler => Some("bundler"),
+        BuildSystem::Composer => Some("composer"),
+        BuildSystem::Mix => Some("mix"),
+        _ => None,
+    }
+}
+
+/// Write `.github/dependabot.yml` with weekly updates for each detected
+/// ecosystem, plus GitHub Actions and Docker when they're used.
+pub fn generate_dependabot(
+    path: &Path,
+    result: &ScanResult,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
+    let output = path.join(".github/dependabot.yml");
+    if keep_existing(&output, ".github/dependabot.yml", opts) {
+        return Ok(());
+    }
+    let mut ecosystems: Vec<&str> = Vec::new();
+    let extra = [
+        (
+            result.ci_systems.contains(&CISystem::GitHubActions),
+            "github-actions",
+        ),
+        (path.join("Dockerfile").is_file(), "docker"),
+    ];
+    let found = result
+        .build_systems
+        .iter()
+        .filter_map(dependabot_ecosystem)
+        .chain(extra.into_iter().filter(|(used, _)| *used).map(|(_, e)| e));
+    for ecosystem in found {
+        if !ecosystems.contains(&ecosystem) {
+            ecosystems.push(ecosystem);
+        }
+    }
+
+    let mut content = String::from(
+        "# Dependabot version updates: https://docs.github.com/code-security/dependabot/dependabot-version-updates
+version: 2
+updates:
+",
+    );
+    if ecosystems.is_empty() {
+        content.push_str("  # Add an entry per package ecosystem\n");
+    }
+    for ecosystem in ecosystems {
+        content.push_str(&format!(
+            "  - package-ecosystem: {}
+    directory: \"/\"
+    schedule:
+      interval: weekly
+",
+            ecosystem
+        ));
+    }
+    if let Some(dir) = output.parent().filter(|_| !opts.dry_run) {
+        fs::create_dir_all(dir)?;
+    }
+    let content = opts.content(".github/dependabot.yml", content)?;
+    write_file(&output, &content, opts)
+}
+
+/// Write a starter CI pipeline for `provider` that builds and tests each
+/// detected build system.
+pub fn generate_ci(
+    path: &Path,
+    result: &ScanResult,
+    provider: Provider,
+    opts: &GenerateOptions,
+) -> io::Result<()> {
+    let output = path.join(provider.path());
+    if keep_existing(&output, provider.path(), opts) {
+        return Ok(());
+    }
+    let branch = result
+        .git_history
+        .as_ref()
+        .and_then(|h| h.default_branch.as_deref())
+        .unwrap_or("main");
+    let content = opts.content(
+        provider.path(),
+        pipeline::render(provider, path, result, branch),
+    )?;
+    if let Some(dir) = output.parent().filter(|_| !opts.dry_run) {
+        fs::create_dir_all(dir)?;
+    }
+    write_file(&output, &content, opts)
 }
 
 pub fn generate_all(
@@ -436,7 +1271,7 @@ pub fn generate_all(
         generate_changelog(path, opts)?;
     }
     if !result.has_editorconfig {
-        generate_editorconfig(path, opts)?;
+        generate_editorconfig(path, result, opts)?;
     }
     if !result.has_code_of_conduct {
         generate_code_of_conduct(path, opts)?;
@@ -444,5 +1279,23 @@ pub fn generate_all(
     if !result.has_security {
         generate_security(path, opts)?;
     }
+    if !result.has_issue_template {
+        generate_issue_templates(path, opts)?;
+    }
+    if !result.has_pr_template {
+        generate_pr_template(path, opts)?;
+    }
+    if result.lacks_coverage() {
+        generate_codecov(path, opts)?;
+    }
+    if result.lacks_dependency_updates() {
+        generate_dependabot(path, result, opts)?;
+    }
+    if !result.lfs.untracked.is_empty() || result.lacks_line_ending_normalization() {
+        generate_gitattributes(path, result, opts)?;
+    }
+    if result.lacks_dockerignore() {
+        generate_dockerignore(path, result, opts)?;
+    }
     Ok(())
 }
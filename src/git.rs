@@ -0,0 +1,434 @@
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+#[cfg(feature = "git-history")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Window used for commit frequency.
+pub const ACTIVITY_DAYS: u64 = 90;
+
+/// Days without commits before the repository is reported as inactive (info).
+pub const INACTIVE_DAYS: u64 = 180;
+
+/// Days without commits before the repository is reported as stale (warning).
+pub const STALE_DAYS: u64 = 365;
+
+/// Commit history facts gathered by shelling out to `git`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GitHistory {
+    /// Date of the last commit on HEAD, `YYYY-MM-DD`.
+    pub last_commit_date: String,
+    pub days_since_last_commit: u64,
+    pub total_commits: u64,
+    /// Commits in the last `ACTIVITY_DAYS` days.
+    pub recent_commits: u64,
+    /// Distinct author emails across the history.
+    pub contributors: usize,
+    /// `origin`'s default branch if known, else the checked-out branch.
+    pub default_branch: Option<String>,
+    pub tags: usize,
+}
+
+impl GitHistory {
+    /// Average commits per week over the activity window.
+    pub fn commits_per_week(&self) -> f64 {
+        self.recent_commits as f64 * 7.0 / ACTIVITY_DAYS as f64
+    }
+}
+
+/// Inspect the history of the repository at `root`.
+///
+/// Returns `None` when git is unavailable, `root` has no valid `.git`, or
+/// the repository has no commits yet.
+#[cfg(feature = "git-history")]
+pub fn analyze(root: &Path) -> Option<GitHistory> {
+    let git_dir = root.join(".git");
+    let run = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .arg("--git-dir")
+            .arg(&git_dir)
+            .args(args)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let last: i64 = run(&["log", "-1", "--format=%ct"])?.parse().ok()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(last);
+    let count = |args: &[&str]| -> u64 { run(args).and_then(|s| s.parse().ok()).unwrap_or(0) };
+    let since = format!("--since={}.days", ACTIVITY_DAYS);
+    let mut emails: Vec<String> = run(&["log", "--format=%aE"])
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.to_lowercase())
+        .collect();
+    emails.sort();
+    emails.dedup();
+    let default_branch = run(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"])
+        .map(|b| b.trim_start_matches("origin/").to_string())
+        .or_else(|| run(&["symbolic-ref", "--short", "HEAD"]))
+        .filter(|b| !b.is_empty());
+    let tags = run(&["tag", "--list"])
+        .map(|t| t.lines().filter(|l| !l.is_empty()).count())
+        .unwrap_or(0);
+
+    Some(GitHistory {
+        last_commit_date: date_from_unix(last),
+        days_since_last_commit: ((now - last).max(0) / 86_400) as u64,
+        total_commits: count(&["rev-list", "--count", "HEAD"]),
+        recent_commits: count(&["rev-list", "--count", &since, "HEAD"]),
+        contributors: emails.len(),
+        default_branch,
+        tags,
+    })
+}
+
+/// A file touched by a change (staged, or since a base ref).
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    /// Path relative to the scanned root.
+    pub path: String,
+    /// Added lines in the new version, as `(first, count)` ranges.
+    pub added: Vec<(usize, usize)>,
+}
+
+impl ChangedFile {
+    /// Whether a finding at `line` is new in this change. Findings without
+    /// a line (large files) count for any changed file.
+    pub fn touches(&self, line: Option<usize>) -> bool {
+        match line {
+            Some(l) => self
+                .added
+                .iter()
+                .any(|(first, count)| (*first..first + count).contains(&l)),
+            None => true,
+        }
+    }
+}
+
+/// Largest history blobs reported by `large_blobs`.
+#[cfg(feature = "git-history")]
+pub const MAX_LARGE_BLOBS: usize = 10;
+
+/// A file version over the size threshold that's in history but not in
+/// `HEAD`, so every clone still downloads it.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryBlob {
+    /// Path the blob was first seen at, newest commit first.
+    pub path: String,
+    /// Uncompressed size in bytes.
+    pub size: u64,
+    /// Whether `HEAD` still has a (smaller or different) file at `path`.
+    pub path_in_head: bool,
+}
+
+/// Blobs larger than `threshold` bytes reachable from any ref but absent
+/// from `HEAD`, largest first, at most `MAX_LARGE_BLOBS`. Sizes come from
+/// the object store (packfiles and loose objects) without checking out
+/// anything. `None` when git is unavailable or `root` is not a repository.
+#[cfg(feature = "git-history")]
+pub fn large_blobs(root: &Path, threshold: u64) -> Option<Vec<HistoryBlob>> {
+    let run = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "core.quotePath=false"])
+            .args(args)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let mut large: Vec<(String, u64)> = run(&[
+        "cat-file",
+        "--batch-all-objects",
+        "--batch-check=%(objecttype) %(objectname) %(objectsize)",
+    ])?
+    .lines()
+    .filter_map(|line| {
+        let mut parts = line.split(' ');
+        let (kind, id, size) = (parts.next()?, parts.next()?, parts.next()?);
+        let size: u64 = size.parse().ok()?;
+        (kind == "blob" && size > threshold).then(|| (id.to_string(), size))
+    })
+    .collect();
+    if large.is_empty() {
+        return Some(Vec::new());
+    }
+    // Versions in HEAD are already reported by the large-file check
+    let mut head_ids = Vec::new();
+    let mut head_paths = Vec::new();
+    for entry in run(&["ls-tree", "-r", "-z", "HEAD"])
+        .unwrap_or_default()
+        .split('\0')
+    {
+        // <mode> <type> <id>\t<path>
+        if let Some((meta, path)) = entry.split_once('\t') {
+            head_ids.extend(meta.split(' ').nth(2).map(String::from));
+            head_paths.push(path.to_string());
+        }
+    }
+    large.retain(|(id, _)| !head_ids.contains(id));
+    large.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    // Objects only reachable from the reflog or nothing at all aren't
+    // cloned, so only blobs rev-list reaches from a ref count
+    let mut blobs = Vec::new();
+    let objects = run(&["rev-list", "--objects", "--all"])?;
+    for (id, size) in large {
+        let path = objects.lines().find_map(|line| {
+            line.strip_prefix(id.as_str())
+                .and_then(|rest| rest.strip_prefix(' '))
+        });
+        if let Some(path) = path {
+            blobs.push(HistoryBlob {
+                path: path.to_string(),
+                size,
+                path_in_head: head_paths.iter().any(|p| p == path),
+            });
+            if blobs.len() == MAX_LARGE_BLOBS {
+                break;
+            }
+        }
+    }
+    Some(blobs)
+}
+
+/// Abbreviated `HEAD` commit, when `root` is a git work tree with commits.
+pub fn head_commit(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string()).filter(|c| !c.is_empty())
+}
+
+/// Files added, copied, modified, or renamed in the index, from
+/// `git diff --cached`. `None` when `root` is not inside a git work tree.
+pub fn staged_files(root: &Path) -> Option<Vec<ChangedFile>> {
+    changed_files_since(root, &["--cached"])
+}
+
+/// Files named in a `--files-from` list: one path per line, or separated by
+/// NULs (`git ls-files -z`), relative to the scanned root. Every line of a
+/// listed file is in scope.
+pub fn listed_files(list: &str) -> Vec<ChangedFile> {
+    let separator = if list.contains('\0') { '\0' } else { '\n' };
+    let mut files: Vec<ChangedFile> = Vec::new();
+    for path in list.split(separator) {
+        let path = path.trim_end_matches('\r').replace('\\', "/");
+        let path = path.trim_start_matches("./");
+        if !path.is_empty() && !files.iter().any(|f| f.path == path) {
+            files.push(ChangedFile {
+                path: path.to_string(),
+                added: vec![(1, usize::MAX - 1)],
+            });
+        }
+    }
+    files
+}
+
+/// Files changed in the work tree since it forked from `base`, from
+/// `git diff --merge-base <base>` (what a pull request against `base` would
+/// show, plus uncommitted edits). `None` outside a work tree or for an
+/// unknown ref.
+pub fn changed_files(root: &Path, base: &str) -> Option<Vec<ChangedFile>> {
+    if base.starts_with('-') {
+        return None;
+    }
+    changed_files_since(root, &["--merge-base", base])
+}
+
+fn changed_files_since(root: &Path, selector: &[&str]) -> Option<Vec<ChangedFile>> {
+    let diff = |args: &[&str]| -> Option<String> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "core.quotePath=false", "diff"])
+            .args(selector)
+            .args(["--relative", "--no-color", "--no-ext-diff"])
+            .args(["--diff-filter=ACMR"])
+            .args(args)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        Some(String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+    let mut files: Vec<ChangedFile> = diff(&["--name-only", "-z"])?
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(|p| ChangedFile {
+            path: p.to_string(),
+            added: Vec::new(),
+        })
+        .collect();
+    // Binary files have no hunks; they keep an empty range list
+    let patch = diff(&["--unified=0"])?;
+    let mut current = None;
+    for line in patch.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            // Git appends a tab to names containing spaces
+            let path = path.trim_end_matches('\t');
+            let path = path.strip_prefix("b/").unwrap_or(path);
+            current = files.iter().position(|f| f.path == path);
+        } else if let (Some(i), Some(hunk)) = (current, line.strip_prefix("@@ ")) {
+            if let Some(range) = added_range(hunk) {
+                files[i].added.push(range);
+            }
+        }
+    }
+    Some(files)
+}
+
+/// `(first, count)` of the `+c,d` side of a hunk header body
+/// (`-a,b +c,d @@ ...`). `None` for pure deletions.
+fn added_range(hunk: &str) -> Option<(usize, usize)> {
+    let new = hunk.split_whitespace().find_map(|p| p.strip_prefix('+'))?;
+    let (first, count) = match new.split_once(',') {
+        Some((f, c)) => (f.parse().ok()?, c.parse().ok()?),
+        None => (new.parse().ok()?, 1),
+    };
+    (count > 0).then_some((first, count))
+}
+
+/// Convert a Unix timestamp to a UTC `YYYY-MM-DD` date.
+pub(crate) fn date_from_unix(secs: i64) -> String {
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let days = secs.div_euclid(86_400);
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + i64::from(m <= 2);
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+/// Parse a UTC timestamp such as `2024-05-01T12:34:56Z` into Unix seconds.
+pub(crate) fn unix_from_iso8601(s: &str) -> Option<i64> {
+    let (date, time) = s.trim_end_matches('Z').split_once('T')?;
+    let mut d = date.splitn(3, '-').map(|p| p.parse::<i64>().ok());
+    let (y, m, day) = (d.next()??, d.next()??, d.next()??);
+    let mut t = time.splitn(3, ':').map(|p| p.get(..2)?.parse::<i64>().ok());
+    let (hh, mm, ss) = (t.next()??, t.next()??, t.next()??);
+    if !(1..=12).contains(&m) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // Days-from-civil, the inverse of `date_from_unix`
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = if m > 2 { m - 3 } else { m + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+    Some(days * 86_400 + hh * 3600 + mm * 60 + ss)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{added_range, date_from_unix, listed_files, unix_from_iso8601};
+    #[cfg(feature = "git-history")]
+    use super::{analyze, large_blobs, staged_files};
+    #[cfg(feature = "git-history")]
+    use crate::testutil::TempDir;
+    #[cfg(feature = "git-history")]
+    use std::fs;
+    #[cfg(feature = "git-history")]
+    use std::path::Path;
+    #[cfg(feature = "git-history")]
+    use std::process::Command;
+
+    #[cfg(feature = "git-history")]
+    fn git(root: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(root)
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(date_from_unix(0), "1970-01-01");
+        assert_eq!(date_from_unix(951_782_400), "2000-02-29");
+        assert_eq!(date_from_unix(-86_400), "1969-12-31");
+        assert_eq!(unix_from_iso8601("2000-02-29T00:00:00Z"), Some(951_782_400));
+        assert_eq!(
+            unix_from_iso8601("2024-05-01T12:34:56.789Z"),
+            Some(1_714_566_896)
+        );
+        assert_eq!(unix_from_iso8601("2024-13-01T00:00:00Z"), None);
+        assert_eq!(unix_from_iso8601("yesterday"), None);
+    }
+
+    #[test]
+    fn hunk_headers() {
+        assert_eq!(added_range("-1,2 +3,4 @@ fn main()"), Some((3, 4)));
+        assert_eq!(added_range("-0,0 +1 @@"), Some((1, 1)));
+        assert_eq!(added_range("-5,2 +4,0 @@"), None);
+    }
+
+    #[test]
+    fn file_lists() {
+        let files = listed_files("./src/a.rs\r\nsrc\\b.rs\n\nsrc/a.rs\n");
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["src/a.rs", "src/b.rs"]);
+        assert!(files[0].touches(Some(10_000)) && files[0].touches(None));
+        let nul = listed_files("a b.txt\0c.txt\0");
+        assert_eq!(nul[0].path, "a b.txt");
+    }
+
+    #[test]
+    #[cfg(feature = "git-history")]
+    fn repository_history() {
+        let root = TempDir::new("git");
+        git(&root, &["init", "-q"]);
+        assert!(analyze(&root).is_none());
+        fs::write(root.join("a.txt"), "one\ntwo\n").unwrap();
+        fs::write(root.join("big.bin"), vec![b'x'; 4096]).unwrap();
+        git(&root, &["add", "."]);
+        git(&root, &["commit", "-qm", "first"]);
+        git(&root, &["rm", "-q", "big.bin"]);
+        git(&root, &["commit", "-qm", "drop"]);
+        git(&root, &["tag", "v1"]);
+
+        let history = analyze(&root).unwrap();
+        assert_eq!(history.total_commits, 2);
+        assert_eq!(history.recent_commits, 2);
+        assert_eq!(history.contributors, 1);
+        assert_eq!(history.tags, 1);
+        assert_eq!(history.days_since_last_commit, 0);
+
+        let blobs = large_blobs(&root, 1024).unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!((blobs[0].path.as_str(), blobs[0].size), ("big.bin", 4096));
+        assert!(!blobs[0].path_in_head);
+
+        fs::write(root.join("a.txt"), "one\nTWO\nthree\n").unwrap();
+        git(&root, &["add", "a.txt"]);
+        let staged = staged_files(&root).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].added, [(2, 2)]);
+        assert!(staged[0].touches(Some(3)) && !staged[0].touches(Some(1)));
+    }
+}
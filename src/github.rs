@@ -0,0 +1,350 @@
+use crate::base64;
+use crate::git::unix_from_iso8601;
+use crate::remote::{self, RemoteInfo, Response};
+use serde::Serialize;
+use serde_json::Value;
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+/// Environment variables checked, in order, for a GitHub API token.
+pub const TOKEN_VARS: &[&str] = &["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// Completed workflow runs on the default branch considered for CI health.
+pub const RUN_WINDOW: usize = 30;
+
+/// Pass rate (percent) below which CI is reported as flaky.
+pub const MIN_PASS_RATE: u8 = 80;
+
+/// Workflow run history on the default branch, from the GitHub API.
+#[derive(Debug, Clone, Serialize)]
+pub struct CiHealth {
+    pub branch: String,
+    /// Completed runs looked at (at most `RUN_WINDOW`).
+    pub runs: usize,
+    pub passed: usize,
+    /// Runs that failed or timed out; cancelled and skipped runs count as neither.
+    pub failed: usize,
+    /// Runs that only finished after a re-run.
+    pub reruns: usize,
+    pub avg_duration_secs: u64,
+    /// Conclusion of the most recent completed run (`success`, `failure`, ...).
+    pub last_conclusion: String,
+}
+
+impl CiHealth {
+    /// Share of passed runs among passed and failed ones, in percent.
+    pub fn pass_rate(&self) -> u8 {
+        let decided = self.passed + self.failed;
+        if decided == 0 {
+            return 100;
+        }
+        (self.passed * 100 / decided) as u8
+    }
+
+    /// The latest run on the default branch failed.
+    pub fn is_red(&self) -> bool {
+        matches!(self.last_conclusion.as_str(), "failure" | "timed_out")
+    }
+
+    /// Low pass rate, or at least one run in ten needed a re-run.
+    pub fn is_flaky(&self) -> bool {
+        self.pass_rate() < MIN_PASS_RATE || (self.runs > 0 && self.reruns * 10 >= self.runs)
+    }
+}
+
+/// Open Dependabot alerts fetched (one page).
+pub const ALERT_PAGE: usize = 100;
+
+/// API token from the environment, if any.
+pub fn token() -> Option<String> {
+    TOKEN_VARS
+        .iter()
+        .filter_map(|v| env::var(v).ok())
+        .find(|t| !t.trim().is_empty())
+}
+
+/// `owner/repo` of the `origin` remote, when it points at GitHub.
+pub fn repo_slug(root: &Path) -> Option<String> {
+    parse_slug(&remote::origin_url(root)?)
+}
+
+/// `owner/repo` from an HTTPS, SSH, or scp-style GitHub remote URL.
+fn parse_slug(url: &str) -> Option<String> {
+    let rest = [
+        "https://github.com/",
+        "http://github.com/",
+        "ssh://git@github.com/",
+    ]
+    .iter()
+    .find_map(|p| url.strip_prefix(p))
+    .or_else(|| url.strip_prefix("git@github.com:"))?;
+    let rest = rest.trim_end_matches('/');
+    let rest = rest.strip_suffix(".git").unwrap_or(rest);
+    let (owner, repo) = rest.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some(format!("{}/{}", owner, repo))
+}
+
+/// Fetch recent workflow runs on the repository's default branch.
+pub fn fetch_ci_health(slug: &str, token: &str) -> Result<CiHealth, String> {
+    let repo = api_get(&format!("/repos/{}", slug), token)?;
+    let branch = repo["default_branch"]
+        .as_str()
+        .ok_or("repository has no default branch")?
+        .to_string();
+    let runs = api_get(
+        &format!(
+            "/repos/{}/actions/runs?branch={}&status=completed&per_page={}",
+            slug, branch, RUN_WINDOW
+        ),
+        token,
+    )?;
+    let runs = runs["workflow_runs"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+
+    let mut health = CiHealth {
+        branch,
+        runs: runs.len(),
+        passed: 0,
+        failed: 0,
+        reruns: 0,
+        avg_duration_secs: 0,
+        last_conclusion: String::new(),
+    };
+    let mut total_secs = 0;
+    let mut timed = 0;
+    // The API returns the newest run first
+    for (i, run) in runs.iter().enumerate() {
+        let conclusion = run["conclusion"].as_str().unwrap_or("");
+        if i == 0 {
+            health.last_conclusion = conclusion.to_string();
+        }
+        match conclusion {
+            "success" => health.passed += 1,
+            "failure" | "timed_out" => health.failed += 1,
+            _ => {}
+        }
+        if run["run_attempt"].as_u64().unwrap_or(1) > 1 {
+            health.reruns += 1;
+        }
+        let started = run["run_started_at"].as_str().and_then(unix_from_iso8601);
+        let updated = run["updated_at"].as_str().and_then(unix_from_iso8601);
+        if let (Some(s), Some(u)) = (started, updated) {
+            if u >= s {
+                total_secs += (u - s) as u64;
+                timed += 1;
+            }
+        }
+    }
+    health.avg_duration_secs = total_secs.checked_div(timed).unwrap_or(0);
+    Ok(health)
+}
+
+/// Fetch default branch protection, open Dependabot alerts, the detected
+/// license, and the archived flag.
+pub fn fetch_remote(slug: &str, token: &str) -> Result<RemoteInfo, String> {
+    let repo = api_get(&format!("/repos/{}", slug), token)?;
+    let branch = repo["default_branch"]
+        .as_str()
+        .ok_or("repository has no default branch")?
+        .to_string();
+    // 404 means unprotected; 403 means the token isn't an admin
+    let protection = api_get_optional(
+        &format!("/repos/{}/branches/{}/protection", slug, branch),
+        token,
+    )?;
+    let protected = match protection.status {
+        200 => Some(true),
+        404 => Some(false),
+        _ => None,
+    };
+    let required_reviews = protection.body.as_ref().map(|p| {
+        p["required_pull_request_reviews"]["required_approving_review_count"]
+            .as_u64()
+            .unwrap_or(0)
+    });
+    // 403 when alerts are disabled or the token lacks the scope
+    let alerts = api_get_optional(
+        &format!(
+            "/repos/{}/dependabot/alerts?state=open&per_page={}",
+            slug, ALERT_PAGE
+        ),
+        token,
+    )?
+    .body
+    .and_then(|a| a.as_array().cloned());
+    let license = repo["license"]["spdx_id"]
+        .as_str()
+        .filter(|id| *id != "NOASSERTION")
+        .map(String::from);
+    Ok(RemoteInfo {
+        host: "github",
+        slug: slug.to_string(),
+        default_branch: branch,
+        archived: repo["archived"].as_bool().unwrap_or(false),
+        protected,
+        required_reviews,
+        dependabot_alerts: alerts.as_ref().map(Vec::len),
+        severe_alerts: alerts.as_ref().map(|a| {
+            a.iter()
+                .filter(|alert| {
+                    matches!(
+                        alert["security_advisory"]["severity"].as_str(),
+                        Some("critical" | "high")
+                    )
+                })
+                .count()
+        }),
+        license,
+        issue_templates: None,
+        merge_request_templates: None,
+    })
+}
+
+/// A repository of an organization or user, from the GitHub API.
+#[derive(Debug, Clone)]
+pub struct OrgRepo {
+    /// `owner/repo`.
+    pub full_name: String,
+    pub clone_url: String,
+    pub archived: bool,
+    pub fork: bool,
+}
+
+/// Repositories listed per API page.
+const PAGE_SIZE: usize = 100;
+
+/// Every repository of `owner`, an organization or else a user, sorted by
+/// name. Without a token only public repositories are listed (and the API
+/// allows 60 requests an hour).
+pub fn list_repos(owner: &str, token: &str) -> Result<Vec<OrgRepo>, String> {
+    let mut kind = "orgs";
+    let mut repos = Vec::new();
+    let mut page = 1;
+    loop {
+        let path = format!(
+            "/{}/{}/repos?per_page={}&page={}&type=all",
+            kind, owner, PAGE_SIZE, page
+        );
+        let response = api_get_optional(&path, token)?;
+        if response.status == 404 && page == 1 {
+            if kind == "users" {
+                return Err(format!("no organization or user named '{}'", owner));
+            }
+            kind = "users";
+            continue;
+        }
+        let body = response.require(&path)?;
+        let items = body.as_array().cloned().unwrap_or_default();
+        repos.extend(items.iter().filter_map(|r| {
+            Some(OrgRepo {
+                full_name: r["full_name"].as_str()?.to_string(),
+                clone_url: r["clone_url"].as_str()?.to_string(),
+                archived: r["archived"].as_bool().unwrap_or(false),
+                fork: r["fork"].as_bool().unwrap_or(false),
+            })
+        }));
+        if items.len() < PAGE_SIZE {
+            break;
+        }
+        page += 1;
+    }
+    repos.sort_by(|a, b| a.full_name.cmp(&b.full_name));
+    Ok(repos)
+}
+
+/// `git clone --depth 1` of `url` into `dest`, authenticated with `token`
+/// when one is given.
+///
+/// The token goes in as an `http.extraHeader` through `GIT_CONFIG_*`
+/// variables, so it stays out of process lists and `.git/config`.
+pub fn shallow_clone(url: &str, dest: &Path, token: &str) -> Result<(), String> {
+    let mut git = Command::new("git");
+    git.args(["clone", "--quiet", "--depth", "1", "--single-branch"])
+        .arg(url)
+        .arg(dest)
+        .env("GIT_TERMINAL_PROMPT", "0");
+    if !token.trim().is_empty() {
+        let credentials = base64::encode(format!("x-access-token:{}", token.trim()).as_bytes());
+        git.env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env(
+                "GIT_CONFIG_VALUE_0",
+                format!("Authorization: Basic {}", credentials),
+            );
+    }
+    let output = git
+        .output()
+        .map_err(|e| format!("could not run git: {}", e))?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        let reason = err.lines().find(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(format!("git clone failed: {}", reason.trim()));
+    }
+    Ok(())
+}
+
+/// GET a GitHub REST endpoint.
+fn api_get(path: &str, token: &str) -> Result<Value, String> {
+    api_get_optional(path, token)?.require(path)
+}
+
+/// GET a GitHub REST endpoint, passing 403 and 404 back to the caller.
+///
+/// `GITHUB_API_URL` (set by Actions on GitHub Enterprise) overrides the host.
+fn api_get_optional(path: &str, token: &str) -> Result<Response, String> {
+    let base = env::var("GITHUB_API_URL").unwrap_or_else(|_| "https://api.github.com".into());
+    let mut headers = String::from("Accept: application/vnd.github+json\nUser-Agent: repomedic\n");
+    if !token.trim().is_empty() {
+        headers.push_str(&format!("Authorization: Bearer {}\n", token.trim()));
+    }
+    remote::get(&base, path, &headers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_slug, CiHealth};
+
+    fn health(runs: usize, passed: usize, failed: usize, reruns: usize, last: &str) -> CiHealth {
+        CiHealth {
+            branch: "main".to_string(),
+            runs,
+            passed,
+            failed,
+            reruns,
+            avg_duration_secs: 60,
+            last_conclusion: last.to_string(),
+        }
+    }
+
+    #[test]
+    fn slugs() {
+        for url in [
+            "https://github.com/acme/tool.git",
+            "https://github.com/acme/tool/",
+            "git@github.com:acme/tool.git",
+            "ssh://git@github.com/acme/tool",
+        ] {
+            assert_eq!(parse_slug(url).as_deref(), Some("acme/tool"), "{}", url);
+        }
+        assert_eq!(parse_slug("https://gitlab.com/acme/tool"), None);
+        assert_eq!(parse_slug("https://github.com/acme"), None);
+        assert_eq!(parse_slug("https://github.com/acme/tool/tree/main"), None);
+    }
+
+    #[test]
+    fn ci_health() {
+        let steady = health(30, 29, 1, 0, "success");
+        assert_eq!(steady.pass_rate(), 96);
+        assert!(!steady.is_flaky() && !steady.is_red());
+        assert!(health(10, 7, 3, 0, "failure").is_flaky());
+        assert!(health(10, 10, 0, 1, "success").is_flaky());
+        assert!(health(5, 0, 0, 0, "timed_out").is_red());
+        assert_eq!(health(2, 0, 0, 0, "cancelled").pass_rate(), 100);
+    }
+}
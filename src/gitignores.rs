@@ -0,0 +1,282 @@
+use crate::scanner::{BuildSystem, Language};
+
+/// A `.gitignore` template from the github/gitignore collection, bundled so
+/// `generate` works offline.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Template {
+    /// Name accepted by `--gitignore-template`, as the collection's file
+    /// name gives it (`Node`, `macOS`, `VisualStudioCode`).
+    pub name: &'static str,
+    pub text: &'static str,
+}
+
+/// Templates available for generation: languages and build tools first,
+/// then operating systems and editors.
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "Rust",
+        text: include_str!("gitignores/Rust.gitignore"),
+    },
+    Template {
+        name: "Node",
+        text: include_str!("gitignores/Node.gitignore"),
+    },
+    Template {
+        name: "Python",
+        text: include_str!("gitignores/Python.gitignore"),
+    },
+    Template {
+        name: "Go",
+        text: include_str!("gitignores/Go.gitignore"),
+    },
+    Template {
+        name: "Java",
+        text: include_str!("gitignores/Java.gitignore"),
+    },
+    Template {
+        name: "Kotlin",
+        text: include_str!("gitignores/Kotlin.gitignore"),
+    },
+    Template {
+        name: "Scala",
+        text: include_str!("gitignores/Scala.gitignore"),
+    },
+    Template {
+        name: "Gradle",
+        text: include_str!("gitignores/Gradle.gitignore"),
+    },
+    Template {
+        name: "Maven",
+        text: include_str!("gitignores/Maven.gitignore"),
+    },
+    Template {
+        name: "VisualStudio",
+        text: include_str!("gitignores/VisualStudio.gitignore"),
+    },
+    Template {
+        name: "C",
+        text: include_str!("gitignores/C.gitignore"),
+    },
+    Template {
+        name: "C++",
+        text: include_str!("gitignores/C++.gitignore"),
+    },
+    Template {
+        name: "CMake",
+        text: include_str!("gitignores/CMake.gitignore"),
+    },
+    Template {
+        name: "Ruby",
+        text: include_str!("gitignores/Ruby.gitignore"),
+    },
+    Template {
+        name: "Composer",
+        text: include_str!("gitignores/Composer.gitignore"),
+    },
+    Template {
+        name: "Swift",
+        text: include_str!("gitignores/Swift.gitignore"),
+    },
+    Template {
+        name: "Haskell",
+        text: include_str!("gitignores/Haskell.gitignore"),
+    },
+    Template {
+        name: "Elixir",
+        text: include_str!("gitignores/Elixir.gitignore"),
+    },
+    Template {
+        name: "Zig",
+        text: include_str!("gitignores/Zig.gitignore"),
+    },
+    Template {
+        name: "Nim",
+        text: include_str!("gitignores/Nim.gitignore"),
+    },
+    Template {
+        name: "Lua",
+        text: include_str!("gitignores/Lua.gitignore"),
+    },
+    Template {
+        name: "R",
+        text: include_str!("gitignores/R.gitignore"),
+    },
+    Template {
+        name: "Perl",
+        text: include_str!("gitignores/Perl.gitignore"),
+    },
+    Template {
+        name: "Dart",
+        text: include_str!("gitignores/Dart.gitignore"),
+    },
+    Template {
+        name: "Terraform",
+        text: include_str!("gitignores/Terraform.gitignore"),
+    },
+    Template {
+        name: "macOS",
+        text: include_str!("gitignores/macOS.gitignore"),
+    },
+    Template {
+        name: "Windows",
+        text: include_str!("gitignores/Windows.gitignore"),
+    },
+    Template {
+        name: "Linux",
+        text: include_str!("gitignores/Linux.gitignore"),
+    },
+    Template {
+        name: "VisualStudioCode",
+        text: include_str!("gitignores/VisualStudioCode.gitignore"),
+    },
+    Template {
+        name: "JetBrains",
+        text: include_str!("gitignores/JetBrains.gitignore"),
+    },
+    Template {
+        name: "Vim",
+        text: include_str!("gitignores/Vim.gitignore"),
+    },
+    Template {
+        name: "Emacs",
+        text: include_str!("gitignores/Emacs.gitignore"),
+    },
+];
+
+impl Template {
+    /// The template called `name`, ignoring case.
+    pub fn find(name: &str) -> Option<&'static Template> {
+        TEMPLATES.iter().find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Parse a `--gitignore-template` value, listing the valid names on
+    /// error.
+    pub fn parse(name: &str) -> Result<&'static Template, String> {
+        Template::find(name.trim()).ok_or_else(|| {
+            let names: Vec<&str> = TEMPLATES.iter().map(|t| t.name).collect();
+            format!(
+                "unknown gitignore template '{}' (expected one of: {})",
+                name,
+                names.join(", ")
+            )
+        })
+    }
+}
+
+/// The template for a detected language.
+pub fn for_language(lang: &Language) -> Option<&'static Template> {
+    let name = match lang {
+        Language::Rust => "Rust",
+        Language::Python => "Python",
+        Language::JavaScript | Language::TypeScript | Language::Vue | Language::Svelte => "Node",
+        Language::Go => "Go",
+        Language::Java => "Java",
+        Language::Kotlin => "Kotlin",
+        Language::Scala => "Scala",
+        Language::CSharp | Language::FSharp => "VisualStudio",
+        Language::Cpp => "C++",
+        Language::C => "C",
+        Language::Ruby => "Ruby",
+        Language::Php => "Composer",
+        Language::Swift | Language::ObjectiveC => "Swift",
+        Language::Haskell => "Haskell",
+        Language::Elixir => "Elixir",
+        Language::Zig => "Zig",
+        Language::Nim => "Nim",
+        Language::Lua => "Lua",
+        Language::R => "R",
+        Language::Perl => "Perl",
+        Language::Dart => "Dart",
+        Language::Groovy => "Gradle",
+        Language::Hcl => "Terraform",
+        Language::Crystal
+        | Language::Shell
+        | Language::PowerShell
+        | Language::Julia
+        | Language::Clojure
+        | Language::Erlang
+        | Language::OCaml
+        | Language::Batchfile
+        | Language::Assembly
+        | Language::Sql
+        | Language::Dockerfile
+        | Language::Yaml
+        | Language::Json => return None,
+    };
+    Template::find(name)
+}
+
+/// The template for a detected build system whose output the language
+/// template doesn't cover.
+pub fn for_build_system(build: &BuildSystem) -> Option<&'static Template> {
+    let name = match build {
+        BuildSystem::Maven => "Maven",
+        BuildSystem::Gradle => "Gradle",
+        BuildSystem::Cmake => "CMake",
+        _ => return None,
+    };
+    Template::find(name)
+}
+
+/// Templates for the detected languages and build systems, then the
+/// `named` ones, each once.
+pub fn select(
+    languages: &[Language],
+    build_systems: &[BuildSystem],
+    named: &[&'static Template],
+) -> Vec<&'static Template> {
+    let detected = languages
+        .iter()
+        .filter_map(for_language)
+        .chain(build_systems.iter().filter_map(for_build_system));
+    let mut selected: Vec<&'static Template> = Vec::new();
+    for template in detected.chain(named.iter().copied()) {
+        if !selected.contains(&template) {
+            selected.push(template);
+        }
+    }
+    selected
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select, Template, TEMPLATES};
+    use crate::scanner::{BuildSystem, Language};
+
+    fn names(templates: &[&Template]) -> Vec<&'static str> {
+        templates.iter().map(|t| t.name).collect()
+    }
+
+    #[test]
+    fn bundled_templates() {
+        for template in TEMPLATES {
+            assert!(!template.text.trim().is_empty(), "{}", template.name);
+        }
+    }
+
+    #[test]
+    fn template_names() {
+        assert_eq!(Template::parse(" macos ").unwrap().name, "macOS");
+        let err = Template::parse("Cobol").unwrap_err();
+        assert!(err.starts_with("unknown gitignore template 'Cobol' (expected one of: Rust, Node,"));
+    }
+
+    #[test]
+    fn selection() {
+        let named = [
+            Template::find("Node").unwrap(),
+            Template::find("macOS").unwrap(),
+        ];
+        let selected = select(
+            &[
+                Language::TypeScript,
+                Language::JavaScript,
+                Language::Shell,
+                Language::Java,
+            ],
+            &[BuildSystem::Npm, BuildSystem::Maven],
+            &named,
+        );
+        assert_eq!(names(&selected), ["Node", "Java", "Maven", "macOS"]);
+    }
+}
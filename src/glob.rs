@@ -0,0 +1,168 @@
+//! Glob matching shared by `.gitignore`-style patterns (ignore files,
+//! `.gitattributes`, CODEOWNERS, `.editorconfig`) and suppression paths.
+
+/// Gitignore-style glob: `*` and `?` stop at `/`, `**` crosses directories
+/// (`**/` matches zero or more whole ones), `[a-z]`/`[!x]` are character
+/// classes, and `\` escapes.
+pub fn glob_match(pat: &[u8], s: &[u8]) -> bool {
+    matches(pat, s, false)
+}
+
+/// Path glob for suppressions and custom rules: like `glob_match`, but `*`
+/// crosses directories too, and a leading `./` on either side is ignored.
+pub fn path_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim_start_matches("./");
+    let path = path.trim_start_matches("./");
+    matches(pattern.as_bytes(), path.as_bytes(), true)
+}
+
+/// Iterative star backtracking: on a mismatch only the most recent star
+/// takes one more byte, so matching needs no recursion and no pattern can
+/// make it blow up.
+fn matches(pat: &[u8], s: &[u8], star_crosses: bool) -> bool {
+    // Where to resume after the last `*`: (pattern index, subject index)
+    let mut star: Option<(usize, usize)> = None;
+    // The same for the last `**`, and whether it only spans whole
+    // directories (`**/`)
+    let mut deep: Option<(usize, usize, bool)> = None;
+    let (mut p, mut i) = (0, 0);
+    loop {
+        if p < pat.len() && pat[p] == b'*' {
+            if pat.get(p + 1) == Some(&b'*') || star_crosses {
+                let run = if pat.get(p + 1) == Some(&b'*') { 2 } else { 1 };
+                let dirs = run == 2 && pat.get(p + 2) == Some(&b'/');
+                p += run + usize::from(dirs);
+                deep = Some((p, i, dirs));
+                star = None;
+            } else {
+                p += 1;
+                star = Some((p, i));
+            }
+            continue;
+        }
+        if p == pat.len() && i == s.len() {
+            return true;
+        }
+        if let Some(len) = s.get(i).and_then(|&c| step(&pat[p..], c)) {
+            p += len;
+            i += 1;
+            continue;
+        }
+        // Mismatch: give the last star one more byte, if it can take it
+        if let Some((sp, si)) = star {
+            if s.get(si).is_some_and(|&c| c != b'/') {
+                star = Some((sp, si + 1));
+                (p, i) = (sp, si + 1);
+                continue;
+            }
+            star = None;
+        }
+        let Some((dp, di, dirs)) = deep else {
+            return false;
+        };
+        let next = if dirs {
+            s[di..].iter().position(|&c| c == b'/').map(|k| di + k + 1)
+        } else {
+            (di < s.len()).then_some(di + 1)
+        };
+        let Some(next) = next else {
+            return false;
+        };
+        deep = Some((dp, next, dirs));
+        (p, i) = (dp, next);
+    }
+}
+
+/// Match `c` against the single-byte token at the start of `pat` (not a
+/// star). Returns the pattern bytes consumed, or `None` on a mismatch.
+fn step(pat: &[u8], c: u8) -> Option<usize> {
+    match *pat.first()? {
+        b'?' => (c != b'/').then_some(1),
+        b'[' => match match_class(&pat[1..], c) {
+            Some((true, len)) => Some(1 + len),
+            Some((false, _)) => None,
+            // Unterminated class: treat `[` literally
+            None => (c == b'[').then_some(1),
+        },
+        b'\\' if pat.len() > 1 => (c == pat[1]).then_some(2),
+        p => (c == p).then_some(1),
+    }
+}
+
+/// Match `c` against a class body (after `[`). Returns (matched, bytes consumed).
+fn match_class(body: &[u8], c: u8) -> Option<(bool, usize)> {
+    let (negated, start) = match body.first() {
+        Some(b'!') | Some(b'^') => (true, 1),
+        _ => (false, 0),
+    };
+    let mut i = start;
+    let mut matched = false;
+    while i < body.len() {
+        if body[i] == b']' && i > start {
+            return Some((matched != negated, i + 1));
+        }
+        if i + 2 < body.len() && body[i + 1] == b'-' && body[i + 2] != b']' {
+            matched |= (body[i]..=body[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            matched |= body[i] == c;
+            i += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, path_match};
+
+    fn globs(pattern: &str, path: &str) -> bool {
+        glob_match(pattern.as_bytes(), path.as_bytes())
+    }
+
+    #[test]
+    fn globs_stop_at_slashes() {
+        assert!(globs("*.log", "debug.log"));
+        assert!(!globs("*.log", "logs/debug.log"));
+        assert!(globs("file?.txt", "file1.txt"));
+        assert!(!globs("a?b", "a/b"));
+        assert!(globs("**/build", "a/b/build"));
+        assert!(globs("**/build", "build"));
+        assert!(!globs("**/build", "a/rebuild"));
+        assert!(globs("docs/**", "docs/a/b.md"));
+        assert!(globs("a/**/z", "a/z"));
+        assert!(globs("a/**/z", "a/b/c/z"));
+        assert!(globs("a/**/*.rs", "a/b/c/main.rs"));
+        assert!(!globs("a/**/*.rs", "a/b/c/main.rs/x"));
+        assert!(globs("*a*b", "xaxxb"));
+        assert!(!globs("*a*b", "xa/b"));
+    }
+
+    #[test]
+    fn classes_and_escapes() {
+        assert!(globs("[a-c].txt", "b.txt"));
+        assert!(!globs("[!a-c].txt", "b.txt"));
+        assert!(globs("[^a-c].txt", "d.txt"));
+        assert!(globs(r"\*.md", "*.md"));
+        assert!(!globs(r"\*.md", "a.md"));
+        assert!(globs("[abc", "[abc"));
+    }
+
+    #[test]
+    fn paths_cross_directories() {
+        assert!(path_match("./src/*.rs", "src/main.rs"));
+        assert!(path_match("*secret*", "config/secrets.yaml"));
+        assert!(!path_match("src/*.rs", "lib/main.rs"));
+        assert!(path_match("src/*", "src/a/b.rs"));
+    }
+
+    #[test]
+    fn pathological_patterns_stay_fast() {
+        let path = "a".repeat(5_000);
+        let pattern = "*a".repeat(50) + "b";
+        assert!(!globs(&pattern, &path));
+        assert!(!path_match(&pattern, &path));
+        let deep = "a/".repeat(2_000) + "b";
+        assert!(!globs(&"**/a".repeat(20), &deep));
+    }
+}
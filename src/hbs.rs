@@ -0,0 +1,315 @@
+/// A template variable: text, or a list for `{{#each}}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    Str(String),
+    List(Vec<String>),
+}
+
+impl Value {
+    /// `{{#if}}` is true for non-empty text and lists.
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Str(s) => !s.is_empty(),
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var(String),
+    If(String, Vec<Node>, Vec<Node>),
+    Each(String, Vec<Node>),
+}
+
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    /// The trimmed text between `{{` and `}}`.
+    Tag(String),
+}
+
+/// Render a Handlebars-style `template` with `vars`.
+///
+/// The subset covers what file templates need: `{{name}}` (lists join with
+/// `, `), `{{#if name}}…{{else}}…{{/if}}`, `{{#each name}}…{{this}}…{{/each}}`,
+/// and `{{! comments }}`. A block tag alone on its line takes the line with
+/// it, so blocks don't leave blank lines behind.
+pub fn render(template: &str, vars: &[(&str, Value)]) -> Result<String, String> {
+    let mut tokens = tokenize(template)?.into_iter();
+    let nodes = parse(&mut tokens, None)?;
+    let mut out = String::new();
+    emit(&nodes, vars, None, &mut out)?;
+    Ok(out)
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let line = template[..template.len() - rest.len() + start]
+            .matches('\n')
+            .count()
+            + 1;
+        let end = rest[start..]
+            .find("}}")
+            .ok_or(format!("line {}: unclosed {{{{", line))?;
+        tokens.push(Token::Text(rest[..start].to_string()));
+        tokens.push(Token::Tag(rest[start + 2..start + end].trim().to_string()));
+        rest = &rest[start + end + 2..];
+    }
+    tokens.push(Token::Text(rest.to_string()));
+    strip_standalone(&mut tokens);
+    Ok(tokens)
+}
+
+/// Drop the indentation and line break around block tags that sit alone on
+/// their line.
+fn strip_standalone(tokens: &mut [Token]) {
+    for i in 0..tokens.len() {
+        let Token::Tag(tag) = &tokens[i] else {
+            continue;
+        };
+        if !(tag.starts_with(['#', '/', '!']) || tag == "else") {
+            continue;
+        }
+        let before = match i.checked_sub(1).map(|j| &tokens[j]) {
+            Some(Token::Text(t)) => {
+                let tail = t.rsplit('\n').next().unwrap_or("");
+                // Mid-template text needs a line break before the tag
+                (t.contains('\n') || i == 1) && tail.trim().is_empty()
+            }
+            _ => false,
+        };
+        let after = match tokens.get(i + 1) {
+            Some(Token::Text(t)) => {
+                let head = t.split('\n').next().unwrap_or("");
+                head.trim().is_empty() && (t.contains('\n') || i + 2 == tokens.len())
+            }
+            _ => false,
+        };
+        if !(before && after) {
+            continue;
+        }
+        if let Token::Text(t) = &mut tokens[i - 1] {
+            let keep = t.rfind('\n').map_or(0, |n| n + 1);
+            t.truncate(keep);
+        }
+        if let Token::Text(t) = &mut tokens[i + 1] {
+            let skip = t.find('\n').map_or(t.len(), |n| n + 1);
+            t.drain(..skip);
+        }
+    }
+}
+
+/// Parse tokens up to the `{{/closing}}` tag (or the end for `None`).
+fn parse(
+    tokens: &mut impl Iterator<Item = Token>,
+    closing: Option<&str>,
+) -> Result<Vec<Node>, String> {
+    let mut nodes = Vec::new();
+    while let Some(token) = tokens.next() {
+        let tag = match token {
+            Token::Text(text) => {
+                nodes.push(Node::Text(text));
+                continue;
+            }
+            Token::Tag(tag) => tag,
+        };
+        if tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            return match closing {
+                Some(open) if open == name.trim() => Ok(nodes),
+                Some(open) => Err(format!("{{{{/{}}}}} closes {{{{#{}}}}}", name.trim(), open)),
+                None => Err(format!("{{{{/{}}}}} without an opening tag", name.trim())),
+            };
+        }
+        if tag == "else" {
+            return Err("{{else}} outside {{#if}}".to_string());
+        }
+        let Some(block) = tag.strip_prefix('#') else {
+            nodes.push(Node::Var(tag));
+            continue;
+        };
+        let (helper, name) = block.split_once(char::is_whitespace).unwrap_or((block, ""));
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(format!("{{{{#{}}}}} needs a variable", helper));
+        }
+        match helper {
+            "if" => {
+                let (then, otherwise) = parse_if(tokens)?;
+                nodes.push(Node::If(name, then, otherwise));
+            }
+            "each" => nodes.push(Node::Each(name, parse(tokens, Some("each"))?)),
+            _ => return Err(format!("unknown block helper '#{}'", helper)),
+        }
+    }
+    match closing {
+        Some(open) => Err(format!("{{{{#{}}}}} is never closed", open)),
+        None => Ok(nodes),
+    }
+}
+
+/// The branches of an `{{#if}}` block, split at `{{else}}`.
+fn parse_if(tokens: &mut impl Iterator<Item = Token>) -> Result<(Vec<Node>, Vec<Node>), String> {
+    let mut then = Vec::new();
+    let mut nested = Vec::new();
+    // Collect tokens up to a top-level {{else}} or {{/if}}
+    let mut depth = 0;
+    let mut saw_else = false;
+    for token in tokens.by_ref() {
+        if let Token::Tag(tag) = &token {
+            if tag.starts_with("#if") {
+                depth += 1;
+            } else if tag == "/if" && depth > 0 {
+                depth -= 1;
+            } else if tag == "/if" {
+                let rest = parse(&mut nested.into_iter(), None)?;
+                return Ok(if saw_else {
+                    (then, rest)
+                } else {
+                    (rest, Vec::new())
+                });
+            } else if tag == "else" && depth == 0 && !saw_else {
+                then = parse(&mut std::mem::take(&mut nested).into_iter(), None)?;
+                saw_else = true;
+                continue;
+            }
+        }
+        nested.push(token);
+    }
+    Err("{{#if}} is never closed".to_string())
+}
+
+fn emit(
+    nodes: &[Node],
+    vars: &[(&str, Value)],
+    this: Option<&str>,
+    out: &mut String,
+) -> Result<(), String> {
+    let lookup = |name: &str| {
+        vars.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v)
+            .ok_or(format!("unknown variable '{}'", name))
+    };
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) if name == "this" => match this {
+                Some(item) => out.push_str(item),
+                None => return Err("{{this}} outside {{#each}}".to_string()),
+            },
+            Node::Var(name) => match lookup(name)? {
+                Value::Str(s) => out.push_str(s),
+                Value::List(items) => out.push_str(&items.join(", ")),
+            },
+            Node::If(name, then, otherwise) => {
+                let branch = if lookup(name)?.truthy() {
+                    then
+                } else {
+                    otherwise
+                };
+                emit(branch, vars, this, out)?;
+            }
+            Node::Each(name, body) => match lookup(name)? {
+                Value::List(items) => {
+                    for item in items {
+                        emit(body, vars, Some(item), out)?;
+                    }
+                }
+                Value::Str(_) => return Err(format!("{{{{#each {}}}}} needs a list", name)),
+            },
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, Value};
+
+    fn text(s: &str) -> Value {
+        Value::Str(s.to_string())
+    }
+
+    fn list(items: &[&str]) -> Value {
+        Value::List(items.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn variables_and_comments() {
+        let vars = [("name", text("demo")), ("langs", list(&["Rust", "Go"]))];
+        assert_eq!(
+            render("# {{ name }}{{! hidden }}\nUses {{langs}}.\n", &vars).unwrap(),
+            "# demo\nUses Rust, Go.\n"
+        );
+    }
+
+    #[test]
+    fn if_else() {
+        let template = "{{#if author}}by {{author}}{{else}}anonymous{{/if}}";
+        assert_eq!(
+            render(template, &[("author", text("Ada"))]).unwrap(),
+            "by Ada"
+        );
+        assert_eq!(
+            render(template, &[("author", text(""))]).unwrap(),
+            "anonymous"
+        );
+        assert_eq!(
+            render(
+                "{{#if a}}{{#if b}}both{{else}}a{{/if}}{{/if}}",
+                &[("a", text("1")), ("b", list(&[]))]
+            )
+            .unwrap(),
+            "a"
+        );
+    }
+
+    #[test]
+    fn each_and_standalone_lines() {
+        let template = "Items:\n{{#each items}}\n- {{this}}\n{{/each}}\nEnd\n";
+        assert_eq!(
+            render(template, &[("items", list(&["a", "b"]))]).unwrap(),
+            "Items:\n- a\n- b\nEnd\n"
+        );
+        assert_eq!(
+            render("{{#if x}}\nshown\n{{/if}}\n", &[("x", text("y"))]).unwrap(),
+            "shown\n"
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(render("a\n{{name", &[]).unwrap_err(), "line 2: unclosed {{");
+        assert_eq!(
+            render("{{missing}}", &[]).unwrap_err(),
+            "unknown variable 'missing'"
+        );
+        assert_eq!(
+            render("{{#if x}}", &[("x", text("1"))]).unwrap_err(),
+            "{{#if}} is never closed"
+        );
+        assert_eq!(
+            render("{{#each x}}{{/if}}", &[]).unwrap_err(),
+            "{{/if}} closes {{#each}}"
+        );
+        assert_eq!(
+            render("{{#each x}}{{/each}}", &[("x", text("1"))]).unwrap_err(),
+            "{{#each x}} needs a list"
+        );
+        assert_eq!(
+            render("{{#with x}}{{/with}}", &[]).unwrap_err(),
+            "unknown block helper '#with'"
+        );
+        assert_eq!(
+            render("{{this}}", &[]).unwrap_err(),
+            "{{this}} outside {{#each}}"
+        );
+    }
+}
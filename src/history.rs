@@ -0,0 +1,170 @@
+use crate::git;
+use crate::locale::Locale;
+use crate::report::{calculate_score, score_grade};
+use crate::scanner::{ScanResult, Severity};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// History file under the repository root, one JSON object per scan.
+pub const HISTORY_FILE: &str = ".repomedic/history.jsonl";
+
+/// One recorded scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    /// When the scan ran, ISO-8601 UTC.
+    pub scanned_at: String,
+    /// Abbreviated `HEAD` commit, when the root is a git work tree.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub score: u8,
+    pub grade: String,
+    pub errors: usize,
+    pub warnings: usize,
+    pub info: usize,
+}
+
+impl Entry {
+    /// Summarize a scan of `root`.
+    pub fn record(root: &Path, result: &ScanResult) -> Self {
+        let issues = &result.issues;
+        let count = |s: Severity| issues.iter().filter(|i| i.severity == s).count();
+        let score = calculate_score(result);
+        Entry {
+            scanned_at: result.scan_stats.scanned_at.clone(),
+            commit: git::head_commit(root),
+            score,
+            grade: score_grade(score).to_string(),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            info: count(Severity::Info),
+        }
+    }
+}
+
+/// Append `entry` to the history file, creating `.repomedic/` if needed.
+pub fn append(root: &Path, entry: &Entry) -> io::Result<()> {
+    let path = root.join(HISTORY_FILE);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let line = serde_json::to_string(entry).map_err(io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Recorded scans, oldest first. A missing file is an empty history; lines
+/// that don't parse (hand edits, a partial write) are skipped.
+pub fn load(root: &Path) -> io::Result<Vec<Entry>> {
+    match fs::read_to_string(root.join(HISTORY_FILE)) {
+        Ok(content) => Ok(content
+            .lines()
+            .filter_map(|l| serde_json::from_str(l).ok())
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Scores as a bar per scan, `▁` for 0 up to `█` for 100.
+pub fn sparkline(entries: &[Entry]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    entries
+        .iter()
+        .map(|e| BARS[(e.score.min(100) as usize * 7 + 50) / 100])
+        .collect()
+}
+
+/// Trend table of `entries` with the score change from each previous scan,
+/// followed by the sparkline.
+pub fn render(entries: &[Entry], locale: &Locale) -> String {
+    let mut out = format!(
+        "{:<12}  {:<9}  {:>5}  {:>5}  {:>5}  {:>6}  {:>8}  {:>4}\n",
+        "DATE", "COMMIT", "SCORE", "CHG", "GRADE", "ERRORS", "WARNINGS", "INFO"
+    );
+    let mut previous: Option<u8> = None;
+    for e in entries {
+        let change = match previous {
+            Some(p) if e.score > p => format!("+{}", e.score - p),
+            Some(p) if e.score < p => format!("-{}", p - e.score),
+            Some(_) => "0".to_string(),
+            None => "-".to_string(),
+        };
+        out.push_str(&format!(
+            "{:<12}  {:<9}  {:>5}  {:>5}  {:>5}  {:>6}  {:>8}  {:>4}\n",
+            locale.date(e.scanned_at.get(..10).unwrap_or(&e.scanned_at)),
+            e.commit.as_deref().unwrap_or("-"),
+            e.score,
+            change,
+            e.grade,
+            e.errors,
+            e.warnings,
+            e.info
+        ));
+        previous = Some(e.score);
+    }
+    if let (Some(first), Some(last)) = (entries.first(), entries.last()) {
+        out.push_str(&format!(
+            "\nTrend: {}  {} -> {} over {} scan(s)\n",
+            sparkline(entries),
+            first.score,
+            last.score,
+            entries.len()
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{append, load, render, sparkline, Entry, HISTORY_FILE};
+    use crate::locale::Locale;
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    fn entry(date: &str, score: u8) -> Entry {
+        Entry {
+            scanned_at: format!("{}T10:00:00Z", date),
+            commit: None,
+            score,
+            grade: "B".to_string(),
+            errors: 0,
+            warnings: 2,
+            info: 1,
+        }
+    }
+
+    #[test]
+    fn append_and_load() {
+        let root = TempDir::new("history");
+        assert!(load(&root).unwrap().is_empty());
+        append(&root, &entry("2026-10-01", 70)).unwrap();
+        // A partial write from an interrupted scan is skipped
+        let path = root.join(HISTORY_FILE);
+        let mut content = fs::read_to_string(&path).unwrap();
+        content.push_str("{\"scanned_at\": \"2026-10\n");
+        fs::write(&path, content).unwrap();
+        append(&root, &entry("2026-10-02", 85)).unwrap();
+        let scores: Vec<u8> = load(&root).unwrap().iter().map(|e| e.score).collect();
+        assert_eq!(scores, [70, 85]);
+    }
+
+    #[test]
+    fn trends() {
+        let entries = [
+            entry("2026-10-01", 0),
+            entry("2026-10-02", 50),
+            entry("2026-10-03", 50),
+            entry("2026-10-04", 100),
+        ];
+        assert_eq!(sparkline(&entries), "▁▅▅█");
+        let table = render(&entries[1..], Locale::find("de-DE").unwrap());
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[1].starts_with("02.10.2026    -             50      -"));
+        assert!(lines[2].contains("   50      0  "));
+        assert!(lines[3].contains("  100    +50  "));
+        assert_eq!(lines[5], "Trend: ▅▅█  50 -> 100 over 3 scan(s)");
+        assert_eq!(render(&[], Locale::find("iso").unwrap()).lines().count(), 1);
+    }
+}
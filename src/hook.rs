@@ -0,0 +1,178 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Command the hook runs: every check, failing on warnings too.
+pub const HOOK_COMMAND: &str = "repomedic --quiet --fail-on-warning";
+
+/// Marks hooks and config entries written by `hook install`.
+const MARKER: &str = "# Installed by repomedic";
+
+/// pre-commit framework config.
+pub const FRAMEWORK_CONFIG: &str = ".pre-commit-config.yaml";
+
+/// Where an existing hook is moved by `hook install --force`, and restored
+/// from by `hook uninstall`.
+const BACKUP: &str = "pre-commit.bak";
+
+fn script() -> String {
+    format!(
+        "#!/bin/sh
+{} (`repomedic hook uninstall` removes this hook)
+if ! command -v repomedic >/dev/null 2>&1; then
+    echo \"repomedic not found on PATH; skipping the pre-commit scan\" >&2
+    exit 0
+fi
+{} && exit 0
+echo \"repomedic: health check failed; run 'repomedic' for details (git commit --no-verify skips)\" >&2
+exit 1
+",
+        MARKER, HOOK_COMMAND
+    )
+}
+
+/// The entry appended under `repos:` in the pre-commit framework config.
+fn framework_entry() -> String {
+    format!(
+        "  {}
+  - repo: local
+    hooks:
+      - id: repomedic
+        name: repomedic
+        entry: {}
+        language: system
+        pass_filenames: false
+",
+        MARKER, HOOK_COMMAND
+    )
+}
+
+/// The hooks directory git uses for `root`, honoring `core.hooksPath` and
+/// worktrees.
+fn hooks_dir(root: &Path) -> io::Result<PathBuf> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "--git-path", "hooks"])
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "{} is not a git repository",
+            root.display()
+        )));
+    }
+    let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if dir.is_absolute() {
+        dir
+    } else {
+        root.join(dir)
+    })
+}
+
+/// Write `.git/hooks/pre-commit`. An existing hook not written by repomedic
+/// is kept unless `force` is set, in which case it's moved to
+/// `pre-commit.bak`.
+pub fn install(root: &Path, force: bool) -> io::Result<()> {
+    let dir = hooks_dir(root)?;
+    let hook = dir.join("pre-commit");
+    if let Ok(existing) = fs::read_to_string(&hook) {
+        if existing.contains(MARKER) {
+            println!("Hook already installed: {}", hook.display());
+            return Ok(());
+        }
+        if !force {
+            return Err(io::Error::other(format!(
+                "{} already exists; rerun with --force to move it to {}, or use --framework",
+                hook.display(),
+                BACKUP
+            )));
+        }
+        fs::rename(&hook, dir.join(BACKUP))?;
+        println!("Moved existing hook to {}", dir.join(BACKUP).display());
+    }
+    fs::create_dir_all(&dir)?;
+    fs::write(&hook, script())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&hook, fs::Permissions::from_mode(0o755))?;
+    }
+    println!("Installed: {} (runs `{}`)", hook.display(), HOOK_COMMAND);
+    Ok(())
+}
+
+/// Add a local repomedic hook to `.pre-commit-config.yaml`, creating it if
+/// needed. An existing config must end with its `repos:` list.
+pub fn install_framework(root: &Path) -> io::Result<()> {
+    let path = root.join(FRAMEWORK_CONFIG);
+    let existing = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    if existing.contains("id: repomedic") {
+        println!("{} already runs repomedic.", FRAMEWORK_CONFIG);
+        return Ok(());
+    }
+    let last_key = existing
+        .lines()
+        .rfind(|l| !l.is_empty() && !l.starts_with([' ', '\t', '#', '-']));
+    let mut content = existing.clone();
+    match last_key {
+        None => content.push_str("repos:\n"),
+        Some(key) if key.trim_end() == "repos:" => {
+            if !content.is_empty() && !content.ends_with('\n') {
+                content.push('\n');
+            }
+        }
+        Some(_) => {
+            return Err(io::Error::other(format!(
+                "{} doesn't end with its `repos:` list; add this entry under `repos:` by hand:\n{}",
+                FRAMEWORK_CONFIG,
+                framework_entry()
+            )))
+        }
+    }
+    content.push_str(&framework_entry());
+    fs::write(&path, content)?;
+    println!(
+        "Added repomedic to {} (run `pre-commit install`)",
+        FRAMEWORK_CONFIG
+    );
+    Ok(())
+}
+
+/// Remove the hook and config entry `install` wrote, restoring a hook it
+/// moved aside. Hooks and entries repomedic didn't write are left alone.
+pub fn uninstall(root: &Path) -> io::Result<()> {
+    let mut removed = false;
+    if let Ok(dir) = hooks_dir(root) {
+        let hook = dir.join("pre-commit");
+        match fs::read_to_string(&hook) {
+            Ok(content) if content.contains(MARKER) => {
+                fs::remove_file(&hook)?;
+                println!("Removed: {}", hook.display());
+                if dir.join(BACKUP).is_file() {
+                    fs::rename(dir.join(BACKUP), &hook)?;
+                    println!("Restored previous hook from {}", BACKUP);
+                }
+                removed = true;
+            }
+            Ok(_) => println!("Left {} alone (not installed by repomedic)", hook.display()),
+            Err(_) => {}
+        }
+    }
+    let config = root.join(FRAMEWORK_CONFIG);
+    if let Ok(content) = fs::read_to_string(&config) {
+        if content.contains(&framework_entry()) {
+            fs::write(&config, content.replace(&framework_entry(), ""))?;
+            println!("Removed repomedic from {}", FRAMEWORK_CONFIG);
+            removed = true;
+        }
+    }
+    if !removed {
+        println!("No repomedic hook installed.");
+    }
+    Ok(())
+}
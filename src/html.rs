@@ -0,0 +1,242 @@
+use crate::detector::Category;
+use crate::license;
+use crate::report::{
+    calculate_score, check_category, clean_path, collect_passed, scan_time, score_grade,
+};
+use crate::rules;
+use crate::scanner::{Issue, ScanResult, Severity};
+use std::path::Path;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Sections issues are grouped into, in report order.
+const SECTIONS: &[(Category, &str)] = &[
+    (Category::Security, "Security"),
+    (Category::Documentation, "Documentation"),
+    (Category::Community, "Community"),
+    (Category::Ci, "CI/CD"),
+    (Category::Project, "Project"),
+    (Category::Files, "Files"),
+];
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 0; background: #f6f8fa; color: #1f2328; }
+main { max-width: 960px; margin: 0 auto; padding: 24px; }
+header { display: flex; align-items: center; gap: 32px; background: #fff; border: 1px solid #d0d7de; border-radius: 8px; padding: 24px; }
+header h1 { margin: 0 0 4px; font-size: 24px; }
+header code { color: #57606a; }
+section { background: #fff; border: 1px solid #d0d7de; border-radius: 8px; padding: 16px 24px; margin-top: 16px; }
+h2 { font-size: 18px; margin: 0 0 12px; }
+.cards { display: flex; gap: 12px; margin-top: 12px; }
+.card { border-radius: 6px; padding: 8px 14px; font-weight: 600; cursor: pointer; border: 2px solid transparent; }
+.card.off { opacity: .4; }
+.error { background: #ffebe9; color: #cf222e; }
+.warning { background: #fff8c5; color: #9a6700; }
+.info { background: #ddf4ff; color: #0969da; }
+table { width: 100%; border-collapse: collapse; font-size: 14px; }
+th, td { text-align: left; padding: 6px 8px; border-bottom: 1px solid #eaeef2; vertical-align: top; }
+td.sev span { border-radius: 10px; padding: 1px 8px; font-size: 12px; font-weight: 600; }
+td code { font-size: 12px; }
+.bar { display: flex; align-items: center; gap: 8px; margin: 4px 0; font-size: 14px; }
+.bar .name { width: 110px; }
+.bar .track { flex: 1; background: #eaeef2; border-radius: 4px; height: 12px; }
+.bar .fill { background: #0969da; border-radius: 4px; height: 12px; }
+ul.passed { list-style: none; padding: 0; margin: 0; columns: 2; font-size: 14px; }
+ul.passed li::before { content: "\2713  "; color: #1a7f37; }
+footer { color: #57606a; font-size: 12px; text-align: center; margin: 24px 0; }
+"#;
+
+/// Clicking a severity card hides or shows rows of that severity.
+const SCRIPT: &str = r#"
+document.querySelectorAll(".card").forEach(function (card) {
+  card.addEventListener("click", function () {
+    card.classList.toggle("off");
+    var hide = card.classList.contains("off");
+    document.querySelectorAll("tr." + card.dataset.sev).forEach(function (row) {
+      row.style.display = hide ? "none" : "";
+    });
+  });
+});
+"#;
+
+/// Standalone HTML report with inline styles, a score gauge, and charts.
+pub fn to_html(result: &ScanResult, path: &Path) -> String {
+    let locale = result.config.locale();
+    let score = calculate_score(result);
+    let issues = &result.issues;
+    let count = |s: Severity| issues.iter().filter(|i| i.severity == s).count();
+    let mut h = String::new();
+    h.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    h.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+    h.push_str(&format!(
+        "<title>RepoMedic report: {}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n",
+        escape(&clean_path(path)),
+        STYLE
+    ));
+
+    h.push_str("<header>\n");
+    h.push_str(&gauge(score));
+    h.push_str(&format!(
+        "<div>\n<h1>Repository Health Report</h1>\n<code>{}</code>\n",
+        escape(&clean_path(path))
+    ));
+    if let Some(spdx) = license::expression(&result.licenses) {
+        h.push_str(&format!("<p>License: {}</p>\n", escape(&spdx)));
+    }
+    if let Some(scanned) = scan_time(result, locale) {
+        h.push_str(&format!("<p>Scanned: {}</p>\n", escape(&scanned)));
+    }
+    h.push_str("<div class=\"cards\">\n");
+    for (sev, label, n) in [
+        ("error", "errors", count(Severity::Error)),
+        ("warning", "warnings", count(Severity::Warning)),
+        ("info", "info", count(Severity::Info)),
+    ] {
+        h.push_str(&format!(
+            "<div class=\"card {0}\" data-sev=\"{0}\">{1} {2}</div>\n",
+            sev, n, label
+        ));
+    }
+    h.push_str("</div>\n</div>\n</header>\n");
+
+    for (category, title) in SECTIONS {
+        let group: Vec<&Issue> = issues
+            .iter()
+            .filter(|i| rules::find(i.id()).is_some() && check_category(i.id()) == *category)
+            .collect();
+        if !group.is_empty() {
+            h.push_str(&issue_table(title, &group));
+        }
+    }
+    // Custom rules aren't in the catalog
+    let ungrouped: Vec<&Issue> = issues
+        .iter()
+        .filter(|i| rules::find(i.id()).is_none())
+        .collect();
+    if !ungrouped.is_empty() {
+        h.push_str(&issue_table("Other", &ungrouped));
+    }
+
+    if !result.language_files.is_empty() {
+        h.push_str("<section>\n<h2>Languages</h2>\n");
+        for l in &result.language_files {
+            h.push_str(&format!(
+                "<div class=\"bar\"><span class=\"name\">{}</span><div class=\"track\"><div class=\"fill\" style=\"width: {:.1}%\"></div></div><span>{:.1}%, {} file(s), {} lines</span></div>\n",
+                escape(l.language.name()),
+                l.percent,
+                l.percent,
+                locale.number(l.files as u64),
+                locale.number(l.lines as u64)
+            ));
+        }
+        h.push_str("</section>\n");
+    }
+
+    let passed = collect_passed(result);
+    if !passed.is_empty() {
+        h.push_str("<section>\n<h2>Passed Checks</h2>\n<ul class=\"passed\">\n");
+        for p in &passed {
+            h.push_str(&format!("<li>{}</li>\n", escape(p)));
+        }
+        h.push_str("</ul>\n</section>\n");
+    }
+
+    let branding = &result.config.branding;
+    let url = escape(branding.url("https://github.com/Brutus1066/repomedic"));
+    let label = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    match branding.footer {
+        Some(ref footer) => h.push_str(&format!(
+            "<footer>{} &middot; <a href=\"{}\">{}</a></footer>\n",
+            escape(footer),
+            url,
+            label
+        )),
+        None => h.push_str(&format!(
+            "<footer>Generated by RepoMedic v{} &middot; <a href=\"{}\">{}</a></footer>\n",
+            VERSION, url, label
+        )),
+    }
+    h.push_str(&format!(
+        "</main>\n<script>{}</script>\n</body>\n</html>\n",
+        SCRIPT
+    ));
+    h
+}
+
+/// Semicircle SVG gauge filled in proportion to the score.
+fn gauge(score: u8) -> String {
+    let color = match score {
+        90..=100 => "#1a7f37",
+        70..=89 => "#9a6700",
+        _ => "#cf222e",
+    };
+    // Arc length of a radius-80 semicircle
+    let arc = std::f64::consts::PI * 80.0;
+    let filled = arc * f64::from(score) / 100.0;
+    format!(
+        "<svg width=\"200\" height=\"120\" viewBox=\"0 0 200 120\" role=\"img\" aria-label=\"Score {score} of 100\">\n\
+<path d=\"M 20 100 A 80 80 0 0 1 180 100\" fill=\"none\" stroke=\"#eaeef2\" stroke-width=\"18\" stroke-linecap=\"round\"/>\n\
+<path d=\"M 20 100 A 80 80 0 0 1 180 100\" fill=\"none\" stroke=\"{color}\" stroke-width=\"18\" stroke-linecap=\"round\" stroke-dasharray=\"{filled:.1} {arc:.1}\"/>\n\
+<text x=\"100\" y=\"88\" text-anchor=\"middle\" font-size=\"36\" font-weight=\"700\" fill=\"{color}\">{score}</text>\n\
+<text x=\"100\" y=\"112\" text-anchor=\"middle\" font-size=\"14\" fill=\"#57606a\">Grade {grade}</text>\n\
+</svg>\n",
+        score = score,
+        color = color,
+        filled = filled,
+        arc = arc,
+        grade = score_grade(score)
+    )
+}
+
+fn issue_table(title: &str, issues: &[&Issue]) -> String {
+    let mut t = format!(
+        "<section>\n<h2>{} ({})</h2>\n<table>\n<tr><th>Severity</th><th>Check</th><th>Message</th><th>Location</th></tr>\n",
+        title,
+        issues.len()
+    );
+    for issue in issues {
+        let sev = issue.severity.name();
+        let location = match (&issue.file, issue.line) {
+            (Some(f), Some(l)) => format!("<code>{}:{}</code>", escape(f), l),
+            (Some(f), None) => format!("<code>{}</code>", escape(f)),
+            _ => String::new(),
+        };
+        let blame = issue
+            .blame
+            .as_ref()
+            .map(|a| {
+                format!(
+                    "<br><small>last changed by {}</small>",
+                    escape(&a.to_string())
+                )
+            })
+            .unwrap_or_default();
+        t.push_str(&format!(
+            "<tr class=\"{0}\"><td class=\"sev\"><span class=\"{0}\">{0}</span></td><td><code>{1}</code></td><td>{2}{3}</td><td>{4}</td></tr>\n",
+            sev,
+            issue.id(),
+            escape(&issue.message),
+            blame,
+            location
+        ));
+    }
+    t.push_str("</table>\n</section>\n");
+    t
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
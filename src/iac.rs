@@ -0,0 +1,376 @@
+use crate::base64;
+use serde::Serialize;
+
+/// A Kubernetes `Secret` manifest with inline values.
+#[derive(Debug, Clone, Serialize)]
+pub struct K8sSecret {
+    pub file: String,
+    /// Line of the `kind: Secret` entry.
+    pub line: usize,
+    /// `metadata.name`, when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Keys under `data` and `stringData` with a literal value.
+    pub keys: Vec<String>,
+}
+
+/// Kinds whose pod template runs containers.
+const WORKLOADS: &[&str] = &[
+    "Pod",
+    "Deployment",
+    "StatefulSet",
+    "DaemonSet",
+    "ReplicaSet",
+    "Job",
+    "CronJob",
+];
+
+/// A workload whose containers don't all set `resources.limits`.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnboundedWorkload {
+    pub file: String,
+    /// Line of the `kind:` entry.
+    pub line: usize,
+    pub kind: String,
+    /// `metadata.name`, when set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Containers (and init containers) without limits.
+    pub containers: Vec<String>,
+}
+
+/// A Helm chart: a directory with a `Chart.yaml`.
+#[derive(Debug, Clone, Serialize)]
+pub struct HelmChart {
+    /// Chart directory, `.` for the root.
+    pub dir: String,
+    /// `name` from Chart.yaml.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// `values.schema.json` validates the chart's values.
+    pub has_schema: bool,
+}
+
+/// Parse a `Chart.yaml` at `file`; library charts, which have no values to
+/// validate, are `None`.
+pub fn helm_chart(file: &str, content: &str, has_schema: bool) -> Option<HelmChart> {
+    let mut name = None;
+    for line in content.lines().filter(|l| !l.starts_with(' ')) {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        match key.trim() {
+            "name" => name = Some(unquote(value.trim()).to_string()),
+            "type" if unquote(value.trim()) == "library" => return None,
+            _ => {}
+        }
+    }
+    Some(HelmChart {
+        dir: file.rsplit_once('/').map_or(".", |(d, _)| d).to_string(),
+        name,
+        has_schema,
+    })
+}
+
+/// Whether a YAML file holds Kubernetes resources: a document with
+/// top-level `apiVersion` and `kind`.
+pub fn is_manifest(content: &str) -> bool {
+    documents(content).iter().any(|doc| {
+        let top = |key: &str| doc.iter().any(|l| l.starts_with(key));
+        top("apiVersion:") && top("kind:")
+    })
+}
+
+/// Workloads (Deployments, StatefulSets, Pods, ...) in a YAML file with a
+/// container that sets no `resources.limits`.
+///
+/// In Helm templates a templated `resources` block (`toYaml
+/// .Values.resources`) counts as set, since the limits come from values.
+pub fn unbounded_workloads(file: &str, content: &str) -> Vec<UnboundedWorkload> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    for doc in documents(content) {
+        if let Some(w) = parse_workload(file, &doc, offset) {
+            found.push(w);
+        }
+        offset += doc.len() + 1;
+    }
+    found
+}
+
+/// Lines of each `---`-separated document.
+fn documents(content: &str) -> Vec<Vec<&str>> {
+    let mut docs = vec![Vec::new()];
+    for line in content.lines() {
+        if line.starts_with("---") {
+            docs.push(Vec::new());
+        } else if let Some(doc) = docs.last_mut() {
+            doc.push(line);
+        }
+    }
+    docs
+}
+
+fn parse_workload(file: &str, lines: &[&str], offset: usize) -> Option<UnboundedWorkload> {
+    let mut kind = None;
+    let mut name = None;
+    let mut in_metadata = false;
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with(' ') {
+            let trimmed = line.trim_start();
+            if in_metadata && name.is_none() && line.len() - trimmed.len() == 2 {
+                if let Some(value) = trimmed.strip_prefix("name:") {
+                    name = Some(unquote(value.trim()).to_string());
+                }
+            }
+            continue;
+        }
+        in_metadata = line.starts_with("metadata:");
+        if let Some(value) = line.strip_prefix("kind:") {
+            let value = unquote(value.trim());
+            if WORKLOADS.contains(&value) {
+                kind = Some((offset + i + 1, value.to_string()));
+            }
+        }
+    }
+    let (line, kind) = kind?;
+
+    // Each `- name:` directly under `containers:` or `initContainers:`
+    // starts a container; its block runs until the indentation drops back.
+    let mut containers: Vec<(String, bool)> = Vec::new();
+    let mut list_indent: Option<usize> = None;
+    let mut item_indent: Option<usize> = None;
+    for raw in lines {
+        let trimmed = raw.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = raw.len() - trimmed.len();
+        if trimmed == "containers:" || trimmed == "initContainers:" {
+            list_indent = Some(indent);
+            item_indent = None;
+            continue;
+        }
+        let Some(at) = list_indent else {
+            continue;
+        };
+        if indent < at || (indent == at && !trimmed.starts_with("- ")) {
+            list_indent = None;
+            continue;
+        }
+        if trimmed.starts_with("- ") && *item_indent.get_or_insert(indent) == indent {
+            let entry = trimmed[2..].trim_start();
+            let container = entry
+                .strip_prefix("name:")
+                .map(|n| unquote(n.trim()).to_string())
+                .unwrap_or_default();
+            containers.push((container, false));
+        } else if let Some((container, limited)) = containers.last_mut() {
+            if container.is_empty() {
+                if let Some(n) = trimmed.strip_prefix("name:") {
+                    *container = unquote(n.trim()).to_string();
+                }
+            }
+            if trimmed.starts_with("limits:")
+                || (trimmed.contains("{{") && trimmed.contains("resources"))
+            {
+                *limited = true;
+            }
+        }
+    }
+    let containers: Vec<String> = containers
+        .into_iter()
+        .filter(|(_, limited)| !limited)
+        .map(|(name, _)| name)
+        .collect();
+    if containers.is_empty() {
+        return None;
+    }
+    Some(UnboundedWorkload {
+        file: file.to_string(),
+        line,
+        kind,
+        name,
+        containers,
+    })
+}
+
+/// A `data:` value of a Secret manifest, base64-decoded.
+#[derive(Debug)]
+pub struct DecodedValue {
+    /// Key path, e.g. `data.password`.
+    pub key: String,
+    pub line: usize,
+    pub value: String,
+}
+
+/// Secret manifests in a YAML file (one per `---` document), with their
+/// decoded `data` values.
+///
+/// Values that are templated (`{{ .Values.x }}`, `${VAR}`) or empty are not
+/// counted, so Helm charts and Kustomize placeholders stay quiet.
+pub fn k8s_secrets(file: &str, content: &str) -> Vec<(K8sSecret, Vec<DecodedValue>)> {
+    let mut found = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let mut start = 0;
+    while start < lines.len() {
+        let end = lines[start..]
+            .iter()
+            .position(|l| l.starts_with("---"))
+            .map_or(lines.len(), |p| start + p);
+        if let Some(secret) = parse_document(file, &lines[start..end], start) {
+            found.push(secret);
+        }
+        start = end + 1;
+    }
+    found
+}
+
+fn parse_document(
+    file: &str,
+    lines: &[&str],
+    offset: usize,
+) -> Option<(K8sSecret, Vec<DecodedValue>)> {
+    let mut kind_line = None;
+    let mut name = None;
+    let mut keys = Vec::new();
+    let mut decoded = Vec::new();
+    // Top-level key the current indented line belongs to
+    let mut section = "";
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let top_level = trimmed.len() == line.len();
+        let Some((key, value)) = trimmed.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        let value = unquote(value.trim());
+        if top_level {
+            section = key;
+            if key == "kind" && value == "Secret" {
+                kind_line = Some(offset + i + 1);
+            }
+            continue;
+        }
+        match section {
+            "metadata" if key == "name" && name.is_none() => name = Some(value.to_string()),
+            "data" | "stringData" if !is_placeholder(value) => {
+                keys.push(key.to_string());
+                if section == "data" {
+                    if let Some(v) = base64::decode(value).and_then(|b| String::from_utf8(b).ok()) {
+                        decoded.push(DecodedValue {
+                            key: format!("data.{}", key),
+                            line: offset + i + 1,
+                            value: v,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    let line = kind_line?;
+    if keys.is_empty() {
+        return None;
+    }
+    Some((
+        K8sSecret {
+            file: file.to_string(),
+            line,
+            name,
+            keys,
+        },
+        decoded,
+    ))
+}
+
+fn unquote(value: &str) -> &str {
+    let value = value.split(" #").next().unwrap_or(value).trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
+
+fn is_placeholder(value: &str) -> bool {
+    value.is_empty()
+        || value == "|"
+        || value == ">"
+        || value.contains("{{")
+        || value.contains("${")
+        || value.starts_with('<')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{helm_chart, is_manifest, k8s_secrets, unbounded_workloads};
+
+    #[test]
+    fn manifests() {
+        assert!(is_manifest("apiVersion: v1\nkind: ConfigMap\n"));
+        assert!(is_manifest(
+            "# values\n---\napiVersion: apps/v1\nkind: Deployment\n"
+        ));
+        assert!(!is_manifest("name: CI\non: push\n"));
+    }
+
+    #[test]
+    fn secrets_with_inline_values() {
+        let manifest = "apiVersion: v1\nkind: ConfigMap\ndata:\n  a: b\n---\n\
+                        apiVersion: v1\nkind: Secret\nmetadata:\n  name: db\n\
+                        data:\n  password: aHVudGVyMg==\n  empty: \"\"\n\
+                        stringData:\n  token: \"{{ .Values.token }}\"\n  user: admin\n";
+        let found = k8s_secrets("k8s/db.yaml", manifest);
+        assert_eq!(found.len(), 1);
+        let (secret, decoded) = &found[0];
+        assert_eq!(secret.line, 7);
+        assert_eq!(secret.name.as_deref(), Some("db"));
+        assert_eq!(secret.keys, ["password", "user"]);
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].key, "data.password");
+        assert_eq!(decoded[0].line, 11);
+        assert_eq!(decoded[0].value, "hunter2");
+    }
+
+    #[test]
+    fn templated_secrets_are_quiet() {
+        let chart = "apiVersion: v1\nkind: Secret\ndata:\n  password: {{ .Values.password | b64enc }}\n  other: ${OTHER}\n";
+        assert!(k8s_secrets("templates/secret.yaml", chart).is_empty());
+    }
+
+    #[test]
+    fn workloads_without_limits() {
+        let deployment = "apiVersion: apps/v1\nkind: Deployment\nmetadata:\n  name: api\nspec:\n  template:\n    spec:\n      initContainers:\n        - name: migrate\n          image: app:1\n      containers:\n        - name: api\n          image: app:1\n          resources:\n            limits:\n              memory: 256Mi\n        - image: sidecar:1\n          name: proxy\n";
+        let found = unbounded_workloads("deploy.yaml", deployment);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, "Deployment");
+        assert_eq!(found[0].line, 2);
+        assert_eq!(found[0].name.as_deref(), Some("api"));
+        assert_eq!(found[0].containers, ["migrate", "proxy"]);
+
+        let templated = "kind: Deployment\nspec:\n  containers:\n    - name: app\n      resources: {{- toYaml .Values.resources | nindent 8 }}\n";
+        assert!(unbounded_workloads("templates/app.yaml", templated).is_empty());
+        assert!(unbounded_workloads("svc.yaml", "kind: Service\nspec:\n  ports: []\n").is_empty());
+    }
+
+    #[test]
+    fn helm_charts() {
+        let chart = helm_chart(
+            "charts/api/Chart.yaml",
+            "apiVersion: v2\nname: \"api\"\nversion: 1.0.0\n",
+            false,
+        )
+        .expect("application chart");
+        assert_eq!(chart.dir, "charts/api");
+        assert_eq!(chart.name.as_deref(), Some("api"));
+        assert_eq!(
+            helm_chart("Chart.yaml", "name: x\n", true).map(|c| c.dir),
+            Some(".".to_string())
+        );
+        assert!(helm_chart("Chart.yaml", "name: common\ntype: library\n", false).is_none());
+    }
+}
@@ -0,0 +1,149 @@
+use crate::glob::glob_match;
+use std::fs;
+use std::path::Path;
+
+/// One line of a `.gitignore` file.
+#[derive(Debug, Clone)]
+struct Pattern {
+    /// Directory (relative to the root, no trailing slash) the file lives in.
+    base: String,
+    glob: String,
+    negated: bool,
+    dir_only: bool,
+    /// Patterns containing a slash match the full path, others the name only.
+    anchored: bool,
+}
+
+/// Gitignore rules in effect while walking the tree.
+///
+/// Patterns from `.git/info/exclude` and the root `.gitignore` are loaded
+/// up front; nested `.gitignore` files are pushed when the walker enters
+/// their directory and popped when it leaves. As in git, the last matching
+/// pattern wins, so deeper files and later lines override earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct GitIgnore {
+    patterns: Vec<Pattern>,
+}
+
+impl GitIgnore {
+    pub fn load(root: &Path) -> Self {
+        let mut ignore = Self::default();
+        if let Ok(content) = fs::read_to_string(root.join(".git/info/exclude")) {
+            ignore.push_rules(&content, "");
+        }
+        ignore.enter(root, "");
+        ignore
+    }
+
+    /// Push the `.gitignore` in `rel_dir` (if any). Returns a mark for `leave`.
+    pub fn enter(&mut self, root: &Path, rel_dir: &str) -> usize {
+        let mark = self.patterns.len();
+        if let Ok(content) = fs::read_to_string(root.join(rel_dir).join(".gitignore")) {
+            self.push_rules(&content, rel_dir);
+        }
+        mark
+    }
+
+    /// Drop patterns pushed since `mark`.
+    pub fn leave(&mut self, mark: usize) {
+        self.patterns.truncate(mark);
+    }
+
+    pub fn push_rules(&mut self, content: &str, base: &str) {
+        for line in content.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line.strip_prefix('\\').unwrap_or(line)),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            if line.is_empty() {
+                continue;
+            }
+            self.patterns.push(Pattern {
+                base: base.to_string(),
+                glob: line.trim_start_matches('/').to_string(),
+                negated,
+                dir_only,
+                anchored: line.contains('/'),
+            });
+        }
+    }
+
+    /// Whether a path (relative to the root, `/`-separated) is ignored.
+    pub fn is_ignored(&self, rel: &str, is_dir: bool) -> bool {
+        let name = rel.rsplit('/').next().unwrap_or(rel);
+        for p in self.patterns.iter().rev() {
+            if p.dir_only && !is_dir {
+                continue;
+            }
+            let sub = if p.base.is_empty() {
+                rel
+            } else {
+                match rel
+                    .strip_prefix(p.base.as_str())
+                    .and_then(|r| r.strip_prefix('/'))
+                {
+                    Some(sub) => sub,
+                    None => continue,
+                }
+            };
+            let subject = if p.anchored { sub } else { name };
+            if glob_match(p.glob.as_bytes(), subject.as_bytes()) {
+                return !p.negated;
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GitIgnore;
+
+    fn rules(content: &str) -> GitIgnore {
+        let mut ignore = GitIgnore::default();
+        ignore.push_rules(content, "");
+        ignore
+    }
+
+    #[test]
+    fn names_paths_and_directories() {
+        let ignore = rules("# comment\n*.log\n/dist\nbuild/\ndocs/*.tmp\n");
+        assert!(ignore.is_ignored("app.log", false));
+        assert!(ignore.is_ignored("src/app.log", false));
+        assert!(ignore.is_ignored("dist", true));
+        assert!(!ignore.is_ignored("src/dist", true));
+        assert!(ignore.is_ignored("src/build", true));
+        assert!(!ignore.is_ignored("build", false));
+        assert!(ignore.is_ignored("docs/a.tmp", false));
+        assert!(!ignore.is_ignored("docs/sub/a.tmp", false));
+    }
+
+    #[test]
+    fn last_match_wins() {
+        let ignore = rules("*.env\n!example.env\n\\!important\n");
+        assert!(ignore.is_ignored("prod.env", false));
+        assert!(!ignore.is_ignored("example.env", false));
+        assert!(ignore.is_ignored("!important", false));
+    }
+
+    #[test]
+    fn nested_files_apply_below_their_directory() {
+        let mut ignore = rules("*.tmp\n");
+        let mark = ignore.patterns.len();
+        ignore.push_rules("!keep.tmp\nout/\n", "pkg");
+        assert!(!ignore.is_ignored("pkg/keep.tmp", false));
+        assert!(ignore.is_ignored("keep.tmp", false));
+        assert!(ignore.is_ignored("pkg/out", true));
+        assert!(!ignore.is_ignored("out", true));
+        ignore.leave(mark);
+        assert!(ignore.is_ignored("pkg/keep.tmp", false));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::sample;
+use serde::Serialize;
+
+/// Files read per extension when surveying indentation.
+const SAMPLE_PER_EXTENSION: usize = 20;
+
+/// Indented lines a file needs before its style counts.
+const MIN_INDENTED_LINES: usize = 3;
+
+/// Extensions whose indentation `.editorconfig` should pin down.
+const EXTENSIONS: &[&str] = &[
+    "rs", "py", "js", "jsx", "mjs", "cjs", "ts", "tsx", "vue", "svelte", "go", "java", "kt", "kts",
+    "scala", "cs", "c", "h", "cc", "cpp", "hpp", "rb", "php", "swift", "hs", "ex", "exs", "zig",
+    "nim", "lua", "r", "pl", "dart", "cr", "sh", "json", "yml", "yaml", "toml", "xml", "html",
+    "css", "scss",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Style {
+    Space,
+    Tab,
+}
+
+/// An indentation style with its size in spaces (`space` only).
+pub type Level = (Style, Option<u8>);
+
+/// The indentation most sampled files with one extension use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Indent {
+    pub extension: String,
+    pub style: Style,
+    /// Spaces per level (`space` only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u8>,
+    /// Sampled files indented this way.
+    pub files: usize,
+    /// Sampled files with enough indented lines to tell.
+    pub sampled: usize,
+}
+
+/// The indentation of one file: tabs or spaces, whichever starts more
+/// lines, and for spaces the most common step between nesting levels.
+pub fn detect(content: &str) -> Option<Level> {
+    let (mut tabs, mut spaces) = (0, 0);
+    let mut steps = [0usize; 9];
+    let mut previous = 0;
+    for line in content.lines() {
+        let rest = line.trim_start_matches([' ', '\t']);
+        if rest.is_empty() {
+            continue;
+        }
+        // Block comment continuations sit one space off the grid
+        if rest.starts_with('*') {
+            continue;
+        }
+        let width = line.len() - rest.len();
+        if line.starts_with('\t') {
+            tabs += 1;
+            continue;
+        }
+        if width > 0 {
+            spaces += 1;
+        }
+        if width > previous && width - previous < steps.len() {
+            steps[width - previous] += 1;
+        }
+        previous = width;
+    }
+    if tabs + spaces < MIN_INDENTED_LINES {
+        return None;
+    }
+    if tabs > spaces {
+        return Some((Style::Tab, None));
+    }
+    // Ties go to the smaller step; one-space steps are alignment, not nesting
+    let size = (2..steps.len())
+        .filter(|&s| steps[s] > 0)
+        .max_by_key(|&s| (steps[s], std::cmp::Reverse(s)))?;
+    Some((Style::Space, Some(size as u8)))
+}
+
+/// The dominant indentation per extension across walked `files`, reading a
+/// sample of each extension's files with `read`.
+pub fn survey(files: &[(String, u64)], read: &dyn Fn(&str) -> Option<String>) -> Vec<Indent> {
+    let mut by_extension: Vec<(String, Vec<&str>)> = Vec::new();
+    for (rel, _) in files {
+        let Some((_, ext)) = rel.rsplit_once('.') else {
+            continue;
+        };
+        let ext = ext.to_lowercase();
+        if !EXTENSIONS.contains(&ext.as_str()) {
+            continue;
+        }
+        match by_extension.iter_mut().find(|(e, _)| *e == ext) {
+            Some((_, paths)) => paths.push(rel),
+            None => by_extension.push((ext, vec![rel])),
+        }
+    }
+
+    let mut survey = Vec::new();
+    for (extension, paths) in by_extension {
+        let mut votes: Vec<(Level, usize)> = Vec::new();
+        for rel in sample::pick(paths, SAMPLE_PER_EXTENSION, |p| p) {
+            let Some(indent) = read(rel).as_deref().and_then(detect) else {
+                continue;
+            };
+            match votes.iter_mut().find(|(i, _)| *i == indent) {
+                Some((_, n)) => *n += 1,
+                None => votes.push((indent, 1)),
+            }
+        }
+        let sampled = votes.iter().map(|(_, n)| n).sum();
+        if let Some(((style, size), files)) = votes.into_iter().max_by_key(|(_, n)| *n) {
+            survey.push(Indent {
+                extension,
+                style,
+                size,
+                files,
+                sampled,
+            });
+        }
+    }
+    survey.sort_by(|a, b| a.extension.cmp(&b.extension));
+    survey
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect, survey, Style};
+
+    #[test]
+    fn detects_spaces_and_step() {
+        let two = "a:\n  b:\n    c: 1\n    d: 2\n  e: 3\n";
+        assert_eq!(detect(two), Some((Style::Space, Some(2))));
+        let four = "fn main() {\n    if x {\n        y();\n    }\n    z();\n}\n";
+        assert_eq!(detect(four), Some((Style::Space, Some(4))));
+    }
+
+    #[test]
+    fn detects_tabs() {
+        let go = "func main() {\n\tif x {\n\t\ty()\n\t}\n}\n";
+        assert_eq!(detect(go), Some((Style::Tab, None)));
+    }
+
+    #[test]
+    fn ignores_comment_continuations_and_short_files() {
+        let c = "/**\n * Doc\n * more\n */\nint f() {\n    return 1;\n    x();\n    y();\n}\n";
+        assert_eq!(detect(c), Some((Style::Space, Some(4))));
+        assert_eq!(detect("a\n  b\n"), None);
+        assert_eq!(detect(""), None);
+    }
+
+    #[test]
+    fn surveys_by_extension() {
+        let files = vec![
+            ("a.py".to_string(), 0),
+            ("b.py".to_string(), 0),
+            ("c.py".to_string(), 0),
+            ("main.go".to_string(), 0),
+            ("notes.txt".to_string(), 0),
+        ];
+        let read = |rel: &str| {
+            Some(match rel {
+                "main.go" => "func f() {\n\tx()\n\ty()\n\tz()\n}\n".to_string(),
+                "c.py" => "def f():\n  a\n  b\n  c\n".to_string(),
+                _ => "def f():\n    a\n    b\n    c\n".to_string(),
+            })
+        };
+        let survey = survey(&files, &read);
+        assert_eq!(survey.len(), 2);
+        assert_eq!(survey[0].extension, "go");
+        assert_eq!(survey[0].style, Style::Tab);
+        assert_eq!(survey[1].extension, "py");
+        assert_eq!(survey[1].size, Some(4));
+        assert_eq!((survey[1].files, survey[1].sampled), (2, 3));
+    }
+}
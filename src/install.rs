@@ -0,0 +1,438 @@
+use crate::config::TomlDoc;
+use crate::readme;
+use serde::Serialize;
+
+/// A package manager the project builds with, and the package name its
+/// manifest declares (`None` for a workspace root or an unnamed package).
+#[derive(Debug, Clone)]
+pub struct Package {
+    /// `cargo`, `npm`, `pip`, `go`, or `composer`.
+    pub manager: &'static str,
+    pub manifest: &'static str,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub version: Option<String>,
+}
+
+/// An install or usage command in the README that doesn't fit the project.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallMismatch {
+    pub line: usize,
+    pub command: String,
+    /// What's wrong, e.g. "installs `foo`, but Cargo.toml names the package `bar`".
+    pub reason: String,
+}
+
+/// Display name for a manager key.
+fn manager_name(manager: &str) -> &'static str {
+    match manager {
+        "cargo" => "Cargo",
+        "npm" => "npm",
+        "pip" => "pip",
+        "go" => "Go modules",
+        _ => "Composer",
+    }
+}
+
+/// Packages declared by the root manifests, read with `read`.
+pub fn packages(read: &dyn Fn(&str) -> Option<String>) -> Vec<Package> {
+    let mut packages = Vec::new();
+    let toml = |file: &str| read(file).and_then(|c| TomlDoc::parse(&c).ok());
+    if let Some(doc) = toml("Cargo.toml") {
+        packages.push(Package {
+            manager: "cargo",
+            manifest: "Cargo.toml",
+            name: doc.get("package", "name").and_then(|v| v.as_str()),
+            description: doc.get("package", "description").and_then(|v| v.as_str()),
+            version: doc.get("package", "version").and_then(|v| v.as_str()),
+        });
+    }
+    let json =
+        |file: &str| read(file).and_then(|c| serde_json::from_str::<serde_json::Value>(&c).ok());
+    if let Some(package) = json("package.json") {
+        packages.push(Package {
+            manager: "npm",
+            manifest: "package.json",
+            name: package["name"].as_str().map(String::from),
+            description: package["description"].as_str().map(String::from),
+            version: package["version"].as_str().map(String::from),
+        });
+    }
+    if let Some(doc) = toml("pyproject.toml") {
+        let field = |key: &str| {
+            doc.get("project", key)
+                .or_else(|| doc.get("tool.poetry", key))
+                .and_then(|v| v.as_str())
+        };
+        packages.push(Package {
+            manager: "pip",
+            manifest: "pyproject.toml",
+            name: field("name"),
+            description: field("description"),
+            version: field("version"),
+        });
+    }
+    if let Some(gomod) = read("go.mod") {
+        packages.push(Package {
+            manager: "go",
+            manifest: "go.mod",
+            name: gomod
+                .lines()
+                .find_map(|l| l.trim().strip_prefix("module "))
+                .map(|m| m.trim().trim_matches('"').to_string()),
+            description: None,
+            version: None,
+        });
+    }
+    if let Some(composer) = json("composer.json") {
+        packages.push(Package {
+            manager: "composer",
+            manifest: "composer.json",
+            name: composer["name"].as_str().map(String::from),
+            description: composer["description"].as_str().map(String::from),
+            version: composer["version"].as_str().map(String::from),
+        });
+    }
+    packages
+}
+
+/// An install command: the manager it belongs to, the tool typed, and the
+/// packages it names (empty when it installs the checkout itself, as in
+/// `cargo install --path .` or `npm install`).
+struct Command {
+    line: usize,
+    text: String,
+    manager: &'static str,
+    tool: String,
+    names: Vec<String>,
+}
+
+/// Check install commands in the README's installation and usage sections
+/// against `packages`. A manager's commands must name its package at least
+/// once; commands for a manager the project doesn't use are flagged when
+/// no command uses one it does.
+pub fn check(content: &str, packages: &[Package]) -> Vec<InstallMismatch> {
+    if packages.is_empty() {
+        return Vec::new();
+    }
+    let commands = commands(content);
+    let mut mismatches = Vec::new();
+    for package in packages {
+        let Some(name) = &package.name else {
+            continue;
+        };
+        let own: Vec<&Command> = commands
+            .iter()
+            .filter(|c| c.manager == package.manager)
+            .collect();
+        let named = own
+            .iter()
+            .any(|c| c.names.is_empty() || c.names.iter().any(|n| same(package.manager, n, name)));
+        if let Some(first) = own.first().filter(|_| !named) {
+            mismatches.push(InstallMismatch {
+                line: first.line,
+                command: first.text.clone(),
+                reason: format!(
+                    "installs `{}`, but {} names the package `{}`",
+                    first.names.join(" "),
+                    package.manifest,
+                    name
+                ),
+            });
+        }
+    }
+    let uses_detected = commands
+        .iter()
+        .any(|c| packages.iter().any(|p| p.manager == c.manager));
+    if !uses_detected {
+        // Another ecosystem's package of the same name is a legitimate extra
+        let foreign = commands.iter().find(|c| {
+            !c.names.iter().any(|n| {
+                packages
+                    .iter()
+                    .filter_map(|p| p.name.as_deref())
+                    .any(|name| same(c.manager, n, name))
+            })
+        });
+        if let Some(c) = foreign {
+            let detected: Vec<&str> = packages.iter().map(|p| manager_name(p.manager)).collect();
+            mismatches.push(InstallMismatch {
+                line: c.line,
+                command: c.text.clone(),
+                reason: format!(
+                    "uses {}, but the project builds with {}",
+                    c.tool,
+                    detected.join(" and ")
+                ),
+            });
+        }
+    }
+    mismatches.sort_by_key(|m| m.line);
+    mismatches
+}
+
+/// Install commands in installation and usage sections: code block lines,
+/// inline code spans, and crates listed under a `[dependencies]` block.
+fn commands(content: &str) -> Vec<Command> {
+    let mut commands = Vec::new();
+    let mut in_code = false;
+    let mut in_deps = false;
+    // Heading level of the installation or usage section we're in
+    let mut section: Option<usize> = None;
+    for (n, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            in_deps = false;
+            continue;
+        }
+        if !in_code {
+            if let Some(level) = heading_level(trimmed) {
+                if section.is_none_or(|s| level <= s) {
+                    let text = trimmed.trim_start_matches('#').to_lowercase();
+                    section = readme::is_install_heading(&text).then_some(level);
+                }
+                continue;
+            }
+        }
+        if section.is_none() {
+            continue;
+        }
+        let mut texts: Vec<&str> = Vec::new();
+        if in_code {
+            if trimmed.starts_with('[') {
+                in_deps = trimmed.ends_with("dependencies]");
+                continue;
+            }
+            if in_deps {
+                if let Some((name, _)) = trimmed.split_once('=') {
+                    commands.push(Command {
+                        line: n + 1,
+                        text: trimmed.to_string(),
+                        manager: "cargo",
+                        tool: "Cargo".to_string(),
+                        names: vec![name.trim().to_string()],
+                    });
+                }
+                continue;
+            }
+            texts.push(trimmed);
+        } else {
+            texts.extend(trimmed.split('`').skip(1).step_by(2));
+        }
+        for text in texts {
+            for part in text.split("&&").flat_map(|p| p.split(';')) {
+                let part = part.trim();
+                let part = part
+                    .strip_prefix("$ ")
+                    .or_else(|| part.strip_prefix("> "))
+                    .unwrap_or(part)
+                    .trim();
+                if let Some((manager, tool, names)) = parse(part) {
+                    commands.push(Command {
+                        line: n + 1,
+                        text: part.to_string(),
+                        manager,
+                        tool,
+                        names,
+                    });
+                }
+            }
+        }
+    }
+    commands
+}
+
+fn heading_level(line: &str) -> Option<usize> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    ((1..=6).contains(&level) && line[level..].starts_with(' ')).then_some(level)
+}
+
+/// Options that take a value, so the next word isn't a package.
+const VALUE_FLAGS: &[&str] = &[
+    "--version",
+    "--vers",
+    "--branch",
+    "--tag",
+    "--rev",
+    "--features",
+    "-F",
+    "--root",
+    "--registry",
+    "--bin",
+    "--target",
+    "-i",
+    "--index-url",
+    "--extra-index-url",
+    "-c",
+    "--constraint",
+    "--python",
+];
+
+/// Options that install from the checkout or a repository, not a registry.
+const LOCAL_FLAGS: &[&str] = &["--path", "--git", "-r", "--requirement", "-e", "--editable"];
+
+/// The manager, tool, and package names of an install command.
+fn parse(command: &str) -> Option<(&'static str, String, Vec<String>)> {
+    let words: Vec<&str> = command
+        .split_whitespace()
+        .skip_while(|w| *w == "sudo")
+        .collect();
+    let (manager, skip) = match words.as_slice() {
+        ["cargo", "install" | "add", ..] => ("cargo", 2),
+        ["npm", "install" | "i" | "add", ..] => ("npm", 2),
+        ["pnpm", "add" | "install" | "i", ..] => ("npm", 2),
+        ["yarn", "global", "add", ..] => ("npm", 3),
+        ["yarn", "add", ..] => ("npm", 2),
+        ["pip" | "pip3" | "pipx", "install", ..] => ("pip", 2),
+        ["python" | "python3", "-m", "pip", "install", ..] => ("pip", 4),
+        ["poetry" | "uv", "add", ..] => ("pip", 2),
+        ["uv", "pip", "install", ..] => ("pip", 3),
+        ["go", "get" | "install", ..] => ("go", 2),
+        ["composer", "require", ..] => ("composer", 2),
+        ["composer", "global", "require", ..] => ("composer", 3),
+        _ => return None,
+    };
+    let mut names = Vec::new();
+    let mut args = words[skip..].iter();
+    while let Some(arg) = args.next() {
+        if LOCAL_FLAGS.contains(arg) {
+            return Some((manager, words[0].to_string(), Vec::new()));
+        }
+        if VALUE_FLAGS.contains(arg) {
+            args.next();
+            continue;
+        }
+        if arg.starts_with('-') {
+            continue;
+        }
+        if arg.starts_with('.')
+            || arg.starts_with('/')
+            || arg.starts_with('<')
+            || arg.contains("://")
+            || arg.starts_with("git+")
+        {
+            return Some((manager, words[0].to_string(), Vec::new()));
+        }
+        names.push(strip_version(manager, arg).to_string());
+    }
+    Some((manager, words[0].to_string(), names))
+}
+
+/// A package argument without its version: `foo@1`, `@scope/foo@1`,
+/// `foo==1.0`, `foo[extra]`, `vendor/foo:^1`.
+fn strip_version<'a>(manager: &str, arg: &'a str) -> &'a str {
+    let arg = arg.trim_matches(['"', '\'']);
+    let end = match manager {
+        // A leading `@` starts a scope, not a version
+        "npm" => arg
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '@')
+            .map(|(i, _)| i),
+        "pip" => arg.find(['[', '=', '<', '>', '!', '~', ';']),
+        "composer" => arg.find(':'),
+        _ => arg.find('@'),
+    };
+    &arg[..end.unwrap_or(arg.len())]
+}
+
+/// Whether `arg` names the package `name` as the manager compares them.
+fn same(manager: &str, arg: &str, name: &str) -> bool {
+    let normalize = |s: &str| {
+        s.to_lowercase()
+            .replace(['_', '.'], "-")
+            .trim_matches('-')
+            .to_string()
+    };
+    match manager {
+        // Commands install a package or binary inside the module
+        "go" => arg == name || arg.starts_with(&format!("{}/", name)),
+        "cargo" | "pip" => normalize(arg) == normalize(name),
+        _ => arg.eq_ignore_ascii_case(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, packages, Package};
+
+    fn declared(files: &[(&str, &str)]) -> Vec<Package> {
+        packages(&|path| {
+            files
+                .iter()
+                .find(|(f, _)| *f == path)
+                .map(|(_, c)| c.to_string())
+        })
+    }
+
+    fn reasons(readme: &str, packages: &[Package]) -> Vec<(usize, String)> {
+        check(readme, packages)
+            .into_iter()
+            .map(|m| (m.line, m.reason))
+            .collect()
+    }
+
+    #[test]
+    fn manifests() {
+        let found = declared(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"demo\"\nversion = \"0.1.0\"\ndescription = \"A demo\"\n",
+            ),
+            ("go.mod", "module github.com/a/demo\n\ngo 1.22\n"),
+            ("pyproject.toml", "[tool.poetry]\nname = \"demo-py\"\n"),
+        ]);
+        let summary: Vec<_> = found
+            .iter()
+            .map(|p| (p.manager, p.name.as_deref(), p.version.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("cargo", Some("demo"), Some("0.1.0")),
+                ("pip", Some("demo-py"), None),
+                ("go", Some("github.com/a/demo"), None),
+            ]
+        );
+        assert_eq!(found[0].description.as_deref(), Some("A demo"));
+    }
+
+    #[test]
+    fn wrong_package_name() {
+        let cargo = declared(&[("Cargo.toml", "[package]\nname = \"repo_medic\"\n")]);
+        let readme =
+            "# Demo\n\n## Installation\n\n```sh\n$ cargo install repomedic --locked\n```\n";
+        assert_eq!(
+            reasons(readme, &cargo),
+            [(
+                6,
+                "installs `repomedic`, but Cargo.toml names the package `repo_medic`".to_string()
+            )]
+        );
+        // Names compare the way Cargo does, and any matching command is enough
+        let ok = "## Install\n\n`cargo install repo-medic` or `cargo install --path .`\n";
+        assert!(reasons(ok, &cargo).is_empty());
+        let deps = "## Usage\n\n```toml\n[dependencies]\nrepo_medic = \"0.1\"\n```\n";
+        assert!(reasons(deps, &cargo).is_empty());
+    }
+
+    #[test]
+    fn foreign_package_manager() {
+        let npm = declared(&[("package.json", "{\"name\": \"@acme/tool\"}")]);
+        let readme = "## Getting started\n\n```\nsudo pip install acme-tool==1.0\n```\n";
+        assert_eq!(
+            reasons(readme, &npm),
+            [(4, "uses pip, but the project builds with npm".to_string())]
+        );
+        let scoped = "## Getting started\n\n```\nnpm i -g @acme/tool@2\n```\n";
+        assert!(reasons(scoped, &npm).is_empty());
+    }
+
+    #[test]
+    fn commands_outside_install_sections() {
+        let cargo = declared(&[("Cargo.toml", "[package]\nname = \"demo\"\n")]);
+        let readme = "# Demo\n\n## Contributing\n\n```\ncargo install cargo-nextest\n```\n";
+        assert!(reasons(readme, &cargo).is_empty());
+    }
+}
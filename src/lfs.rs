@@ -0,0 +1,183 @@
+use crate::glob::glob_match;
+use serde::Serialize;
+
+/// Binary asset types that belong in Git LFS, by extension.
+pub const ASSET_TYPES: &[(&str, &[&str])] = &[
+    (
+        "Images",
+        &[
+            "psd", "png", "jpg", "jpeg", "gif", "tif", "tiff", "bmp", "webp", "exr", "hdr",
+        ],
+    ),
+    ("Video", &["mp4", "mov", "avi", "mkv", "webm"]),
+    ("Audio", &["wav", "mp3", "flac", "ogg", "aiff"]),
+    (
+        "Models",
+        &[
+            "onnx",
+            "pt",
+            "pth",
+            "ckpt",
+            "safetensors",
+            "h5",
+            "pb",
+            "tflite",
+            "gguf",
+        ],
+    ),
+    ("Data", &["parquet", "npy", "npz", "sqlite", "db"]),
+    ("3D", &["fbx", "blend", "glb", "3ds", "max"]),
+    (
+        "Archives",
+        &["zip", "tar", "gz", "tgz", "bz2", "xz", "7z", "rar"],
+    ),
+];
+
+/// Git LFS setup and the binary assets it should cover.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LfsStatus {
+    /// `.gitattributes` patterns with `filter=lfs`.
+    pub tracked: Vec<String>,
+    /// Extensions of binary assets in the tree, any size.
+    pub asset_extensions: Vec<String>,
+    /// Assets over the large-file threshold not matched by a tracked
+    /// pattern.
+    pub untracked: Vec<String>,
+}
+
+/// The asset extension of `rel`, lowercased, if it's a binary asset type.
+pub fn asset_extension(rel: &str) -> Option<String> {
+    let (_, ext) = rel.rsplit_once('.')?;
+    let ext = ext.to_lowercase();
+    ASSET_TYPES
+        .iter()
+        .any(|(_, exts)| exts.contains(&ext.as_str()))
+        .then_some(ext)
+}
+
+/// Patterns `.gitattributes` routes through the LFS filter.
+pub fn tracked_patterns(gitattributes: &str) -> Vec<String> {
+    gitattributes
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let pattern = parts.next().filter(|p| !p.starts_with('#'))?;
+            parts
+                .any(|attr| attr == "filter=lfs")
+                .then(|| pattern.to_string())
+        })
+        .collect()
+}
+
+/// Whether a `.gitattributes` pattern matches `rel`. Like `.gitignore`, a
+/// pattern without a slash matches the file name at any depth.
+pub fn matches(pattern: &str, rel: &str) -> bool {
+    match pattern.strip_prefix('/') {
+        Some(anchored) => glob_match(anchored.as_bytes(), rel.as_bytes()),
+        None if pattern.contains('/') => glob_match(pattern.as_bytes(), rel.as_bytes()),
+        None => {
+            let name = rel.rsplit('/').next().unwrap_or(rel);
+            glob_match(pattern.as_bytes(), name.as_bytes())
+        }
+    }
+}
+
+/// Check walked `files` (path, size) against the LFS patterns in
+/// `gitattributes`; assets larger than `threshold` bytes must be tracked.
+pub fn analyze(files: &[(String, u64)], gitattributes: Option<&str>, threshold: u64) -> LfsStatus {
+    let tracked = gitattributes.map(tracked_patterns).unwrap_or_default();
+    let mut status = LfsStatus {
+        tracked,
+        ..LfsStatus::default()
+    };
+    for (rel, size) in files {
+        let Some(ext) = asset_extension(rel) else {
+            continue;
+        };
+        if !status.asset_extensions.contains(&ext) {
+            status.asset_extensions.push(ext);
+        }
+        if *size > threshold && !status.tracked.iter().any(|p| matches(p, rel)) {
+            status.untracked.push(rel.clone());
+        }
+    }
+    status.asset_extensions.sort();
+    status
+}
+
+/// `.gitattributes` lines tracking `extensions` with LFS, grouped by asset
+/// type, skipping any `existing` already covers.
+pub fn attribute_lines(extensions: &[String], existing: &[String]) -> String {
+    let mut out = String::new();
+    for (kind, exts) in ASSET_TYPES {
+        let lines: Vec<String> = exts
+            .iter()
+            .filter(|e| extensions.iter().any(|x| x == *e))
+            .map(|e| format!("*.{}", e))
+            .filter(|p| !existing.contains(p))
+            .map(|p| format!("{} filter=lfs diff=lfs merge=lfs -text\n", p))
+            .collect();
+        if !lines.is_empty() {
+            out.push_str(&format!("# {} (Git LFS)\n", kind));
+            out.extend(lines);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, asset_extension, attribute_lines, matches, tracked_patterns};
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn asset_extensions() {
+        assert_eq!(asset_extension("art/Hero.PNG").as_deref(), Some("png"));
+        assert_eq!(
+            asset_extension("model.safetensors").as_deref(),
+            Some("safetensors")
+        );
+        assert_eq!(asset_extension("src/main.rs"), None);
+        assert_eq!(asset_extension("Makefile"), None);
+    }
+
+    #[test]
+    fn patterns() {
+        let attributes = "# assets\n*.psd filter=lfs diff=lfs merge=lfs -text\n*.rs text\n/data/*.db filter=lfs\n";
+        assert_eq!(tracked_patterns(attributes), ["*.psd", "/data/*.db"]);
+        assert!(matches("*.psd", "art/deep/cover.psd"));
+        assert!(matches("/data/*.db", "data/app.db"));
+        assert!(!matches("/data/*.db", "sub/data/app.db"));
+        assert!(matches("assets/**", "assets/a/b.bin"));
+    }
+
+    #[test]
+    fn large_untracked_assets() {
+        let files = vec![
+            ("art/cover.psd".to_string(), 50 * MB),
+            ("art/icon.png".to_string(), 50 * MB),
+            ("art/small.jpg".to_string(), MB),
+            ("src/big.rs".to_string(), 50 * MB),
+        ];
+        let status = analyze(&files, Some("*.psd filter=lfs\n"), 10 * MB);
+        assert_eq!(status.tracked, ["*.psd"]);
+        assert_eq!(status.asset_extensions, ["jpg", "png", "psd"]);
+        assert_eq!(status.untracked, ["art/icon.png"]);
+    }
+
+    #[test]
+    fn attribute_lines_skip_existing() {
+        let lines = attribute_lines(
+            &["png".to_string(), "psd".to_string(), "zip".to_string()],
+            &["*.psd".to_string()],
+        );
+        assert_eq!(
+            lines,
+            "# Images (Git LFS)\n*.png filter=lfs diff=lfs merge=lfs -text\n\n\
+             # Archives (Git LFS)\n*.zip filter=lfs diff=lfs merge=lfs -text\n\n"
+        );
+        assert_eq!(attribute_lines(&[], &[]), "");
+    }
+}
@@ -0,0 +1,171 @@
+//! RepoMedic - fast repository health scanner.
+//!
+//! The library exposes the scanner and scoring used by the `repomedic` CLI
+//! so other tools can embed them: [`scan`] or [`scan_with`] a repository,
+//! then read the [`ScanResult`] through [`score`] and [`issues`], or render
+//! it with the [`report`] formatters. Detectors plugged in through
+//! [`scan_with`] report [`Finding`]s built from the [`findings`] types.
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! let result = repomedic::scan(Path::new(".")).expect("scan failed");
+//! println!("score: {}", repomedic::score(&result));
+//! for issue in repomedic::issues(&result) {
+//!     println!("{:?}: {}", issue.severity, issue.message);
+//! }
+//! ```
+
+// The `#[doc(hidden)]` public modules are there for the `repomedic` binary
+// and aren't part of the library API.
+mod analyzer;
+#[doc(hidden)]
+pub mod badges;
+mod base64;
+#[doc(hidden)]
+pub mod baseline;
+#[doc(hidden)]
+pub mod batch;
+#[cfg(feature = "git-history")]
+mod blame;
+#[doc(hidden)]
+pub mod cache;
+#[doc(hidden)]
+pub mod capabilities;
+mod ci;
+mod codeowners;
+#[doc(hidden)]
+pub mod config;
+mod content;
+mod context;
+mod coverage;
+#[doc(hidden)]
+pub mod custom;
+mod deps;
+#[doc(hidden)]
+pub mod detector;
+mod diff;
+mod docker;
+mod editorconfig;
+#[doc(hidden)]
+pub mod events;
+#[doc(hidden)]
+pub mod evidence;
+#[doc(hidden)]
+pub mod fix;
+mod freshness;
+#[doc(hidden)]
+pub mod generator;
+#[doc(hidden)]
+pub mod git;
+#[doc(hidden)]
+pub mod github;
+#[doc(hidden)]
+pub mod gitignores;
+mod gitlab;
+mod glob;
+mod hbs;
+#[doc(hidden)]
+pub mod history;
+#[doc(hidden)]
+pub mod hook;
+#[cfg(feature = "html")]
+mod html;
+mod iac;
+mod ignore;
+mod indent;
+mod install;
+mod lfs;
+#[doc(hidden)]
+pub mod license;
+mod line_endings;
+mod links;
+#[doc(hidden)]
+pub mod locale;
+#[doc(hidden)]
+pub mod migrate;
+mod naming;
+mod packages;
+#[doc(hidden)]
+pub mod pipeline;
+mod plugins;
+mod policy;
+#[doc(hidden)]
+pub mod profile;
+mod readme;
+mod regex;
+#[doc(hidden)]
+pub mod remote;
+pub mod report;
+#[doc(hidden)]
+pub mod rules;
+mod sample;
+mod sbom;
+#[doc(hidden)]
+pub mod scanner;
+mod secrets;
+#[doc(hidden)]
+pub mod selftest;
+mod sops;
+#[doc(hidden)]
+pub mod suppress;
+mod templates;
+mod terraform;
+#[cfg(test)]
+mod testutil;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod tui;
+#[doc(hidden)]
+pub mod watch;
+mod workflows;
+mod yaml;
+
+pub use config::Config;
+pub use context::ScanContext;
+pub use detector::{Category, Detector, Finding, Registry};
+pub use scanner::{
+    scan, scan_with, Attribution, Issue, ScanError, ScanErrorKind, ScanResult, Severity,
+};
+
+/// Payloads of the [`Finding`] variants.
+pub mod findings {
+    pub use crate::badges::StaleBadge;
+    pub use crate::ci::CiAdvice;
+    pub use crate::codeowners::{Coverage, Problem as CodeownersProblem};
+    pub use crate::custom::Violation;
+    pub use crate::deps::DependencyProblem;
+    pub use crate::detector::Flag;
+    pub use crate::docker::ContainerProblem;
+    pub use crate::editorconfig::Violation as EditorconfigViolation;
+    pub use crate::freshness::LockFreshness;
+    pub use crate::git::{GitHistory, HistoryBlob};
+    pub use crate::github::CiHealth;
+    pub use crate::iac::{HelmChart, K8sSecret, UnboundedWorkload};
+    pub use crate::indent::Indent;
+    pub use crate::lfs::LfsStatus;
+    pub use crate::license::License;
+    pub use crate::line_endings::LineEndings;
+    pub use crate::links::DeadLink;
+    pub use crate::naming::NameSource;
+    pub use crate::packages::PackageProblem;
+    pub use crate::readme::ReadmeQuality;
+    pub use crate::remote::RemoteInfo;
+    pub use crate::sample::Estimate;
+    pub use crate::scanner::{BuildSystem, CISystem, Language, LanguageFiles, PotentialSecret};
+    pub use crate::sops::UnencryptedFile;
+    pub use crate::suppress::Rule;
+    pub use crate::templates::Problem as TemplateProblem;
+    pub use crate::terraform::TerraformProblem;
+    pub use crate::workflows::Workflow;
+}
+
+/// Repository health score (0-100) for a scan result.
+pub fn score(result: &ScanResult) -> u8 {
+    report::calculate_score(result)
+}
+
+/// All issues found in a scan result, in report order.
+pub fn issues(result: &ScanResult) -> Vec<Issue> {
+    result.issues.clone()
+}
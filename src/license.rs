@@ -0,0 +1,405 @@
+use serde::Serialize;
+
+/// License file names checked at the root, in lookup order. Dual-licensed
+/// projects often ship `LICENSE-MIT` and `LICENSE-APACHE` instead of one
+/// `LICENSE`; GPL projects often use `COPYING`.
+pub const FILES: &[&str] = &[
+    "LICENSE",
+    "LICENSE.md",
+    "LICENSE.txt",
+    "LICENCE",
+    "license",
+    "License",
+    "COPYING",
+    "LICENSE-MIT",
+    "LICENSE-APACHE",
+];
+
+/// Longest text (in words) still read as a bare license heading.
+const HEADING_WORDS: usize = 40;
+
+/// Text fingerprint of a well-known license.
+///
+/// Phrases are compared after `normalize`, so punctuation, case, and line
+/// wrapping don't matter.
+struct Fingerprint {
+    spdx: &'static str,
+    /// Heading or name that appears even when the body is cut short.
+    title: &'static str,
+    /// Phrases that together identify the license.
+    markers: &'static [&'static str],
+    /// Phrase near the end of the canonical text.
+    ending: &'static str,
+}
+
+/// Known licenses. GNU licenses are told apart by their heading (name,
+/// version, and date), since their texts mention each other. Variants that
+/// extend another license's wording (BSD-3-Clause over BSD-2-Clause, ISC
+/// over 0BSD's grant) come first.
+const FINGERPRINTS: &[Fingerprint] = &[
+    Fingerprint {
+        spdx: "AGPL-3.0",
+        title: "gnu affero general public license",
+        markers: &["gnu affero general public license version 3 19 november 2007"],
+        ending: "end of terms and conditions",
+    },
+    Fingerprint {
+        spdx: "LGPL-3.0",
+        title: "gnu lesser general public license",
+        markers: &["gnu lesser general public license version 3 29 june 2007"],
+        ending: "authorization for you to choose that version for the library",
+    },
+    Fingerprint {
+        spdx: "LGPL-2.1",
+        title: "gnu lesser general public license",
+        markers: &["gnu lesser general public license version 2 1 february 1999"],
+        ending: "end of terms and conditions",
+    },
+    Fingerprint {
+        spdx: "GPL-3.0",
+        title: "gnu general public license",
+        markers: &["gnu general public license version 3 29 june 2007"],
+        ending: "end of terms and conditions",
+    },
+    Fingerprint {
+        spdx: "LGPL-2.0",
+        title: "gnu library general public license",
+        markers: &["gnu library general public license version 2 june 1991"],
+        ending: "end of terms and conditions",
+    },
+    Fingerprint {
+        spdx: "GPL-2.0",
+        title: "gnu general public license",
+        markers: &["gnu general public license version 2 june 1991"],
+        ending: "end of terms and conditions",
+    },
+    Fingerprint {
+        spdx: "Apache-2.0",
+        title: "apache license",
+        markers: &["apache license", "version 2 0 january 2004"],
+        ending: "end of terms and conditions",
+    },
+    Fingerprint {
+        spdx: "MPL-2.0",
+        title: "mozilla public license",
+        markers: &["mozilla public license version 2 0"],
+        ending: "as defined by the mozilla public license v 2 0",
+    },
+    Fingerprint {
+        spdx: "BSD-3-Clause",
+        title: "bsd 3 clause license",
+        markers: &[
+            "redistribution and use in source and binary forms with or without modification are permitted",
+            "neither the name of",
+        ],
+        ending: "even if advised of the possibility of such damage",
+    },
+    Fingerprint {
+        spdx: "BSD-2-Clause",
+        title: "bsd 2 clause license",
+        markers: &[
+            "redistribution and use in source and binary forms with or without modification are permitted",
+        ],
+        ending: "even if advised of the possibility of such damage",
+    },
+    Fingerprint {
+        spdx: "MIT",
+        title: "mit license",
+        markers: &["permission is hereby granted free of charge to any person obtaining a copy"],
+        ending: "or other dealings in the software",
+    },
+    Fingerprint {
+        spdx: "ISC",
+        title: "isc license",
+        markers: &[
+            "permission to use copy modify and or distribute this software for any purpose",
+            "provided that the above copyright notice and this permission notice appear in all copies",
+        ],
+        ending: "arising out of or in connection with the use or performance of this software",
+    },
+    Fingerprint {
+        spdx: "0BSD",
+        title: "zero clause bsd",
+        markers: &["permission to use copy modify and or distribute this software for any purpose"],
+        ending: "arising out of or in connection with the use or performance of this software",
+    },
+    Fingerprint {
+        spdx: "CC0-1.0",
+        title: "cc0 1 0 universal",
+        markers: &["cc0 1 0 universal", "affirmer"],
+        ending: "this cc0 or use of the work",
+    },
+    Fingerprint {
+        spdx: "Unlicense",
+        title: "unlicense",
+        markers: &["this is free and unencumbered software released into the public domain"],
+        ending: "for more information please refer to",
+    },
+];
+
+/// A license `generate` can write, with its canonical text bundled.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Choice {
+    /// Name accepted by `--license-type` and `license_type` in the config.
+    pub key: &'static str,
+    pub spdx: &'static str,
+    /// Human-readable name, as choosealicense.com gives it.
+    pub name: &'static str,
+    /// Full text, with `{year}` and `{holder}` in the copyright line of
+    /// licenses that have one.
+    text: &'static str,
+}
+
+/// License generated when none is chosen.
+pub const DEFAULT_CHOICE: &Choice = &CHOICES[0];
+
+/// Licenses available for generation.
+pub const CHOICES: &[Choice] = &[
+    Choice {
+        key: "mit",
+        spdx: "MIT",
+        name: "MIT License",
+        text: include_str!("licenses/mit.txt"),
+    },
+    Choice {
+        key: "apache-2.0",
+        spdx: "Apache-2.0",
+        name: "Apache License 2.0",
+        text: include_str!("licenses/apache-2.0.txt"),
+    },
+    Choice {
+        key: "gpl-2.0",
+        spdx: "GPL-2.0",
+        name: "GNU General Public License v2.0",
+        text: include_str!("licenses/gpl-2.0.txt"),
+    },
+    Choice {
+        key: "gpl-3.0",
+        spdx: "GPL-3.0",
+        name: "GNU General Public License v3.0",
+        text: include_str!("licenses/gpl-3.0.txt"),
+    },
+    Choice {
+        key: "lgpl-2.1",
+        spdx: "LGPL-2.1",
+        name: "GNU Lesser General Public License v2.1",
+        text: include_str!("licenses/lgpl-2.1.txt"),
+    },
+    Choice {
+        key: "mpl-2.0",
+        spdx: "MPL-2.0",
+        name: "Mozilla Public License 2.0",
+        text: include_str!("licenses/mpl-2.0.txt"),
+    },
+    Choice {
+        key: "bsd-2-clause",
+        spdx: "BSD-2-Clause",
+        name: "BSD 2-Clause \"Simplified\" License",
+        text: include_str!("licenses/bsd-2-clause.txt"),
+    },
+    Choice {
+        key: "bsd-3-clause",
+        spdx: "BSD-3-Clause",
+        name: "BSD 3-Clause \"New\" or \"Revised\" License",
+        text: include_str!("licenses/bsd-3-clause.txt"),
+    },
+    Choice {
+        key: "isc",
+        spdx: "ISC",
+        name: "ISC License",
+        text: include_str!("licenses/isc.txt"),
+    },
+    Choice {
+        key: "unlicense",
+        spdx: "Unlicense",
+        name: "The Unlicense",
+        text: include_str!("licenses/unlicense.txt"),
+    },
+    Choice {
+        key: "cc0-1.0",
+        spdx: "CC0-1.0",
+        name: "Creative Commons Zero v1.0 Universal",
+        text: include_str!("licenses/cc0-1.0.txt"),
+    },
+];
+
+impl Choice {
+    /// The license for `name`, by key or SPDX identifier, ignoring case.
+    pub fn find(name: &str) -> Option<&'static Choice> {
+        CHOICES
+            .iter()
+            .find(|c| c.key.eq_ignore_ascii_case(name) || c.spdx.eq_ignore_ascii_case(name))
+    }
+
+    /// Parse a `--license-type` value, listing the valid names on error.
+    pub fn parse(name: &str) -> Result<&'static Choice, String> {
+        Choice::find(name).ok_or_else(|| {
+            let keys: Vec<&str> = CHOICES.iter().map(|c| c.key).collect();
+            format!(
+                "unknown license type '{}' (expected one of: {})",
+                name,
+                keys.join(", ")
+            )
+        })
+    }
+
+    /// The license text for `holder` in `year`.
+    pub fn render(&self, year: u32, holder: &str) -> String {
+        self.text
+            .replace("{year}", &year.to_string())
+            .replace("{holder}", holder)
+    }
+}
+
+/// A license file at the root and what it was identified as.
+#[derive(Debug, Clone, Serialize)]
+pub struct License {
+    pub file: String,
+    /// SPDX identifier, when the text or an `SPDX-License-Identifier` line
+    /// is recognized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spdx: Option<String>,
+    /// The text starts like `spdx` but is missing the rest of it.
+    pub truncated: bool,
+}
+
+impl License {
+    /// Identify the license text in `content`, read from `file`.
+    pub fn identify(file: &str, content: &str) -> Self {
+        let (spdx, truncated) = match spdx_tag(content) {
+            Some(id) => (Some(id), false),
+            None => match fingerprint(&normalize(content)) {
+                Some((id, truncated)) => (Some(id.to_string()), truncated),
+                None => (None, false),
+            },
+        };
+        License {
+            file: file.to_string(),
+            spdx,
+            truncated,
+        }
+    }
+}
+
+/// SPDX expression for a set of license files: recognized identifiers
+/// joined with `OR` (the usual reading of `LICENSE-MIT` + `LICENSE-APACHE`).
+pub fn expression(licenses: &[License]) -> Option<String> {
+    let mut ids: Vec<&str> = Vec::new();
+    for id in licenses.iter().filter_map(|l| l.spdx.as_deref()) {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    (!ids.is_empty()).then(|| ids.join(" OR "))
+}
+
+/// Value of an `SPDX-License-Identifier:` line.
+fn spdx_tag(content: &str) -> Option<String> {
+    content.lines().find_map(|l| {
+        let (_, rest) = l.split_once("SPDX-License-Identifier:")?;
+        let id = rest.trim().trim_end_matches("*/").trim();
+        (!id.is_empty()).then(|| id.to_string())
+    })
+}
+
+/// The fingerprint matching `text` (normalized), and whether it is cut short.
+fn fingerprint(text: &str) -> Option<(&'static str, bool)> {
+    if let Some(f) = FINGERPRINTS
+        .iter()
+        .find(|f| f.markers.iter().all(|m| text.contains(m)))
+    {
+        return Some((f.spdx, !text.contains(f.ending)));
+    }
+    // Only a heading (`MIT License` plus a copyright line) is left
+    if text.split(' ').count() > HEADING_WORDS {
+        return None;
+    }
+    FINGERPRINTS
+        .iter()
+        .find(|f| text.contains(f.title))
+        .map(|f| (f.spdx, true))
+}
+
+/// Lowercase words separated by single spaces, without punctuation.
+fn normalize(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    for word in content
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        out.push_str(&word.to_lowercase());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expression, Choice, License, CHOICES};
+
+    fn identify(content: &str) -> (Option<String>, bool) {
+        let license = License::identify("LICENSE", content);
+        (license.spdx, license.truncated)
+    }
+
+    #[test]
+    fn bundled_texts_identify_as_themselves() {
+        for choice in CHOICES {
+            let text = choice.render(2026, "Jane Doe");
+            assert!(!text.contains("{year}") && !text.contains("{holder}"));
+            assert_eq!(
+                identify(&text),
+                (Some(choice.spdx.to_string()), false),
+                "{}",
+                choice.key
+            );
+        }
+    }
+
+    #[test]
+    fn truncated_and_unknown_texts() {
+        let mit = Choice::find("MIT").unwrap().render(2026, "Jane Doe");
+        let cut = &mit[..mit.len() / 2];
+        assert_eq!(identify(cut), (Some("MIT".to_string()), true));
+        assert_eq!(
+            identify("MIT License\n\nCopyright (c) 2026 Jane Doe\n"),
+            (Some("MIT".to_string()), true)
+        );
+        assert_eq!(identify("All rights reserved.\n"), (None, false));
+    }
+
+    #[test]
+    fn spdx_tags() {
+        assert_eq!(
+            identify("/* SPDX-License-Identifier: MIT OR Apache-2.0 */\n"),
+            (Some("MIT OR Apache-2.0".to_string()), false)
+        );
+    }
+
+    #[test]
+    fn dual_licenses() {
+        let licenses = [
+            License::identify(
+                "LICENSE-MIT",
+                &Choice::find("mit").unwrap().render(2026, "x"),
+            ),
+            License::identify(
+                "LICENSE-APACHE",
+                &Choice::find("apache-2.0").unwrap().render(2026, "x"),
+            ),
+            License::identify("COPYING", "MIT License\n"),
+        ];
+        assert_eq!(expression(&licenses).as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(expression(&[]), None);
+    }
+
+    #[test]
+    fn choices() {
+        assert_eq!(Choice::parse("BSD-3-Clause").unwrap().key, "bsd-3-clause");
+        assert!(Choice::parse("wtfpl")
+            .unwrap_err()
+            .starts_with("unknown license type 'wtfpl' (expected one of: mit,"));
+    }
+}
@@ -0,0 +1,290 @@
+use crate::lfs;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+/// Extensions that only work with CRLF (cmd.exe, Visual Studio solutions).
+const CRLF_EXTENSIONS: &[&str] = &["bat", "cmd", "sln"];
+
+/// Extensions that only work with LF (shebang scripts).
+const LF_EXTENSIONS: &[&str] = &["sh", "bash", "zsh"];
+
+/// Extensions where a UTF-8 BOM is expected: Windows PowerShell reads BOM-less
+/// scripts as ANSI, and Visual Studio writes solutions with one.
+const BOM_EXTENSIONS: &[&str] = &["ps1", "psm1", "psd1", "sln"];
+
+/// The line endings a text file uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Ending {
+    Lf,
+    Crlf,
+    /// Both in one file.
+    Mixed,
+}
+
+impl Ending {
+    /// Classify `content`; `None` without any line break.
+    pub fn of(content: &str) -> Option<Self> {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+        match (lf, crlf) {
+            (0, 0) => None,
+            (_, 0) => Some(Ending::Lf),
+            (0, _) => Some(Ending::Crlf),
+            _ => Some(Ending::Mixed),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ending::Lf => "LF",
+            Ending::Crlf => "CRLF",
+            Ending::Mixed => "mixed",
+        }
+    }
+}
+
+/// A file whose endings differ from the rest of the repository, or from
+/// what `.gitattributes` sets for it.
+#[derive(Debug, Clone, Serialize)]
+pub struct InconsistentFile {
+    pub file: String,
+    pub ending: Ending,
+}
+
+/// Line endings and byte order marks across the tree's text files.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LineEndings {
+    pub lf_files: usize,
+    pub crlf_files: usize,
+    /// `.gitattributes` normalizes every text file (`* text=auto` or `* text`).
+    pub normalized: bool,
+    pub inconsistent: Vec<InconsistentFile>,
+    /// Text files starting with a UTF-8 byte order mark.
+    pub bom_files: Vec<String>,
+}
+
+impl LineEndings {
+    /// The style most files use; LF, Git's own, on a tie.
+    pub fn dominant(&self) -> Ending {
+        if self.crlf_files > self.lf_files {
+            Ending::Crlf
+        } else {
+            Ending::Lf
+        }
+    }
+
+    /// The `.gitattributes` line that normalizes the repository: LF
+    /// checkouts everywhere for an LF repository, the platform's endings
+    /// otherwise.
+    pub fn text_rule(&self) -> &'static str {
+        match self.dominant() {
+            Ending::Crlf => "* text=auto",
+            _ => "* text=auto eol=lf",
+        }
+    }
+
+    /// `.gitattributes` lines for the text rule, keeping CRLF for
+    /// Windows-only files when everything else checks out as LF.
+    pub fn attribute_lines(&self) -> String {
+        let mut lines = format!("{}\n", self.text_rule());
+        if self.dominant() == Ending::Lf {
+            for ext in CRLF_EXTENSIONS {
+                lines.push_str(&format!("*.{} text eol=crlf\n", ext));
+            }
+        }
+        lines
+    }
+}
+
+/// What `.gitattributes` says about one file's endings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Declared {
+    Eol(Ending),
+    /// `-text` or `binary`: Git leaves the bytes alone.
+    Untouched,
+}
+
+/// Whether `.gitattributes` normalizes every text file.
+pub fn normalizes(gitattributes: &str) -> bool {
+    gitattributes.lines().any(|line| {
+        let mut parts = line.split_whitespace();
+        parts.next() == Some("*") && parts.any(|attr| attr == "text" || attr == "text=auto")
+    })
+}
+
+/// The last `eol=`, `-text`, or `binary` setting matching `rel`.
+fn declared(gitattributes: &str, rel: &str) -> Option<Declared> {
+    let mut found = None;
+    for line in gitattributes.lines() {
+        let mut parts = line.split_whitespace();
+        let Some(pattern) = parts.next().filter(|p| !p.starts_with('#')) else {
+            continue;
+        };
+        if !lfs::matches(pattern, rel) {
+            continue;
+        }
+        for attr in parts {
+            match attr {
+                "eol=lf" => found = Some(Declared::Eol(Ending::Lf)),
+                "eol=crlf" => found = Some(Declared::Eol(Ending::Crlf)),
+                "-text" | "binary" => found = Some(Declared::Untouched),
+                _ => {}
+            }
+        }
+    }
+    found
+}
+
+/// Compare each text file's `(path, ending)` against the repository's
+/// dominant style, or the `eol` `.gitattributes` sets for it. Windows-only
+/// and shell scripts keep the endings they need.
+pub fn analyze(files: &[(String, Ending)], gitattributes: Option<&str>) -> LineEndings {
+    let attributes = gitattributes.unwrap_or_default();
+    let mut status = LineEndings {
+        normalized: normalizes(attributes),
+        ..LineEndings::default()
+    };
+    let mut checked = Vec::new();
+    for (rel, ending) in files {
+        let declared = declared(attributes, rel);
+        if declared == Some(Declared::Untouched) {
+            continue;
+        }
+        match ending {
+            Ending::Lf => status.lf_files += 1,
+            Ending::Crlf => status.crlf_files += 1,
+            Ending::Mixed => {}
+        }
+        checked.push((rel, *ending, declared));
+    }
+    let dominant = status.dominant();
+    for (rel, ending, declared) in checked {
+        let ext = rel.rsplit_once('.').map_or("", |(_, e)| e).to_lowercase();
+        let expected = match declared {
+            Some(Declared::Eol(eol)) => eol,
+            _ if CRLF_EXTENSIONS.contains(&ext.as_str()) => Ending::Crlf,
+            _ if LF_EXTENSIONS.contains(&ext.as_str()) => Ending::Lf,
+            _ => dominant,
+        };
+        if ending != expected {
+            status.inconsistent.push(InconsistentFile {
+                file: rel.clone(),
+                ending,
+            });
+        }
+    }
+    status
+}
+
+/// Whether the file at `path` starts with a UTF-8 byte order mark where one
+/// isn't expected.
+pub fn has_unexpected_bom(path: &Path) -> io::Result<bool> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    if BOM_EXTENSIONS.contains(&ext.as_str()) {
+        return Ok(false);
+    }
+    let mut head = Vec::with_capacity(3);
+    File::open(path)?.take(3).read_to_end(&mut head)?;
+    Ok(head == [0xEF, 0xBB, 0xBF])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{analyze, has_unexpected_bom, normalizes, Ending, LineEndings};
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    fn files(list: &[(&str, Ending)]) -> Vec<(String, Ending)> {
+        list.iter().map(|(f, e)| (f.to_string(), *e)).collect()
+    }
+
+    fn flagged(status: &LineEndings) -> Vec<&str> {
+        status
+            .inconsistent
+            .iter()
+            .map(|f| f.file.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn classifies_endings() {
+        assert_eq!(Ending::of("a\nb\n"), Some(Ending::Lf));
+        assert_eq!(Ending::of("a\r\nb\r\n"), Some(Ending::Crlf));
+        assert_eq!(Ending::of("a\r\nb\n"), Some(Ending::Mixed));
+        assert_eq!(Ending::of("no break"), None);
+    }
+
+    #[test]
+    fn flags_files_against_the_dominant_style() {
+        let status = analyze(
+            &files(&[
+                ("a.rs", Ending::Lf),
+                ("b.rs", Ending::Lf),
+                ("c.rs", Ending::Crlf),
+                ("d.rs", Ending::Mixed),
+                ("run.bat", Ending::Crlf),
+                ("setup.sh", Ending::Lf),
+            ]),
+            None,
+        );
+        assert_eq!(status.dominant(), Ending::Lf);
+        assert_eq!(flagged(&status), ["c.rs", "d.rs"]);
+        assert!(!status.normalized);
+        assert_eq!(
+            status.attribute_lines(),
+            "* text=auto eol=lf\n*.bat text eol=crlf\n*.cmd text eol=crlf\n*.sln text eol=crlf\n"
+        );
+    }
+
+    #[test]
+    fn windows_scripts_and_shell_scripts_keep_their_endings() {
+        let status = analyze(
+            &files(&[
+                ("a.cs", Ending::Crlf),
+                ("b.cs", Ending::Crlf),
+                ("build.sh", Ending::Crlf),
+                ("run.cmd", Ending::Lf),
+            ]),
+            None,
+        );
+        assert_eq!(status.dominant(), Ending::Crlf);
+        assert_eq!(status.text_rule(), "* text=auto");
+        assert_eq!(flagged(&status), ["build.sh", "run.cmd"]);
+    }
+
+    #[test]
+    fn gitattributes_settings_win() {
+        let attributes = "* text=auto\n*.ps1 eol=crlf\nvendor/** -text\n";
+        assert!(normalizes(attributes));
+        assert!(!normalizes("*.rs text\n"));
+        let status = analyze(
+            &files(&[
+                ("a.rs", Ending::Lf),
+                ("tool.ps1", Ending::Crlf),
+                ("vendor/lib.c", Ending::Crlf),
+                ("other.ps1", Ending::Lf),
+            ]),
+            Some(attributes),
+        );
+        assert_eq!(status.crlf_files, 1);
+        assert_eq!(flagged(&status), ["other.ps1"]);
+    }
+
+    #[test]
+    fn byte_order_marks() {
+        let dir = TempDir::new("bom");
+        fs::write(dir.join("a.txt"), b"\xEF\xBB\xBFhello").unwrap();
+        fs::write(dir.join("b.ps1"), b"\xEF\xBB\xBFhello").unwrap();
+        fs::write(dir.join("c.txt"), b"hi").unwrap();
+        assert!(has_unexpected_bom(&dir.join("a.txt")).unwrap());
+        assert!(!has_unexpected_bom(&dir.join("b.ps1")).unwrap());
+        assert!(!has_unexpected_bom(&dir.join("c.txt")).unwrap());
+    }
+}
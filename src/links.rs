@@ -0,0 +1,301 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+#[cfg(feature = "network")]
+use std::thread;
+
+/// Seconds to wait for each HTTP link before calling it dead.
+#[cfg(feature = "network")]
+pub const HTTP_TIMEOUT_SECS: u64 = 10;
+
+/// HTTP links checked at once.
+#[cfg(feature = "network")]
+const HTTP_WORKERS: usize = 8;
+
+/// A link in a Markdown file whose target doesn't exist or doesn't answer.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeadLink {
+    pub file: String,
+    pub line: usize,
+    pub target: String,
+    pub reason: String,
+}
+
+/// Markdown files whose links are checked: those at the root and under
+/// `docs/` or `doc/`, from the walked (root-relative) file list.
+pub fn markdown_files<'a>(walked: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+    walked
+        .filter(|f| {
+            let lower = f.to_lowercase();
+            (lower.ends_with(".md") || lower.ends_with(".markdown"))
+                && (!f.contains('/') || f.starts_with("docs/") || f.starts_with("doc/"))
+        })
+        .collect()
+}
+
+/// Targets of Markdown links, images, and reference definitions on a line.
+pub fn targets(line: &str) -> Vec<&str> {
+    let mut targets = Vec::new();
+    let mut rest = line;
+    while let Some(i) = rest.find("](") {
+        let after = &rest[i + 2..];
+        let end = after.find(')').unwrap_or(after.len());
+        targets.push(after[..end].trim());
+        rest = &after[end..];
+    }
+    // [name]: target
+    let trimmed = line.trim_start();
+    if trimmed.starts_with('[') && !trimmed.starts_with("[!") {
+        if let Some((_, target)) = trimmed.split_once("]: ") {
+            targets.push(target.trim());
+        }
+    }
+    targets
+        .into_iter()
+        .filter_map(|t| {
+            // Drop an optional title: [x](path "title")
+            let t = t.split_whitespace().next()?;
+            Some(t.trim_start_matches('<').trim_end_matches('>'))
+        })
+        .collect()
+}
+
+/// Whether a link leaves the repository (a URL, `mailto:`, or an anchor
+/// within the same page).
+pub fn is_external(target: &str) -> bool {
+    target.is_empty()
+        || target.starts_with('#')
+        || target.starts_with("//")
+        || target.contains("://")
+        || target.starts_with("mailto:")
+}
+
+/// Whether a relative link in `file` (root-relative) points at nothing.
+/// Paths starting with `/` resolve from the root.
+pub fn is_missing(root: &Path, file: &str, target: &str) -> bool {
+    if is_external(target) {
+        return false;
+    }
+    let path = target.split(['#', '?']).next().unwrap_or(target);
+    if path.is_empty() {
+        return false;
+    }
+    let path = path.replace("%20", " ");
+    let base = match path.strip_prefix('/') {
+        Some(_) => root.to_path_buf(),
+        None => root
+            .join(file)
+            .parent()
+            .map_or(root.to_path_buf(), Path::to_path_buf),
+    };
+    !base.join(path.trim_start_matches('/')).exists()
+}
+
+/// Links in Markdown `content` with their line numbers, skipping fenced
+/// code blocks.
+pub fn extract(content: &str) -> Vec<(usize, &str)> {
+    let mut links = Vec::new();
+    let mut in_code = false;
+    for (n, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if !in_code {
+            links.extend(targets(line).into_iter().map(|t| (n + 1, t)));
+        }
+    }
+    links
+}
+
+/// Check relative links in each `(file, content)`, and HTTP links too when
+/// `http` is set. `skip_relative` names a file whose relative links are
+/// checked elsewhere (the README, by its quality check).
+pub fn check(
+    root: &Path,
+    files: &[(&str, &str)],
+    skip_relative: Option<&str>,
+    http: bool,
+) -> Vec<DeadLink> {
+    let mut dead = Vec::new();
+    let mut urls: Vec<(&str, usize, &str)> = Vec::new();
+    for (file, content) in files {
+        for (line, target) in extract(content) {
+            if target.starts_with("http://") || target.starts_with("https://") {
+                urls.push((file, line, target));
+            } else if Some(*file) != skip_relative && is_missing(root, file, target) {
+                dead.push(DeadLink {
+                    file: file.to_string(),
+                    line,
+                    target: target.to_string(),
+                    reason: "no such file".to_string(),
+                });
+            }
+        }
+    }
+    if http {
+        let mut unique: Vec<&str> = urls.iter().map(|(_, _, url)| *url).collect();
+        unique.sort_unstable();
+        unique.dedup();
+        let failures = probe_all(&unique);
+        for (file, line, url) in urls {
+            if let Some(reason) = failures.get(url) {
+                dead.push(DeadLink {
+                    file: file.to_string(),
+                    line,
+                    target: url.to_string(),
+                    reason: reason.clone(),
+                });
+            }
+        }
+    }
+    dead.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    dead
+}
+
+/// Whether `curl`, used for HTTP links, can be run. Always false in builds
+/// without the `network` feature.
+pub fn curl_available() -> bool {
+    cfg!(feature = "network") && Command::new("curl").arg("--version").output().is_ok()
+}
+
+/// Probe URLs in parallel; returns the failing ones with a reason.
+#[cfg(feature = "network")]
+fn probe_all(urls: &[&str]) -> HashMap<String, String> {
+    let chunk = urls.len().div_ceil(HTTP_WORKERS).max(1);
+    thread::scope(|s| {
+        let handles: Vec<_> = urls
+            .chunks(chunk)
+            .map(|batch| {
+                s.spawn(move || {
+                    batch
+                        .iter()
+                        .filter_map(|url| Some((url.to_string(), probe(url)?)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// Why `url` is dead, or `None` if it answers. Servers that refuse bots
+/// (401, 403, 429) are given the benefit of the doubt.
+#[cfg(feature = "network")]
+fn probe(url: &str) -> Option<String> {
+    let output = Command::new("curl")
+        .args(["-s", "-o", "/dev/null", "-L", "-A", "repomedic"])
+        .args(["--max-time", &HTTP_TIMEOUT_SECS.to_string()])
+        .args(["-w", "%{http_code}"])
+        .arg(url)
+        .output();
+    // A missing curl is reported once by the caller
+    let output = output.ok()?;
+    let code: u16 = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse()
+        .unwrap_or(0);
+    match code {
+        0 if output.status.code() == Some(28) => {
+            Some(format!("no response within {}s", HTTP_TIMEOUT_SECS))
+        }
+        0 => Some("could not connect".to_string()),
+        401 | 403 | 429 => None,
+        400.. => Some(format!("HTTP {}", code)),
+        _ => None,
+    }
+}
+
+#[cfg(not(feature = "network"))]
+fn probe_all(_urls: &[&str]) -> HashMap<String, String> {
+    HashMap::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, extract, is_external, markdown_files, targets};
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    #[test]
+    fn link_targets() {
+        assert_eq!(
+            targets("See [docs](docs/a.md \"Docs\") and ![logo](<img/logo.png>)."),
+            ["docs/a.md", "img/logo.png"]
+        );
+        assert_eq!(
+            targets("[ref]: https://example.com"),
+            ["https://example.com"]
+        );
+        assert!(targets("> [!NOTE]: careful").is_empty());
+    }
+
+    #[test]
+    fn external_targets() {
+        for target in [
+            "",
+            "#usage",
+            "//cdn.example.com/x.js",
+            "https://a.b",
+            "mailto:me@a.b",
+        ] {
+            assert!(is_external(target), "{:?}", target);
+        }
+        assert!(!is_external("docs/guide.md#setup"));
+    }
+
+    #[test]
+    fn fenced_code_is_skipped() {
+        let content = "[a](a.md)\n```\n[b](b.md)\n```\n[c](c.md)\n";
+        assert_eq!(extract(content), [(1, "a.md"), (5, "c.md")]);
+    }
+
+    #[test]
+    fn checked_markdown_files() {
+        let walked = [
+            "README.md",
+            "docs/guide.MD",
+            "src/notes.md",
+            "doc/x.markdown",
+            "a.txt",
+        ];
+        assert_eq!(
+            markdown_files(walked.into_iter()),
+            ["README.md", "docs/guide.MD", "doc/x.markdown"]
+        );
+    }
+
+    #[test]
+    fn missing_relative_links() {
+        let dir = TempDir::new("links");
+        fs::create_dir_all(dir.join("docs")).unwrap();
+        fs::write(dir.join("docs/guide.md"), "").unwrap();
+        fs::write(dir.join("my file.md"), "").unwrap();
+        let readme = "[g](docs/guide.md#setup)\n[s](my%20file.md)\n[x](missing.md)\n";
+        let guide = "[up](../README.md)\n[abs](/docs/guide.md)\n[gone](other.md)\n";
+        let dead: Vec<_> = check(
+            &dir,
+            &[("README.md", readme), ("docs/guide.md", guide)],
+            None,
+            false,
+        )
+        .into_iter()
+        .map(|d| (d.file, d.line, d.target))
+        .collect();
+        assert_eq!(
+            dead,
+            [
+                ("README.md".to_string(), 3, "missing.md".to_string()),
+                ("docs/guide.md".to_string(), 1, "../README.md".to_string()),
+                ("docs/guide.md".to_string(), 3, "other.md".to_string()),
+            ]
+        );
+        let skipped = check(&dir, &[("README.md", readme)], Some("README.md"), false);
+        assert!(skipped.is_empty());
+    }
+}
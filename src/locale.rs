@@ -0,0 +1,288 @@
+use crate::git::date_from_unix;
+use std::env;
+
+/// How a locale writes a calendar date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DateStyle {
+    /// `2026-10-17`
+    Iso,
+    /// `Oct 17, 2026`
+    MonthDayYear,
+    /// `17 Oct 2026`
+    DayMonthYear,
+    /// `17.10.2026`
+    Dotted,
+    /// `17/10/2026`
+    Slashed,
+    /// `17-10-2026`
+    Dashed,
+    /// `2026/10/17`
+    YearFirst,
+}
+
+/// Conventions for dates and numbers in human-readable exports. Machine
+/// formats (JSON, CSV, SARIF) always use ISO-8601 and plain numbers.
+#[derive(Debug, PartialEq)]
+pub struct Locale {
+    /// BCP 47 tag, or `iso`.
+    pub tag: &'static str,
+    date: DateStyle,
+    /// Thousands separator (empty for none).
+    group: &'static str,
+    decimal: char,
+}
+
+/// ISO-8601 dates and ungrouped numbers; the fallback for unknown locales.
+pub const ISO: Locale = Locale {
+    tag: "iso",
+    date: DateStyle::Iso,
+    group: "",
+    decimal: '.',
+};
+
+pub static LOCALES: &[Locale] = &[
+    ISO,
+    Locale {
+        tag: "en-US",
+        date: DateStyle::MonthDayYear,
+        group: ",",
+        decimal: '.',
+    },
+    Locale {
+        tag: "en-GB",
+        date: DateStyle::DayMonthYear,
+        group: ",",
+        decimal: '.',
+    },
+    Locale {
+        tag: "de-DE",
+        date: DateStyle::Dotted,
+        group: ".",
+        decimal: ',',
+    },
+    Locale {
+        tag: "fr-FR",
+        date: DateStyle::Slashed,
+        group: "\u{202f}",
+        decimal: ',',
+    },
+    Locale {
+        tag: "es-ES",
+        date: DateStyle::Slashed,
+        group: ".",
+        decimal: ',',
+    },
+    Locale {
+        tag: "it-IT",
+        date: DateStyle::Slashed,
+        group: ".",
+        decimal: ',',
+    },
+    Locale {
+        tag: "nl-NL",
+        date: DateStyle::Dashed,
+        group: ".",
+        decimal: ',',
+    },
+    Locale {
+        tag: "ja-JP",
+        date: DateStyle::YearFirst,
+        group: ",",
+        decimal: '.',
+    },
+];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl Locale {
+    /// The locale for a tag such as `de-DE`, `de`, or `en_GB.UTF-8`. English
+    /// outside the US uses `en-GB` conventions; `C` and `POSIX` are `iso`.
+    pub fn find(tag: &str) -> Option<&'static Locale> {
+        let tag = tag
+            .split(['.', '@'])
+            .next()
+            .unwrap_or(tag)
+            .replace('_', "-");
+        if tag.eq_ignore_ascii_case("C") || tag.eq_ignore_ascii_case("POSIX") {
+            return Some(&LOCALES[0]);
+        }
+        if let Some(exact) = LOCALES.iter().find(|l| l.tag.eq_ignore_ascii_case(&tag)) {
+            return Some(exact);
+        }
+        let (language, region) = tag.split_once('-').unwrap_or((&tag, ""));
+        let language = match language.to_lowercase().as_str() {
+            "en" if !region.is_empty() && !region.eq_ignore_ascii_case("US") => "en-GB",
+            other => {
+                return LOCALES
+                    .iter()
+                    .find(|l| l.tag.starts_with(&format!("{}-", other)))
+            }
+        };
+        LOCALES.iter().find(|l| l.tag == language)
+    }
+
+    /// Parse a `--locale` value, listing the valid tags on error.
+    pub fn parse(tag: &str) -> Result<&'static Locale, String> {
+        Locale::find(tag).ok_or_else(|| {
+            let tags: Vec<&str> = LOCALES.iter().map(|l| l.tag).collect();
+            format!(
+                "unknown locale '{}' (expected one of: {})",
+                tag,
+                tags.join(", ")
+            )
+        })
+    }
+
+    /// The locale from `LC_ALL`, `LC_TIME`, or `LANG`, else `iso`.
+    pub fn from_env() -> &'static Locale {
+        ["LC_ALL", "LC_TIME", "LANG"]
+            .iter()
+            .find_map(|var| env::var(var).ok().filter(|v| !v.is_empty()))
+            .and_then(|tag| Locale::find(&tag))
+            .unwrap_or(&LOCALES[0])
+    }
+
+    /// A `YYYY-MM-DD` date in this locale; anything else is returned as is.
+    pub fn date(&self, iso: &str) -> String {
+        let mut parts = iso.splitn(3, '-').map(|p| p.parse::<usize>().ok());
+        let (Some(Some(y)), Some(Some(m)), Some(Some(d))) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            return iso.to_string();
+        };
+        if !(1..=12).contains(&m) {
+            return iso.to_string();
+        }
+        match self.date {
+            DateStyle::Iso => iso.to_string(),
+            DateStyle::MonthDayYear => format!("{} {}, {}", MONTHS[m - 1], d, y),
+            DateStyle::DayMonthYear => format!("{} {} {}", d, MONTHS[m - 1], y),
+            DateStyle::Dotted => format!("{:02}.{:02}.{}", d, m, y),
+            DateStyle::Slashed => format!("{:02}/{:02}/{}", d, m, y),
+            DateStyle::Dashed => format!("{:02}-{:02}-{}", d, m, y),
+            DateStyle::YearFirst => format!("{}/{:02}/{:02}", y, m, d),
+        }
+    }
+
+    /// An integer with thousands separators: `12,345` or `12.345`.
+    pub fn number(&self, n: u64) -> String {
+        let digits = n.to_string();
+        let mut out = String::new();
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i).is_multiple_of(3) {
+                out.push_str(self.group);
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// `x` to `places` decimals with this locale's separators.
+    pub fn decimal(&self, x: f64, places: usize) -> String {
+        let fixed = format!("{:.*}", places, x.abs());
+        let (whole, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+        let mut out = if x < 0.0 {
+            "-".to_string()
+        } else {
+            String::new()
+        };
+        out.push_str(&self.number(whole.parse().unwrap_or(0)));
+        if !fraction.is_empty() {
+            out.push(self.decimal);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// `850 ms`, `1.4 s`, or `4 min 12 s`.
+    pub fn duration_ms(&self, ms: u64) -> String {
+        match ms {
+            0..=999 => format!("{} ms", ms),
+            1_000..=59_999 => format!("{} s", self.decimal(ms as f64 / 1000.0, 1)),
+            _ => {
+                let secs = ms / 1000;
+                format!("{} min {:02} s", self.number(secs / 60), secs % 60)
+            }
+        }
+    }
+}
+
+/// A UTC timestamp as ISO-8601, `2026-10-17T14:03:09Z`.
+pub fn timestamp(secs: i64) -> String {
+    let time = secs.rem_euclid(86_400);
+    format!(
+        "{}T{:02}:{:02}:{:02}Z",
+        date_from_unix(secs),
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{timestamp, Locale};
+
+    fn tag(name: &str) -> Option<&'static str> {
+        Locale::find(name).map(|l| l.tag)
+    }
+
+    #[test]
+    fn tags() {
+        assert_eq!(tag("de_DE.UTF-8"), Some("de-DE"));
+        assert_eq!(tag("EN-us"), Some("en-US"));
+        assert_eq!(tag("en_AU"), Some("en-GB"));
+        assert_eq!(tag("en"), Some("en-US"));
+        assert_eq!(tag("fr"), Some("fr-FR"));
+        assert_eq!(tag("fr_CA"), Some("fr-FR"));
+        assert_eq!(tag("POSIX"), Some("iso"));
+        assert_eq!(tag("sv-SE"), None);
+        assert!(Locale::parse("xx")
+            .unwrap_err()
+            .starts_with("unknown locale 'xx' (expected one of: iso, en-US,"));
+    }
+
+    #[test]
+    fn dates() {
+        let date = |t: &str| Locale::find(t).unwrap().date("2026-03-07");
+        assert_eq!(date("iso"), "2026-03-07");
+        assert_eq!(date("en-US"), "Mar 7, 2026");
+        assert_eq!(date("en-GB"), "7 Mar 2026");
+        assert_eq!(date("de-DE"), "07.03.2026");
+        assert_eq!(date("fr-FR"), "07/03/2026");
+        assert_eq!(date("nl-NL"), "07-03-2026");
+        assert_eq!(date("ja-JP"), "2026/03/07");
+        let us = Locale::find("en-US").unwrap();
+        assert_eq!(us.date("2026-13-01"), "2026-13-01");
+        assert_eq!(us.date("unknown"), "unknown");
+    }
+
+    #[test]
+    fn numbers() {
+        let us = Locale::find("en-US").unwrap();
+        let de = Locale::find("de-DE").unwrap();
+        assert_eq!(us.number(1_234_567), "1,234,567");
+        assert_eq!(us.number(999), "999");
+        assert_eq!(de.number(12_345), "12.345");
+        assert_eq!(Locale::find("iso").unwrap().number(12_345), "12345");
+        assert_eq!(us.decimal(-1234.56, 1), "-1,234.6");
+        assert_eq!(de.decimal(0.5, 2), "0,50");
+        assert_eq!(de.decimal(3.0, 0), "3");
+    }
+
+    #[test]
+    fn durations() {
+        let de = Locale::find("de-DE").unwrap();
+        assert_eq!(de.duration_ms(850), "850 ms");
+        assert_eq!(de.duration_ms(1_420), "1,4 s");
+        assert_eq!(de.duration_ms(252_000), "4 min 12 s");
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(timestamp(1_792_245_789), "2026-10-17T14:03:09Z");
+    }
+}
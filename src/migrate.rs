@@ -0,0 +1,432 @@
+use crate::baseline::{self, fingerprint};
+use crate::config::parse_severity;
+use crate::scanner::{Issue, Severity};
+use crate::secrets;
+use serde_json::{json, Value};
+
+/// Schema version of JSON reports written by this build.
+///
+/// 1: original format, no `schema_version` field.
+/// 2: issues carry `file`/`line`; `result` has `scan_warnings`.
+/// 3: every issue carries its `check` ID.
+pub const REPORT_VERSION: u64 = 3;
+
+/// JSON Schema of reports at `REPORT_VERSION`, printed by `repomedic schema`.
+pub const REPORT_SCHEMA: &str = include_str!("schema/report.schema.json");
+
+/// Schema version of a JSON report (reports without the field are version 1).
+pub fn report_version(report: &Value) -> u64 {
+    report
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+}
+
+/// Bring an exported JSON report up to `REPORT_VERSION`.
+pub fn migrate_report(mut report: Value) -> Result<Value, String> {
+    if !report.is_object() {
+        return Err("report is not a JSON object".into());
+    }
+    loop {
+        match report_version(&report) {
+            1 => report = report_v1_to_v2(report),
+            2 => report = report_v2_to_v3(report),
+            REPORT_VERSION => return Ok(report),
+            v => {
+                return Err(format!(
+                    "report schema version {} is newer than supported ({}); upgrade repomedic",
+                    v, REPORT_VERSION
+                ))
+            }
+        }
+    }
+}
+
+/// Bring a baseline up to the current baseline version.
+///
+/// Exported JSON reports are accepted too and converted to a baseline of
+/// their issues, so an old `repomedic export report.json` can seed one.
+pub fn migrate_baseline(value: Value) -> Result<Value, String> {
+    if value.get("score").is_some() {
+        let report = migrate_report(value)?;
+        let issues: Vec<Value> = report["issues"]
+            .as_array()
+            .map(|a| a.iter().filter_map(issue_from_json).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .iter()
+            .map(|i| {
+                json!({
+                    "fingerprint": fingerprint(i),
+                    "severity": i.severity.name(),
+                    "message": i.message,
+                })
+            })
+            .collect();
+        return Ok(json!({ "version": baseline::VERSION, "issues": issues }));
+    }
+    let mut value = value;
+    loop {
+        match value.get("version").and_then(Value::as_u64) {
+            Some(1) => value = baseline_v1_to_v2(value),
+            Some(v) if v == u64::from(baseline::VERSION) => return Ok(value),
+            Some(v) if v > u64::from(baseline::VERSION) => {
+                return Err(format!(
+                    "baseline version {} is newer than supported ({}); upgrade repomedic",
+                    v,
+                    baseline::VERSION
+                ))
+            }
+            _ => return Err("not a repomedic baseline or report".into()),
+        }
+    }
+}
+
+/// v1 fingerprinted every secret as `potential-secret`; re-derive the rule
+/// from the stored message.
+fn baseline_v1_to_v2(mut value: Value) -> Value {
+    if let Some(entries) = value.get_mut("issues").and_then(Value::as_array_mut) {
+        for entry in entries.iter_mut() {
+            let Some(fp) = entry["fingerprint"].as_str() else {
+                continue;
+            };
+            let Some(rest) = fp.strip_prefix(secrets::FAMILY) else {
+                continue;
+            };
+            let message = entry["message"].as_str().unwrap_or("");
+            let id = legacy_check_id(message).unwrap_or(secrets::FAMILY);
+            entry["fingerprint"] = json!(format!("{}{}", id, rest));
+        }
+    }
+    value["version"] = json!(2);
+    value
+}
+
+/// v1 issues only had a message; recover the location from it.
+fn report_v1_to_v2(mut report: Value) -> Value {
+    if let Some(issues) = report.get_mut("issues").and_then(Value::as_array_mut) {
+        for issue in issues.iter_mut() {
+            let message = issue["message"].as_str().unwrap_or("").to_string();
+            if let Some((file, line)) = location_from_message(&message) {
+                issue["file"] = json!(file);
+                if let Some(line) = line {
+                    issue["line"] = json!(line);
+                }
+            }
+        }
+    }
+    if let Some(result) = report.get_mut("result").and_then(Value::as_object_mut) {
+        result.entry("scan_warnings").or_insert_with(|| json!([]));
+    }
+    report["schema_version"] = json!(2);
+    report
+}
+
+/// v2 issues had a `check` only for custom rules; derive the rest.
+fn report_v2_to_v3(mut report: Value) -> Value {
+    if let Some(issues) = report.get_mut("issues").and_then(Value::as_array_mut) {
+        for issue in issues.iter_mut() {
+            if issue.get("check").is_some() {
+                continue;
+            }
+            let message = issue["message"].as_str().unwrap_or("");
+            issue["check"] = json!(legacy_check_id(message).unwrap_or("unknown"));
+        }
+    }
+    report["schema_version"] = json!(3);
+    report
+}
+
+/// Check ID of an issue from a report before schema version 3, which only
+/// had the message. The wording of those versions is fixed, so it is
+/// matched here rather than kept in sync with current messages.
+fn legacy_check_id(message: &str) -> Option<&'static str> {
+    let m = message;
+    let id = if let Some(rest) = m.strip_prefix("Potential ") {
+        let name = rest.split(" in ").next().unwrap_or(rest);
+        secrets::rule_by_name(name).map_or(secrets::FAMILY, |r| r.id)
+    } else if m.starts_with("No dependency cache") {
+        "ci-missing-cache"
+    } else if m.starts_with("Artifacts uploaded without") {
+        "ci-artifact-retention"
+    } else if m.starts_with("Workflow token can write everything") {
+        "workflow-write-all"
+    } else if m.starts_with("Workflow publishes on push") {
+        "ci-publish-on-push"
+    } else if m.starts_with("Unpinned action") {
+        "workflow-unpinned-action"
+    } else if m.starts_with("Tests present but no coverage") {
+        "no-coverage"
+    } else if m.starts_with("Dependencies declared but no Dependabot") {
+        "no-dependency-updates"
+    } else if m.starts_with("Kubernetes Secret") {
+        "k8s-secret-manifest"
+    } else if m.starts_with("No resource limits for") {
+        "k8s-missing-limits"
+    } else if m.starts_with("Helm chart") && m.contains("values.schema.json") {
+        "helm-missing-schema"
+    } else if m.starts_with("Terraform state committed") {
+        "terraform-state-committed"
+    } else if m.starts_with("No .terraform.lock.hcl") {
+        "terraform-missing-lock"
+    } else if m.starts_with("Unpinned Terraform provider") {
+        "terraform-unpinned-provider"
+    } else if m.starts_with("Terraform variable") && m.contains("looks like a secret") {
+        "terraform-tfvars-secret"
+    } else if m.starts_with("Terraform code but no") {
+        "terraform-no-fmt-check"
+    } else if m.starts_with("EditorConfig not followed") {
+        "editorconfig-violation"
+    } else if m.starts_with("Inconsistent line endings") {
+        "inconsistent-line-endings"
+    } else if m.starts_with("UTF-8 byte order mark") {
+        "utf8-bom"
+    } else if m.starts_with("No .dockerignore for") {
+        "missing-dockerignore"
+    } else if m.starts_with("Unpinned image") {
+        "docker-latest-tag"
+    } else if m.starts_with("Container runs as root") {
+        "docker-root-user"
+    } else if m.starts_with("ADD used for local files") {
+        "docker-add-instead-of-copy"
+    } else if m.starts_with("Secret baked into") {
+        "docker-secret-env"
+    } else if m.starts_with("Unencrypted file matches .sops.yaml") {
+        "sops-unencrypted"
+    } else if m.starts_with("Unrecognized license") {
+        "license-unrecognized"
+    } else if m.starts_with("Truncated license") {
+        "license-truncated"
+    } else if m.starts_with("Bad CODEOWNERS rule") {
+        "codeowners-invalid"
+    } else if m.starts_with("Broken template") {
+        "template-invalid"
+    } else if m.starts_with("CODEOWNERS leaves") {
+        "codeowners-unowned"
+    } else if m.starts_with("Stale badge") {
+        "stale-badge"
+    } else if m.starts_with("README is missing sections") {
+        "readme-incomplete"
+    } else if m.starts_with("Project name differs") {
+        "name-mismatch"
+    } else if m.starts_with("README install mismatch") {
+        "readme-install-mismatch"
+    } else if m.starts_with("README is short") {
+        "readme-short"
+    } else if m.starts_with("Broken link") {
+        "readme-broken-link"
+    } else if m.starts_with("Dead link") {
+        "dead-link"
+    } else if m.starts_with("Placeholder in") {
+        "readme-placeholder"
+    } else if m.starts_with("CI is failing") {
+        "ci-failing"
+    } else if m.starts_with("Flaky CI") {
+        "ci-flaky"
+    } else if m.starts_with("Repository is archived") {
+        "repo-archived"
+    } else if m.starts_with("Default branch") && m.contains("not protected") {
+        "branch-unprotected"
+    } else if m.contains("requests to") && m.contains("without a required review") {
+        "no-required-reviews"
+    } else if m.starts_with("Open Dependabot alerts") {
+        "dependabot-alerts"
+    } else if m.starts_with("GitHub doesn't recognize the license") {
+        "license-undetected"
+    } else if m.starts_with("No GitLab") && m.contains("templates") {
+        "missing-gitlab-templates"
+    } else if m.starts_with("Package ") && m.contains("no README") {
+        "package-missing-readme"
+    } else if m.starts_with("Package ") && m.contains("no LICENSE") {
+        "package-missing-license"
+    } else if m.starts_with("Duplicate of the root") {
+        "duplicate-root-file"
+    } else if m.starts_with("Stale copy of the root") {
+        "stale-license-copy"
+    } else if m.starts_with("Large binary assets") {
+        "lfs-recommended"
+    } else if m.starts_with("Large file in git history") {
+        "history-large-file"
+    } else if m.contains("Large file") {
+        "large-file"
+    } else if m.starts_with("Wildcard dependency") {
+        "dependency-wildcard"
+    } else if m.starts_with("Git dependency") {
+        "dependency-git"
+    } else if m.starts_with("Outdated dependency") {
+        "dependency-outdated"
+    } else if m.starts_with("Stale lock file") {
+        "stale-lockfile"
+    } else if m.contains("lock file") || m.contains("Cargo.lock") {
+        "missing-lock-file"
+    } else if m.contains("README") {
+        "missing-readme"
+    } else if m.contains("LICENSE") {
+        "missing-license"
+    } else if m.contains("Git repository") {
+        "not-git-repo"
+    } else if m.contains(".gitignore") {
+        "missing-gitignore"
+    } else if m.contains("CHANGELOG") {
+        "missing-changelog"
+    } else if m.contains("CONTRIBUTING") {
+        "missing-contributing"
+    } else if m.contains("CODE_OF_CONDUCT") {
+        "missing-code-of-conduct"
+    } else if m.contains("SECURITY") {
+        "missing-security"
+    } else if m.contains(".editorconfig") {
+        "missing-editorconfig"
+    } else if m.contains("CI/CD") {
+        "no-ci"
+    } else if m.contains("No test") {
+        "no-tests"
+    } else if m.starts_with("No commits in") {
+        "stale-repo"
+    } else if m.contains("No source code") {
+        "no-source"
+    } else if m.contains("No build system") {
+        "no-build-system"
+    } else {
+        return None;
+    };
+    Some(id)
+}
+
+fn location_from_message(message: &str) -> Option<(String, Option<usize>)> {
+    if let Some(rest) = message.strip_prefix("Potential ") {
+        let (_, located) = rest.split_once(" in ")?;
+        let (file, line) = located.rsplit_once(" at line ")?;
+        return Some((file.to_string(), line.parse().ok()));
+    }
+    if message.starts_with("Large file detected") {
+        let (_, file) = message.split_once(": ")?;
+        return Some((file.to_string(), None));
+    }
+    None
+}
+
+fn issue_from_json(value: &Value) -> Option<Issue> {
+    let message = value["message"].as_str()?;
+    let check = value["check"].as_str()?;
+    let issue = match parse_severity(value["severity"].as_str()?)? {
+        Severity::Error => Issue::error(check, message),
+        Severity::Warning => Issue::warning(check, message),
+        Severity::Info => Issue::info(check, message),
+    };
+    Some(match value["file"].as_str() {
+        Some(file) => issue.at(file, value["line"].as_u64().map(|l| l as usize)),
+        None => issue,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{migrate_baseline, migrate_report, report_version, REPORT_VERSION};
+    use crate::baseline;
+    use serde_json::json;
+
+    #[test]
+    fn report_v1() {
+        let v1 = json!({
+            "score": 80,
+            "issues": [
+                { "severity": "error", "message": "Potential GitHub token in src/a.rs at line 3" },
+                { "severity": "warning", "message": "Large file detected (12 MB): assets/big.bin" },
+                { "severity": "info", "message": "No CHANGELOG" },
+            ],
+            "result": {},
+        });
+        assert_eq!(report_version(&v1), 1);
+        let report = migrate_report(v1).unwrap();
+        assert_eq!(report_version(&report), REPORT_VERSION);
+        assert_eq!(report["issues"][0]["file"], "src/a.rs");
+        assert_eq!(report["issues"][0]["line"], 3);
+        assert_eq!(report["issues"][1]["file"], "assets/big.bin");
+        assert!(report["issues"][1].get("line").is_none());
+        assert!(report["issues"][2].get("file").is_none());
+        assert_eq!(report["result"]["scan_warnings"], json!([]));
+    }
+
+    #[test]
+    fn report_v2_check_ids() {
+        let v2 = json!({
+            "schema_version": 2,
+            "issues": [
+                { "severity": "warning", "message": "Missing CHANGELOG" },
+                { "severity": "error", "message": "No OWNERS file", "check": "acme-owners" },
+                { "severity": "info", "message": "Something new" },
+            ],
+        });
+        let report = migrate_report(v2).unwrap();
+        let checks: Vec<&str> = report["issues"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| i["check"].as_str().unwrap())
+            .collect();
+        assert_eq!(checks, ["missing-changelog", "acme-owners", "unknown"]);
+    }
+
+    #[test]
+    fn report_errors() {
+        assert_eq!(
+            migrate_report(json!([])).unwrap_err(),
+            "report is not a JSON object"
+        );
+        assert!(migrate_report(json!({ "schema_version": 99 }))
+            .unwrap_err()
+            .starts_with("report schema version 99 is newer than supported"));
+    }
+
+    #[test]
+    fn baseline_v1() {
+        let v1 = json!({
+            "version": 1,
+            "issues": [{
+                "fingerprint": "potential-secret:.env",
+                "severity": "error",
+                "message": "Potential GitHub token in .env at line 1",
+            }],
+        });
+        let baseline = migrate_baseline(v1).unwrap();
+        assert_eq!(baseline["version"], baseline::VERSION);
+        assert_eq!(
+            baseline["issues"][0]["fingerprint"],
+            "secret-github-token:.env"
+        );
+    }
+
+    #[test]
+    fn baseline_from_report() {
+        let report = json!({
+            "schema_version": 2,
+            "score": 90,
+            "issues": [{
+                "severity": "error",
+                "message": "Potential GitHub token in .env at line 1",
+                "file": ".env",
+                "line": 1,
+            }],
+        });
+        let baseline = migrate_baseline(report).unwrap();
+        assert_eq!(
+            baseline["issues"],
+            json!([{
+                "fingerprint": "secret-github-token:.env",
+                "severity": "error",
+                "message": "Potential GitHub token in .env at line 1",
+            }])
+        );
+    }
+
+    #[test]
+    fn baseline_errors() {
+        assert!(migrate_baseline(json!({ "version": 9, "issues": [] }))
+            .unwrap_err()
+            .starts_with("baseline version 9 is newer than supported"));
+        assert_eq!(
+            migrate_baseline(json!({ "issues": [] })).unwrap_err(),
+            "not a repomedic baseline or report"
+        );
+    }
+}
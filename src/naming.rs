@@ -0,0 +1,216 @@
+use crate::install::Package;
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+/// A place the project's name is written, and what it says there.
+#[derive(Debug, Clone, Serialize)]
+pub struct NameSource {
+    /// `directory`, a manifest file name, `README`, or `remote`.
+    pub source: String,
+    pub name: String,
+}
+
+/// The project name from the root directory, the manifests in `packages`,
+/// the README title, and the `origin` remote, where each is available. The
+/// README title only counts when it names the project (see `title_name`).
+pub fn collect(root: &Path, packages: &[Package], readme_title: Option<String>) -> Vec<NameSource> {
+    let mut sources = Vec::new();
+    let dir = root
+        .canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()));
+    let mut push = |source: &str, name: Option<String>| {
+        if let Some(name) = name.filter(|n| !normalize(n).is_empty()) {
+            sources.push(NameSource {
+                source: source.to_string(),
+                name,
+            });
+        }
+    };
+    push("directory", dir);
+    for package in packages {
+        push(package.manifest, package.name.clone());
+    }
+    push("README", readme_title.as_deref().and_then(title_name));
+    push("remote", remote_repo(root));
+    sources
+}
+
+/// The project name in a README title: the title itself, or the part before
+/// a ` - `, ` — `, `: ` or ` | ` separator, if that is a single identifier-like
+/// word. Descriptive titles ("Parser for Rust source code") name nothing.
+fn title_name(title: &str) -> Option<String> {
+    let head = [" - ", " — ", " – ", ": ", " | "]
+        .iter()
+        .filter_map(|sep| title.find(sep))
+        .min()
+        .map_or(title, |end| &title[..end]);
+    let head = head.trim().trim_matches(|c| matches!(c, '`' | '*' | '_'));
+    let identifier = !head.is_empty()
+        && head.chars().any(char::is_alphanumeric)
+        && head
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.' | '/' | '@'));
+    identifier.then(|| head.to_string())
+}
+
+/// Whether the names disagree: no single name is close to all the others.
+/// Names are compared without case and punctuation, by their last path
+/// segment (npm scopes, Go module paths, Composer vendors), and a name
+/// contained in another (`repomedic` in "RepoMedic - health scanner")
+/// counts as close. The directory is listed but never decides: checkouts
+/// are routinely renamed (`/app`, `/src`, `/workspace`).
+pub fn diverge(sources: &[NameSource]) -> bool {
+    let names: Vec<String> = sources
+        .iter()
+        .filter(|s| s.source != "directory")
+        .map(|s| normalize(&s.name))
+        .collect();
+    names.len() > 1
+        && !names
+            .iter()
+            .any(|a| names.iter().all(|b| a == b || close(a, b)))
+}
+
+/// `sources` as `directory \`foo\`, Cargo.toml \`bar\``.
+pub fn describe(sources: &[NameSource]) -> String {
+    sources
+        .iter()
+        .map(|s| format!("{} `{}`", s.source, s.name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Lowercase letters and digits. Of a path (not a title), only the last
+/// segment, without a Go major version suffix (`example.com/foo/v2` is `foo`).
+fn normalize(name: &str) -> String {
+    let mut last = name;
+    if !name.contains(char::is_whitespace) {
+        let mut segments = name.trim_end_matches('/').rsplit('/');
+        last = segments.next().unwrap_or(name);
+        let major = last
+            .strip_prefix('v')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+        if major {
+            last = segments.next().unwrap_or(last);
+        }
+    }
+    last.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// One name contains the other, or they're a typo apart.
+fn close(a: &str, b: &str) -> bool {
+    a.contains(b) || b.contains(a) || distance(a, b) <= a.len().min(b.len()) / 4
+}
+
+/// Levenshtein distance.
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Repository name in the `origin` remote URL, on any host.
+fn remote_repo(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("--git-dir")
+        .arg(root.join(".git"))
+        .args(["config", "--get", "remote.origin.url"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let url = url.trim_end_matches('/');
+    let name = url.rsplit(['/', ':']).next()?;
+    let name = name.strip_suffix(".git").unwrap_or(name);
+    Some(name.to_string()).filter(|n| !n.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe, distance, diverge, normalize, title_name, NameSource};
+
+    fn sources(list: &[(&str, &str)]) -> Vec<NameSource> {
+        list.iter()
+            .map(|(source, name)| NameSource {
+                source: source.to_string(),
+                name: name.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn normalizes_names() {
+        assert_eq!(normalize("Repo-Medic"), "repomedic");
+        assert_eq!(normalize("@scope/my_pkg"), "mypkg");
+        assert_eq!(normalize("github.com/acme/tool/v2"), "tool");
+        assert_eq!(
+            normalize("RepoMedic - health scanner"),
+            "repomedichealthscanner"
+        );
+    }
+
+    #[test]
+    fn names_in_readme_titles() {
+        assert_eq!(title_name("repomedic").as_deref(), Some("repomedic"));
+        assert_eq!(
+            title_name("RepoMedic - fast repository health scanner").as_deref(),
+            Some("RepoMedic")
+        );
+        assert_eq!(
+            title_name("`serde_json`: JSON for Rust").as_deref(),
+            Some("serde_json")
+        );
+        assert_eq!(title_name("Parser for Rust source code"), None);
+        assert_eq!(title_name("My Cool Project - a tool"), None);
+    }
+
+    #[test]
+    fn edit_distance() {
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("", "abc"), 3);
+        assert_eq!(distance("same", "same"), 0);
+    }
+
+    #[test]
+    fn agreeing_names() {
+        assert!(!diverge(&sources(&[
+            ("Cargo.toml", "repomedic"),
+            ("README", "RepoMedic - fast repository health scanner"),
+            ("remote", "repomedic"),
+        ])));
+        // A typo apart still counts as the same name
+        assert!(!diverge(&sources(&[
+            ("package.json", "dashboard"),
+            ("remote", "dashbaord"),
+        ])));
+        // The checkout directory never decides
+        assert!(!diverge(&sources(&[
+            ("directory", "workspace"),
+            ("Cargo.toml", "tool"),
+        ])));
+    }
+
+    #[test]
+    fn diverging_names() {
+        let names = sources(&[("Cargo.toml", "oldname"), ("remote", "newproject")]);
+        assert!(diverge(&names));
+        assert_eq!(
+            describe(&names),
+            "Cargo.toml `oldname`, remote `newproject`"
+        );
+    }
+}
@@ -0,0 +1,260 @@
+use crate::config::TomlDoc;
+use crate::license::{self, License};
+use serde::Serialize;
+
+/// Directory names whose manifests and license files belong to someone
+/// else or aren't published (fixtures, examples, vendored code).
+const SKIP_DIRS: &[&str] = &[
+    "test",
+    "tests",
+    "testdata",
+    "fixtures",
+    "example",
+    "examples",
+    "vendor",
+    "third_party",
+    "third-party",
+    "node_modules",
+];
+
+/// Manifests marking a directory as a package of its own.
+const MANIFESTS: &[&str] = &[
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "go.mod",
+    "composer.json",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemKind {
+    /// A published package without a README of its own.
+    MissingReadme,
+    /// A published package without a LICENSE of its own.
+    MissingLicense,
+    /// A README or LICENSE outside any package, identical to the root one.
+    Duplicate,
+    /// A LICENSE outside any package for the root's license, but with
+    /// different text (an old year or holder).
+    Stale,
+}
+
+/// A README or LICENSE problem below the repository root.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageProblem {
+    /// Package directory, or the copy's path for `duplicate` and `stale`.
+    pub path: String,
+    pub kind: ProblemKind,
+    /// Root file the copy matches (`duplicate` and `stale`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_file: Option<String>,
+}
+
+fn is_readme(name: &str) -> bool {
+    name.to_lowercase().starts_with("readme")
+}
+
+fn is_license(name: &str) -> bool {
+    license::FILES.contains(&name)
+}
+
+/// Whether the manifest `name` in `content` is published to a registry,
+/// and whether it names its README and license file itself (for instance
+/// `readme = "../README.md"` in Cargo.toml).
+fn published(name: &str, content: &str) -> Option<(bool, bool)> {
+    match name {
+        "Cargo.toml" => {
+            let doc = TomlDoc::parse(content).ok()?;
+            if !doc.has_section("package")
+                || doc.get("package", "publish").and_then(|v| v.as_bool()) == Some(false)
+            {
+                return None;
+            }
+            let has = |key: &str| {
+                doc.entries.iter().any(|(_, s, k, _)| {
+                    s == "package" && (k == key || k.starts_with(&format!("{}.", key)))
+                })
+            };
+            Some((has("readme"), has("license-file")))
+        }
+        "package.json" => {
+            let package: serde_json::Value = serde_json::from_str(content).ok()?;
+            if package.get("private").and_then(|p| p.as_bool()) == Some(true) {
+                return None;
+            }
+            Some((false, false))
+        }
+        "pyproject.toml" => {
+            let doc = TomlDoc::parse(content).ok()?;
+            let section = ["project", "tool.poetry"]
+                .into_iter()
+                .find(|s| doc.has_section(s))?;
+            let license_file = doc
+                .get(section, "license")
+                .and_then(|v| v.get("file"))
+                .is_some();
+            Some((doc.get(section, "readme").is_some(), license_file))
+        }
+        "setup.py" => Some((false, false)),
+        _ => None,
+    }
+}
+
+/// Check packages and README/LICENSE copies below the root. `files` are
+/// the walked files (root-relative, `/`-separated); `read` reads one.
+pub fn check(files: &[String], read: &dyn Fn(&str) -> Option<String>) -> Vec<PackageProblem> {
+    let mut dirs: Vec<(&str, Vec<&str>)> = Vec::new();
+    for rel in files {
+        let Some((dir, name)) = rel.rsplit_once('/') else {
+            continue;
+        };
+        if dir
+            .split('/')
+            .any(|c| c.starts_with('.') || SKIP_DIRS.contains(&c))
+        {
+            continue;
+        }
+        match dirs.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, names)) => names.push(name),
+            None => dirs.push((dir, vec![name])),
+        }
+    }
+    let root_readme = files
+        .iter()
+        .find(|f| !f.contains('/') && is_readme(f))
+        .and_then(|f| Some((f.as_str(), read(f)?)));
+    let root_license = files
+        .iter()
+        .find(|f| !f.contains('/') && is_license(f))
+        .and_then(|f| Some((f.as_str(), read(f)?)));
+
+    let mut problems = Vec::new();
+    for (dir, names) in &dirs {
+        let manifest = names.iter().find(|n| MANIFESTS.contains(n));
+        if let Some(manifest) = manifest {
+            let content = read(&format!("{}/{}", dir, manifest)).unwrap_or_default();
+            let Some((names_readme, names_license)) = published(manifest, &content) else {
+                continue;
+            };
+            let mut missing = |kind| {
+                problems.push(PackageProblem {
+                    path: dir.to_string(),
+                    kind,
+                    root_file: None,
+                })
+            };
+            if !names_readme && !names.iter().any(|n| is_readme(n)) {
+                missing(ProblemKind::MissingReadme);
+            }
+            if !names_license && !names.iter().any(|n| is_license(n)) {
+                missing(ProblemKind::MissingLicense);
+            }
+            continue;
+        }
+        for name in names {
+            let (root, license) = if is_readme(name) {
+                (&root_readme, false)
+            } else if is_license(name) {
+                (&root_license, true)
+            } else {
+                continue;
+            };
+            let Some((root_name, root_content)) = root else {
+                continue;
+            };
+            let path = format!("{}/{}", dir, name);
+            let Some(content) = read(&path) else {
+                continue;
+            };
+            let kind = if content.trim() == root_content.trim() {
+                ProblemKind::Duplicate
+            } else if license
+                && License::identify(&path, &content)
+                    .spdx
+                    .is_some_and(|id| License::identify(root_name, root_content).spdx == Some(id))
+            {
+                ProblemKind::Stale
+            } else {
+                continue;
+            };
+            problems.push(PackageProblem {
+                path,
+                kind,
+                root_file: Some(root_name.to_string()),
+            });
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check, ProblemKind};
+
+    fn problems(files: &[(&str, &str)]) -> Vec<(String, ProblemKind)> {
+        let walked: Vec<String> = files.iter().map(|(f, _)| f.to_string()).collect();
+        let read = |path: &str| {
+            files
+                .iter()
+                .find(|(f, _)| *f == path)
+                .map(|(_, c)| c.to_string())
+        };
+        check(&walked, &read)
+            .into_iter()
+            .map(|p| (p.path, p.kind))
+            .collect()
+    }
+
+    #[test]
+    fn published_packages_need_readme_and_license() {
+        let found = problems(&[
+            ("crates/core/Cargo.toml", "[package]\nname = \"core\"\n"),
+            (
+                "crates/cli/Cargo.toml",
+                "[package]\nname = \"cli\"\nreadme = \"../../README.md\"\nlicense-file = \"../../LICENSE\"\n",
+            ),
+            (
+                "crates/internal/Cargo.toml",
+                "[package]\nname = \"internal\"\npublish = false\n",
+            ),
+            ("web/package.json", "{\"private\": true}"),
+            ("tests/fixture/Cargo.toml", "[package]\nname = \"fixture\"\n"),
+            ("py/pyproject.toml", "[project]\nname = \"py\"\nreadme = \"README.md\"\n"),
+            ("py/LICENSE", "MIT License\n"),
+        ]);
+        assert_eq!(
+            found,
+            [
+                ("crates/core".to_string(), ProblemKind::MissingReadme),
+                ("crates/core".to_string(), ProblemKind::MissingLicense),
+            ]
+        );
+    }
+
+    #[test]
+    fn copies_of_root_files() {
+        let found = problems(&[
+            ("README.md", "# Demo\n"),
+            ("LICENSE", "MIT License\n\nCopyright (c) 2026 Jane Doe\n"),
+            ("docs/README.md", "# Demo\n"),
+            (
+                "docs/LICENSE",
+                "MIT License\n\nCopyright (c) 2019 Jane Doe\n",
+            ),
+            (
+                "assets/LICENSE",
+                "All rights reserved by the font foundry.\n",
+            ),
+            ("guide/README.md", "# Guide\n"),
+        ]);
+        assert_eq!(
+            found,
+            [
+                ("docs/README.md".to_string(), ProblemKind::Duplicate),
+                ("docs/LICENSE".to_string(), ProblemKind::Stale),
+            ]
+        );
+    }
+}
@@ -0,0 +1,666 @@
+use crate::scanner::{BuildSystem, ScanResult};
+use std::path::Path;
+
+/// CI services `generate --ci` writes a starter pipeline for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    GitHub,
+    GitLab,
+    CircleCi,
+}
+
+impl Provider {
+    /// Name accepted by `--ci`.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Provider::GitHub => "github",
+            Provider::GitLab => "gitlab",
+            Provider::CircleCi => "circleci",
+        }
+    }
+
+    /// Where the pipeline is written, relative to the root.
+    pub fn path(&self) -> &'static str {
+        match self {
+            Provider::GitHub => ".github/workflows/ci.yml",
+            Provider::GitLab => ".gitlab-ci.yml",
+            Provider::CircleCi => ".circleci/config.yml",
+        }
+    }
+
+    /// Parse a `--ci` value, listing the valid names on error.
+    pub fn parse(name: &str) -> Result<Provider, String> {
+        [Provider::GitHub, Provider::GitLab, Provider::CircleCi]
+            .into_iter()
+            .find(|p| p.key().eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                format!(
+                    "unknown CI provider '{}' (expected one of: github, gitlab, circleci)",
+                    name
+                )
+            })
+    }
+}
+
+/// How one build system is installed, cached, built, and tested in CI.
+struct Toolchain {
+    /// Job name.
+    job: &'static str,
+    /// GitHub Actions steps after checkout that install and cache the
+    /// toolchain, indented for the `steps` list.
+    actions_setup: &'static str,
+    /// Image for GitLab CI.
+    gitlab_image: &'static str,
+    /// Image for CircleCI.
+    circleci_image: &'static str,
+    /// Commands run in the image before the steps.
+    setup: &'static [&'static str],
+    /// GitLab only caches inside the project, so tools are pointed there.
+    gitlab_variables: &'static [(&'static str, &'static str)],
+    gitlab_cache: &'static [&'static str],
+    circleci_cache: &'static [&'static str],
+    /// Files whose contents key the cache; the first one present is used.
+    cache_keys: &'static [&'static str],
+    /// `(name, command)` steps: install dependencies, build, lint, test.
+    steps: &'static [(&'static str, &'static str)],
+}
+
+const RUST: Toolchain = Toolchain {
+    job: "rust",
+    actions_setup: "      - uses: dtolnay/rust-toolchain@stable
+        with:
+          components: clippy, rustfmt
+      - uses: Swatinem/rust-cache@v2
+",
+    gitlab_image: "rust:latest",
+    circleci_image: "cimg/rust:1.82",
+    setup: &["rustup component add clippy rustfmt"],
+    gitlab_variables: &[("CARGO_HOME", "$CI_PROJECT_DIR/.cargo")],
+    gitlab_cache: &[".cargo/", "target/"],
+    circleci_cache: &["~/.cargo", "target"],
+    cache_keys: &["Cargo.lock", "Cargo.toml"],
+    steps: &[
+        ("Format", "cargo fmt --all -- --check"),
+        ("Clippy", "cargo clippy --all-targets -- -D warnings"),
+        ("Build", "cargo build --all-targets"),
+        ("Test", "cargo test"),
+    ],
+};
+
+const NPM: Toolchain = Toolchain {
+    job: "node",
+    actions_setup: "      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: npm
+",
+    gitlab_image: "node:20",
+    circleci_image: "cimg/node:20.18",
+    setup: &[],
+    gitlab_variables: &[("npm_config_cache", "$CI_PROJECT_DIR/.npm")],
+    gitlab_cache: &[".npm/"],
+    circleci_cache: &["~/.npm"],
+    cache_keys: &["package-lock.json", "package.json"],
+    steps: &[
+        ("Install", "npm ci"),
+        ("Build", "npm run build --if-present"),
+        ("Test", "npm test"),
+    ],
+};
+
+const YARN: Toolchain = Toolchain {
+    job: "node",
+    actions_setup: "      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: yarn
+",
+    gitlab_image: "node:20",
+    circleci_image: "cimg/node:20.18",
+    setup: &[],
+    gitlab_variables: &[("YARN_CACHE_FOLDER", "$CI_PROJECT_DIR/.yarn-cache")],
+    gitlab_cache: &[".yarn-cache/"],
+    circleci_cache: &["~/.cache/yarn"],
+    cache_keys: &["yarn.lock", "package.json"],
+    steps: &[
+        ("Install", "yarn install --frozen-lockfile"),
+        ("Test", "yarn test"),
+    ],
+};
+
+const PNPM: Toolchain = Toolchain {
+    job: "node",
+    actions_setup: "      - uses: pnpm/action-setup@v4
+        with:
+          version: 9
+      - uses: actions/setup-node@v4
+        with:
+          node-version: 20
+          cache: pnpm
+",
+    gitlab_image: "node:20",
+    circleci_image: "cimg/node:20.18",
+    setup: &["corepack enable", "pnpm config set store-dir .pnpm-store"],
+    gitlab_variables: &[],
+    gitlab_cache: &[".pnpm-store/"],
+    circleci_cache: &[".pnpm-store"],
+    cache_keys: &["pnpm-lock.yaml", "package.json"],
+    steps: &[
+        ("Install", "pnpm install --frozen-lockfile"),
+        ("Build", "pnpm run --if-present build"),
+        ("Test", "pnpm test"),
+    ],
+};
+
+const PIP_ACTIONS_SETUP: &str = "      - uses: actions/setup-python@v5
+        with:
+          python-version: \"3.12\"
+          cache: pip
+";
+
+/// pip with a `requirements.txt`.
+const PIP_REQUIREMENTS: Toolchain = Toolchain {
+    job: "python",
+    actions_setup: PIP_ACTIONS_SETUP,
+    gitlab_image: "python:3.12",
+    circleci_image: "cimg/python:3.12",
+    setup: &[],
+    gitlab_variables: &[("PIP_CACHE_DIR", "$CI_PROJECT_DIR/.cache/pip")],
+    gitlab_cache: &[".cache/pip/"],
+    circleci_cache: &["~/.cache/pip"],
+    cache_keys: &["requirements.txt"],
+    steps: &[
+        ("Install", "pip install -r requirements.txt pytest"),
+        ("Test", "pytest"),
+    ],
+};
+
+/// pip installing the package itself (`setup.py` or `pyproject.toml`).
+const PIP_PACKAGE: Toolchain = Toolchain {
+    cache_keys: &["pyproject.toml", "setup.py"],
+    steps: &[("Install", "pip install -e . pytest"), ("Test", "pytest")],
+    ..PIP_REQUIREMENTS
+};
+
+const POETRY: Toolchain = Toolchain {
+    job: "python",
+    actions_setup: "      - run: pipx install poetry
+      - uses: actions/setup-python@v5
+        with:
+          python-version: \"3.12\"
+          cache: poetry
+",
+    gitlab_image: "python:3.12",
+    circleci_image: "cimg/python:3.12",
+    setup: &["pip install poetry"],
+    gitlab_variables: &[("POETRY_CACHE_DIR", "$CI_PROJECT_DIR/.cache/pypoetry")],
+    gitlab_cache: &[".cache/pypoetry/"],
+    circleci_cache: &["~/.cache/pypoetry"],
+    cache_keys: &["poetry.lock", "pyproject.toml"],
+    steps: &[("Install", "poetry install"), ("Test", "poetry run pytest")],
+};
+
+const GO: Toolchain = Toolchain {
+    job: "go",
+    // setup-go caches modules and the build cache itself
+    actions_setup: "      - uses: actions/setup-go@v5
+        with:
+          go-version-file: go.mod
+",
+    gitlab_image: "golang:1.22",
+    circleci_image: "cimg/go:1.22",
+    setup: &[],
+    gitlab_variables: &[
+        ("GOPATH", "$CI_PROJECT_DIR/.go"),
+        ("GOCACHE", "$CI_PROJECT_DIR/.go-build"),
+    ],
+    gitlab_cache: &[".go/pkg/mod/", ".go-build/"],
+    circleci_cache: &["~/go/pkg/mod", "~/.cache/go-build"],
+    cache_keys: &["go.sum", "go.mod"],
+    steps: &[
+        ("Build", "go build ./..."),
+        ("Vet", "go vet ./..."),
+        ("Test", "go test ./..."),
+    ],
+};
+
+const MAVEN: Toolchain = Toolchain {
+    job: "java",
+    actions_setup: "      - uses: actions/setup-java@v4
+        with:
+          distribution: temurin
+          java-version: 21
+          cache: maven
+",
+    gitlab_image: "maven:3-eclipse-temurin-21",
+    circleci_image: "cimg/openjdk:21.0",
+    setup: &[],
+    gitlab_variables: &[(
+        "MAVEN_OPTS",
+        "-Dmaven.repo.local=$CI_PROJECT_DIR/.m2/repository",
+    )],
+    gitlab_cache: &[".m2/repository/"],
+    circleci_cache: &["~/.m2"],
+    cache_keys: &["pom.xml"],
+    steps: &[("Build and test", "mvn -B verify")],
+};
+
+const GRADLE: Toolchain = Toolchain {
+    job: "java",
+    actions_setup: "      - uses: actions/setup-java@v4
+        with:
+          distribution: temurin
+          java-version: 21
+      - uses: gradle/actions/setup-gradle@v4
+",
+    gitlab_image: "eclipse-temurin:21",
+    circleci_image: "cimg/openjdk:21.0",
+    setup: &[],
+    gitlab_variables: &[("GRADLE_USER_HOME", "$CI_PROJECT_DIR/.gradle")],
+    gitlab_cache: &[".gradle/caches/", ".gradle/wrapper/"],
+    circleci_cache: &["~/.gradle/caches", "~/.gradle/wrapper"],
+    cache_keys: &["build.gradle.kts", "build.gradle"],
+    steps: &[("Build and test", "./gradlew build")],
+};
+
+const DOTNET: Toolchain = Toolchain {
+    job: "dotnet",
+    actions_setup: "      - uses: actions/setup-dotnet@v4
+        with:
+          dotnet-version: 8.0.x
+",
+    gitlab_image: "mcr.microsoft.com/dotnet/sdk:8.0",
+    circleci_image: "mcr.microsoft.com/dotnet/sdk:8.0",
+    setup: &[],
+    gitlab_variables: &[("NUGET_PACKAGES", "$CI_PROJECT_DIR/.nuget/packages")],
+    gitlab_cache: &[".nuget/packages/"],
+    circleci_cache: &["~/.nuget/packages"],
+    cache_keys: &[],
+    steps: &[
+        ("Restore", "dotnet restore"),
+        ("Build", "dotnet build --no-restore"),
+        ("Test", "dotnet test --no-build"),
+    ],
+};
+
+const RUBY: Toolchain = Toolchain {
+    job: "ruby",
+    actions_setup: "      - uses: ruby/setup-ruby@v1
+        with:
+          ruby-version: \"3.3\"
+          bundler-cache: true
+",
+    gitlab_image: "ruby:3.3",
+    circleci_image: "cimg/ruby:3.3",
+    setup: &["bundle config set path vendor/bundle"],
+    gitlab_variables: &[],
+    gitlab_cache: &["vendor/bundle/"],
+    circleci_cache: &["vendor/bundle"],
+    cache_keys: &["Gemfile.lock", "Gemfile"],
+    steps: &[("Install", "bundle install"), ("Test", "bundle exec rake")],
+};
+
+const PHP: Toolchain = Toolchain {
+    job: "php",
+    actions_setup: "      - uses: shivammathur/setup-php@v2
+        with:
+          php-version: \"8.3\"
+          tools: composer
+      - uses: actions/cache@v4
+        with:
+          path: ~/.cache/composer
+          key: composer-cache-${{ hashFiles('composer.lock', 'composer.json') }}
+",
+    gitlab_image: "composer:2",
+    circleci_image: "cimg/php:8.3",
+    setup: &[],
+    gitlab_variables: &[("COMPOSER_CACHE_DIR", "$CI_PROJECT_DIR/.composer-cache")],
+    gitlab_cache: &[".composer-cache/"],
+    circleci_cache: &["~/.cache/composer"],
+    cache_keys: &["composer.lock", "composer.json"],
+    steps: &[
+        ("Install", "composer install --no-interaction --prefer-dist"),
+        ("Test", "vendor/bin/phpunit"),
+    ],
+};
+
+const ELIXIR: Toolchain = Toolchain {
+    job: "elixir",
+    actions_setup: "      - uses: erlef/setup-beam@v1
+        with:
+          elixir-version: \"1.16\"
+          otp-version: \"26\"
+      - uses: actions/cache@v4
+        with:
+          path: |
+            deps
+            _build
+          key: mix-${{ hashFiles('mix.lock') }}
+",
+    gitlab_image: "elixir:1.16",
+    circleci_image: "elixir:1.16",
+    setup: &["mix local.hex --force", "mix local.rebar --force"],
+    gitlab_variables: &[],
+    gitlab_cache: &["deps/", "_build/"],
+    circleci_cache: &["deps", "_build"],
+    cache_keys: &["mix.lock", "mix.exs"],
+    steps: &[("Install", "mix deps.get"), ("Test", "mix test")],
+};
+
+const CMAKE: Toolchain = Toolchain {
+    job: "cmake",
+    // Hosted runners ship CMake and a compiler
+    actions_setup: "",
+    gitlab_image: "ubuntu:24.04",
+    circleci_image: "ubuntu:24.04",
+    setup: &["apt-get update && apt-get install -y cmake g++"],
+    gitlab_variables: &[],
+    gitlab_cache: &[],
+    circleci_cache: &[],
+    cache_keys: &[],
+    steps: &[
+        ("Configure", "cmake -B build"),
+        ("Build", "cmake --build build"),
+        ("Test", "ctest --test-dir build"),
+    ],
+};
+
+const MAKE: Toolchain = Toolchain {
+    job: "make",
+    actions_setup: "",
+    gitlab_image: "gcc:latest",
+    circleci_image: "gcc:latest",
+    setup: &[],
+    gitlab_variables: &[],
+    gitlab_cache: &[],
+    circleci_cache: &[],
+    cache_keys: &[],
+    steps: &[("Build", "make"), ("Test", "make test")],
+};
+
+/// Stand-in when no build system is recognized.
+const GENERIC: Toolchain = Toolchain {
+    job: "build",
+    actions_setup: "",
+    gitlab_image: "ubuntu:24.04",
+    circleci_image: "cimg/base:current",
+    setup: &[],
+    gitlab_variables: &[],
+    gitlab_cache: &[],
+    circleci_cache: &[],
+    cache_keys: &[],
+    steps: &[("Build", "echo \"Replace with build and test commands\"")],
+};
+
+/// Toolchains for the detected build systems, one job each. Yarn and pnpm
+/// replace npm, and Poetry is used when `pyproject.toml` configures it.
+fn toolchains(root: &Path, result: &ScanResult) -> Vec<&'static Toolchain> {
+    let has = |b: BuildSystem| result.build_systems.contains(&b);
+    let file = |f: &str| root.join(f).is_file();
+    let mut found = Vec::new();
+    if has(BuildSystem::Cargo) {
+        found.push(&RUST);
+    }
+    if has(BuildSystem::Pnpm) {
+        found.push(&PNPM);
+    } else if has(BuildSystem::Yarn) {
+        found.push(&YARN);
+    } else if has(BuildSystem::Npm) {
+        found.push(&NPM);
+    }
+    let poetry = std::fs::read_to_string(root.join("pyproject.toml"))
+        .is_ok_and(|p| p.contains("[tool.poetry"));
+    if poetry {
+        found.push(&POETRY);
+    } else if file("requirements.txt") {
+        found.push(&PIP_REQUIREMENTS);
+    } else if has(BuildSystem::Pip) || has(BuildSystem::Poetry) {
+        found.push(&PIP_PACKAGE);
+    }
+    if has(BuildSystem::Go) {
+        found.push(&GO);
+    }
+    if has(BuildSystem::Maven) {
+        found.push(&MAVEN);
+    } else if has(BuildSystem::Gradle) {
+        found.push(&GRADLE);
+    }
+    if has(BuildSystem::Msbuild) {
+        found.push(&DOTNET);
+    }
+    if has(BuildSystem::Bundler) {
+        found.push(&RUBY);
+    }
+    if has(BuildSystem::Composer) {
+        found.push(&PHP);
+    }
+    if has(BuildSystem::Mix) {
+        found.push(&ELIXIR);
+    }
+    // CMake projects often keep a Makefile too; CMake drives the build
+    if has(BuildSystem::Cmake) {
+        found.push(&CMAKE);
+    } else if has(BuildSystem::Make) && found.is_empty() {
+        found.push(&MAKE);
+    }
+    if found.is_empty() {
+        found.push(&GENERIC);
+    }
+    found
+}
+
+/// Starter pipeline for `provider`, with a build-and-test job per detected
+/// build system. GitHub Actions runs on pushes to `branch` and on pull
+/// requests, with a read-only token.
+pub fn render(provider: Provider, root: &Path, result: &ScanResult, branch: &str) -> String {
+    let jobs = toolchains(root, result);
+    let cache_key = |t: &Toolchain| {
+        t.cache_keys
+            .iter()
+            .copied()
+            .find(|f| root.join(f).is_file())
+    };
+    match provider {
+        Provider::GitHub => github(&jobs, branch),
+        Provider::GitLab => gitlab(&jobs, &cache_key),
+        Provider::CircleCi => circleci(&jobs, &cache_key),
+    }
+}
+
+fn github(jobs: &[&Toolchain], branch: &str) -> String {
+    let mut out = format!(
+        "name: CI
+
+on:
+  push:
+    branches: [{}]
+  pull_request:
+
+permissions:
+  contents: read
+
+jobs:
+",
+        branch
+    );
+    for (i, t) in jobs.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "  {}:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n",
+            t.job
+        ));
+        out.push_str(t.actions_setup);
+        for (name, command) in t.steps {
+            out.push_str(&format!(
+                "      - name: {}\n        run: {}\n",
+                name, command
+            ));
+        }
+    }
+    out
+}
+
+fn gitlab(jobs: &[&Toolchain], cache_key: &dyn Fn(&Toolchain) -> Option<&'static str>) -> String {
+    let mut out = String::from("stages:\n  - test\n");
+    for t in jobs {
+        out.push_str(&format!(
+            "\n{}:\n  stage: test\n  image: {}\n",
+            t.job, t.gitlab_image
+        ));
+        if !t.gitlab_variables.is_empty() {
+            out.push_str("  variables:\n");
+            for (name, value) in t.gitlab_variables {
+                out.push_str(&format!("    {}: \"{}\"\n", name, value));
+            }
+        }
+        if !t.gitlab_cache.is_empty() {
+            out.push_str("  cache:\n");
+            match cache_key(t) {
+                Some(file) => {
+                    out.push_str(&format!("    key:\n      files:\n        - {}\n", file))
+                }
+                None => out.push_str("    key: \"$CI_JOB_NAME\"\n"),
+            }
+            out.push_str("    paths:\n");
+            for path in t.gitlab_cache {
+                out.push_str(&format!("      - {}\n", path));
+            }
+        }
+        if !t.setup.is_empty() {
+            out.push_str("  before_script:\n");
+            for command in t.setup {
+                out.push_str(&format!("    - {}\n", command));
+            }
+        }
+        out.push_str("  script:\n");
+        for (_, command) in t.steps {
+            out.push_str(&format!("    - {}\n", command));
+        }
+    }
+    out
+}
+
+fn circleci(jobs: &[&Toolchain], cache_key: &dyn Fn(&Toolchain) -> Option<&'static str>) -> String {
+    let mut out = String::from("version: 2.1\n\njobs:\n");
+    for (i, t) in jobs.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "  {}:\n    docker:\n      - image: {}\n    steps:\n      - checkout\n",
+            t.job, t.circleci_image
+        ));
+        for command in t.setup {
+            out.push_str(&format!("      - run: {}\n", command));
+        }
+        let key = match cache_key(t) {
+            Some(file) => format!("{}-v1-{{{{ checksum \"{}\" }}}}", t.job, file),
+            None => format!("{}-v1", t.job),
+        };
+        let cached = !t.circleci_cache.is_empty();
+        if cached {
+            out.push_str(&format!(
+                "      - restore_cache:\n          keys:\n            - {}\n",
+                key
+            ));
+        }
+        for (name, command) in t.steps {
+            out.push_str(&format!(
+                "      - run:\n          name: {}\n          command: {}\n",
+                name, command
+            ));
+        }
+        if cached {
+            out.push_str(&format!(
+                "      - save_cache:\n          key: {}\n          paths:\n",
+                key
+            ));
+            for path in t.circleci_cache {
+                out.push_str(&format!("            - {}\n", path));
+            }
+        }
+    }
+    out.push_str("\nworkflows:\n  ci:\n    jobs:\n");
+    for t in jobs {
+        out.push_str(&format!("      - {}\n", t.job));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render, toolchains, Provider};
+    use crate::ci::{self, CiAdvice};
+    use crate::scanner::{BuildSystem, CISystem, ScanResult};
+    use crate::testutil::TempDir;
+    use crate::workflows::Workflow;
+    use crate::yaml;
+    use std::fs;
+
+    fn detected(build_systems: &[BuildSystem]) -> ScanResult {
+        ScanResult {
+            build_systems: build_systems.to_vec(),
+            ..ScanResult::default()
+        }
+    }
+
+    fn jobs(result: &ScanResult) -> Vec<&'static str> {
+        toolchains(&TempDir::new("pipeline-jobs"), result)
+            .iter()
+            .map(|t| t.job)
+            .collect()
+    }
+
+    #[test]
+    fn providers() {
+        assert_eq!(Provider::parse("GitLab"), Ok(Provider::GitLab));
+        assert_eq!(
+            Provider::parse("jenkins").unwrap_err(),
+            "unknown CI provider 'jenkins' (expected one of: github, gitlab, circleci)"
+        );
+    }
+
+    #[test]
+    fn jobs_per_build_system() {
+        let result = detected(&[
+            BuildSystem::Cargo,
+            BuildSystem::Npm,
+            BuildSystem::Yarn,
+            BuildSystem::Cmake,
+            BuildSystem::Make,
+        ]);
+        assert_eq!(jobs(&result), ["rust", "node", "cmake"]);
+        assert_eq!(jobs(&detected(&[BuildSystem::Make])), ["make"]);
+        assert_eq!(jobs(&detected(&[])), ["build"]);
+    }
+
+    #[test]
+    fn generated_pipelines_pass_their_own_checks() {
+        let root = TempDir::new("pipeline");
+        fs::write(root.join("Cargo.lock"), "").unwrap();
+        let result = detected(&[BuildSystem::Cargo, BuildSystem::Npm, BuildSystem::Go]);
+        for (provider, ci) in [
+            (Provider::GitHub, CISystem::GitHubActions),
+            (Provider::GitLab, CISystem::GitLab),
+            (Provider::CircleCi, CISystem::CircleCI),
+        ] {
+            let pipeline = render(provider, &root, &result, "main");
+            assert!(yaml::parse(&pipeline).is_ok(), "{}", provider.key());
+            let uncached: Vec<_> = ci::analyze(provider.path(), &ci, &pipeline)
+                .into_iter()
+                .filter(|a| matches!(a, CiAdvice::MissingCache { .. }))
+                .collect();
+            assert!(uncached.is_empty(), "{}: {:?}", provider.key(), uncached);
+        }
+        let gitlab = render(Provider::GitLab, &root, &result, "main");
+        assert!(gitlab.contains("    key:\n      files:\n        - Cargo.lock\n"));
+
+        let github = render(Provider::GitHub, &root, &result, "trunk");
+        let workflow = Workflow::parse("ci.yml", &github).unwrap();
+        assert_eq!(workflow.triggers, ["push", "pull_request"]);
+        assert_eq!(workflow.permissions, "contents: read");
+        assert!(!workflow.write_all && !workflow.push_all_branches);
+    }
+}
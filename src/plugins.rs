@@ -0,0 +1,260 @@
+use crate::config::parse_severity;
+use crate::custom::Violation;
+use crate::scanner::{ScanError, ScanResult, Severity};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Executables on `PATH` whose names start with this are external checks:
+/// `repomedic-check-owners` is the plugin `owners`.
+pub const PREFIX: &str = "repomedic-check-";
+
+/// Version of the JSON a plugin receives, sent as `protocol`.
+pub const PROTOCOL: u32 = 1;
+
+/// Seconds a plugin may run before it is killed.
+const TIMEOUT_SECS: u64 = 60;
+
+/// An external check found on `PATH`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    /// The executable's name without `PREFIX` (or `.exe`).
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// What a plugin reads on stdin.
+#[derive(Serialize)]
+struct Input<'a> {
+    protocol: u32,
+    /// Absolute path of the repository root, also the plugin's working
+    /// directory.
+    root: String,
+    /// Files the scan walked, relative to the root, with their sizes.
+    files: &'a [(String, u64)],
+    /// Everything the built-in detectors found, as in the JSON report.
+    result: &'a ScanResult,
+}
+
+/// What a plugin writes on stdout.
+#[derive(Deserialize)]
+struct Output {
+    #[serde(default)]
+    findings: Vec<Reported>,
+}
+
+/// One finding from a plugin. `id` defaults to the plugin's name and
+/// `severity` to warning.
+#[derive(Deserialize)]
+struct Reported {
+    id: Option<String>,
+    severity: Option<String>,
+    message: String,
+    file: Option<String>,
+    line: Option<usize>,
+}
+
+/// Plugins on `PATH`, by name; the first directory wins when two provide
+/// the same one.
+pub fn discover() -> Vec<Plugin> {
+    let Some(path) = env::var_os("PATH") else {
+        return Vec::new();
+    };
+    let mut plugins: Vec<Plugin> = Vec::new();
+    for dir in env::split_paths(&path) {
+        let Ok(entries) = dir.read_dir() else {
+            continue;
+        };
+        let mut found: Vec<Plugin> = entries
+            .flatten()
+            .filter(|e| is_executable(&e.path()))
+            .filter_map(|e| {
+                let file = e.file_name().to_str()?.to_string();
+                let name = file.strip_prefix(PREFIX)?;
+                let name = name.strip_suffix(".exe").unwrap_or(name);
+                Some(Plugin {
+                    name: name.to_string(),
+                    path: e.path(),
+                })
+            })
+            .filter(|p| !p.name.is_empty())
+            .collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        for plugin in found {
+            if !plugins.iter().any(|p| p.name == plugin.name) {
+                plugins.push(plugin);
+            }
+        }
+    }
+    plugins
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|e| e.eq_ignore_ascii_case("exe"))
+}
+
+/// Run `plugin` in `root` with the scan so far on stdin and turn what it
+/// prints into violations. A plugin that can't start, exits non-zero, runs
+/// past the timeout, or prints something other than the expected JSON
+/// becomes a scan warning instead.
+pub fn run(
+    plugin: &Plugin,
+    root: &Path,
+    files: &[(String, u64)],
+    result: &ScanResult,
+) -> Result<Vec<Violation>, ScanError> {
+    let label = format!("{}{}", PREFIX, plugin.name);
+    let fail = |message: String| ScanError::parse(&label, message);
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let input = serde_json::to_vec(&Input {
+        protocol: PROTOCOL,
+        root: root.display().to_string(),
+        files,
+        result,
+    })
+    .map_err(|e| fail(e.to_string()))?;
+
+    let mut child = Command::new(&plugin.path)
+        .current_dir(&root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| ScanError::io(&label, &e))?;
+    // Feed stdin and drain the pipes on threads so a chatty plugin can't
+    // block on a full pipe while we wait for it
+    let mut stdin = child.stdin.take();
+    let writer = thread::spawn(move || {
+        if let Some(stdin) = stdin.as_mut() {
+            // A plugin that ignores its input may close stdin early
+            let _ = stdin.write_all(&input);
+        }
+    });
+    let drain = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut text = String::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_string(&mut text);
+            }
+            text
+        })
+    };
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as _));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as _));
+
+    let deadline = Instant::now() + Duration::from_secs(TIMEOUT_SECS);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(20)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(fail(format!("timed out after {}s", TIMEOUT_SECS)));
+            }
+            Err(e) => return Err(ScanError::io(&label, &e)),
+        }
+    };
+    let _ = writer.join();
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        let reason = stderr.lines().next().unwrap_or("").trim();
+        return Err(fail(match status.code() {
+            Some(code) if reason.is_empty() => format!("exited with status {}", code),
+            Some(code) => format!("exited with status {}: {}", code, reason),
+            None => "killed by a signal".to_string(),
+        }));
+    }
+    let output: Output =
+        serde_json::from_str(&stdout).map_err(|e| fail(format!("invalid output ({})", e)))?;
+    Ok(output
+        .findings
+        .into_iter()
+        .map(|f| Violation {
+            rule: f.id.unwrap_or_else(|| plugin.name.clone()),
+            severity: f
+                .severity
+                .as_deref()
+                .and_then(parse_severity)
+                .unwrap_or(Severity::Warning),
+            message: f.message,
+            file: f.file,
+            line: f.line,
+        })
+        .collect())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::{run, Plugin};
+    use crate::scanner::{ScanResult, Severity};
+    use crate::testutil::TempDir;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+
+    /// A plugin named `name` running the shell `script`.
+    fn plugin(dir: &Path, name: &str, script: &str) -> Plugin {
+        let path = dir.join(format!("repomedic-check-{}", name));
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        Plugin {
+            name: name.to_string(),
+            path,
+        }
+    }
+
+    fn error(dir: &Path, plugin: &Plugin) -> String {
+        run(plugin, dir, &[], &ScanResult::default())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn findings() {
+        let dir = TempDir::new("plugins");
+        // Echo the protocol back to show the plugin read its input
+        let owners = plugin(
+            &dir,
+            "owners",
+            r#"grep -q '"protocol":1' && echo '{"findings": [
+  {"message": "No owner for src/", "file": "src", "line": 2},
+  {"id": "owners-stale", "severity": "error", "message": "Stale team"}
+]}'"#,
+        );
+        let files = [("src/main.rs".to_string(), 10)];
+        let found = run(&owners, &dir, &files, &ScanResult::default()).unwrap();
+        let summary: Vec<_> = found
+            .iter()
+            .map(|v| (v.rule.as_str(), v.severity, v.file.as_deref(), v.line))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("owners", Severity::Warning, Some("src"), Some(2)),
+                ("owners-stale", Severity::Error, None, None),
+            ]
+        );
+
+        let failing = plugin(&dir, "fail", "echo 'no config' >&2; exit 3");
+        assert!(error(&dir, &failing).contains("exited with status 3: no config"));
+        let garbled = plugin(&dir, "garbled", "echo 'not json'");
+        assert!(error(&dir, &garbled).contains("invalid output"));
+    }
+}
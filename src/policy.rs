@@ -0,0 +1,329 @@
+use crate::cache;
+use crate::config::{self, Value};
+use crate::scanner::ScanError;
+use crate::yaml::{self, Node};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+#[cfg(feature = "network")]
+use std::process::Command;
+
+/// Last fetched copy of a policy URL, kept in the Git directory
+/// (`.git/repomedic-policy.json`), or at the root of a directory outside Git.
+pub const CACHE_FILE: &str = ".repomedic-policy.json";
+
+/// Seconds to wait for the policy server.
+#[cfg(feature = "network")]
+const FETCH_TIMEOUT_SECS: u64 = 15;
+
+/// Where the policy a config is layered over was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Origin {
+    /// A local file.
+    File,
+    /// Downloaded this run.
+    Fetched,
+    /// The server answered that the cached copy is current (ETag match).
+    NotModified,
+    /// The cached copy, without asking the server: `--offline`, or the
+    /// fetch failed for `reason`.
+    Cached { reason: Option<String> },
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cached {
+    url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    body: String,
+}
+
+/// Whether `location` is a URL rather than a file path.
+pub fn is_url(location: &str) -> bool {
+    location.starts_with("https://") || location.starts_with("http://")
+}
+
+/// Read the policy at `location`: a file relative to `base` (the config's
+/// directory), or an HTTPS URL cached under `root`. Plain HTTP needs
+/// `allow_http`. `offline` reads the cached copy of a URL without fetching it.
+pub fn load(
+    root: &Path,
+    base: &Path,
+    location: &str,
+    allow_http: bool,
+    offline: bool,
+) -> Result<(Origin, String), ScanError> {
+    if !is_url(location) {
+        return fs::read_to_string(base.join(location))
+            .map(|text| (Origin::File, text))
+            .map_err(|e| ScanError::io(location, &e));
+    }
+    if !location.starts_with("https://") && !allow_http {
+        return Err(ScanError::network(
+            location,
+            "policy URLs must use https (set `allow_http_policy = true` to allow http)",
+        ));
+    }
+    let cache_path = cache::git_dir(root).map_or_else(
+        || root.join(CACHE_FILE),
+        |dir| dir.join("repomedic-policy.json"),
+    );
+    let cached: Option<Cached> = fs::read_to_string(&cache_path)
+        .ok()
+        .and_then(|s| serde_json::from_str::<Cached>(&s).ok())
+        .filter(|c| c.url == location);
+    if offline {
+        return cached
+            .map(|c| (Origin::Cached { reason: None }, c.body))
+            .ok_or_else(|| {
+                ScanError::network(location, "not cached yet; run once without --offline")
+            });
+    }
+    match fetch(
+        location,
+        allow_http,
+        cached.as_ref().and_then(|c| c.etag.as_deref()),
+    ) {
+        Ok(None) => match cached {
+            Some(c) => Ok((Origin::NotModified, c.body)),
+            None => Err(ScanError::network(
+                location,
+                "server sent 304 without a cached copy",
+            )),
+        },
+        Ok(Some((etag, body))) => {
+            let fresh = Cached {
+                url: location.to_string(),
+                etag,
+                body,
+            };
+            // A cache that can't be written only costs the next run a download
+            if let Ok(json) = serde_json::to_string_pretty(&fresh) {
+                let _ = fs::write(&cache_path, json + "\n");
+            }
+            Ok((Origin::Fetched, fresh.body))
+        }
+        Err(e) => match cached {
+            Some(c) => Ok((Origin::Cached { reason: Some(e) }, c.body)),
+            None => Err(ScanError::network(location, e)),
+        },
+    }
+}
+
+/// GET `url` with `curl`, sending `etag` as `If-None-Match`. `None` means
+/// the server answered 304 Not Modified; otherwise the new ETag and body.
+/// Requests and redirects stay on HTTPS, or HTTP(S) with `allow_http`, so a
+/// redirect can't reach `file://` or other schemes curl speaks.
+#[cfg(feature = "network")]
+fn fetch(
+    url: &str,
+    allow_http: bool,
+    etag: Option<&str>,
+) -> Result<Option<(Option<String>, String)>, String> {
+    let protocols = if allow_http { "=http,https" } else { "=https" };
+    let mut command = Command::new("curl");
+    command
+        .args(["-sS", "-L", "-D", "-", "-H", "User-Agent: repomedic"])
+        .args(["--proto", protocols, "--proto-redir", protocols])
+        .arg("--max-time")
+        .arg(FETCH_TIMEOUT_SECS.to_string());
+    if let Some(etag) = etag {
+        command.arg("-H").arg(format!("If-None-Match: {}", etag));
+    }
+    let output = command
+        .arg(url)
+        .output()
+        .map_err(|e| format!("could not run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    // Headers come first, one block per response when redirects are followed
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut rest: &str = &text;
+    let (mut status, mut etag) = (0, None);
+    while rest.starts_with("HTTP/") {
+        let (head, body) = rest.split_once("\r\n\r\n").unwrap_or((rest, ""));
+        status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        etag = head.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("etag")
+                .then(|| value.trim().to_string())
+        });
+        rest = body;
+    }
+    match status {
+        304 => Ok(None),
+        200..=299 => Ok(Some((etag, rest.to_string()))),
+        code => Err(format!("HTTP {}", code)),
+    }
+}
+
+#[cfg(not(feature = "network"))]
+fn fetch(
+    _url: &str,
+    _allow_http: bool,
+    _etag: Option<&str>,
+) -> Result<Option<(Option<String>, String)>, String> {
+    Err("this build of repomedic has no network support (the `network` feature)".to_string())
+}
+
+/// Config entries in the policy text: YAML when `location` ends in `.yaml`
+/// or `.yml`, the config's TOML otherwise. A policy can't point at another
+/// policy.
+pub fn entries(location: &str, text: &str) -> Result<Vec<(usize, String, String, Value)>, String> {
+    let path = location.split(['?', '#']).next().unwrap_or(location);
+    let entries = if path.ends_with(".yaml") || path.ends_with(".yml") {
+        yaml_entries(text)?
+    } else {
+        config::parse_toml(text)?
+    };
+    if let Some((line, ..)) = entries
+        .iter()
+        .find(|(_, section, key, _)| section.is_empty() && key == "policy")
+    {
+        return Err(format!("line {}: a policy can't set `policy`", line));
+    }
+    Ok(entries)
+}
+
+/// Flatten a YAML mapping into config entries: top-level scalars are
+/// top-level keys, and nested mappings are sections (`rules:` then
+/// `catalog-info:` is `[rules.catalog-info]`).
+fn yaml_entries(text: &str) -> Result<Vec<(usize, String, String, Value)>, String> {
+    let root = yaml::parse(text)?;
+    let mut entries = Vec::new();
+    match &root.value {
+        yaml::Value::Null => {}
+        yaml::Value::Map(items) => flatten("", items, &mut entries)?,
+        _ => return Err(format!("line {}: policy must be a mapping", root.line)),
+    }
+    Ok(entries)
+}
+
+fn flatten(
+    section: &str,
+    items: &[(String, Node)],
+    entries: &mut Vec<(usize, String, String, Value)>,
+) -> Result<(), String> {
+    for (key, node) in items {
+        if let yaml::Value::Map(inner) = &node.value {
+            let nested = if section.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", section, key)
+            };
+            flatten(&nested, inner, entries)?;
+            continue;
+        }
+        entries.push((node.line, section.to_string(), key.clone(), value(node)?));
+    }
+    Ok(())
+}
+
+fn value(node: &Node) -> Result<Value, String> {
+    match &node.value {
+        yaml::Value::Scalar(s) => Ok(match s.as_str() {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            _ => s.parse().map_or_else(|_| Value::Str(s.clone()), Value::Int),
+        }),
+        yaml::Value::Seq(items) => items
+            .iter()
+            .map(value)
+            .collect::<Result<_, _>>()
+            .map(Value::List),
+        yaml::Value::Null => Err(format!("line {}: missing value", node.line)),
+        yaml::Value::Map(_) => Err(format!("line {}: unexpected mapping", node.line)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{entries, is_url, load, Origin};
+    use crate::config::Value;
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    #[test]
+    fn yaml_policies() {
+        let yaml = "min_score: 80\ndisabled: [missing-changelog]\nrules:\n  catalog-info:\n    require_file: catalog-info.yaml\n    strict: true\n";
+        assert_eq!(
+            entries("https://example.com/policy.yml?ref=main", yaml).unwrap(),
+            [
+                (1, String::new(), "min_score".to_string(), Value::Int(80)),
+                (
+                    2,
+                    String::new(),
+                    "disabled".to_string(),
+                    Value::List(vec![Value::Str("missing-changelog".to_string())])
+                ),
+                (
+                    5,
+                    "rules.catalog-info".to_string(),
+                    "require_file".to_string(),
+                    Value::Str("catalog-info.yaml".to_string())
+                ),
+                (
+                    6,
+                    "rules.catalog-info".to_string(),
+                    "strict".to_string(),
+                    Value::Bool(true)
+                ),
+            ]
+        );
+        assert_eq!(
+            entries("policy.yaml", "- a\n").unwrap_err(),
+            "line 1: policy must be a mapping"
+        );
+        assert_eq!(
+            entries("policy.yaml", "min_score:\n").unwrap_err(),
+            "line 1: missing value"
+        );
+    }
+
+    #[test]
+    fn toml_policies() {
+        let found = entries("policy.toml", "min_score = 70\n").unwrap();
+        assert_eq!(found[0].2, "min_score");
+        assert_eq!(
+            entries("policy.toml", "# shared\npolicy = \"other.toml\"\n").unwrap_err(),
+            "line 2: a policy can't set `policy`"
+        );
+    }
+
+    #[test]
+    fn locations() {
+        assert!(is_url("https://example.com/p.toml"));
+        assert!(!is_url("../policy.toml"));
+        let dir = TempDir::new("policy");
+        fs::write(dir.join("policy.toml"), "min_score = 70\n").unwrap();
+        assert_eq!(
+            load(&dir, &dir, "policy.toml", false, false).unwrap(),
+            (Origin::File, "min_score = 70\n".to_string())
+        );
+        let http = load(&dir, &dir, "http://example.com/p.toml", false, true).unwrap_err();
+        assert!(http.to_string().contains("allow_http_policy = true"));
+        let offline = load(&dir, &dir, "https://example.com/p.toml", false, true).unwrap_err();
+        assert!(offline.to_string().contains("not cached yet"));
+
+        // The cached copy lives in .git, out of the working tree
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(
+            dir.join(".git/repomedic-policy.json"),
+            r#"{"url": "https://example.com/p.toml", "body": "min_score = 80\n"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            load(&dir, &dir, "https://example.com/p.toml", false, true).unwrap(),
+            (
+                Origin::Cached { reason: None },
+                "min_score = 80\n".to_string()
+            )
+        );
+    }
+}
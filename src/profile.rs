@@ -0,0 +1,33 @@
+use std::env;
+
+/// Where repomedic is running, which picks defaults for color, the banner,
+/// the fail level, and the output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// A CI job: plain output, and the `[ci]` policy from the config.
+    Ci,
+    /// A developer's terminal.
+    Local,
+}
+
+impl Profile {
+    /// `Ci` when `CI`, `GITHUB_ACTIONS`, or `GITLAB_CI` is set to anything
+    /// but empty, `false`, or `0`.
+    pub fn detect() -> Self {
+        let set =
+            |var: &str| env::var(var).is_ok_and(|v| !matches!(v.trim(), "" | "0") && v != "false");
+        if ["CI", "GITHUB_ACTIONS", "GITLAB_CI"].iter().any(|v| set(v)) {
+            Profile::Ci
+        } else {
+            Profile::Local
+        }
+    }
+}
+
+/// The CI service running this job, when it's one repomedic has an output
+/// format for: `github` (Actions).
+pub fn provider() -> Option<&'static str> {
+    env::var("GITHUB_ACTIONS")
+        .is_ok_and(|v| v == "true")
+        .then_some("github")
+}
@@ -0,0 +1,399 @@
+use crate::badges::image_urls;
+use crate::install::{self, InstallMismatch, Package};
+use crate::links;
+use serde::Serialize;
+use std::path::Path;
+
+/// Fewer prose words than this makes a README "short".
+pub const MIN_WORDS: usize = 100;
+
+/// Sections a complete README has, in the order they're reported.
+pub const SECTIONS: &[&str] = &[
+    "title",
+    "description",
+    "installation",
+    "usage",
+    "license",
+    "badges",
+];
+
+/// Heading words that mark each section. A heading may count for several
+/// (`Getting started` usually covers installation and usage).
+const HEADINGS: &[(&str, &[&str])] = &[
+    (
+        "description",
+        &[
+            "about",
+            "description",
+            "overview",
+            "introduction",
+            "what is",
+        ],
+    ),
+    (
+        "installation",
+        &[
+            "install",
+            "setup",
+            "set up",
+            "getting started",
+            "quick start",
+            "quickstart",
+            "building",
+            "requirements",
+        ],
+    ),
+    (
+        "usage",
+        &[
+            "usage",
+            "example",
+            "how to use",
+            "getting started",
+            "quick start",
+            "quickstart",
+            "tutorial",
+        ],
+    ),
+    ("license", &["license", "licence", "licensing"]),
+];
+
+/// Text the README generator leaves for the author to replace.
+const GENERATED_PLACEHOLDERS: &[&str] = &[
+    "A brief description of the project.",
+    "# Add installation instructions",
+    "# Add usage examples",
+    "Lorem ipsum",
+];
+
+/// How complete a README is.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadmeQuality {
+    pub file: String,
+    /// 0-100: sections, length, links, and placeholders.
+    pub score: u8,
+    /// Entries from `SECTIONS` the README lacks.
+    pub missing_sections: Vec<String>,
+    /// Prose words, not counting code blocks.
+    pub words: usize,
+    pub broken_links: Vec<BrokenLink>,
+    pub placeholders: Vec<Placeholder>,
+    /// Install commands naming another package or package manager.
+    pub install_mismatches: Vec<InstallMismatch>,
+}
+
+/// A relative link or image whose target doesn't exist.
+#[derive(Debug, Clone, Serialize)]
+pub struct BrokenLink {
+    pub line: usize,
+    pub target: String,
+}
+
+/// A `TODO`, `FIXME`, or generated placeholder left in the text.
+#[derive(Debug, Clone, Serialize)]
+pub struct Placeholder {
+    pub line: usize,
+    pub text: String,
+}
+
+impl ReadmeQuality {
+    /// Analyze `content`, read from `file` under `root`, checking install
+    /// commands against the manifests' `packages`.
+    pub fn analyze(root: &Path, file: &str, content: &str, packages: &[Package]) -> Self {
+        let mut found: Vec<&str> = Vec::new();
+        let mut words = 0;
+        let mut broken_links = Vec::new();
+        let mut placeholders = Vec::new();
+        let mut in_code = false;
+        let mut seen_heading = false;
+        let mut prev = "";
+        for (n, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code = !in_code;
+                prev = "";
+                continue;
+            }
+            if let Some(p) = GENERATED_PLACEHOLDERS.iter().find(|p| trimmed.contains(*p)) {
+                placeholders.push(Placeholder {
+                    line: n + 1,
+                    text: p.to_string(),
+                });
+            }
+            if in_code {
+                continue;
+            }
+            if let Some(word) = placeholder_word(&without_code_spans(trimmed)) {
+                placeholders.push(Placeholder {
+                    line: n + 1,
+                    text: word.to_string(),
+                });
+            }
+            for target in links::targets(line) {
+                if links::is_missing(root, file, target) {
+                    broken_links.push(BrokenLink {
+                        line: n + 1,
+                        target: target.to_string(),
+                    });
+                }
+            }
+            if is_badge_line(line) {
+                found.push("badges");
+                prev = "";
+                continue;
+            }
+            // `Title` underlined with `===`
+            let setext =
+                !prev.is_empty() && !trimmed.is_empty() && trimmed.trim_matches('=').is_empty();
+            if let Some(heading) = heading(trimmed) {
+                if heading.level == 1 || !seen_heading {
+                    found.push("title");
+                }
+                seen_heading = true;
+                let text = heading.text.to_lowercase();
+                for (section, words) in HEADINGS {
+                    if words.iter().any(|w| text.contains(w)) {
+                        found.push(section);
+                    }
+                }
+            } else if setext {
+                found.push("title");
+                seen_heading = true;
+            } else {
+                let count = trimmed
+                    .split_whitespace()
+                    .filter(|w| w.chars().any(char::is_alphanumeric))
+                    .count();
+                words += count;
+                // Any sentence-length paragraph describes the project
+                if count >= 8 && !trimmed.starts_with(['<', '|', '-', '*', '>', '!', '[']) {
+                    found.push("description");
+                }
+            }
+            prev = trimmed;
+        }
+        let missing_sections: Vec<String> = SECTIONS
+            .iter()
+            .filter(|s| !found.contains(s))
+            .map(|s| s.to_string())
+            .collect();
+        let mut quality = ReadmeQuality {
+            file: file.to_string(),
+            score: 0,
+            missing_sections,
+            words,
+            broken_links,
+            placeholders,
+            install_mismatches: install::check(content, packages),
+        };
+        quality.score = quality.compute_score();
+        quality
+    }
+
+    pub fn is_short(&self) -> bool {
+        self.words < MIN_WORDS
+    }
+
+    /// 10 points per section, 20 for length (scaled below `MIN_WORDS`),
+    /// and 10 each for no broken links and no placeholders.
+    fn compute_score(&self) -> u8 {
+        let sections = (SECTIONS.len() - self.missing_sections.len()) * 10;
+        let length = self.words.min(MIN_WORDS) * 20 / MIN_WORDS;
+        let links = if self.broken_links.is_empty() { 10 } else { 0 };
+        let placeholders = if self.placeholders.is_empty() { 10 } else { 0 };
+        (sections + length + links + placeholders).min(100) as u8
+    }
+}
+
+/// Text of the README's title: the first level-1 heading (ATX, setext, or
+/// `<h1>`), without badges, link targets, HTML tags, or emphasis.
+pub fn title(content: &str) -> Option<String> {
+    let mut in_code = false;
+    let mut prev = "";
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+        let text = match heading(trimmed) {
+            Some(h) if h.level == 1 => Some(h.text),
+            _ if !prev.is_empty()
+                && !trimmed.is_empty()
+                && trimmed.trim_matches('=').is_empty() =>
+            {
+                Some(prev)
+            }
+            _ => None,
+        };
+        if let Some(text) = text.map(plain_text).filter(|t| !t.is_empty()) {
+            return Some(text);
+        }
+        prev = trimmed;
+    }
+    None
+}
+
+/// Markdown inline text as plain text: images and HTML tags dropped, links
+/// reduced to their text.
+fn plain_text(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let skip = if rest.starts_with("![") {
+            // Image (often a badge): drop it, alt text included
+            rest.find(')').map_or(rest.len(), |i| i + 1)
+        } else if c == '<' {
+            rest.find('>').map_or(rest.len(), |i| i + 1)
+        } else if c == ']' && rest[1..].starts_with('(') {
+            rest.find(')').map_or(rest.len(), |i| i + 1)
+        } else {
+            if !matches!(c, '[' | ']' | '*' | '_' | '`' | '~') {
+                out.push(c);
+            }
+            c.len_utf8()
+        };
+        rest = &rest[skip..];
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether lowercased heading text names an installation or usage section.
+pub fn is_install_heading(text: &str) -> bool {
+    HEADINGS
+        .iter()
+        .filter(|(section, _)| matches!(*section, "installation" | "usage"))
+        .any(|(_, words)| words.iter().any(|w| text.contains(w)))
+}
+
+struct Heading<'a> {
+    level: usize,
+    text: &'a str,
+}
+
+/// An ATX (`## Usage`) or HTML (`<h1>`) heading.
+fn heading(line: &str) -> Option<Heading<'_>> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&level) && line[level..].starts_with(' ') {
+        return Some(Heading {
+            level,
+            text: line[level..].trim().trim_end_matches('#').trim(),
+        });
+    }
+    let lower = line.to_ascii_lowercase();
+    let rest = lower.strip_prefix("<h")?;
+    let level = rest.chars().next()?.to_digit(10)? as usize;
+    let start = line.find('>')? + 1;
+    let end = line[start..].find("</").map_or(line.len(), |i| start + i);
+    Some(Heading {
+        level,
+        text: &line[start..end],
+    })
+}
+
+/// Whether a line shows a status badge (shields.io, a workflow badge, ...).
+fn is_badge_line(line: &str) -> bool {
+    image_urls(line).iter().any(|url| {
+        let url = url.to_ascii_lowercase();
+        url.contains("shields.io") || url.contains("badge") || url.contains("badgen.net")
+    })
+}
+
+/// `line` with inline code spans blanked out.
+fn without_code_spans(line: &str) -> String {
+    line.split('`')
+        .enumerate()
+        .filter(|(i, _)| i % 2 == 0)
+        .map(|(_, part)| part)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A `TODO`, `FIXME`, `TBD`, or `XXX` written as its own word.
+fn placeholder_word(line: &str) -> Option<&'static str> {
+    ["TODO", "FIXME", "TBD", "XXX"].into_iter().find(|word| {
+        line.match_indices(word).any(|(i, _)| {
+            let before = line[..i].chars().next_back();
+            let after = line[i + word.len()..].chars().next();
+            !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+                && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_install_heading, title, ReadmeQuality, MIN_WORDS};
+    use crate::testutil::TempDir;
+
+    fn analyze(content: &str) -> ReadmeQuality {
+        ReadmeQuality::analyze(&TempDir::new("readme"), "README.md", content, &[])
+    }
+
+    #[test]
+    fn complete_readme() {
+        let prose = "word ".repeat(MIN_WORDS);
+        let content = format!(
+            "# Demo\n\n[![CI](https://img.shields.io/badge/ci-passing-green)](https://ci)\n\n{}\n\n## Getting Started\n\n```sh\n# TODO in a code block is fine\ncargo install demo\n```\n\n## License\n\nMIT, see `TODO.md`.\n",
+            prose
+        );
+        let quality = analyze(&content);
+        assert!(
+            quality.missing_sections.is_empty(),
+            "{:?}",
+            quality.missing_sections
+        );
+        assert!(quality.placeholders.is_empty());
+        assert!(!quality.is_short());
+        assert_eq!(quality.score, 100);
+    }
+
+    #[test]
+    fn sparse_readme() {
+        let quality = analyze(
+            "Demo\n====\n\nTODO: describe.\nA brief description of the project.\n\n![shot](repomedic-missing-shot.png)\n",
+        );
+        assert_eq!(
+            quality.missing_sections,
+            ["description", "installation", "usage", "license", "badges"]
+        );
+        let placeholders: Vec<_> = quality
+            .placeholders
+            .iter()
+            .map(|p| (p.line, p.text.as_str()))
+            .collect();
+        assert_eq!(
+            placeholders,
+            [(4, "TODO"), (5, "A brief description of the project.")]
+        );
+        assert_eq!(quality.broken_links[0].line, 7);
+        assert_eq!(quality.words, 10);
+        assert!(quality.is_short());
+        // The title, plus 10 of the 100 words for length
+        assert_eq!(quality.score, 12);
+    }
+
+    #[test]
+    fn titles() {
+        assert_eq!(
+            title("```\n# not this\n```\n# [Demo](https://x) ![badge](b.svg) *tool*\n").as_deref(),
+            Some("Demo tool")
+        );
+        assert_eq!(title("Demo\n===\n").as_deref(), Some("Demo"));
+        assert_eq!(
+            title("<h1 align=\"center\">Demo</h1>\n").as_deref(),
+            Some("Demo")
+        );
+        assert_eq!(title("## Only a subheading\n"), None);
+    }
+
+    #[test]
+    fn install_headings() {
+        assert!(is_install_heading("quick start"));
+        assert!(is_install_heading("installing from source"));
+        assert!(!is_install_heading("license"));
+    }
+}
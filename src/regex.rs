@@ -0,0 +1,443 @@
+/// A small regular expression engine for config-supplied patterns
+/// (`.sops.yaml` path rules, custom content rules).
+///
+/// Supports literals and escapes, `.`, classes (`[a-z]`, `[^/]`, `\d`,
+/// `\w`, `\s`), groups with `|`, `*` `+` `?` `{n,m}`, and `^` `$`. Matches
+/// are unanchored searches. Patterns compile to an NFA that runs one step per
+/// character over every live state at once, so matching takes time linear
+/// in the text and never recurses, however long the line.
+#[derive(Debug, Clone)]
+pub struct Regex {
+    pattern: String,
+    program: Vec<Inst>,
+}
+
+/// Instructions a compiled pattern is made of; longer patterns are refused.
+const MAX_PROGRAM: usize = 10_000;
+
+impl Regex {
+    /// `None` for a pattern outside the supported subset.
+    pub fn new(pattern: &str) -> Option<Regex> {
+        let mut parser = Parser {
+            chars: pattern.chars().collect(),
+            pos: 0,
+        };
+        let node = parser.alternation()?;
+        if parser.pos != parser.chars.len() {
+            return None;
+        }
+        let mut program = Vec::new();
+        compile(&node, &mut program)?;
+        program.push(Inst::Match);
+        Some(Regex {
+            pattern: pattern.to_string(),
+            program,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the pattern matches anywhere in `text`.
+    pub fn is_match(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        let mut current = Vec::new();
+        let mut next = Vec::new();
+        // The step each instruction was last added in, so it's added once
+        let mut seen = vec![usize::MAX; self.program.len()];
+        for pos in 0..=text.len() {
+            // A new thread at every position makes the search unanchored
+            if self.add(&mut current, &mut seen, 0, pos, text.len()) {
+                return true;
+            }
+            let Some(&c) = text.get(pos) else {
+                break;
+            };
+            for &pc in &current {
+                let step = match &self.program[pc] {
+                    Inst::Char(x) => *x == c,
+                    Inst::Any => true,
+                    Inst::Class(ranges, negated) => {
+                        ranges.iter().any(|(lo, hi)| (*lo..=*hi).contains(&c)) != *negated
+                    }
+                    _ => false,
+                };
+                if step && self.add(&mut next, &mut seen, pc + 1, pos + 1, text.len()) {
+                    return true;
+                }
+            }
+            current.clear();
+            std::mem::swap(&mut current, &mut next);
+        }
+        false
+    }
+
+    /// Add the thread at `pc` and everything reachable from it without
+    /// consuming a character to `list`. True when that reaches `Match`.
+    fn add(
+        &self,
+        list: &mut Vec<usize>,
+        seen: &mut [usize],
+        pc: usize,
+        pos: usize,
+        len: usize,
+    ) -> bool {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if seen[pc] == pos {
+                continue;
+            }
+            seen[pc] = pos;
+            match self.program[pc] {
+                Inst::Match => return true,
+                Inst::Jump(to) => stack.push(to),
+                Inst::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::Start => {
+                    if pos == 0 {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::End => {
+                    if pos == len {
+                        stack.push(pc + 1);
+                    }
+                }
+                _ => list.push(pc),
+            }
+        }
+        false
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Alt(Vec<Node>),
+    Seq(Vec<Node>),
+    Repeat(Box<Node>, usize, Option<usize>),
+    Char(char),
+    Any,
+    Class(Vec<(char, char)>, bool),
+    Start,
+    End,
+}
+
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Vec<(char, char)>, bool),
+    Start,
+    End,
+    /// Continue at both targets.
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+/// Append the instructions for `node`; `None` once the program outgrows
+/// `MAX_PROGRAM` (a large `{n,m}` on a large group).
+fn compile(node: &Node, program: &mut Vec<Inst>) -> Option<()> {
+    if program.len() > MAX_PROGRAM {
+        return None;
+    }
+    match node {
+        Node::Alt(options) => {
+            let mut exits = Vec::new();
+            for (i, option) in options.iter().enumerate() {
+                if i + 1 == options.len() {
+                    compile(option, program)?;
+                    break;
+                }
+                let split = program.len();
+                program.push(Inst::Split(split + 1, 0));
+                compile(option, program)?;
+                exits.push(program.len());
+                program.push(Inst::Jump(0));
+                let next = program.len();
+                program[split] = Inst::Split(split + 1, next);
+            }
+            let end = program.len();
+            for exit in exits {
+                program[exit] = Inst::Jump(end);
+            }
+        }
+        Node::Seq(items) => {
+            for item in items {
+                compile(item, program)?;
+            }
+        }
+        Node::Repeat(inner, min, max) => {
+            for _ in 0..*min {
+                compile(inner, program)?;
+            }
+            match max {
+                None => {
+                    let split = program.len();
+                    program.push(Inst::Split(split + 1, 0));
+                    compile(inner, program)?;
+                    program.push(Inst::Jump(split));
+                    let end = program.len();
+                    program[split] = Inst::Split(split + 1, end);
+                }
+                Some(max) => {
+                    let mut splits = Vec::new();
+                    for _ in *min..*max {
+                        splits.push(program.len());
+                        program.push(Inst::Split(0, 0));
+                        compile(inner, program)?;
+                    }
+                    let end = program.len();
+                    for split in splits {
+                        program[split] = Inst::Split(split + 1, end);
+                    }
+                }
+            }
+        }
+        Node::Char(c) => program.push(Inst::Char(*c)),
+        Node::Any => program.push(Inst::Any),
+        Node::Class(ranges, negated) => program.push(Inst::Class(ranges.clone(), *negated)),
+        Node::Start => program.push(Inst::Start),
+        Node::End => program.push(Inst::End),
+    }
+    (program.len() <= MAX_PROGRAM).then_some(())
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn alternation(&mut self) -> Option<Node> {
+        let mut options = vec![self.sequence()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            options.push(self.sequence()?);
+        }
+        Some(if options.len() == 1 {
+            options.remove(0)
+        } else {
+            Node::Alt(options)
+        })
+    }
+
+    fn sequence(&mut self) -> Option<Node> {
+        let mut items = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let atom = self.atom()?;
+            let node = match self.peek() {
+                Some('*') => Node::Repeat(Box::new(atom), 0, None),
+                Some('+') => Node::Repeat(Box::new(atom), 1, None),
+                Some('?') => Node::Repeat(Box::new(atom), 0, Some(1)),
+                Some('{') => {
+                    let (min, max) = self.counts()?;
+                    Node::Repeat(Box::new(atom), min, max)
+                }
+                _ => {
+                    items.push(atom);
+                    continue;
+                }
+            };
+            self.pos += 1;
+            // Lazy quantifiers match the same set of strings
+            if self.peek() == Some('?') {
+                self.pos += 1;
+            }
+            items.push(node);
+        }
+        Some(Node::Seq(items))
+    }
+
+    /// `{n}`, `{n,}` or `{n,m}`, leaving `pos` on the closing brace.
+    fn counts(&mut self) -> Option<(usize, Option<usize>)> {
+        let close = self.pos + self.chars[self.pos..].iter().position(|c| *c == '}')?;
+        let inner: String = self.chars[self.pos + 1..close].iter().collect();
+        self.pos = close;
+        Some(match inner.split_once(',') {
+            None => {
+                let n = inner.parse().ok()?;
+                (n, Some(n))
+            }
+            Some((min, "")) => (min.parse().ok()?, None),
+            Some((min, max)) => (min.parse().ok()?, Some(max.parse().ok()?)),
+        })
+    }
+
+    fn atom(&mut self) -> Option<Node> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(match c {
+            '.' => Node::Any,
+            '^' => Node::Start,
+            '$' => Node::End,
+            '(' => {
+                // Non-capturing group marker
+                if self.chars[self.pos..].starts_with(&['?', ':']) {
+                    self.pos += 2;
+                }
+                let inner = self.alternation()?;
+                if self.peek() != Some(')') {
+                    return None;
+                }
+                self.pos += 1;
+                inner
+            }
+            '[' => self.class()?,
+            '\\' => self.escape()?,
+            '*' | '+' | '?' | ')' | '{' => return None,
+            c => Node::Char(c),
+        })
+    }
+
+    fn escape(&mut self) -> Option<Node> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(match c {
+            'd' => Node::Class(vec![('0', '9')], false),
+            'w' => Node::Class(WORD.to_vec(), false),
+            's' => Node::Class(SPACE.to_vec(), false),
+            'D' => Node::Class(vec![('0', '9')], true),
+            'W' => Node::Class(WORD.to_vec(), true),
+            'S' => Node::Class(SPACE.to_vec(), true),
+            c if c.is_ascii_alphanumeric() => return None,
+            c => Node::Char(c),
+        })
+    }
+
+    fn class(&mut self) -> Option<Node> {
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut ranges = Vec::new();
+        let mut first = true;
+        loop {
+            let c = self.peek()?;
+            self.pos += 1;
+            if c == ']' && !first {
+                break;
+            }
+            first = false;
+            let lo = if c == '\\' {
+                let e = self.peek()?;
+                self.pos += 1;
+                match e {
+                    'd' => {
+                        ranges.push(('0', '9'));
+                        continue;
+                    }
+                    'w' => {
+                        ranges.extend_from_slice(WORD);
+                        continue;
+                    }
+                    's' => {
+                        ranges.extend_from_slice(SPACE);
+                        continue;
+                    }
+                    e => e,
+                }
+            } else {
+                c
+            };
+            if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|c| *c != ']') {
+                let hi = self.chars[self.pos + 1];
+                self.pos += 2;
+                ranges.push((lo, hi));
+            } else {
+                ranges.push((lo, lo));
+            }
+        }
+        Some(Node::Class(ranges, negated))
+    }
+}
+
+const WORD: &[(char, char)] = &[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')];
+const SPACE: &[(char, char)] = &[(' ', ' '), ('\t', '\t'), ('\n', '\n'), ('\r', '\r')];
+
+#[cfg(test)]
+mod tests {
+    use super::Regex;
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        Regex::new(pattern)
+            .expect("pattern should parse")
+            .is_match(text)
+    }
+
+    #[test]
+    fn literals_and_escapes() {
+        assert!(matches("abc", "xxabcxx"));
+        assert!(!matches("abc", "abx"));
+        assert!(matches(r"a\.b", "a.b"));
+        assert!(!matches(r"a\.b", "axb"));
+    }
+
+    #[test]
+    fn classes() {
+        assert!(matches("[a-c]+x", "zzbcax"));
+        assert!(!matches("[^/]+$", "dir/"));
+        assert!(matches(r"\d{3}-\d{4}", "call 555-1234"));
+        assert!(matches(r"\w+\s\w+", "hello world"));
+        assert!(!matches(r"^\S+$", "two words"));
+    }
+
+    #[test]
+    fn alternation_and_groups() {
+        assert!(matches("^(dev|prod)/", "prod/secrets.yaml"));
+        assert!(!matches("^(dev|prod)/", "staging/secrets.yaml"));
+        assert!(matches("(?:ab)+c", "ababc"));
+        assert!(matches("a|b|c", "c"));
+    }
+
+    #[test]
+    fn repetition() {
+        assert!(matches("^a{2}$", "aa"));
+        assert!(!matches("^a{2}$", "aaa"));
+        assert!(matches("^a{2,}$", "aaaa"));
+        assert!(matches("^a{1,3}b$", "aaab"));
+        assert!(!matches("^a{1,3}b$", "aaaab"));
+        assert!(matches("^colou?r$", "color"));
+        assert!(matches("x.*?y", "x123y"));
+    }
+
+    #[test]
+    fn anchors() {
+        assert!(matches("^$", ""));
+        assert!(!matches("^$", "a"));
+        assert!(matches("end$", "the end"));
+        assert!(!matches("^start", "a start"));
+    }
+
+    #[test]
+    fn nested_empty_loops_terminate() {
+        assert!(matches("^(a*)*$", "aaaa"));
+        assert!(!matches("^(a*)*b$", &"a".repeat(50)));
+    }
+
+    #[test]
+    fn long_lines_do_not_overflow() {
+        let line = format!("foo{}bar", "x".repeat(60_000));
+        assert!(matches("foo.*bar", &line));
+        assert!(!matches("foo.*baz", &line));
+        assert!(matches("(x|y)+bar$", &line));
+    }
+
+    #[test]
+    fn unsupported_patterns() {
+        assert!(Regex::new("a)").is_none());
+        assert!(Regex::new("*a").is_none());
+        assert!(Regex::new(r"\bword").is_none());
+        assert!(Regex::new("(a{1000}){1000}").is_none());
+    }
+}
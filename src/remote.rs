@@ -0,0 +1,159 @@
+use serde::Serialize;
+use serde_json::Value;
+#[cfg(feature = "network")]
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+#[cfg(feature = "network")]
+use std::process::Stdio;
+
+/// Hosting providers `--remote` can read repository settings from.
+pub const HOSTS: &[&str] = &["github", "gitlab"];
+
+/// Repository settings from the hosting provider's API (only with
+/// `--remote`). Fields a provider doesn't have, or the token can't read,
+/// are `None`.
+#[derive(Debug, Clone, Serialize)]
+pub struct RemoteInfo {
+    /// `github` or `gitlab`.
+    pub host: &'static str,
+    /// `owner/repo` on GitHub, `group/subgroup/project` on GitLab.
+    pub slug: String,
+    pub default_branch: String,
+    pub archived: bool,
+    /// Branch protection on the default branch; `None` when the token can't
+    /// read it (GitHub needs admin access to the repository).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    /// Approving reviews a pull or merge request needs before merging (0
+    /// when none are required).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_reviews: Option<u64>,
+    /// Open Dependabot alerts (GitHub, at most one page); `None` when alerts
+    /// are off or the token can't read them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dependabot_alerts: Option<usize>,
+    /// Open alerts of critical or high severity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severe_alerts: Option<usize>,
+    /// SPDX ID of the license GitHub detected, `None` when it found none.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Issue templates the project offers, its own and its group's (GitLab).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_templates: Option<usize>,
+    /// Merge request templates the project offers (GitLab).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_request_templates: Option<usize>,
+}
+
+impl RemoteInfo {
+    /// The provider's display name.
+    pub fn host_name(&self) -> &'static str {
+        match self.host {
+            "gitlab" => "GitLab",
+            _ => "GitHub",
+        }
+    }
+
+    /// What the provider calls a change proposal.
+    pub fn request_name(&self) -> &'static str {
+        match self.host {
+            "gitlab" => "Merge requests",
+            _ => "Pull requests",
+        }
+    }
+
+    /// Merging needs an approving review.
+    pub fn requires_review(&self) -> bool {
+        self.required_reviews.unwrap_or(0) > 0
+    }
+}
+
+/// URL of the `origin` remote.
+pub fn origin_url(root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .arg("--git-dir")
+        .arg(root.join(".git"))
+        .args(["config", "--get", "remote.origin.url"])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// An API response that may be an expected failure.
+pub(crate) struct Response {
+    pub status: u16,
+    /// The parsed body of a 2xx response.
+    pub body: Option<Value>,
+}
+
+impl Response {
+    /// The body, or an error naming `path` and the status.
+    pub fn require(self, path: &str) -> Result<Value, String> {
+        let status = self.status;
+        self.body
+            .ok_or_else(|| format!("{}: HTTP {}", path, status))
+    }
+}
+
+/// GET `base` + `path` with `curl`, passing 403 and 404 back to the caller
+/// instead of failing.
+///
+/// `headers` (one per line) go in through stdin so tokens don't show up in
+/// process lists.
+#[cfg(feature = "network")]
+pub(crate) fn get(base: &str, path: &str, headers: &str) -> Result<Response, String> {
+    let url = format!("{}{}", base.trim_end_matches('/'), path);
+    let mut child = Command::new("curl")
+        .args(["-sS", "-L", "--max-time", "20", "-H", "@-"])
+        // Never send the token over anything but HTTPS, redirects included
+        .args(["--proto", "=https", "--proto-redir", "=https"])
+        .args(["-w", "\n%{http_code}"])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("could not run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(headers.as_bytes())
+            .map_err(|e| e.to_string())?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{}: {}", path, err.trim()));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    let status: u16 = status.trim().parse().unwrap_or(0);
+    match status {
+        200..=299 => {
+            let body = serde_json::from_str(body).map_err(|e| format!("{}: {}", path, e))?;
+            Ok(Response {
+                status,
+                body: Some(body),
+            })
+        }
+        403 | 404 => Ok(Response { status, body: None }),
+        _ => {
+            let message = serde_json::from_str::<Value>(body)
+                .ok()
+                .and_then(|v| v["message"].as_str().map(String::from))
+                .unwrap_or_default();
+            Err(format!("{}: HTTP {} {}", path, status, message)
+                .trim_end()
+                .to_string())
+        }
+    }
+}
+
+#[cfg(not(feature = "network"))]
+pub(crate) fn get(_base: &str, _path: &str, _headers: &str) -> Result<Response, String> {
+    Err("this build of repomedic has no network support (the `network` feature)".to_string())
+}
@@ -1,5 +1,26 @@
-use crate::scanner::{Issue, ScanResult, Severity};
+//! Reports of a scan: [`to_json`], [`to_ndjson`], [`to_markdown`],
+//! [`to_sarif`], [`to_csv`], and the terminal output the CLI prints.
+
+use crate::baseline::fingerprint;
+use crate::cache;
+use crate::config::Branding;
+use crate::detector::Category;
+use crate::freshness;
+use crate::git::{self, ChangedFile};
+#[cfg(feature = "html")]
+use crate::html;
+use crate::license;
+use crate::locale::{self, Locale};
+use crate::migrate::REPORT_VERSION;
+use crate::remote::RemoteInfo;
+use crate::rules;
+use crate::sbom;
+use crate::scanner::{language_breakdown, Attribution, Issue, ScanResult, Severity};
+use crate::secrets;
+use crate::suppress::Debt;
+use crate::workflows::Workflow;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io;
@@ -7,18 +28,32 @@ use std::path::Path;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
-/// Clean Windows extended-length path prefix for display.
+/// A path as reports show it: without the Windows extended-length prefix
+/// (`\\?\C:\repo`, `\\?\UNC\server\share`) and with `/` separators, so
+/// reports from Windows and Unix agents compare and diff cleanly.
 pub fn clean_path(path: &Path) -> String {
     let s = path.display().to_string();
-    s.strip_prefix(r"\\?\").unwrap_or(&s).to_string()
+    let s = match s.strip_prefix(r"\\?\UNC\") {
+        Some(share) => format!(r"\\{}", share),
+        None => s.strip_prefix(r"\\?\").unwrap_or(&s).to_string(),
+    };
+    s.replace('\\', "/")
 }
 
 #[derive(Serialize)]
 pub struct JsonReport<'a> {
+    pub schema_version: u64,
     pub path: String,
     pub score: u8,
     pub grade: &'static str,
+    pub categories: Vec<CategoryScore>,
+    /// SPDX expression of the license files, when recognized.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
     pub issues: Vec<&'a Issue>,
+    /// Expired suppressions whose findings are reported again.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suppression_debt: Vec<Debt>,
     pub result: &'a ScanResult,
 }
 
@@ -34,13 +69,16 @@ fn ansi(s: &str, code: &str, color: bool) -> String {
     }
 }
 
-pub fn generate(result: &ScanResult, path: &Path) -> io::Result<String> {
+/// Generate the Markdown report (`REPO_REPORT.md`).
+pub fn to_markdown(result: &ScanResult, path: &Path) -> String {
+    let locale = result.config.locale();
     let mut r = String::new();
     r.push_str("# Repository Health Report\n\n");
-    r.push_str(&format!(
-        "**Path:** `{}`\n\n## Status\n\n| Check | Status |\n|-------|--------|\n",
-        clean_path(path)
-    ));
+    r.push_str(&format!("**Path:** `{}`\n\n", clean_path(path)));
+    if let Some(scanned) = scan_time(result, locale) {
+        r.push_str(&format!("**Scanned:** {}\n\n", scanned));
+    }
+    r.push_str("## Status\n\n| Check | Status |\n|-------|--------|\n");
     let checks = [
         ("Git repository", result.has_git),
         ("README", result.has_readme),
@@ -62,6 +100,9 @@ pub fn generate(result: &ScanResult, path: &Path) -> io::Result<String> {
             if ok { "OK" } else { "MISSING" }
         ));
     }
+    if let Some(spdx) = license::expression(&result.licenses) {
+        r.push_str(&format!("| License | {} |\n", spdx));
+    }
     r.push('\n');
     if result.is_monorepo {
         if let Some(ref wt) = result.workspace_type {
@@ -75,6 +116,13 @@ pub fn generate(result: &ScanResult, path: &Path) -> io::Result<String> {
         }
         r.push('\n');
     }
+    if !result.coverage_configs.is_empty() {
+        r.push_str("## Coverage\n\n");
+        for c in &result.coverage_configs {
+            r.push_str(&format!("- `{}`\n", c));
+        }
+        r.push('\n');
+    }
     if !result.ci_systems.is_empty() {
         r.push_str("## CI/CD\n\n");
         for ci in &result.ci_systems {
@@ -82,7 +130,19 @@ pub fn generate(result: &ScanResult, path: &Path) -> io::Result<String> {
         }
         r.push('\n');
     }
-    if !result.languages.is_empty() {
+    if !result.language_files.is_empty() {
+        r.push_str("## Languages Detected\n\n| Language | Share | Files | Lines |\n|----------|-------|-------|-------|\n");
+        for l in &result.language_files {
+            r.push_str(&format!(
+                "| {} | {:.1}% | {} | {} |\n",
+                l.language.name(),
+                l.percent,
+                l.files,
+                l.lines
+            ));
+        }
+        r.push('\n');
+    } else if !result.languages.is_empty() {
         r.push_str("## Languages Detected\n\n");
         for l in &result.languages {
             r.push_str(&format!("- {}\n", l.name()));
@@ -103,24 +163,154 @@ pub fn generate(result: &ScanResult, path: &Path) -> io::Result<String> {
         }
         r.push('\n');
     }
-    let issues = collect_issues(result);
+    if !result.lockfiles.is_empty() {
+        r.push_str("## Lock Files\n\n");
+        for l in &result.lockfiles {
+            r.push_str(&format!(
+                "- `{}`: last changed {} ({} ago)",
+                l.lockfile,
+                locale.date(&l.updated),
+                freshness::humanize_days(l.days_since_update)
+            ));
+            if let (Some(m), Some(date)) = (&l.manifest, &l.manifest_updated) {
+                r.push_str(&format!("; `{}` {}", m, locale.date(date)));
+            }
+            r.push('\n');
+        }
+        r.push('\n');
+    }
+    if let Some(ref h) = result.git_history {
+        r.push_str("## Git History\n\n");
+        r.push_str(&format!(
+            "- Last commit: {} ({} days ago)\n",
+            locale.date(&h.last_commit_date),
+            locale.number(h.days_since_last_commit)
+        ));
+        r.push_str(&format!(
+            "- Commits: {} total, {} in the last {} days (~{}/week)\n",
+            locale.number(h.total_commits),
+            locale.number(h.recent_commits),
+            git::ACTIVITY_DAYS,
+            locale.decimal(h.commits_per_week(), 1)
+        ));
+        r.push_str(&format!(
+            "- Contributors: {}\n",
+            locale.number(h.contributors as u64)
+        ));
+        if let Some(ref b) = h.default_branch {
+            r.push_str(&format!("- Default branch: `{}`\n", b));
+        }
+        r.push_str(&format!("- Tags: {}\n\n", h.tags));
+    }
+    if !result.workflows.is_empty() {
+        r.push_str("## Workflows\n\n");
+        r.push_str("| Workflow | Triggers | Schedule | Permissions | Flags |\n");
+        r.push_str("|----------|----------|----------|-------------|-------|\n");
+        for w in &result.workflows {
+            let cell = |items: &[String]| {
+                if items.is_empty() {
+                    "-".to_string()
+                } else {
+                    items
+                        .iter()
+                        .map(|i| format!("`{}`", i))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            };
+            r.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} |\n",
+                w.file,
+                cell(&w.triggers),
+                cell(&w.schedules),
+                w.permissions_summary(),
+                workflow_flags(w).join(", ")
+            ));
+        }
+        r.push('\n');
+    }
+    if let Some(ref h) = result.ci_health {
+        r.push_str("## CI Health\n\n");
+        r.push_str(&format!("- Branch: `{}`\n", h.branch));
+        r.push_str(&format!(
+            "- Pass rate: {}% ({} passed, {} failed of the last {} runs)\n",
+            h.pass_rate(),
+            h.passed,
+            h.failed,
+            h.runs
+        ));
+        r.push_str(&format!("- Re-runs: {}\n", h.reruns));
+        r.push_str(&format!(
+            "- Average duration: {}\n",
+            locale.duration_ms(h.avg_duration_secs * 1000)
+        ));
+        r.push_str(&format!("- Latest run: {}\n\n", h.last_conclusion));
+    }
+    if let Some(ref g) = result.remote {
+        r.push_str(&format!("## {}\n\n", g.host_name()));
+        r.push_str(&format!("- Repository: `{}`\n", g.slug));
+        for (label, value) in remote_facts(g) {
+            r.push_str(&format!("- {}: {}\n", label, value));
+        }
+        r.push('\n');
+    }
+    let issues = &result.issues;
     if !issues.is_empty() {
         r.push_str("## Issues\n\n");
-        for i in &issues {
+        for i in issues {
             r.push_str(&format!(
-                "- [{}] {}\n",
+                "- [{}] {}{}\n",
                 severity_label(i.severity),
-                i.message
+                i.message,
+                blame_suffix(i, locale)
             ));
         }
         r.push('\n');
     }
-    r.push_str("---\n*Generated by [RepoMedic](https://kindware.dev)*\n");
-    Ok(r)
+    let debt = suppression_debt(result);
+    if !debt.is_empty() {
+        r.push_str("## Suppression Debt\n\n");
+        for d in &debt {
+            r.push_str(&format!("- {}\n", debt_line(d)));
+        }
+        r.push('\n');
+    }
+    if !result.scan_warnings.is_empty() {
+        r.push_str("## Scan Warnings\n\n");
+        for w in &result.scan_warnings {
+            r.push_str(&format!("- {}\n", w));
+        }
+        r.push('\n');
+    }
+    let passed = collect_passed(result);
+    if !passed.is_empty() {
+        r.push_str("## Passed Checks\n\n");
+        for p in &passed {
+            r.push_str(&format!("- {}\n", p));
+        }
+        r.push('\n');
+    }
+    r.push_str(&format!(
+        "---\n*{}*\n",
+        markdown_footer(&result.config.branding)
+    ));
+    r
+}
+
+/// Closing line of Markdown reports: the `[branding]` footer, else a link
+/// to RepoMedic (or the branded help URL).
+pub fn markdown_footer(branding: &Branding) -> String {
+    match branding.footer {
+        Some(ref footer) => footer.clone(),
+        None => format!(
+            "Generated by [RepoMedic]({})",
+            branding.url("https://kindware.dev")
+        ),
+    }
 }
 
 pub fn write(result: &ScanResult, path: &Path) -> io::Result<()> {
-    let report = generate(result, path)?;
+    let report = to_markdown(result, path);
     let output_path = path.join("REPO_REPORT.md");
     fs::write(&output_path, report)?;
     println!("Report written to: {}", clean_path(&output_path));
@@ -135,181 +325,838 @@ fn severity_label(s: Severity) -> &'static str {
     }
 }
 
+/// The scan's reported issues (`ScanResult::issues`).
 pub fn collect_issues(result: &ScanResult) -> Vec<Issue> {
-    let mut issues = Vec::new();
-    if !result.has_git {
-        issues.push(Issue::error("Not a Git repository (no .git directory)"));
+    result.issues.clone()
+}
+
+/// Issues hidden because the baseline already records them.
+pub fn collect_baselined(result: &ScanResult) -> Vec<Issue> {
+    result.baselined.clone()
+}
+
+/// Suppressions and baseline entries past their `until` date, with the
+/// findings each would still silence.
+pub fn suppression_debt(result: &ScanResult) -> Vec<Debt> {
+    let issues = &result.issues;
+    let mut debt: Vec<Debt> = result
+        .suppressions
+        .expired
+        .iter()
+        .map(|rule| Debt {
+            origin: rule.origin.clone(),
+            target: rule.target(),
+            until: rule.until.clone().unwrap_or_default(),
+            owner: rule.owner.clone(),
+            reason: rule.reason.clone(),
+            findings: issues.iter().filter(|i| rule.matches(i)).count(),
+        })
+        .collect();
+    if let Some(baseline) = &result.baseline {
+        let origin = result.config.baseline.clone().unwrap_or_default();
+        debt.extend(baseline.expired.iter().map(|entry| {
+            Debt {
+                origin: origin.clone(),
+                target: entry.fingerprint.clone(),
+                until: entry.until.clone().unwrap_or_default(),
+                owner: entry.owner.clone(),
+                reason: entry.reason.clone(),
+                findings: issues
+                    .iter()
+                    .filter(|i| fingerprint(i) == entry.fingerprint)
+                    .count(),
+            }
+        }));
     }
-    if !result.has_readme {
-        issues.push(Issue::error("Missing README.md"));
+    debt
+}
+
+/// `missing-changelog (.repomedicignore:3) expired 2025-09-01, owner
+/// alice: reason; 1 finding(s) reported again`.
+fn debt_line(debt: &Debt) -> String {
+    let mut line = format!("{} ({}) expired {}", debt.target, debt.origin, debt.until);
+    if let Some(owner) = &debt.owner {
+        line.push_str(&format!(", owner {}", owner));
     }
-    if !result.has_license {
-        issues.push(Issue::error("Missing LICENSE file"));
+    if let Some(reason) = &debt.reason {
+        line.push_str(&format!(": {}", reason));
     }
-    if !result.has_gitignore {
-        issues.push(Issue::warning("Missing .gitignore"));
+    line.push_str(&format!("; {} finding(s) reported again", debt.findings));
+    line
+}
+
+/// What `--remote` read, as `(label, value)` pairs for the host's fields.
+/// Values the token couldn't read show as `unknown`.
+fn remote_facts(remote: &RemoteInfo) -> Vec<(&'static str, String)> {
+    let unknown = || "unknown".to_string();
+    let protection = match (remote.protected, remote.required_reviews) {
+        (Some(true), Some(n)) => format!("on, {} review(s) required", n),
+        (Some(true), None) => "on".to_string(),
+        (Some(false), _) => "off".to_string(),
+        (None, _) => unknown(),
+    };
+    let mut facts = vec![
+        ("Default branch", remote.default_branch.clone()),
+        ("Branch protection", protection),
+    ];
+    if remote.host == "gitlab" {
+        let count = |n: Option<usize>| n.map_or_else(unknown, |n| n.to_string());
+        facts.push(("Issue templates", count(remote.issue_templates)));
+        facts.push((
+            "Merge request templates",
+            count(remote.merge_request_templates),
+        ));
+    } else {
+        let alerts = match remote.dependabot_alerts {
+            Some(open) => format!(
+                "{} open ({} critical or high)",
+                open,
+                remote.severe_alerts.unwrap_or(0)
+            ),
+            None => unknown(),
+        };
+        facts.push(("Dependabot alerts", alerts));
+        facts.push((
+            "License",
+            remote
+                .license
+                .clone()
+                .unwrap_or_else(|| "not detected".to_string()),
+        ));
     }
-    if !result.has_changelog {
-        issues.push(Issue::warning("Missing CHANGELOG"));
+    let archived = if remote.archived { "yes" } else { "no" };
+    facts.push(("Archived", archived.to_string()));
+    facts
+}
+
+/// `4m12s`-style duration.
+fn format_duration(secs: u64) -> String {
+    if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
     }
-    if !result.has_contributing {
-        issues.push(Issue::warning("Missing CONTRIBUTING.md"));
+}
+
+/// " (last changed by ...)" suffix for issues with blame attached.
+fn blame_suffix(issue: &Issue, locale: &Locale) -> String {
+    match &issue.blame {
+        Some(a) => format!(
+            " (last changed by {}, {}, {})",
+            a.author,
+            locale.date(&a.date),
+            a.commit
+        ),
+        None => String::new(),
     }
-    if !result.has_code_of_conduct {
-        issues.push(Issue::warning("Missing CODE_OF_CONDUCT.md"));
+}
+
+/// When the scan ran and how long it took, e.g. `Oct 17, 2026 (1.4 s)`.
+/// `None` for results that didn't come from a scan.
+pub(crate) fn scan_time(result: &ScanResult, locale: &Locale) -> Option<String> {
+    let date = result.scan_stats.scanned_at.get(..10)?;
+    Some(format!(
+        "{} ({})",
+        locale.date(date),
+        locale.duration_ms(result.scan_stats.scan_duration_ms)
+    ))
+}
+
+/// Detector category a check ID belongs to, for grouping issues.
+pub fn check_category(id: &str) -> Category {
+    rules::find(id).map_or(Category::Project, |c| c.category)
+}
+
+/// Whether a configured check name (an ID or `RM` code) covers `id`.
+///
+/// `potential-secret` also covers every individual `secret-*` rule.
+pub fn check_matches(check: &str, id: &str) -> bool {
+    let check = rules::find(check).map_or(check, |c| c.id);
+    check == id || (check == secrets::FAMILY && secrets::rule_by_id(id).is_some())
+}
+
+/// Collect checks the repository passes (the inverse of `collect_issues`).
+pub fn collect_passed(result: &ScanResult) -> Vec<String> {
+    let mut passed = Vec::new();
+    // A broken template is reported instead of passing
+    let is_pr = |p: &crate::templates::Problem| p.file.to_lowercase().contains("pull_request");
+    let pr_broken = result.template_problems.iter().any(is_pr);
+    let issue_broken = result.template_problems.iter().any(|p| !is_pr(p));
+    let checks = [
+        (result.has_git, "Git repository initialized"),
+        (result.has_readme, "README present"),
+        (result.has_license, "LICENSE present"),
+        (result.has_gitignore, ".gitignore present"),
+        (result.has_changelog, "CHANGELOG present"),
+        (result.has_contributing, "CONTRIBUTING.md present"),
+        (result.has_code_of_conduct, "CODE_OF_CONDUCT.md present"),
+        (result.has_security, "SECURITY.md present"),
+        (result.has_editorconfig, ".editorconfig present"),
+        (result.has_gitattributes, ".gitattributes present"),
+        (result.has_codeowners, "CODEOWNERS present"),
+        (
+            result.has_issue_template && !issue_broken,
+            "Issue template present",
+        ),
+        (
+            result.has_pr_template && !pr_broken,
+            "Pull request template present",
+        ),
+        (result.has_tests, "Test directory present"),
+        (result.has_docs, "Docs directory present"),
+    ];
+    for (ok, name) in checks {
+        if ok {
+            passed.push(name.to_string());
+        }
     }
-    if !result.has_security {
-        issues.push(Issue::warning("Missing SECURITY.md"));
+    let identified: Vec<String> = result
+        .licenses
+        .iter()
+        .filter(|l| !l.truncated)
+        .filter_map(|l| Some(format!("{} ({})", l.spdx.as_ref()?, l.file)))
+        .collect();
+    if !identified.is_empty() {
+        passed.push(format!("License identified: {}", identified.join(", ")));
     }
-    if !result.has_editorconfig {
-        issues.push(Issue::warning("Missing .editorconfig"));
+    if let Some(ref q) = result.readme {
+        if q.score == 100 {
+            passed.push("README complete (quality 100/100)".to_string());
+        }
     }
-    if result.ci_systems.is_empty() {
-        issues.push(Issue::warning("No CI/CD configuration detected"));
+    if let Some(ref c) = result.codeowners_coverage {
+        if c.unowned_files == 0 && c.total_files > 0 {
+            passed.push(format!("CODEOWNERS covers all {} files", c.total_files));
+        }
     }
-    if result.languages.is_empty() {
-        issues.push(Issue::info("No source code detected"));
+    if !result.ci_systems.is_empty() {
+        passed.push(format!(
+            "CI/CD configured ({})",
+            result
+                .ci_systems
+                .iter()
+                .map(|c| c.name())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
     }
-    if result.build_systems.is_empty() && !result.languages.is_empty() {
-        issues.push(Issue::info("No build system detected"));
+    if !result.linter_configs.is_empty() {
+        passed.push(format!(
+            "Linter configs present ({})",
+            result.linter_configs.join(", ")
+        ));
     }
-    if !result.has_tests && !result.languages.is_empty() {
-        issues.push(Issue::info("No test directory detected"));
+    if !result.coverage_configs.is_empty() {
+        passed.push(format!(
+            "Coverage measured ({})",
+            result.coverage_configs.join(", ")
+        ));
+    } else if result.has_coverage_badge {
+        passed.push("Coverage badge in README".to_string());
     }
-    // Check for missing lock files when manifest exists
-    let lock_checks: &[(&str, &[&str], &str)] = &[
-        (
-            "package.json",
-            &["package-lock.json", "yarn.lock", "pnpm-lock.yaml"],
-            "npm/yarn/pnpm lock file",
-        ),
-        ("Pipfile", &["Pipfile.lock"], "Pipfile.lock"),
-        ("Gemfile", &["Gemfile.lock"], "Gemfile.lock"),
-        ("composer.json", &["composer.lock"], "composer.lock"),
-        ("go.mod", &["go.sum"], "go.sum"),
-        ("mix.exs", &["mix.lock"], "mix.lock"),
-    ];
-    for (manifest, locks, desc) in lock_checks {
-        if result.dependency_files.iter().any(|f| f == *manifest) {
-            let has_lock = locks
-                .iter()
-                .any(|l| result.dependency_files.iter().any(|f| f == *l));
-            if !has_lock {
-                issues.push(Issue::warning(format!(
-                    "Missing {}: found {} but no lock file (reproducible builds)",
-                    desc, manifest
-                )));
-            }
+    if let Some(ref config) = result.dependency_updates {
+        passed.push(format!("Dependency updates automated ({})", config));
+    }
+    if !result.helm_charts.is_empty() && result.helm_charts.iter().all(|c| c.has_schema) {
+        passed.push(format!(
+            "Helm chart values validated ({} chart(s) with values.schema.json)",
+            result.helm_charts.len()
+        ));
+    }
+    if !result.k8s_manifests.is_empty() && result.unbounded_workloads.is_empty() {
+        passed.push("Kubernetes workloads set resource limits".to_string());
+    }
+    let endings = &result.line_endings;
+    if endings.lf_files + endings.crlf_files > 0 && endings.inconsistent.is_empty() {
+        passed.push(format!(
+            "Line endings consistent ({})",
+            endings.dominant().name()
+        ));
+    }
+    if result.has_editorconfig && result.editorconfig_violations.is_empty() {
+        passed.push("Files follow .editorconfig".to_string());
+    }
+    if result.has_terraform() && result.terraform_problems.is_empty() {
+        passed.push(format!(
+            "Terraform providers pinned and locked ({} module(s))",
+            result.terraform_modules.len()
+        ));
+    }
+    if !result.container_files.is_empty() && result.container_problems.is_empty() {
+        passed.push(format!(
+            "Container files follow best practices ({})",
+            result.container_files.join(", ")
+        ));
+    }
+    if let Some(ref h) = result.git_history {
+        if h.days_since_last_commit <= git::INACTIVE_DAYS {
+            passed.push(format!(
+                "Recently active (last commit {})",
+                h.last_commit_date
+            ));
+        }
+        if h.tags > 0 {
+            passed.push(format!("Releases tagged ({})", h.tags));
         }
     }
-    // Cargo.lock check: only warn for binaries, not libraries
-    if result.dependency_files.iter().any(|f| f == "Cargo.toml") {
-        let has_lock = result.dependency_files.iter().any(|f| f == "Cargo.lock");
-        if !has_lock {
-            // Info-level since libraries often don't commit Cargo.lock
-            issues.push(Issue::info(
-                "No Cargo.lock found (consider committing for binary/app projects)",
+    if let Some(ref h) = result.ci_health {
+        if !h.is_red() && !h.is_flaky() {
+            passed.push(format!(
+                "CI healthy on {} ({}% of {} runs passed)",
+                h.branch,
+                h.pass_rate(),
+                h.runs
             ));
         }
     }
-    for f in &result.large_files {
-        issues.push(Issue::warning(format!("Large file detected (>5MB): {}", f)));
+    if let Some(ref g) = result.remote {
+        if g.protected == Some(true) && g.requires_review() {
+            passed.push(format!(
+                "Default branch {} protected on {} ({} review(s) required)",
+                g.default_branch,
+                g.host_name(),
+                g.required_reviews.unwrap_or(0)
+            ));
+        }
+        if g.dependabot_alerts == Some(0) {
+            passed.push("No open Dependabot alerts".to_string());
+        }
+        if g.issue_templates.is_some_and(|n| n > 0)
+            && g.merge_request_templates.is_some_and(|n| n > 0)
+        {
+            passed.push("GitLab issue and merge request templates present".to_string());
+        }
+    }
+    if result.large_files.is_empty() {
+        passed.push(format!(
+            "No large files (>{}MB)",
+            result.config.large_file_threshold_mb()
+        ));
+    }
+    if result.potential_secrets.is_empty() {
+        passed.push("No potential secrets detected".to_string());
     }
-    for s in &result.potential_secrets {
-        issues.push(Issue::error(format!(
-            "Potential {} in {} at line {}",
-            s.pattern, s.file, s.line
-        )));
+    if !result.encrypted_files.is_empty() {
+        passed.push(format!(
+            "Secrets encrypted with SOPS/age ({})",
+            result.encrypted_files.join(", ")
+        ));
     }
-    issues
+    passed
 }
 
-pub fn has_errors(result: &ScanResult) -> bool {
-    collect_issues(result)
-        .iter()
-        .any(|i| i.severity == Severity::Error)
+/// Whether `id` is a leaked or unprotected credential: a secret pattern, a
+/// plaintext Kubernetes Secret, a credential in a Dockerfile, Compose file,
+/// or `.tfvars`, committed Terraform state, or a file `.sops.yaml` says to
+/// encrypt.
+pub fn is_secret_check(id: &str) -> bool {
+    check_matches(secrets::FAMILY, id)
+        || id == "k8s-secret-manifest"
+        || id == "docker-secret-env"
+        || id == "terraform-state-committed"
+        || id == "terraform-tfvars-secret"
+        || id == "sops-unencrypted"
 }
 
-pub fn has_warnings(result: &ScanResult) -> bool {
-    collect_issues(result)
+/// Process exit code for `issues`: 3 if any secret finding is at least as
+/// severe as `fail_at`, 2 for any other such issue, else 0. `None` never
+/// fails.
+pub fn exit_code(issues: &[Issue], fail_at: Option<Severity>) -> i32 {
+    let Some(level) = fail_at else {
+        return 0;
+    };
+    let failing: Vec<&Issue> = issues
         .iter()
-        .any(|i| i.severity == Severity::Warning)
+        .filter(|i| i.severity.at_least(level))
+        .collect();
+    if failing.iter().any(|i| is_secret_check(i.id())) {
+        3
+    } else if failing.is_empty() {
+        0
+    } else {
+        2
+    }
 }
 
 pub fn to_json(result: &ScanResult, path: &Path) -> String {
-    let issues = collect_issues(result);
+    let issues = &result.issues;
     let score = calculate_score(result);
     let report = JsonReport {
+        schema_version: REPORT_VERSION,
         path: clean_path(path),
         score,
         grade: score_grade(score),
+        categories: category_scores(result),
+        license: license::expression(&result.licenses),
         issues: issues.iter().collect(),
+        suppression_debt: suppression_debt(result),
         result,
     };
     serde_json::to_string_pretty(&report).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// One line of `--format ndjson`, tagged by `type`: a `finding` per issue,
+/// then the repository's `summary`. `scan-all` writes an `error` line for
+/// a repository it couldn't scan.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NdjsonRecord<'a> {
+    Finding {
+        repository: &'a str,
+        scanned_at: &'a str,
+        rule_id: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        code: Option<&'static str>,
+        category: &'static str,
+        severity: Severity,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        line: Option<usize>,
+        message: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fix: Option<&'static str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        blame: Option<&'a Attribution>,
+    },
+    Summary {
+        repository: &'a str,
+        scanned_at: &'a str,
+        score: u8,
+        grade: &'static str,
+        errors: usize,
+        warnings: usize,
+        info: usize,
+    },
+    Error {
+        repository: &'a str,
+        message: &'a str,
+    },
+}
+
+/// Write `record` as one line and flush it, so a reader sees it at once.
+pub fn write_ndjson(out: &mut dyn io::Write, record: &NdjsonRecord) -> io::Result<()> {
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    writeln!(out, "{}", line)?;
+    out.flush()
+}
+
+/// The `finding` line for `issue` of a scan of `repository`.
+pub fn ndjson_finding<'a>(
+    repository: &'a str,
+    scanned_at: &'a str,
+    issue: &'a Issue,
+) -> NdjsonRecord<'a> {
+    let id = issue.id();
+    let check = rules::find(id);
+    NdjsonRecord::Finding {
+        repository,
+        scanned_at,
+        rule_id: id,
+        code: check.map(|c| c.code),
+        category: check_category(id).name(),
+        severity: issue.severity,
+        file: issue.file.as_deref(),
+        line: issue.line,
+        message: &issue.message,
+        fix: check.map(|c| c.fix),
+        blame: issue.blame.as_ref(),
+    }
+}
+
+/// The `summary` line closing the findings of `result`.
+pub fn ndjson_summary<'a>(result: &'a ScanResult, repository: &'a str) -> NdjsonRecord<'a> {
+    let count = |s: Severity| result.issues.iter().filter(|i| i.severity == s).count();
+    let score = calculate_score(result);
+    NdjsonRecord::Summary {
+        repository,
+        scanned_at: &result.scan_stats.scanned_at,
+        score,
+        grade: score_grade(score),
+        errors: count(Severity::Error),
+        warnings: count(Severity::Warning),
+        info: count(Severity::Info),
+    }
+}
+
+/// Stream the findings of `result` to `out` as NDJSON, one flushed line per
+/// finding, then a summary line.
+pub fn stream_ndjson(result: &ScanResult, path: &Path, out: &mut dyn io::Write) -> io::Result<()> {
+    let repository = clean_path(path);
+    for issue in &result.issues {
+        write_ndjson(
+            out,
+            &ndjson_finding(&repository, &result.scan_stats.scanned_at, issue),
+        )?;
+    }
+    write_ndjson(out, &ndjson_summary(result, &repository))
+}
+
+/// The NDJSON stream of `result` as a string, for `--also`.
+pub fn to_ndjson(result: &ScanResult, path: &Path) -> String {
+    let mut out = Vec::new();
+    let _ = stream_ndjson(result, path, &mut out);
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// Where SARIF rules link for help, unless `[branding] help_url` is set.
+const RULES_HELP_URI: &str = "https://github.com/Brutus1066/repomedic#detection-coverage";
+
+/// SARIF `security-severity` for a security finding, on GitHub's 0-10
+/// scale: leaked credentials rank critical.
+fn security_severity(id: &str, severity: Severity) -> &'static str {
+    match severity {
+        _ if is_secret_check(id) => "9.0",
+        Severity::Error => "7.0",
+        Severity::Warning => "5.0",
+        Severity::Info => "2.0",
+    }
+}
+
+/// SARIF rule help: the description, then the fix as a command block or
+/// advice.
+fn help_markdown(check: &rules::Check) -> String {
+    let mut help = format!("{}\n\n", check.description);
+    match split_fix_hint(check.fix) {
+        (Some(command), advice) => {
+            help.push_str(&format!("```sh\n{}\n```\n\n", command));
+            if let Some(advice) = advice {
+                help.push_str(&format!("{}\n\n", advice));
+            }
+        }
+        (None, advice) => help.push_str(&format!("{}\n\n", advice.unwrap_or_default())),
+    }
+    help.push_str(&format!("More: `repomedic rules explain {}`", check.code));
+    help
+}
+
+/// SARIF 2.1.0 log for GitHub Code Scanning.
+///
+/// Every rule carries its description, fix, and help link, and security
+/// rules a `security-severity` so alerts get a rank. Every location is
+/// relative to `%SRCROOT%`: file findings point at the file (and line),
+/// repository-level ones at the root itself.
 pub fn to_sarif(result: &ScanResult, path: &Path) -> String {
-    let issues = collect_issues(result);
-    let rule_map = [
-        ("missing-readme", "Missing README.md", "error"),
-        ("missing-license", "Missing LICENSE file", "error"),
-        ("not-git-repo", "Not a Git repository", "error"),
-        ("missing-gitignore", "Missing .gitignore", "warning"),
-        ("missing-changelog", "Missing CHANGELOG", "warning"),
-        ("missing-contributing", "Missing CONTRIBUTING.md", "warning"),
-        (
-            "missing-code-of-conduct",
-            "Missing CODE_OF_CONDUCT.md",
-            "warning",
-        ),
-        ("missing-security", "Missing SECURITY.md", "warning"),
-        ("missing-editorconfig", "Missing .editorconfig", "warning"),
-        ("no-ci", "No CI/CD configuration detected", "warning"),
-        ("no-tests", "No test directory detected", "warning"),
-        (
-            "missing-lock-file",
-            "Missing lock file for reproducible builds",
-            "warning",
-        ),
-        ("large-file", "Large file detected (>5MB)", "warning"),
-        ("potential-secret", "Potential secret detected", "error"),
-    ];
-    let rules: Vec<_> = rule_map.iter().map(|(id, desc, level)| serde_json::json!({
-        "id": id, "shortDescription": { "text": desc }, "defaultConfiguration": { "level": level }
-    })).collect();
-    let results: Vec<_> = issues.iter().filter_map(|issue| {
-        let m = issue.message.as_str();
-        let rule_id = if m.contains("README") { "missing-readme" }
-            else if m.contains("LICENSE") { "missing-license" } else if m.contains("Git repository") { "not-git-repo" }
-            else if m.contains(".gitignore") { "missing-gitignore" } else if m.contains("CHANGELOG") { "missing-changelog" }
-            else if m.contains("CONTRIBUTING") { "missing-contributing" } else if m.contains("CODE_OF_CONDUCT") { "missing-code-of-conduct" }
-            else if m.contains("SECURITY") { "missing-security" } else if m.contains(".editorconfig") { "missing-editorconfig" }
-            else if m.contains("CI/CD") { "no-ci" } else if m.contains("test") { "no-tests" }
-            else if m.contains("lock file") || m.contains("Cargo.lock") { "missing-lock-file" }
-            else if m.contains("Large file") { "large-file" } else if m.contains("secret") { "potential-secret" } else { return None };
-        let level = match issue.severity { Severity::Error => "error", Severity::Warning => "warning", Severity::Info => "note" };
-        Some(serde_json::json!({ "ruleId": rule_id, "level": level, "message": { "text": &issue.message },
-            "locations": [{ "physicalLocation": { "artifactLocation": { "uri": clean_path(path) } } }] }))
+    let issues = &result.issues;
+    let help_uri = result.config.branding.url(RULES_HELP_URI);
+    let mut rules: Vec<_> = rules::all().into_iter().map(|c| {
+        let mut properties = serde_json::json!({ "code": c.code, "tags": [c.category.name()] });
+        if c.category == Category::Security {
+            properties["security-severity"] = serde_json::json!(security_severity(c.id, c.severity));
+        }
+        serde_json::json!({
+            "id": c.id, "name": c.full_id(), "shortDescription": { "text": c.description },
+            "fullDescription": { "text": format!("{}. Fix: {}", c.description, c.fix) },
+            "help": { "text": c.fix, "markdown": help_markdown(&c) },
+            "helpUri": help_uri, "defaultConfiguration": { "level": c.sarif_level() }, "properties": properties
+        })
+    }).collect();
+    rules.extend(result.config.custom_rules.iter().map(|r| serde_json::json!({
+        "id": r.id, "shortDescription": { "text": r.description() }, "helpUri": help_uri,
+        "defaultConfiguration": { "level": r.severity.sarif_level() }, "properties": { "custom": true }
+    })));
+    let rule_index = |id: &str| rules.iter().position(|r| r["id"] == id);
+    let results: Vec<_> = issues.iter().map(|issue| {
+        let rule_id = issue.id();
+        let level = issue.severity.sarif_level();
+        let location = match (&issue.file, issue.line) {
+            (Some(file), Some(line)) => serde_json::json!({ "artifactLocation": { "uri": file, "uriBaseId": "%SRCROOT%" }, "region": { "startLine": line } }),
+            (Some(file), None) => serde_json::json!({ "artifactLocation": { "uri": file, "uriBaseId": "%SRCROOT%" } }),
+            _ => serde_json::json!({ "artifactLocation": { "uri": ".", "uriBaseId": "%SRCROOT%" } }),
+        };
+        let mut sarif = serde_json::json!({ "ruleId": rule_id, "level": level, "message": { "text": &issue.message },
+            "locations": [{ "physicalLocation": location }],
+            "partialFingerprints": { "repomedic/v2": sarif_fingerprint(issue, path) } });
+        if let Some(index) = rule_index(rule_id) {
+            sarif["ruleIndex"] = serde_json::json!(index);
+        }
+        sarif
     }).collect();
     serde_json::to_string_pretty(&serde_json::json!({
         "$schema": "https://json.schemastore.org/sarif-2.1.0.json", "version": "2.1.0",
-        "runs": [{ "tool": { "driver": { "name": "RepoMedic", "version": VERSION, "informationUri": "https://kindware.dev", "rules": rules } }, "results": results }]
+        "runs": [{ "tool": { "driver": { "name": "RepoMedic", "version": VERSION, "informationUri": result.config.branding.url("https://kindware.dev"), "rules": rules } },
+            "originalUriBaseIds": { "%SRCROOT%": { "uri": format!("{}/", file_uri(path).trim_end_matches('/')) } },
+            "results": results }]
     })).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// SARIF `partialFingerprints` value: the baseline fingerprint (check and
+/// file) plus a hash of the flagged line's text, so findings on different
+/// lines of one file stay separate alerts and an alert follows its line
+/// when code above it moves. Falls back to the line number when the file
+/// can't be read.
+fn sarif_fingerprint(issue: &Issue, root: &Path) -> String {
+    let base = fingerprint(issue);
+    let (Some(file), Some(line)) = (&issue.file, issue.line) else {
+        return base;
+    };
+    let text = fs::read_to_string(root.join(file))
+        .ok()
+        .and_then(|content| {
+            let text = content.lines().nth(line.checked_sub(1)?)?;
+            Some(text.trim().to_string())
+        });
+    match text {
+        Some(text) => format!("{}:{:016x}", base, cache::hash(&text)),
+        None => format!("{}:{}", base, line),
+    }
+}
+
+/// Findings as LSP `PublishDiagnosticsParams`, one entry per file URI.
+///
+/// Repository-level findings (missing files, no CI) are keyed by the root
+/// directory URI; file findings cover their whole line.
+pub fn to_lsp_diagnostics(result: &ScanResult, path: &Path) -> String {
+    let mut by_uri: Vec<(String, Vec<serde_json::Value>)> = Vec::new();
+    for issue in &result.issues {
+        let uri = match &issue.file {
+            Some(file) => file_uri(&path.join(file)),
+            None => file_uri(path),
+        };
+        let line = issue.line.map(|l| l.saturating_sub(1)).unwrap_or(0);
+        let severity = match issue.severity {
+            Severity::Error => 1,
+            Severity::Warning => 2,
+            Severity::Info => 3,
+        };
+        let diagnostic = serde_json::json!({
+            "range": {
+                "start": { "line": line, "character": 0 },
+                "end": { "line": line + 1, "character": 0 }
+            },
+            "severity": severity,
+            "code": issue.id(),
+            "source": "repomedic",
+            "message": issue.message,
+        });
+        match by_uri.iter_mut().find(|(u, _)| *u == uri) {
+            Some((_, diagnostics)) => diagnostics.push(diagnostic),
+            None => by_uri.push((uri, vec![diagnostic])),
+        }
+    }
+    let params: Vec<_> = by_uri
+        .into_iter()
+        .map(|(uri, diagnostics)| serde_json::json!({ "uri": uri, "diagnostics": diagnostics }))
+        .collect();
+    serde_json::to_string_pretty(&params).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Findings as GitHub Actions workflow commands (`::error file=...::msg`),
+/// which the runner turns into annotations on the PR diff.
+pub fn to_github_annotations(result: &ScanResult) -> String {
+    let mut out = String::new();
+    for issue in &result.issues {
+        let command = match issue.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "notice",
+        };
+        let mut props = Vec::new();
+        if let Some(ref file) = issue.file {
+            props.push(format!("file={}", escape_workflow_property(file)));
+            if let Some(line) = issue.line {
+                props.push(format!("line={}", line));
+            }
+        }
+        props.push(format!("title={}", escape_workflow_property(issue.id())));
+        let props = if props.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", props.join(","))
+        };
+        out.push_str(&format!(
+            "::{}{}::{}\n",
+            command,
+            props,
+            escape_workflow_data(&issue.message)
+        ));
+    }
+    out
+}
+
+/// Issues located in staged files, limited to lines the change adds.
+pub fn staged_issues(result: &ScanResult, staged: &[ChangedFile]) -> Vec<Issue> {
+    collect_issues(result)
+        .into_iter()
+        .filter(|issue| {
+            issue.file.as_ref().is_some_and(|file| {
+                staged
+                    .iter()
+                    .any(|s| &s.path == file && s.touches(issue.line))
+            })
+        })
+        .collect()
+}
+
+/// One terse `file:line: severity[check]: message` line per issue, for
+/// pre-commit hooks. Empty when there is nothing to report.
+pub fn to_hook(issues: &[Issue]) -> String {
+    let mut out = String::new();
+    for issue in issues {
+        let location = match (&issue.file, issue.line) {
+            (Some(file), Some(line)) => format!("{}:{}", file, line),
+            (Some(file), None) => file.clone(),
+            _ => continue,
+        };
+        let severity = match issue.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        };
+        out.push_str(&format!(
+            "{}: {}[{}]: {}\n",
+            location,
+            severity,
+            issue.id(),
+            issue.message
+        ));
+    }
+    out
+}
+
+/// Markdown job summary for `$GITHUB_STEP_SUMMARY`.
+pub fn to_github_summary(result: &ScanResult, path: &Path) -> String {
+    let score = calculate_score(result);
+    let issues = &result.issues;
+    let mut s = format!(
+        "## RepoMedic: {} ({})\n\n`{}`\n\n",
+        score,
+        score_grade(score),
+        clean_path(path)
+    );
+    if issues.is_empty() {
+        s.push_str("No issues found.\n");
+    } else {
+        let count = |sev: Severity| issues.iter().filter(|i| i.severity == sev).count();
+        s.push_str(&format!(
+            "{} error(s), {} warning(s), {} info\n\n| Severity | Check | Message | Location |\n|----------|-------|---------|----------|\n",
+            count(Severity::Error),
+            count(Severity::Warning),
+            count(Severity::Info)
+        ));
+        for issue in issues {
+            let location = match (&issue.file, issue.line) {
+                (Some(f), Some(l)) => format!("`{}:{}`", f, l),
+                (Some(f), None) => format!("`{}`", f),
+                _ => String::new(),
+            };
+            s.push_str(&format!(
+                "| {} | `{}` | {} | {} |\n",
+                issue.severity.name(),
+                issue.id(),
+                issue.message.replace('|', "\\|"),
+                location
+            ));
+        }
+    }
+    let passed = collect_passed(result);
+    if !passed.is_empty() {
+        s.push_str(&format!(
+            "\n<details><summary>{} passed check(s)</summary>\n\n",
+            passed.len()
+        ));
+        for p in &passed {
+            s.push_str(&format!("- {}\n", p));
+        }
+        s.push_str("\n</details>\n");
+    }
+    s
+}
+
+/// Append the job summary to `$GITHUB_STEP_SUMMARY`, when running in Actions.
+pub fn append_github_summary(result: &ScanResult, path: &Path) -> io::Result<()> {
+    let Some(file) = env::var_os("GITHUB_STEP_SUMMARY").filter(|f| !f.is_empty()) else {
+        return Ok(());
+    };
+    let mut out = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file)?;
+    io::Write::write_all(&mut out, to_github_summary(result, path).as_bytes())
+}
+
+/// Escape a workflow command message.
+fn escape_workflow_data(s: &str) -> String {
+    s.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a workflow command property value (`file=`, `title=`).
+fn escape_workflow_property(s: &str) -> String {
+    escape_workflow_data(s)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// `file://` URI for a path, percent-encoding anything outside the safe set.
+fn file_uri(path: &Path) -> String {
+    let raw = clean_path(path);
+    let mut uri = String::from("file://");
+    if !raw.starts_with('/') {
+        uri.push('/');
+    }
+    for (i, b) in raw.bytes().enumerate() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(b as char)
+            }
+            // Windows drive letter, e.g. `C:/repo`
+            b':' if i == 1 => uri.push(':'),
+            _ => uri.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    uri
+}
+
 pub fn print_summary(result: &ScanResult, quiet: bool, verbose: bool, color: bool) {
     if quiet {
         return;
     }
     println!("Repository scan complete.\n");
+    if let Some(ref files) = result.config.changed_files {
+        println!(
+            "Scope: {} file(s); only findings in them are reported\n",
+            files.len()
+        );
+    }
+    for e in &result.sampling {
+        println!(
+            "Sampled {} of {} files for {}: {} matched, about {} overall (95% CI {}-{})",
+            e.sampled, e.population, e.check, e.matched, e.estimate, e.low, e.high
+        );
+    }
+    if !result.sampling.is_empty() {
+        println!();
+    }
     if verbose {
         println!(
-            "Stats: {} files scanned, {} dirs traversed, {}ms\n",
+            "Stats: {} files scanned, {} dirs traversed, {} files read ({} binary skipped, {} cached), {} checks run, {}ms",
             result.scan_stats.files_scanned,
             result.scan_stats.dirs_traversed,
+            result.scan_stats.files_read,
+            result.scan_stats.binary_skipped,
+            result.scan_stats.cache_hits,
+            result.scan_stats.checks_run,
             result.scan_stats.scan_duration_ms
         );
+        if !result.scan_stats.checks_skipped.is_empty() {
+            println!(
+                "Skipped (not applicable): {}",
+                result.scan_stats.checks_skipped.join(", ")
+            );
+        }
+        println!(
+            "Timings: {}",
+            result
+                .scan_stats
+                .detector_timings
+                .iter()
+                .map(|t| format!("{} {}us", t.name, t.duration_us))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        println!();
     }
     println!("Status:");
     let (yes, no) = (ansi("yes", "32", color), ansi("no", "31", color));
@@ -334,6 +1181,9 @@ pub fn print_summary(result: &ScanResult, quiet: bool, verbose: bool, color: boo
             if ok { &yes } else { &no }
         );
     }
+    if let Some(spdx) = license::expression(&result.licenses) {
+        println!("  {:16} {}", "License:", spdx);
+    }
     if result.is_monorepo {
         if let Some(ref wt) = result.workspace_type {
             println!("\nMonorepo: {}", wt);
@@ -342,6 +1192,9 @@ pub fn print_summary(result: &ScanResult, quiet: bool, verbose: bool, color: boo
     if !result.linter_configs.is_empty() {
         println!("\nLinter configs: {}", result.linter_configs.join(", "));
     }
+    if !result.coverage_configs.is_empty() {
+        println!("\nCoverage: {}", result.coverage_configs.join(", "));
+    }
     if !result.ci_systems.is_empty() {
         println!(
             "\nCI/CD: {}",
@@ -353,7 +1206,9 @@ pub fn print_summary(result: &ScanResult, quiet: bool, verbose: bool, color: boo
                 .join(", ")
         );
     }
-    if !result.languages.is_empty() {
+    if !result.language_files.is_empty() {
+        println!("Languages: {}", language_breakdown(&result.language_files));
+    } else if !result.languages.is_empty() {
         println!(
             "Languages: {}",
             result
@@ -375,7 +1230,40 @@ pub fn print_summary(result: &ScanResult, quiet: bool, verbose: bool, color: boo
                 .join(", ")
         );
     }
-    let issues = collect_issues(result);
+    if let Some(ref h) = result.git_history {
+        println!(
+            "History: last commit {} ({}d ago), {} commits in {}d, {} contributor(s), {} tag(s){}",
+            h.last_commit_date,
+            h.days_since_last_commit,
+            h.recent_commits,
+            git::ACTIVITY_DAYS,
+            h.contributors,
+            h.tags,
+            h.default_branch
+                .as_ref()
+                .map(|b| format!(", branch {}", b))
+                .unwrap_or_default()
+        );
+    }
+    if let Some(ref h) = result.ci_health {
+        println!(
+            "CI health: {}% of {} runs passed on {}, avg {}, {} re-run(s), latest {}",
+            h.pass_rate(),
+            h.runs,
+            h.branch,
+            format_duration(h.avg_duration_secs),
+            h.reruns,
+            h.last_conclusion
+        );
+    }
+    if let Some(ref g) = result.remote {
+        let facts: Vec<String> = remote_facts(g)
+            .into_iter()
+            .map(|(label, value)| format!("{} {}", label.to_lowercase(), value))
+            .collect();
+        println!("{}: {}", g.host_name(), facts.join(", "));
+    }
+    let issues = &result.issues;
     if !issues.is_empty() {
         let (e, w, i) = (
             issues
@@ -392,41 +1280,216 @@ pub fn print_summary(result: &ScanResult, quiet: bool, verbose: bool, color: boo
                 .count(),
         );
         println!("\nIssues: {} error(s), {} warning(s), {} info", e, w, i);
-        for issue in &issues {
+        for issue in issues {
             let prefix = match issue.severity {
                 Severity::Error => ansi("!", "31", color),
                 Severity::Warning => ansi("?", "33", color),
                 Severity::Info => ansi("-", "34", color),
             };
-            println!("  {} {}", prefix, issue.message);
+            if verbose {
+                println!(
+                    "  {} {}{}",
+                    prefix,
+                    issue.message,
+                    blame_suffix(issue, &locale::ISO)
+                );
+            } else {
+                println!("  {} {}", prefix, issue.message);
+            }
         }
     } else {
         println!("\nNo issues found.");
     }
+    let baselined = &result.baselined;
+    if !baselined.is_empty() {
+        println!("\nBaseline: {} known issue(s) hidden", baselined.len());
+        if verbose {
+            for issue in baselined {
+                println!("  {} {}", ansi("=", "90", color), issue.message);
+            }
+        }
+    }
+    let debt = suppression_debt(result);
+    if !debt.is_empty() {
+        println!("\nSuppression debt: {} expired suppression(s)", debt.len());
+        for d in &debt {
+            println!("  {} {}", ansi("!", "33", color), debt_line(d));
+        }
+    }
+    if verbose {
+        let suppressed = &result.suppressed;
+        if !suppressed.is_empty() {
+            println!("\nSuppressed: {}", suppressed.len());
+            for (issue, origin) in suppressed {
+                println!(
+                    "  {} {} (suppressed by {})",
+                    ansi("x", "90", color),
+                    issue.message,
+                    origin
+                );
+            }
+        }
+    }
+    if !result.scan_warnings.is_empty() {
+        println!(
+            "\nScan warnings: {} check(s) skipped",
+            result.scan_warnings.len()
+        );
+        for w in &result.scan_warnings {
+            println!("  {} {}", ansi("~", "33", color), w);
+        }
+    }
+}
+
+/// Print everything the repository does right.
+pub fn print_passed(result: &ScanResult, color: bool) {
+    let passed = collect_passed(result);
+    if passed.is_empty() {
+        println!("\nNo checks passed.");
+        return;
+    }
+    println!("\nPassed checks: {}", passed.len());
+    for p in &passed {
+        println!("  {} {}", ansi("+", "32", color), p);
+    }
 }
 
 /// Calculate repository health score (0-100).
 pub fn calculate_score(result: &ScanResult) -> u8 {
-    let mut score: i32 = 100;
-    let issues = collect_issues(result);
-    for issue in &issues {
-        match issue.severity {
-            Severity::Error => score -= 15,
-            Severity::Warning => score -= 5,
-            Severity::Info => score -= 1,
-        }
+    let total: i32 = score_breakdown(result).iter().map(|i| i.points).sum();
+    (100 + total).clamp(0, 100) as u8
+}
+
+/// Score categories as `(config key, display name)`, in report order.
+pub const SCORE_CATEGORIES: &[(&str, &str)] = &[
+    ("documentation", "Documentation"),
+    ("community", "Community health"),
+    ("ci", "CI & testing"),
+    ("security", "Security"),
+    ("hygiene", "Hygiene"),
+];
+
+/// Score category a check ID counts toward, from its rule's category.
+pub fn score_category(id: &str) -> &'static str {
+    match check_category(id) {
+        Category::Documentation => "documentation",
+        Category::Community => "community",
+        Category::Ci => "ci",
+        Category::Security => "security",
+        Category::Project | Category::Files => "hygiene",
     }
-    // Bonus points for good practices
-    if result.has_tests {
-        score += 5;
+}
+
+/// One contribution to the health score.
+#[derive(Debug, Clone)]
+pub struct ScoreItem {
+    /// Points added (positive) or subtracted (negative), after weighting.
+    pub points: i32,
+    pub reason: String,
+    /// Key from `SCORE_CATEGORIES`.
+    pub category: &'static str,
+}
+
+/// Every deduction and bonus applied to the starting 100 points, in order,
+/// scaled by the category weights from the config.
+pub fn score_breakdown(result: &ScanResult) -> Vec<ScoreItem> {
+    let mut items = unweighted_breakdown(result);
+    for item in &mut items {
+        let weight = result.config.score_weight(item.category);
+        item.points = (f64::from(item.points) * weight).round() as i32;
     }
-    if result.has_docs {
-        score += 3;
+    items
+}
+
+/// Most points one check can deduct, however many findings it reports, so
+/// a single noisy rule (a hundred vendored secrets) can't sink the grade.
+pub const MAX_CHECK_DEDUCTION: i32 = 30;
+
+fn unweighted_breakdown(result: &ScanResult) -> Vec<ScoreItem> {
+    let mut items: Vec<ScoreItem> = Vec::new();
+    // Per check: points it can still deduct, its last item, findings past the cap
+    let mut checks: HashMap<String, (i32, usize, usize)> = HashMap::new();
+    for issue in collect_issues(result) {
+        let points = match issue.severity {
+            Severity::Error => 15,
+            Severity::Warning => 5,
+            Severity::Info => 1,
+        };
+        let check = checks
+            .entry(issue.id().to_string())
+            .or_insert((MAX_CHECK_DEDUCTION, 0, 0));
+        if check.0 == 0 {
+            check.2 += 1;
+            continue;
+        }
+        let points = points.min(check.0);
+        check.0 -= points;
+        check.1 = items.len();
+        items.push(ScoreItem {
+            points: -points,
+            category: score_category(issue.id()),
+            reason: issue.message,
+        });
     }
-    if !result.ci_systems.is_empty() {
-        score += 5;
+    for (_, last, capped) in checks.into_values() {
+        if capped > 0 {
+            items[last].reason = format!(
+                "{} (+{} more, capped at -{})",
+                items[last].reason, capped, MAX_CHECK_DEDUCTION
+            );
+        }
+    }
+    // Bonus points for good practices
+    let bonuses = [
+        (result.has_tests, 5, "Tests directory present", "ci"),
+        (
+            result.has_docs,
+            3,
+            "Docs directory present",
+            "documentation",
+        ),
+        (!result.ci_systems.is_empty(), 5, "CI/CD configured", "ci"),
+    ];
+    for (present, points, reason, category) in bonuses {
+        if present {
+            items.push(ScoreItem {
+                points,
+                reason: reason.to_string(),
+                category,
+            });
+        }
     }
-    score.clamp(0, 100) as u8
+    items
+}
+
+/// Sub-score of one category: 100 plus its unweighted deductions and bonuses.
+#[derive(Debug, Clone, Serialize)]
+pub struct CategoryScore {
+    pub category: &'static str,
+    pub name: &'static str,
+    pub score: u8,
+    pub weight: f64,
+}
+
+/// Sub-scores for every category in `SCORE_CATEGORIES`.
+pub fn category_scores(result: &ScanResult) -> Vec<CategoryScore> {
+    let items = unweighted_breakdown(result);
+    SCORE_CATEGORIES
+        .iter()
+        .map(|(category, name)| {
+            let total: i32 = items
+                .iter()
+                .filter(|i| i.category == *category)
+                .map(|i| i.points)
+                .sum();
+            CategoryScore {
+                category,
+                name,
+                score: (100 + total).clamp(0, 100) as u8,
+                weight: result.config.score_weight(category),
+            }
+        })
+        .collect()
 }
 
 /// Get letter grade from score.
@@ -441,7 +1504,7 @@ pub fn score_grade(score: u8) -> &'static str {
 }
 
 /// Get ANSI color code for score.
-fn score_color(score: u8) -> &'static str {
+pub(crate) fn score_color(score: u8) -> &'static str {
     match score {
         90..=100 => "32", // green
         70..=89 => "33",  // yellow
@@ -449,22 +1512,52 @@ fn score_color(score: u8) -> &'static str {
     }
 }
 
+/// Styles shields.io draws badges in.
+pub const BADGE_STYLES: &[&str] = &["flat", "flat-square", "plastic", "for-the-badge", "social"];
+
 /// Generate shields.io badge markdown.
 pub fn generate_badge(score: u8) -> String {
-    let grade = score_grade(score);
-    let color = match score {
+    badge_markdown(&badge_url(score, None), None)
+}
+
+/// shields.io color name for a score.
+pub fn badge_color(score: u8) -> &'static str {
+    match score {
         90..=100 => "brightgreen",
         80..=89 => "green",
         70..=79 => "yellow",
         60..=69 => "orange",
         _ => "red",
-    };
+    }
+}
+
+/// Text on the right of the badge: `B (84%)`.
+pub fn badge_message(score: u8) -> String {
+    format!("{} ({}%)", score_grade(score), score)
+}
+
+/// shields.io badge URL for `score`, in `style` (shields.io's flat by
+/// default).
+pub fn badge_url(score: u8, style: Option<&str>) -> String {
+    let style = style.map_or(String::new(), |s| format!("?style={}", s));
     format!(
-        "![RepoMedic Score](https://img.shields.io/badge/RepoMedic-{}%20({}%25)-{})",
-        grade, score, color
+        "https://img.shields.io/badge/RepoMedic-{}%20({}%25)-{}{}",
+        score_grade(score),
+        score,
+        badge_color(score),
+        style
     )
 }
 
+/// Badge Markdown showing `image`, linked to `link` when given.
+pub fn badge_markdown(image: &str, link: Option<&str>) -> String {
+    let image = format!("![RepoMedic Score]({})", image);
+    match link {
+        Some(url) => format!("[{}]({})", image, url),
+        None => image,
+    }
+}
+
 /// Print score summary.
 pub fn print_score(result: &ScanResult, color: bool) {
     let score = calculate_score(result);
@@ -478,41 +1571,67 @@ pub fn print_score(result: &ScanResult, color: bool) {
         "Repository Health Score: {}/100 (Grade: {})",
         colored_score, grade
     );
+    println!("\nBy category:");
+    for c in category_scores(result) {
+        let sub = if color {
+            format!("\x1b[{}m{:>3}\x1b[0m", score_color(c.score), c.score)
+        } else {
+            format!("{:>3}", c.score)
+        };
+        let weight = if c.weight == 1.0 {
+            String::new()
+        } else {
+            format!("  (weight {})", c.weight)
+        };
+        println!("  {:18} {}{}", c.name, sub, weight);
+    }
     println!("\nBadge for README:");
     println!("  {}", generate_badge(score));
 }
 
-/// Get fix command for an issue.
-fn get_fix_command(msg: &str) -> Option<&'static str> {
-    if msg.contains("README") {
-        Some("repomedic generate --readme")
-    } else if msg.contains("LICENSE") {
-        Some("repomedic generate --license --author \"Your Name\"")
-    } else if msg.contains(".gitignore") {
-        Some("repomedic generate --gitignore")
-    } else if msg.contains("CHANGELOG") {
-        Some("repomedic generate --changelog")
-    } else if msg.contains("CONTRIBUTING") {
-        Some("repomedic generate --contributing")
-    } else if msg.contains("CODE_OF_CONDUCT") {
-        Some("repomedic generate --codeofconduct")
-    } else if msg.contains("SECURITY") {
-        Some("repomedic generate --security")
-    } else if msg.contains(".editorconfig") {
-        Some("repomedic generate --editorconfig")
-    } else if msg.contains("Git repository") {
-        Some("git init")
+/// Print how each finding and bonus moved the score from 100.
+pub fn print_score_explanation(result: &ScanResult, color: bool) {
+    let items = score_breakdown(result);
+    let paint = |code: &str, text: String| {
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text
+        }
+    };
+    println!("Score breakdown:");
+    println!("  {:>4}  Starting score", 100);
+    for item in &items {
+        let points = format!("{:+4}", item.points);
+        let code = if item.points < 0 { "31" } else { "32" };
+        println!(
+            "  {}  {:15} {}",
+            paint(code, points),
+            format!("[{}]", item.category),
+            item.reason
+        );
+    }
+    let raw = 100 + items.iter().map(|i| i.points).sum::<i32>();
+    let score = calculate_score(result);
+    if raw == i32::from(score) {
+        println!("  {:>4}  Total", score);
     } else {
-        None
+        println!("  {:>4}  Total ({} clamped to 0-100)", score, raw);
     }
+    println!();
+}
+
+/// Fix hint for an issue, from its rule.
+fn get_fix_command(issue: &Issue) -> Option<&'static str> {
+    rules::find(issue.id()).map(|c| c.fix)
 }
 
 /// Print actionable fix suggestions.
 pub fn print_suggestions(result: &ScanResult, color: bool) {
-    let issues = collect_issues(result);
+    let issues = &result.issues;
     let fixable: Vec<_> = issues
         .iter()
-        .filter_map(|i| get_fix_command(&i.message).map(|cmd| (i, cmd)))
+        .filter_map(|i| get_fix_command(i).map(|cmd| (i, cmd)))
         .collect();
 
     if fixable.is_empty() {
@@ -551,11 +1670,236 @@ pub fn print_suggestions(result: &ScanResult, color: bool) {
     }
 }
 
+/// Programs a fix hint can start with to be run as-is from a script.
+const SCRIPT_COMMANDS: &[&str] = &["repomedic ", "git ", "sops "];
+
+/// Split a fix hint into the command it starts with, if any, and the
+/// advice after it (`repomedic generate --dependabot (or add a
+/// renovate.json)`).
+fn split_fix_hint(hint: &str) -> (Option<&str>, Option<&str>) {
+    if !SCRIPT_COMMANDS.iter().any(|c| hint.starts_with(c)) {
+        return (None, Some(hint));
+    }
+    let end = [" (", ", "]
+        .iter()
+        .filter_map(|sep| hint.find(sep))
+        .min()
+        .unwrap_or(hint.len());
+    let rest = hint[end..]
+        .trim_start_matches([',', ' '])
+        .trim_start_matches("then ")
+        .trim_start_matches("and ");
+    (Some(&hint[..end]), Some(rest).filter(|r| !r.is_empty()))
+}
+
+/// Quote `s` as one argument for a POSIX shell or PowerShell. Both take
+/// single-quoted strings literally; only the quote itself is escaped.
+fn shell_quote(s: &str, powershell: bool) -> String {
+    if powershell {
+        format!("'{}'", s.replace('\'', "''"))
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
+
+/// A script running every suggested fix command in order, with each issue
+/// as a comment above its command. Advice that isn't a command, or a
+/// command with a placeholder left in it, is kept as a comment to act on
+/// by hand. `powershell` writes a `.ps1` script instead of `sh`. Returns
+/// the script and the number of commands in it.
+pub fn suggestions_script(result: &ScanResult, path: &Path, powershell: bool) -> (String, usize) {
+    let mut out = String::new();
+    if powershell {
+        out.push_str("# RepoMedic fix suggestions. Review before running.\n");
+        out.push_str("$ErrorActionPreference = \"Stop\"\n");
+    } else {
+        out.push_str("#!/bin/sh\n");
+        out.push_str("# RepoMedic fix suggestions. Review before running.\n");
+        out.push_str("set -eu\n");
+    }
+    out.push_str(&format!(
+        "cd {}\n",
+        shell_quote(&clean_path(path), powershell)
+    ));
+
+    let mut emitted: Vec<String> = Vec::new();
+    for issue in &result.issues {
+        let Some(hint) = get_fix_command(issue) else {
+            continue;
+        };
+        out.push_str(&format!(
+            "\n# [{}] {}\n",
+            issue.severity.name(),
+            issue.message
+        ));
+        let (command, advice) = split_fix_hint(hint);
+        let command = command.map(|c| {
+            let mut c = c.to_string();
+            if let Some(file) = &issue.file {
+                c = c.replace("<file>", &shell_quote(file, powershell));
+            }
+            if let Some(author) = &result.config.author {
+                c = c.replace("\"Your Name\"", &shell_quote(author, powershell));
+            }
+            c
+        });
+        match command {
+            Some(c) if c.contains('<') => out.push_str(&format!("# Manual: {}\n", c)),
+            Some(c) if emitted.contains(&c) => {
+                out.push_str(&format!("# Covered by `{}` above\n", c))
+            }
+            Some(c) => {
+                out.push_str(&format!("{}\n", c));
+                emitted.push(c);
+            }
+            None => {}
+        }
+        if let Some(advice) = advice {
+            out.push_str(&format!("# Manual: {}\n", advice));
+        }
+    }
+    if emitted.is_empty() {
+        out.push_str("\n# No commands to run.\n");
+    }
+    (out, emitted.len())
+}
+
+/// Write `suggestions_script` to `file` (PowerShell for `.ps1`) and make
+/// it executable. Returns the number of commands written.
+pub fn write_suggestions_script(
+    result: &ScanResult,
+    path: &Path,
+    file: &Path,
+) -> io::Result<usize> {
+    let powershell = file
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("ps1"));
+    let (script, commands) = suggestions_script(result, path, powershell);
+    fs::write(file, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(file, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(commands)
+}
+
+/// Flags shown next to a workflow in tables (`-` when none).
+fn workflow_flags(w: &Workflow) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+    if w.write_all {
+        flags.push("write-all");
+    }
+    if w.push_all_branches {
+        flags.push("push to all branches");
+    }
+    if !w.publishes.is_empty() {
+        flags.push("publishes");
+    }
+    if flags.is_empty() {
+        flags.push("-");
+    }
+    flags
+}
+
+/// Print a table of GitHub Actions workflows: triggers, cron schedules,
+/// effective permissions, and flags.
+pub fn print_workflows(result: &ScanResult, color: bool) {
+    if result.workflows.is_empty() {
+        println!("No GitHub Actions workflows found.");
+        return;
+    }
+    let width = |f: fn(&Workflow) -> String, header: &str| {
+        result
+            .workflows
+            .iter()
+            .map(|w| f(w).len())
+            .chain([header.len()])
+            .max()
+            .unwrap_or(0)
+    };
+    let file = |w: &Workflow| w.file.trim_start_matches(".github/workflows/").to_string();
+    let triggers = |w: &Workflow| w.triggers.join(", ");
+    let schedule = |w: &Workflow| {
+        if w.schedules.is_empty() {
+            "-".to_string()
+        } else {
+            w.schedules.join("; ")
+        }
+    };
+    let (fw, tw, sw, pw) = (
+        width(file, "WORKFLOW"),
+        width(triggers, "TRIGGERS"),
+        width(schedule, "SCHEDULE"),
+        width(Workflow::permissions_summary, "PERMISSIONS"),
+    );
+    println!(
+        "{:fw$}  {:tw$}  {:sw$}  {:pw$}  FLAGS",
+        "WORKFLOW", "TRIGGERS", "SCHEDULE", "PERMISSIONS"
+    );
+    for w in &result.workflows {
+        let flags = workflow_flags(w).join(", ");
+        let flags = if w.write_all || (w.push_all_branches && !w.publishes.is_empty()) {
+            ansi(&flags, "33", color)
+        } else {
+            flags
+        };
+        println!(
+            "{:fw$}  {:tw$}  {:sw$}  {:pw$}  {}",
+            file(w),
+            triggers(w),
+            schedule(w),
+            w.permissions_summary(),
+            flags
+        );
+    }
+}
+
+/// What `doctor --json-out` writes, for monitoring agents polling many
+/// checkouts.
+#[derive(Debug, Serialize)]
+pub struct DoctorStatus {
+    pub path: String,
+    pub score: u8,
+    pub grade: &'static str,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Issues for which `is_secret_check` holds, at any severity.
+    pub secrets: usize,
+    /// Abbreviated `HEAD` commit, when the root is a git work tree.
+    pub commit: Option<String>,
+    /// When the scan ran, ISO-8601 UTC.
+    pub scanned_at: String,
+}
+
+impl DoctorStatus {
+    pub fn new(result: &ScanResult, path: &Path) -> Self {
+        let issues = &result.issues;
+        let count = |s: Severity| issues.iter().filter(|i| i.severity == s).count();
+        let score = calculate_score(result);
+        DoctorStatus {
+            path: clean_path(path),
+            score,
+            grade: score_grade(score),
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            secrets: issues.iter().filter(|i| is_secret_check(i.id())).count(),
+            commit: git::head_commit(path),
+            scanned_at: result.scan_stats.scanned_at.clone(),
+        }
+    }
+}
+
 /// Print one-line doctor summary (for shell prompts and quick checks).
 pub fn print_doctor(result: &ScanResult, color: bool) {
+    println!("{}", doctor_line(result, color));
+}
+
+/// `✓ RepoMedic: A (95/100) | 0 error(s), 1 warning(s)`.
+pub fn doctor_line(result: &ScanResult, color: bool) -> String {
     let score = calculate_score(result);
     let grade = score_grade(score);
-    let issues = collect_issues(result);
+    let issues = &result.issues;
     let errors = issues
         .iter()
         .filter(|i| i.severity == Severity::Error)
@@ -589,22 +1933,26 @@ pub fn print_doctor(result: &ScanResult, color: bool) {
         grade.to_string()
     };
 
-    println!(
+    format!(
         "{} RepoMedic: {} ({}/100) | {} error(s), {} warning(s)",
         status, grade_colored, score, errors, warnings
-    );
+    )
 }
 
 /// Generate plain text report.
 pub fn to_text(result: &ScanResult, path: &Path) -> String {
+    let locale = result.config.locale();
     let score = calculate_score(result);
     let grade = score_grade(score);
-    let issues = collect_issues(result);
+    let issues = &result.issues;
     let mut out = String::new();
 
     out.push_str("RepoMedic Scan Report\n");
     out.push_str("=====================\n\n");
     out.push_str(&format!("Path:  {}\n", clean_path(path)));
+    if let Some(scanned) = scan_time(result, locale) {
+        out.push_str(&format!("Date:  {}\n", scanned));
+    }
     out.push_str(&format!("Score: {}/100 (Grade {})\n\n", score, grade));
 
     out.push_str("Status:\n");
@@ -628,72 +1976,121 @@ pub fn to_text(result: &ScanResult, path: &Path) -> String {
             if ok { "YES" } else { "NO" }
         ));
     }
+    if let Some(spdx) = license::expression(&result.licenses) {
+        out.push_str(&format!("  {:16} {}\n", "License:", spdx));
+    }
 
     if !issues.is_empty() {
         out.push_str("\nIssues:\n");
-        for issue in &issues {
+        for issue in issues {
             let prefix = match issue.severity {
                 Severity::Error => "[ERROR]",
                 Severity::Warning => "[WARN] ",
                 Severity::Info => "[INFO] ",
             };
-            out.push_str(&format!("  {} {}\n", prefix, issue.message));
+            out.push_str(&format!(
+                "  {} {}{}\n",
+                prefix,
+                issue.message,
+                blame_suffix(issue, locale)
+            ));
+        }
+    }
+
+    let debt = suppression_debt(result);
+    if !debt.is_empty() {
+        out.push_str("\nSuppression debt:\n");
+        for d in &debt {
+            out.push_str(&format!("  [DEBT]  {}\n", debt_line(d)));
+        }
+    }
+
+    let passed = collect_passed(result);
+    if !passed.is_empty() {
+        out.push_str("\nPassed:\n");
+        for p in &passed {
+            out.push_str(&format!("  [PASS]  {}\n", p));
         }
     }
 
-    out.push_str("\n---\nGenerated by RepoMedic (https://github.com/Brutus1066/repomedic)\n");
+    let branding = &result.config.branding;
+    match branding.footer {
+        Some(ref footer) => out.push_str(&format!("\n---\n{}\n", footer)),
+        None => out.push_str(&format!(
+            "\n---\nGenerated by RepoMedic ({})\n",
+            branding.url("https://github.com/Brutus1066/repomedic")
+        )),
+    }
     out
 }
 
-/// Generate CSV report.
+/// Columns of the CSV and TSV exports, one row per finding.
+const TABLE_COLUMNS: &[&str] = &[
+    "repository",
+    "scanned_at",
+    "rule_id",
+    "code",
+    "category",
+    "severity",
+    "file",
+    "line",
+    "message",
+    "fix",
+];
+
+/// Generate CSV report: one row per finding.
 pub fn to_csv(result: &ScanResult, path: &Path) -> String {
-    let score = calculate_score(result);
-    let grade = score_grade(score);
-    let issues = collect_issues(result);
-    let mut out = String::new();
+    to_table(result, path, ',')
+}
 
-    out.push_str("type,item,status,message\n");
-    out.push_str(&format!("score,health,{},{}\n", score, grade));
+/// Generate TSV report: one row per finding.
+pub fn to_tsv(result: &ScanResult, path: &Path) -> String {
+    to_table(result, path, '\t')
+}
 
-    let checks = [
-        ("check", "git_repository", result.has_git),
-        ("check", "readme", result.has_readme),
-        ("check", "license", result.has_license),
-        ("check", "gitignore", result.has_gitignore),
-        ("check", "changelog", result.has_changelog),
-        ("check", "contributing", result.has_contributing),
-        ("check", "code_of_conduct", result.has_code_of_conduct),
-        ("check", "security", result.has_security),
-        ("check", "editorconfig", result.has_editorconfig),
-        ("check", "tests", result.has_tests),
-        ("check", "docs", result.has_docs),
-    ];
-    for (typ, name, ok) in checks {
-        out.push_str(&format!(
-            "{},{},{},\n",
-            typ,
-            name,
-            if ok { "pass" } else { "fail" }
-        ));
+/// One row per finding under `TABLE_COLUMNS`, fields split by `separator`.
+/// The repository and scan time repeat on every row so exports from many
+/// repositories can be concatenated and pivoted.
+fn to_table(result: &ScanResult, path: &Path, separator: char) -> String {
+    let sep = separator.to_string();
+    let mut out = TABLE_COLUMNS.join(&sep);
+    out.push('\n');
+    let repository = clean_path(path);
+    for issue in &result.issues {
+        let id = issue.id();
+        let check = rules::find(id);
+        let line = issue.line.map(|l| l.to_string()).unwrap_or_default();
+        let message = format!("{}{}", issue.message, blame_suffix(issue, &locale::ISO));
+        let row = [
+            repository.as_str(),
+            result.scan_stats.scanned_at.as_str(),
+            id,
+            check.map_or("", |c| c.code),
+            check_category(id).name(),
+            issue.severity.name(),
+            issue.file.as_deref().unwrap_or(""),
+            line.as_str(),
+            message.as_str(),
+            check.map_or("", |c| c.fix),
+        ];
+        let fields: Vec<String> = row.iter().map(|f| table_field(f, separator)).collect();
+        out.push_str(&fields.join(&sep));
+        out.push('\n');
     }
+    out
+}
 
-    for issue in &issues {
-        let sev = match issue.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
-            Severity::Info => "info",
-        };
-        // Escape quotes in message for CSV
-        let msg = issue.message.replace('"', "\"\"");
-        out.push_str(&format!(
-            "issue,{},\"{}\",\"{}\"\n",
-            sev,
-            clean_path(path),
-            msg
-        ));
+/// A field quoted for CSV when it holds the separator, a quote, or a line
+/// break; TSV has no quoting, so tabs and line breaks become spaces.
+fn table_field(value: &str, separator: char) -> String {
+    if separator == '\t' {
+        return value.replace(['\t', '\r', '\n'], " ");
+    }
+    if value.contains([separator, '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
     }
-
-    out
 }
 
 /// Export report to file based on extension.
@@ -704,10 +2101,25 @@ pub fn export_to_file(result: &ScanResult, path: &Path, output_file: &Path) -> i
         .unwrap_or("txt")
         .to_lowercase();
 
-    let content = match ext.as_str() {
-        "json" => to_json(result, path),
-        "md" | "markdown" => generate(result, path)?,
-        "csv" => to_csv(result, path),
+    let name = output_file
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    let content = match (sbom::Format::for_file(&name), ext.as_str()) {
+        (Some(sbom::Format::CycloneDx), _) => sbom::to_cyclonedx(result, path),
+        (Some(sbom::Format::Spdx), _) => sbom::to_spdx(result, path),
+        (None, "json") => to_json(result, path),
+        (None, "md" | "markdown") => to_markdown(result, path),
+        (None, "csv") => to_csv(result, path),
+        (None, "tsv") => to_tsv(result, path),
+        #[cfg(feature = "html")]
+        (None, "html" | "htm") => html::to_html(result, path),
+        #[cfg(not(feature = "html"))]
+        (None, "html" | "htm") => {
+            return Err(io::Error::other(
+                "HTML export is not available in this build (the `html` feature)",
+            ))
+        }
         _ => to_text(result, path), // .txt and others
     };
 
@@ -715,3 +2127,48 @@ pub fn export_to_file(result: &ScanResult, path: &Path, output_file: &Path) -> i
     println!("Exported to: {}", clean_path(output_file));
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{calculate_score, sarif_fingerprint, score_breakdown, MAX_CHECK_DEDUCTION};
+    use crate::scanner::{Issue, ScanResult};
+    use crate::testutil::TempDir;
+    use std::fs;
+
+    #[test]
+    fn sarif_fingerprints_follow_line_content() {
+        let root = TempDir::new("report");
+        fs::write(root.join("a.env"), "TOKEN=one\nTOKEN=two\n").unwrap();
+        let at = |line| Issue::error("secret", "Secret").at("a.env", Some(line));
+
+        let first = sarif_fingerprint(&at(1), &root);
+        assert!(first.starts_with("secret:a.env:"));
+        assert_ne!(first, sarif_fingerprint(&at(2), &root));
+        fs::write(root.join("a.env"), "# moved\nTOKEN=one\n").unwrap();
+        assert_eq!(first, sarif_fingerprint(&at(2), &root));
+        assert_eq!(sarif_fingerprint(&at(9), &root), "secret:a.env:9");
+        assert_eq!(
+            sarif_fingerprint(&Issue::warning("no-ci", "No CI"), &root),
+            "no-ci"
+        );
+    }
+
+    #[test]
+    fn deductions_are_capped_per_check() {
+        let mut result = ScanResult::default();
+        for i in 0..10 {
+            result
+                .issues
+                .push(Issue::error("secret", format!("Secret {}", i)).at("a.env", Some(i)));
+        }
+        result.issues.push(Issue::warning("no-ci", "No CI"));
+        let items = score_breakdown(&result);
+        let points: Vec<i32> = items.iter().map(|i| i.points).collect();
+        assert_eq!(points, [-15, -15, -5]);
+        assert_eq!(items[1].reason, "Secret 1 (+8 more, capped at -30)");
+        assert_eq!(
+            calculate_score(&result),
+            (100 - MAX_CHECK_DEDUCTION - 5) as u8
+        );
+    }
+}
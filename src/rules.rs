@@ -0,0 +1,640 @@
+use crate::detector::Category;
+use crate::scanner::Severity;
+use crate::secrets;
+use serde::Serialize;
+
+/// A check RepoMedic can report, with its stable identifiers and metadata.
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct Check {
+    /// Numbered code, e.g. `RM001`. Codes are never reused.
+    pub code: &'static str,
+    /// Check ID used in config, suppressions, baselines, and SARIF.
+    pub id: &'static str,
+    pub category: Category,
+    /// Default severity (before `[severity]` overrides).
+    pub severity: Severity,
+    pub description: &'static str,
+    /// How to resolve a finding: a command when one exists, advice otherwise.
+    pub fix: &'static str,
+}
+
+impl Check {
+    /// `RM001-missing-readme`.
+    pub fn full_id(&self) -> String {
+        format!("{}-{}", self.code, self.id)
+    }
+
+    /// SARIF `level` for the default severity.
+    pub fn sarif_level(&self) -> &'static str {
+        self.severity.sarif_level()
+    }
+}
+
+/// Fix advice shared by every secret rule.
+const SECRET_FIX: &str =
+    "Revoke the credential, remove it from the file (and history), and load it from the environment or a secret manager";
+
+/// Built-in checks, by code. Secret pattern rules follow from `secrets::RULES`.
+pub const CHECKS: &[Check] = &[
+    Check {
+        code: "RM001",
+        id: "missing-readme",
+        category: Category::Documentation,
+        severity: Severity::Error,
+        description: "Missing README.md",
+        fix: "repomedic generate --readme",
+    },
+    Check {
+        code: "RM002",
+        id: "missing-license",
+        category: Category::Community,
+        severity: Severity::Error,
+        description: "Missing LICENSE file",
+        fix: "repomedic generate --license --author \"Your Name\"",
+    },
+    Check {
+        code: "RM003",
+        id: "not-git-repo",
+        category: Category::Project,
+        severity: Severity::Error,
+        description: "Not a Git repository",
+        fix: "git init",
+    },
+    Check {
+        code: "RM004",
+        id: "missing-gitignore",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Missing .gitignore",
+        fix: "repomedic generate --gitignore",
+    },
+    Check {
+        code: "RM005",
+        id: "missing-changelog",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "Missing CHANGELOG",
+        fix: "repomedic generate --changelog",
+    },
+    Check {
+        code: "RM006",
+        id: "missing-contributing",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Missing CONTRIBUTING.md",
+        fix: "repomedic generate --contributing",
+    },
+    Check {
+        code: "RM007",
+        id: "missing-code-of-conduct",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Missing CODE_OF_CONDUCT.md",
+        fix: "repomedic generate --codeofconduct",
+    },
+    Check {
+        code: "RM008",
+        id: "missing-security",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Missing SECURITY.md",
+        fix: "repomedic generate --security",
+    },
+    Check {
+        code: "RM009",
+        id: "missing-editorconfig",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Missing .editorconfig",
+        fix: "repomedic generate --editorconfig",
+    },
+    Check {
+        code: "RM010",
+        id: "no-ci",
+        category: Category::Ci,
+        severity: Severity::Warning,
+        description: "No CI/CD configuration detected",
+        fix: "Add a workflow under .github/workflows (or your CI provider's config)",
+    },
+    Check {
+        code: "RM011",
+        id: "no-tests",
+        category: Category::Ci,
+        severity: Severity::Info,
+        description: "No test directory detected",
+        fix: "Add a tests/ (or test/, spec/) directory",
+    },
+    Check {
+        code: "RM012",
+        id: "no-coverage",
+        category: Category::Ci,
+        severity: Severity::Warning,
+        description: "Tests present but no coverage measurement configured",
+        fix: "repomedic generate --codecov",
+    },
+    Check {
+        code: "RM013",
+        id: "no-source",
+        category: Category::Project,
+        severity: Severity::Info,
+        description: "No source code detected",
+        fix: "Nothing to do for docs-only or config-only repositories",
+    },
+    Check {
+        code: "RM014",
+        id: "no-build-system",
+        category: Category::Project,
+        severity: Severity::Info,
+        description: "No build system detected",
+        fix: "Add a manifest for the project's build tool (Cargo.toml, package.json, ...)",
+    },
+    Check {
+        code: "RM015",
+        id: "missing-lock-file",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "Missing lock file for reproducible builds",
+        fix: "Run the package manager's install and commit the lock file",
+    },
+    Check {
+        code: "RM016",
+        id: "large-file",
+        category: Category::Files,
+        severity: Severity::Warning,
+        description: "Large file detected",
+        fix: "Move the file to Git LFS or out of the repository",
+    },
+    Check {
+        code: "RM017",
+        id: secrets::FAMILY,
+        category: Category::Security,
+        severity: Severity::Error,
+        description: "Potential secret detected (covers every secret-* rule)",
+        fix: SECRET_FIX,
+    },
+    Check {
+        code: "RM018",
+        id: "stale-repo",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "No recent commits",
+        fix: "Archive the repository or note its maintenance status in the README",
+    },
+    Check {
+        code: "RM019",
+        id: "ci-missing-cache",
+        category: Category::Ci,
+        severity: Severity::Info,
+        description: "CI builds without a dependency cache",
+        fix: "Cache the package manager's downloads (actions/cache or the setup action's cache option)",
+    },
+    Check {
+        code: "RM020",
+        id: "stale-badge",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "README badge points at a renamed repo, workflow, or package",
+        fix: "Update or remove the badge URL",
+    },
+    Check {
+        code: "RM021",
+        id: "ci-failing",
+        category: Category::Ci,
+        severity: Severity::Warning,
+        description: "Latest CI run on the default branch failed",
+        fix: "Fix the failing workflow run on the default branch",
+    },
+    Check {
+        code: "RM022",
+        id: "ci-flaky",
+        category: Category::Ci,
+        severity: Severity::Warning,
+        description: "CI pass rate is low or runs often need re-runs",
+        fix: "Find and fix or quarantine the flaky tests",
+    },
+    Check {
+        code: "RM023",
+        id: "ci-artifact-retention",
+        category: Category::Ci,
+        severity: Severity::Info,
+        description: "CI artifacts uploaded without a retention period",
+        fix: "Set retention-days (GitHub) or expire_in (GitLab) on uploaded artifacts",
+    },
+    Check {
+        code: "RM024",
+        id: "k8s-secret-manifest",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Kubernetes Secret manifest with committed values",
+        fix: "Encrypt with Sealed Secrets (kubeseal) or SOPS, or create the Secret at deploy time",
+    },
+    Check {
+        code: "RM025",
+        id: "sops-unencrypted",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "File matching a .sops.yaml creation rule is not encrypted",
+        fix: "sops --encrypt --in-place <file>, and rotate any values already committed",
+    },
+    Check {
+        code: "RM026",
+        id: "codeowners-unowned",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "CODEOWNERS leaves files without an owner",
+        fix: "Add CODEOWNERS rules for the listed directories, or a `*` catch-all owner first",
+    },
+    Check {
+        code: "RM027",
+        id: "codeowners-invalid",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "CODEOWNERS rule is malformed, has an invalid owner, matches no files, or repeats an earlier pattern",
+        fix: "Fix or remove the rule at the reported line (GitHub skips lines it can't parse)",
+    },
+    Check {
+        code: "RM028",
+        id: "license-unrecognized",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "License text doesn't match a known license or carry an SPDX identifier",
+        fix: "Use the unmodified text of a standard license (https://choosealicense.com), or add an SPDX-License-Identifier line",
+    },
+    Check {
+        code: "RM029",
+        id: "license-truncated",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "License file starts like a known license but is incomplete",
+        fix: "Replace the file with the full license text",
+    },
+    Check {
+        code: "RM030",
+        id: "template-invalid",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Issue form fails GitHub's schema, or an issue/PR template is empty, lacks front matter, or still has TODO placeholders",
+        fix: "Fix the template at the reported line (GitHub falls back to a blank issue for forms it can't parse)",
+    },
+    Check {
+        code: "RM031",
+        id: "readme-incomplete",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "README lacks a title, description, installation, usage, license, or badges section",
+        fix: "Add the missing sections (headings like \"Installation\", \"Usage\", and \"License\")",
+    },
+    Check {
+        code: "RM032",
+        id: "readme-short",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "README has too little prose to explain the project",
+        fix: "Describe what the project does, who it's for, and how to get started",
+    },
+    Check {
+        code: "RM033",
+        id: "readme-broken-link",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "README links to a relative path that doesn't exist",
+        fix: "Point the link at an existing file or remove it",
+    },
+    Check {
+        code: "RM034",
+        id: "readme-placeholder",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "README still contains TODO/FIXME markers or generated placeholder text",
+        fix: "Replace the placeholder with real content",
+    },
+    Check {
+        code: "RM035",
+        id: "workflow-write-all",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "GitHub Actions workflow token has write-all permissions, set explicitly or by omitting `permissions`",
+        fix: "Add `permissions: contents: read` at the top and grant writes per job",
+    },
+    Check {
+        code: "RM036",
+        id: "ci-publish-on-push",
+        category: Category::Ci,
+        severity: Severity::Warning,
+        description: "Workflow runs on push to every branch and publishes artifacts, releases, or packages",
+        fix: "Limit `on.push` with `branches:` or `tags:`, or move publishing to a release workflow",
+    },
+    Check {
+        code: "RM037",
+        id: "dead-link",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "Markdown doc links to a missing file or an unreachable URL (with --check-links)",
+        fix: "Update the link to the moved page or file, or remove it",
+    },
+    Check {
+        code: "RM038",
+        id: "stale-lockfile",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Lock file unchanged for longer than lockfile_max_age_days (default 365)",
+        fix: "Update dependencies (cargo update, npm update, ...) and commit the lock file",
+    },
+    Check {
+        code: "RM039",
+        id: "readme-install-mismatch",
+        category: Category::Documentation,
+        severity: Severity::Warning,
+        description: "README install or usage commands name another package or package manager",
+        fix: "Use the package manager and package name from the manifest (a copy-pasted README?)",
+    },
+    Check {
+        code: "RM040",
+        id: "no-dependency-updates",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Dependency files present but no Dependabot or Renovate config",
+        fix: "repomedic generate --dependabot (or add a renovate.json)",
+    },
+    Check {
+        code: "RM041",
+        id: "name-mismatch",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "Directory, manifest, README title, and remote disagree on the project name",
+        fix: "Rename the leftovers of the old name (a cloned-and-renamed project?)",
+    },
+    Check {
+        code: "RM042",
+        id: "dependency-wildcard",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Dependency accepts any version (`*`, `latest`, only a lower bound, or no pin)",
+        fix: "Pin a version requirement (e.g. ^1.4 or ==1.4.2) and commit the lock file",
+    },
+    Check {
+        code: "RM043",
+        id: "dependency-git",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Dependency fetched from a git repository or untagged commit instead of a registry release",
+        fix: "Depend on a published release; if git is unavoidable, pin a tag or commit",
+    },
+    Check {
+        code: "RM044",
+        id: "dependency-outdated",
+        category: Category::Project,
+        severity: Severity::Info,
+        description: "Dependency is a major version behind its registry (only with --online)",
+        fix: "Read the changelog, raise the requirement, and run the tests",
+    },
+    Check {
+        code: "RM045",
+        id: "history-large-file",
+        category: Category::Files,
+        severity: Severity::Warning,
+        description: "Large file committed and later deleted or replaced, still in every clone",
+        fix: "Purge it with `git filter-repo --invert-paths --path <file>`, or move it to LFS with `git lfs migrate import --everything --include=<file>`",
+    },
+    Check {
+        code: "RM046",
+        id: "package-missing-readme",
+        category: Category::Community,
+        severity: Severity::Info,
+        description: "Published monorepo package has no README of its own",
+        fix: "Add a README to the package directory (or point the manifest's readme field at one)",
+    },
+    Check {
+        code: "RM047",
+        id: "package-missing-license",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Published monorepo package has no LICENSE of its own",
+        fix: "Copy the root LICENSE into the package directory (or set license-file in Cargo.toml)",
+    },
+    Check {
+        code: "RM048",
+        id: "duplicate-root-file",
+        category: Category::Files,
+        severity: Severity::Info,
+        description: "README or LICENSE outside any package duplicates the root one",
+        fix: "Delete the copy and link to the root file",
+    },
+    Check {
+        code: "RM049",
+        id: "stale-license-copy",
+        category: Category::Files,
+        severity: Severity::Info,
+        description: "LICENSE outside any package has drifted from the root one",
+        fix: "Delete the copy, or update it to match the root LICENSE",
+    },
+    Check {
+        code: "RM050",
+        id: "lfs-recommended",
+        category: Category::Files,
+        severity: Severity::Warning,
+        description: "Large images, models, or archives committed without Git LFS",
+        fix: "repomedic generate --gitattributes, then `git lfs migrate import --include=<patterns>`",
+    },
+    Check {
+        code: "RM051",
+        id: "repo-archived",
+        category: Category::Community,
+        severity: Severity::Warning,
+        description: "Repository is archived on GitHub or GitLab",
+        fix: "Unarchive it in the repository settings, or point users at its successor in the README",
+    },
+    Check {
+        code: "RM052",
+        id: "branch-unprotected",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Default branch has no branch protection on GitHub or GitLab",
+        fix: "Add a branch protection rule or ruleset (GitLab: a protected branch) for the default branch",
+    },
+    Check {
+        code: "RM053",
+        id: "no-required-reviews",
+        category: Category::Security,
+        severity: Severity::Info,
+        description: "Protected default branch merges without an approving review",
+        fix: "Require at least one approving review in the branch protection rule (GitLab: an approval rule)",
+    },
+    Check {
+        code: "RM054",
+        id: "dependabot-alerts",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Open Dependabot alerts (an error when any is critical or high)",
+        fix: "Update the vulnerable dependencies, or dismiss alerts that don't apply",
+    },
+    Check {
+        code: "RM055",
+        id: "license-undetected",
+        category: Category::Community,
+        severity: Severity::Info,
+        description: "GitHub doesn't recognize the LICENSE file",
+        fix: "repomedic generate --license --force, or restore the unmodified license text",
+    },
+    Check {
+        code: "RM056",
+        id: "missing-gitlab-templates",
+        category: Category::Community,
+        severity: Severity::Info,
+        description: "GitLab project offers no issue or merge request templates",
+        fix: "Add Markdown templates to .gitlab/issue_templates/ and .gitlab/merge_request_templates/",
+    },
+    Check {
+        code: "RM057",
+        id: "missing-dockerignore",
+        category: Category::Files,
+        severity: Severity::Warning,
+        description: "Dockerfile without a .dockerignore, so .git and local files enter the build context",
+        fix: "repomedic generate --dockerignore",
+    },
+    Check {
+        code: "RM058",
+        id: "docker-latest-tag",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "Base or service image on `latest` or without a tag",
+        fix: "Pin the image to a version tag or digest",
+    },
+    Check {
+        code: "RM059",
+        id: "docker-root-user",
+        category: Category::Security,
+        severity: Severity::Warning,
+        description: "Container image runs as root",
+        fix: "Add a non-root USER to the final stage",
+    },
+    Check {
+        code: "RM060",
+        id: "docker-add-instead-of-copy",
+        category: Category::Project,
+        severity: Severity::Info,
+        description: "ADD used for local files",
+        fix: "Use COPY; keep ADD for URLs and archives it should unpack",
+    },
+    Check {
+        code: "RM061",
+        id: "docker-secret-env",
+        category: Category::Security,
+        severity: Severity::Error,
+        description: "Credential set in a Dockerfile ENV or ARG, or a Compose environment",
+        fix: "Pass it at runtime (an env file or secret) or use a BuildKit secret mount, and rotate it",
+    },
+    Check {
+        code: "RM062",
+        id: "k8s-missing-limits",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "Kubernetes workload container without resource limits",
+        fix: "Set resources.limits (and requests) for CPU and memory on every container",
+    },
+    Check {
+        code: "RM063",
+        id: "helm-missing-schema",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "Helm chart without values.schema.json",
+        fix: "Add values.schema.json next to Chart.yaml (helm-schema or helm-values-schema-json can generate one)",
+    },
+    Check {
+        code: "RM064",
+        id: "terraform-state-committed",
+        category: Category::Security,
+        severity: Severity::Error,
+        description: "Terraform state file committed",
+        fix: "git rm --cached the state, ignore *.tfstate*, move state to a remote backend, and rotate the secrets it held",
+    },
+    Check {
+        code: "RM065",
+        id: "terraform-missing-lock",
+        category: Category::Files,
+        severity: Severity::Warning,
+        description: "Terraform root module without .terraform.lock.hcl",
+        fix: "Run `terraform init` (or `terraform providers lock`) and commit .terraform.lock.hcl",
+    },
+    Check {
+        code: "RM066",
+        id: "terraform-unpinned-provider",
+        category: Category::Project,
+        severity: Severity::Warning,
+        description: "Terraform provider without a version constraint",
+        fix: "Add a version constraint for each provider under terraform { required_providers { ... } }",
+    },
+    Check {
+        code: "RM067",
+        id: "terraform-tfvars-secret",
+        category: Category::Security,
+        severity: Severity::Error,
+        description: "Credential-looking variable set in a committed .tfvars file",
+        fix: "Pass it as a TF_VAR_ environment variable or from a secret store, commit a .tfvars.example instead, and rotate it",
+    },
+    Check {
+        code: "RM068",
+        id: "terraform-no-fmt-check",
+        category: Category::Ci,
+        severity: Severity::Info,
+        description: "Terraform code with no `terraform fmt` check in CI or pre-commit",
+        fix: "Run `terraform fmt -check -recursive` in CI, or add the terraform_fmt pre-commit hook",
+    },
+    Check {
+        code: "RM069",
+        id: "editorconfig-violation",
+        category: Category::Files,
+        severity: Severity::Info,
+        description: "Files not following the line endings, indent style, final newline, or trailing whitespace .editorconfig sets",
+        fix: "Let the editor apply .editorconfig on save, or run a formatter over the listed files",
+    },
+    Check {
+        code: "RM070",
+        id: "inconsistent-line-endings",
+        category: Category::Files,
+        severity: Severity::Warning,
+        description: "Text files with CRLF in an LF repository (or the reverse), or mixed endings",
+        fix: "repomedic generate --gitattributes (adds `* text=auto`), then `git add --renormalize .`",
+    },
+    Check {
+        code: "RM071",
+        id: "utf8-bom",
+        category: Category::Files,
+        severity: Severity::Info,
+        description: "Text files starting with a UTF-8 byte order mark",
+        fix: "Save the files as UTF-8 without a BOM",
+    },
+    Check {
+        code: "RM072",
+        id: "workflow-unpinned-action",
+        category: Category::Security,
+        severity: Severity::Info,
+        description: "GitHub Actions workflow uses an action or reusable workflow by tag or branch instead of a commit SHA",
+        fix: "Pin `uses:` to the full commit SHA and keep the tag in a comment",
+    },
+];
+
+/// Every check: the built-in ones followed by one per secret pattern.
+pub fn all() -> Vec<Check> {
+    CHECKS
+        .iter()
+        .copied()
+        .chain(secrets::RULES.iter().map(|r| Check {
+            code: r.code,
+            id: r.id,
+            category: Category::Security,
+            severity: Severity::Error,
+            description: r.name,
+            fix: SECRET_FIX,
+        }))
+        .collect()
+}
+
+/// Look up a check by code (`RM001`), ID (`missing-readme`), or both
+/// (`RM001-missing-readme`). Codes are case-insensitive.
+pub fn find(name: &str) -> Option<Check> {
+    let name = name.trim();
+    all().into_iter().find(|c| {
+        c.id == name || c.code.eq_ignore_ascii_case(name) || c.full_id().eq_ignore_ascii_case(name)
+    })
+}
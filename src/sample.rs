@@ -0,0 +1,121 @@
+use crate::cache;
+use serde::Serialize;
+
+/// z-score for a 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Up to `n` of `files`, in their original order. The pick depends only on
+/// the paths, so rescanning an unchanged tree reads the same files (and
+/// hits the same cache entries).
+pub fn pick<T, F: Fn(&T) -> &str>(files: Vec<T>, n: usize, path: F) -> Vec<T> {
+    if files.len() <= n {
+        return files;
+    }
+    let mut ranked: Vec<(u64, usize)> = files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (cache::hash(path(f)), i))
+        .collect();
+    ranked.sort_unstable();
+    let mut keep: Vec<usize> = ranked.into_iter().take(n).map(|(_, i)| i).collect();
+    keep.sort_unstable();
+    let mut keep = keep.into_iter().peekable();
+    files
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, f)| (keep.next_if_eq(&i).is_some()).then_some(f))
+        .collect()
+}
+
+/// How many files a sampled check would flag if it had read them all.
+#[derive(Debug, Clone, Serialize)]
+pub struct Estimate {
+    /// Check ID, or the pass name for custom content rules.
+    pub check: String,
+    /// Files eligible for the check.
+    pub population: usize,
+    pub sampled: usize,
+    /// Sampled files with at least one finding.
+    pub matched: usize,
+    /// Expected number of matching files across the population.
+    pub estimate: usize,
+    /// 95% confidence interval for `estimate`.
+    pub low: usize,
+    pub high: usize,
+}
+
+impl Estimate {
+    /// Scale `matched` of `sampled` up to `population`, with a Wilson score
+    /// interval narrowed by the finite population correction (a sample of
+    /// everything has no uncertainty).
+    pub fn new(check: &str, population: usize, sampled: usize, matched: usize) -> Self {
+        let (n, total) = (sampled.max(1) as f64, population as f64);
+        let p = matched as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let fpc = if population > 1 {
+            ((total - n) / (total - 1.0)).max(0.0).sqrt()
+        } else {
+            0.0
+        };
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let half = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / (1.0 + z2 / n) * fpc;
+        // At least the files seen matching, at most all but those seen clean
+        let (min, max) = (matched, population - (sampled - matched));
+        let low = (((center - half) * total).floor().max(0.0) as usize).clamp(min, max);
+        let high = (((center + half) * total).ceil() as usize).clamp(min, max);
+        Estimate {
+            check: check.to_string(),
+            population,
+            sampled,
+            matched,
+            estimate: ((p * total).round() as usize).clamp(low, high),
+            low,
+            high,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pick, Estimate};
+
+    fn paths(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("src/file{}.rs", i)).collect()
+    }
+
+    #[test]
+    fn picks_are_stable_and_ordered() {
+        let first = pick(paths(50), 10, |p| p.as_str());
+        assert_eq!(first.len(), 10);
+        assert_eq!(first, pick(paths(50), 10, |p| p.as_str()));
+        let index = |p: &String| {
+            p.trim_start_matches("src/file")
+                .trim_end_matches(".rs")
+                .parse::<usize>()
+                .unwrap()
+        };
+        assert!(first.windows(2).all(|w| index(&w[0]) < index(&w[1])));
+        // A file's membership doesn't depend on the others' order
+        let mut reversed = paths(50);
+        reversed.reverse();
+        let mut again = pick(reversed, 10, |p| p.as_str());
+        again.sort_by_key(index);
+        assert_eq!(again, first);
+        assert_eq!(pick(paths(3), 10, |p| p.as_str()), paths(3));
+    }
+
+    #[test]
+    fn estimates() {
+        let full = Estimate::new("todo", 40, 40, 7);
+        assert_eq!((full.estimate, full.low, full.high), (7, 7, 7));
+
+        let sampled = Estimate::new("todo", 1000, 100, 10);
+        assert_eq!(sampled.estimate, 100);
+        assert!(sampled.low < 100 && sampled.high > 100);
+        assert!(sampled.low >= 10 && sampled.high <= 910);
+
+        let none = Estimate::new("todo", 1000, 100, 0);
+        assert_eq!((none.estimate, none.low), (0, 0));
+        assert!(none.high > 0);
+    }
+}
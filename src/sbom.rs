@@ -0,0 +1,597 @@
+use crate::base64;
+use crate::config::TomlDoc;
+use crate::deps::{self, Ecosystem};
+use crate::scanner::ScanResult;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Software bill of materials formats written by `export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// CycloneDX 1.5 JSON (`*.cdx.json`).
+    CycloneDx,
+    /// SPDX 2.3 JSON (`*.spdx.json`).
+    Spdx,
+}
+
+impl Format {
+    /// The SBOM format an export file name asks for, if any.
+    pub fn for_file(name: &str) -> Option<Format> {
+        let name = name.to_lowercase();
+        if name.ends_with(".cdx.json") || name.ends_with(".cyclonedx.json") {
+            Some(Format::CycloneDx)
+        } else if name.ends_with(".spdx.json") {
+            Some(Format::Spdx)
+        } else {
+            None
+        }
+    }
+}
+
+/// Digest algorithms lock files record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashAlg {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl HashAlg {
+    fn parse(name: &str) -> Option<HashAlg> {
+        match name.to_lowercase().as_str() {
+            "sha1" => Some(HashAlg::Sha1),
+            "sha256" => Some(HashAlg::Sha256),
+            "sha512" => Some(HashAlg::Sha512),
+            _ => None,
+        }
+    }
+
+    fn cyclonedx(self) -> &'static str {
+        match self {
+            HashAlg::Sha1 => "SHA-1",
+            HashAlg::Sha256 => "SHA-256",
+            HashAlg::Sha512 => "SHA-512",
+        }
+    }
+
+    fn spdx(self) -> &'static str {
+        match self {
+            HashAlg::Sha1 => "SHA1",
+            HashAlg::Sha256 => "SHA256",
+            HashAlg::Sha512 => "SHA512",
+        }
+    }
+}
+
+/// A package pinned by a lock file.
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub ecosystem: Ecosystem,
+    /// Lock file the package was read from.
+    pub lockfile: &'static str,
+    pub name: String,
+    pub version: String,
+    /// Tarball or git URL the package is fetched from, when recorded.
+    pub download: Option<String>,
+    /// Hex digest of the package archive, when recorded.
+    pub hash: Option<(HashAlg, String)>,
+}
+
+impl Component {
+    /// Package URL, e.g. `pkg:cargo/serde@1.0.200` or `pkg:npm/%40types/node@20.1.0`.
+    pub fn purl(&self) -> String {
+        let (kind, name) = match self.ecosystem {
+            Ecosystem::Cargo => ("cargo", self.name.clone()),
+            Ecosystem::Npm => ("npm", self.name.replacen('@', "%40", 1)),
+            Ecosystem::Pip => ("pypi", self.name.to_lowercase().replace('_', "-")),
+            Ecosystem::Go => ("golang", self.name.clone()),
+        };
+        format!("pkg:{}/{}@{}", kind, name, self.version.replace('+', "%2B"))
+    }
+}
+
+/// Packages pinned by the lock files in `root`: Cargo.lock,
+/// package-lock.json, yarn.lock, poetry.lock, Pipfile.lock, pinned
+/// (`==`) requirements.txt lines, and go.mod requirements. Each package
+/// appears once per ecosystem and version; local and workspace packages
+/// are left out.
+pub fn locked(root: &Path) -> Vec<Component> {
+    let read = |name: &str| fs::read_to_string(root.join(name)).ok();
+    let mut components = Vec::new();
+    if let Some(content) = read("Cargo.lock") {
+        components.extend(cargo(&content));
+    }
+    if let Some(content) = read("package-lock.json") {
+        components.extend(npm(&content));
+    } else if let Some(content) = read("yarn.lock") {
+        components.extend(yarn(&content));
+    }
+    if let Some(content) = read("poetry.lock") {
+        components.extend(poetry(&content));
+    } else if let Some(content) = read("Pipfile.lock") {
+        components.extend(pipfile(&content));
+    } else if let Some(content) = read("requirements.txt") {
+        components.extend(requirements(&content));
+    }
+    if let Some(content) = read("go.mod") {
+        components.extend(go(&content));
+    }
+    let mut seen = Vec::new();
+    components.retain(|c| {
+        let key = (c.ecosystem, c.name.clone(), c.version.clone());
+        let new = !seen.contains(&key);
+        seen.push(key);
+        new
+    });
+    components
+}
+
+fn component(ecosystem: Ecosystem, lockfile: &'static str, name: &str, version: &str) -> Component {
+    Component {
+        ecosystem,
+        lockfile,
+        name: name.to_string(),
+        version: version.to_string(),
+        download: None,
+        hash: None,
+    }
+}
+
+/// `[[package]]` tables of a TOML lock file as `(key, value)` lists.
+fn toml_packages(content: &str) -> Vec<Vec<(String, String)>> {
+    let Ok(doc) = TomlDoc::parse(content) else {
+        return Vec::new();
+    };
+    let mut packages: Vec<Vec<(String, String)>> = Vec::new();
+    for (_, section, key, value) in doc.entries {
+        if section != "package" {
+            continue;
+        }
+        // Every table starts with its name
+        if key == "name" || packages.is_empty() {
+            packages.push(Vec::new());
+        }
+        if let (Some(package), Some(value)) = (packages.last_mut(), value.as_str()) {
+            package.push((key, value));
+        }
+    }
+    packages
+}
+
+fn cargo(content: &str) -> Vec<Component> {
+    toml_packages(content)
+        .into_iter()
+        .filter_map(|package| {
+            let field = |k: &str| package.iter().find(|(key, _)| key == k).map(|(_, v)| v);
+            let (name, version) = (field("name")?, field("version")?);
+            // Packages without a source are the workspace's own crates
+            let source = field("source")?;
+            let mut c = component(Ecosystem::Cargo, "Cargo.lock", name, version);
+            if let Some(git) = source.strip_prefix("git+") {
+                c.download = Some(git.to_string());
+            } else if source.contains("crates.io-index") {
+                c.download = Some(format!(
+                    "https://crates.io/api/v1/crates/{}/{}/download",
+                    name, version
+                ));
+            }
+            c.hash = field("checksum").map(|h| (HashAlg::Sha256, h.clone()));
+            Some(c)
+        })
+        .collect()
+}
+
+fn npm(content: &str) -> Vec<Component> {
+    let Ok(lock) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let mut components = Vec::new();
+    // lockfileVersion 2 and 3 list every installed path under "packages"
+    if let Some(packages) = lock.get("packages").and_then(|p| p.as_object()) {
+        for (path, entry) in packages {
+            let Some((_, name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            if entry.get("link").and_then(|l| l.as_bool()) == Some(true) {
+                continue;
+            }
+            if let Some(c) = npm_component(name, entry) {
+                components.push(c);
+            }
+        }
+    } else if let Some(deps) = lock.get("dependencies") {
+        npm_tree(deps, &mut components);
+    }
+    components
+}
+
+/// lockfileVersion 1: nested `dependencies` objects.
+fn npm_tree(deps: &Value, components: &mut Vec<Component>) {
+    let Some(deps) = deps.as_object() else {
+        return;
+    };
+    for (name, entry) in deps {
+        if let Some(c) = npm_component(name, entry) {
+            components.push(c);
+        }
+        if let Some(nested) = entry.get("dependencies") {
+            npm_tree(nested, components);
+        }
+    }
+}
+
+fn npm_component(name: &str, entry: &Value) -> Option<Component> {
+    let version = entry.get("version")?.as_str()?;
+    // Local packages are recorded as file: or link: versions
+    if version.starts_with("file:") || version.starts_with("link:") {
+        return None;
+    }
+    let mut c = component(Ecosystem::Npm, "package-lock.json", name, version);
+    c.download = entry
+        .get("resolved")
+        .and_then(|r| r.as_str())
+        .map(String::from);
+    c.hash = entry
+        .get("integrity")
+        .and_then(|i| i.as_str())
+        .and_then(subresource_integrity);
+    Some(c)
+}
+
+/// An `sha512-<base64>` integrity string as an algorithm and hex digest.
+fn subresource_integrity(integrity: &str) -> Option<(HashAlg, String)> {
+    // Several digests may be listed; the first is enough
+    let first = integrity.split_whitespace().next()?;
+    let (alg, digest) = first.split_once('-')?;
+    Some((HashAlg::parse(alg)?, to_hex(&base64::decode(digest)?)))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// yarn.lock, classic (`version "1.2.3"`) or Berry (`version: 1.2.3`).
+fn yarn(content: &str) -> Vec<Component> {
+    let mut components: Vec<Component> = Vec::new();
+    let mut current: Option<Component> = None;
+    for line in content.lines() {
+        if !line.starts_with(' ') && line.ends_with(':') {
+            components.extend(current.take());
+            // "@babel/core@^7.0.0", "@babel/core@^7.1.0":
+            let spec = line
+                .trim_end_matches(':')
+                .split(',')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .trim_matches('"');
+            let local = ["@workspace:", "@link:", "@portal:", "@file:"]
+                .iter()
+                .any(|p| spec.contains(p));
+            // The name may itself start with @ (a scope)
+            let at = spec.get(1..).and_then(|s| s.find('@'));
+            if let (Some(at), false) = (at, local) {
+                current = Some(component(Ecosystem::Npm, "yarn.lock", &spec[..at + 1], ""));
+            }
+            continue;
+        }
+        let Some(c) = current.as_mut() else {
+            continue;
+        };
+        let line = line.trim();
+        let (key, value) = line
+            .split_once(": ")
+            .or_else(|| line.split_once(' '))
+            .unwrap_or((line, ""));
+        let value = value.trim().trim_matches('"');
+        match key.trim_end_matches(':') {
+            "version" => c.version = value.to_string(),
+            "resolved" => c.download = Some(value.to_string()),
+            "integrity" => c.hash = subresource_integrity(value),
+            _ => {}
+        }
+    }
+    components.extend(current);
+    components.retain(|c| !c.version.is_empty());
+    components
+}
+
+fn poetry(content: &str) -> Vec<Component> {
+    toml_packages(content)
+        .into_iter()
+        .filter_map(|package| {
+            let field = |k: &str| package.iter().find(|(key, _)| key == k).map(|(_, v)| v);
+            Some(component(
+                Ecosystem::Pip,
+                "poetry.lock",
+                field("name")?,
+                field("version")?,
+            ))
+        })
+        .collect()
+}
+
+fn pipfile(content: &str) -> Vec<Component> {
+    let Ok(lock) = serde_json::from_str::<Value>(content) else {
+        return Vec::new();
+    };
+    let mut components = Vec::new();
+    for group in ["default", "develop"] {
+        let Some(entries) = lock.get(group).and_then(|g| g.as_object()) else {
+            continue;
+        };
+        for (name, entry) in entries {
+            let Some(version) = entry
+                .get("version")
+                .and_then(|v| v.as_str())
+                .and_then(|v| v.strip_prefix("=="))
+            else {
+                continue;
+            };
+            let mut c = component(Ecosystem::Pip, "Pipfile.lock", name, version);
+            c.hash = entry
+                .get("hashes")
+                .and_then(|h| h.get(0))
+                .and_then(|h| h.as_str())
+                .and_then(|h| h.split_once(':'))
+                .and_then(|(alg, hex)| Some((HashAlg::parse(alg)?, hex.to_string())));
+            components.push(c);
+        }
+    }
+    components
+}
+
+/// `name==version` lines; ranges aren't pinned, so they're left out.
+fn requirements(content: &str) -> Vec<Component> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let spec = line.split(['#', ';']).next()?.split_whitespace().next()?;
+            let (name, version) = spec.split_once("==")?;
+            // Drop extras: requests[socks]==2.31.0
+            let name = name.split('[').next()?;
+            (!name.is_empty() && !version.is_empty())
+                .then(|| component(Ecosystem::Pip, "requirements.txt", name, version))
+        })
+        .collect()
+}
+
+/// go.mod requirements, indirect ones included: since Go 1.17 they list
+/// every module the build needs.
+fn go(content: &str) -> Vec<Component> {
+    deps::go_requires(content)
+        .into_iter()
+        .map(|(_, path, version, _)| component(Ecosystem::Go, "go.mod", path, version))
+        .collect()
+}
+
+/// Directory name of the scanned repository, for the SBOM's root package.
+fn project_name(path: &Path) -> String {
+    path.canonicalize()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+        .unwrap_or_else(|| "repository".to_string())
+}
+
+/// CycloneDX 1.5 JSON for the packages locked under `path`.
+pub fn to_cyclonedx(result: &ScanResult, path: &Path) -> String {
+    let components: Vec<Value> = locked(path)
+        .iter()
+        .map(|c| {
+            let purl = c.purl();
+            let mut entry = json!({
+                "type": "library",
+                "bom-ref": purl,
+                "name": c.name,
+                "version": c.version,
+                "purl": purl,
+                "properties": [{ "name": "repomedic:lockfile", "value": c.lockfile }],
+            });
+            if let Some((alg, hex)) = &c.hash {
+                entry["hashes"] = json!([{ "alg": alg.cyclonedx(), "content": hex }]);
+            }
+            if let Some(url) = &c.download {
+                entry["externalReferences"] = json!([{ "type": "distribution", "url": url }]);
+            }
+            entry
+        })
+        .collect();
+    let bom = json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": result.scan_stats.scanned_at,
+            "tools": { "components": [{ "type": "application", "name": "repomedic", "version": VERSION }] },
+            "component": { "type": "application", "bom-ref": "root", "name": project_name(path) },
+        },
+        "components": components,
+    });
+    serde_json::to_string_pretty(&bom).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// SPDX 2.3 JSON for the packages locked under `path`.
+pub fn to_spdx(result: &ScanResult, path: &Path) -> String {
+    let name = project_name(path);
+    let components = locked(path);
+    let mut packages = vec![json!({
+        "name": name,
+        "SPDXID": "SPDXRef-RootPackage",
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+    })];
+    let mut relationships = vec![json!({
+        "spdxElementId": "SPDXRef-DOCUMENT",
+        "relationshipType": "DESCRIBES",
+        "relatedSpdxElement": "SPDXRef-RootPackage",
+    })];
+    for (n, c) in components.iter().enumerate() {
+        let id = format!("SPDXRef-Package-{}", n + 1);
+        let mut package = json!({
+            "name": c.name,
+            "SPDXID": id,
+            "versionInfo": c.version,
+            "downloadLocation": c.download.as_deref().unwrap_or("NOASSERTION"),
+            "filesAnalyzed": false,
+            "externalRefs": [{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": c.purl(),
+            }],
+        });
+        if let Some((alg, hex)) = &c.hash {
+            package["checksums"] = json!([{ "algorithm": alg.spdx(), "checksumValue": hex }]);
+        }
+        packages.push(package);
+        relationships.push(json!({
+            "spdxElementId": "SPDXRef-RootPackage",
+            "relationshipType": "DEPENDS_ON",
+            "relatedSpdxElement": id,
+        }));
+    }
+    let scanned_at = &result.scan_stats.scanned_at;
+    let doc = json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": name,
+        "documentNamespace": format!(
+            "https://spdx.org/spdxdocs/repomedic/{}-{}",
+            name.replace(' ', "-"),
+            scanned_at
+        ),
+        "creationInfo": {
+            "created": scanned_at,
+            "creators": [format!("Tool: repomedic-{}", VERSION)],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    });
+    serde_json::to_string_pretty(&doc).unwrap_or_else(|_| "{}".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        cargo, npm, pipfile, requirements, subresource_integrity, yarn, Component, Format, HashAlg,
+    };
+    use crate::deps::Ecosystem;
+
+    fn versions(components: &[Component]) -> Vec<(&str, &str)> {
+        components
+            .iter()
+            .map(|c| (c.name.as_str(), c.version.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn formats() {
+        assert_eq!(Format::for_file("SBOM.cdx.json"), Some(Format::CycloneDx));
+        assert_eq!(Format::for_file("bom.spdx.json"), Some(Format::Spdx));
+        assert_eq!(Format::for_file("report.json"), None);
+    }
+
+    #[test]
+    fn purls() {
+        let mut c = Component {
+            ecosystem: Ecosystem::Npm,
+            lockfile: "package-lock.json",
+            name: "@types/node".to_string(),
+            version: "20.1.0".to_string(),
+            download: None,
+            hash: None,
+        };
+        assert_eq!(c.purl(), "pkg:npm/%40types/node@20.1.0");
+        c.ecosystem = Ecosystem::Pip;
+        c.name = "Flask_Login".to_string();
+        c.version = "1.0+local".to_string();
+        assert_eq!(c.purl(), "pkg:pypi/flask-login@1.0%2Blocal");
+    }
+
+    #[test]
+    fn cargo_lock() {
+        let lock = "version = 3\n\n[[package]]\nname = \"demo\"\nversion = \"0.1.0\"\n\n[[package]]\nname = \"serde\"\nversion = \"1.0.200\"\nsource = \"registry+https://github.com/rust-lang/crates.io-index\"\nchecksum = \"abc123\"\n\n[[package]]\nname = \"fork\"\nversion = \"0.2.0\"\nsource = \"git+https://example.com/fork?rev=1#deadbeef\"\n";
+        let components = cargo(lock);
+        assert_eq!(
+            versions(&components),
+            [("serde", "1.0.200"), ("fork", "0.2.0")]
+        );
+        assert_eq!(
+            components[0].download.as_deref(),
+            Some("https://crates.io/api/v1/crates/serde/1.0.200/download")
+        );
+        assert_eq!(
+            components[0].hash,
+            Some((HashAlg::Sha256, "abc123".to_string()))
+        );
+        assert_eq!(
+            components[1].download.as_deref(),
+            Some("https://example.com/fork?rev=1#deadbeef")
+        );
+    }
+
+    #[test]
+    fn npm_locks() {
+        let v3 = r#"{"packages": {
+            "": {"name": "demo"},
+            "node_modules/left-pad": {"version": "1.3.0", "integrity": "sha1-AAEC"},
+            "node_modules/a/node_modules/@scope/b": {"version": "2.0.0"},
+            "node_modules/local": {"link": true},
+            "node_modules/util": {"version": "file:../util"}
+        }}"#;
+        let components = npm(v3);
+        assert_eq!(
+            versions(&components),
+            [("@scope/b", "2.0.0"), ("left-pad", "1.3.0")]
+        );
+        assert_eq!(
+            components[1].hash,
+            Some((HashAlg::Sha1, "000102".to_string()))
+        );
+        let v1 = r#"{"dependencies": {"a": {"version": "1.0.0", "dependencies": {"b": {"version": "2.0.0"}}}}}"#;
+        assert_eq!(versions(&npm(v1)), [("a", "1.0.0"), ("b", "2.0.0")]);
+    }
+
+    #[test]
+    fn yarn_locks() {
+        let classic = "# yarn lockfile v1\n\n\"@babel/core@^7.0.0\", \"@babel/core@^7.1.0\":\n  version \"7.2.0\"\n  resolved \"https://registry.yarnpkg.com/@babel/core/-/core-7.2.0.tgz\"\n\nlodash@^4:\n  version \"4.17.21\"\n";
+        let components = yarn(classic);
+        assert_eq!(
+            versions(&components),
+            [("@babel/core", "7.2.0"), ("lodash", "4.17.21")]
+        );
+        assert!(components[0].download.is_some());
+        let berry = "\"demo@workspace:.\":\n  version: 0.0.0-use.local\n\n\"left-pad@npm:^1.3.0\":\n  version: 1.3.0\n";
+        assert_eq!(versions(&yarn(berry)), [("left-pad", "1.3.0")]);
+    }
+
+    #[test]
+    fn python_locks() {
+        let lock = r#"{"default": {"requests": {"version": "==2.31.0", "hashes": ["sha256:abc"]}},
+            "develop": {"pytest": {"version": "*"}}}"#;
+        let components = pipfile(lock);
+        assert_eq!(versions(&components), [("requests", "2.31.0")]);
+        assert_eq!(
+            components[0].hash,
+            Some((HashAlg::Sha256, "abc".to_string()))
+        );
+        assert_eq!(
+            versions(&requirements(
+                "requests[socks]==2.31.0 ; python_version > '3'\nflask>=2\n# x==1\n"
+            )),
+            [("requests", "2.31.0")]
+        );
+    }
+
+    #[test]
+    fn integrity_strings() {
+        assert_eq!(
+            subresource_integrity("sha512-AAEC sha1-AAAA"),
+            Some((HashAlg::Sha512, "000102".to_string()))
+        );
+        assert_eq!(subresource_integrity("md5-AAEC"), None);
+    }
+}
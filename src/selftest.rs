@@ -0,0 +1,299 @@
+use crate::license;
+use crate::report::{calculate_score, collect_passed, score_grade};
+use crate::scanner::{self, ScanResult};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A synthetic repository bundled into the binary with its expected report.
+pub struct Fixture {
+    pub name: &'static str,
+    pub files: &'static [(&'static str, &'static str)],
+    pub golden: &'static str,
+}
+
+pub enum Outcome {
+    Pass,
+    /// Snapshot differs from the golden report.
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// A report invariant does not hold.
+    Violation(String),
+    /// No golden report existed; the current snapshot was written.
+    Recorded(PathBuf),
+}
+
+pub struct CaseResult {
+    pub name: String,
+    pub outcome: Outcome,
+}
+
+impl CaseResult {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Pass | Outcome::Recorded(_))
+    }
+}
+
+pub const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "empty",
+        files: &[("notes.txt", "nothing to see here\n")],
+        golden: "\
+score: 19 (F)
+present: -
+languages: -
+build systems: -
+ci: -
+license: -
+issues:
+  error not-git-repo: Not a Git repository (no .git directory)
+  error missing-readme: Missing README.md
+  error missing-license: Missing LICENSE file
+  warning missing-gitignore: Missing .gitignore
+  warning missing-changelog: Missing CHANGELOG
+  warning missing-contributing: Missing CONTRIBUTING.md
+  warning missing-code-of-conduct: Missing CODE_OF_CONDUCT.md
+  warning missing-security: Missing SECURITY.md
+  warning missing-editorconfig: Missing .editorconfig
+  warning no-ci: No CI/CD configuration detected
+  info no-source: No source code detected
+",
+    },
+    Fixture {
+        name: "rust-healthy",
+        files: &[
+            (".git/HEAD", "ref: refs/heads/main\n"),
+            (
+                "README.md",
+                "# rust-healthy\n\n[![CI](https://img.shields.io/badge/ci-passing-green)](https://example.com)\n\nDemo is a small command-line tool that prints a friendly greeting. It exists to show \
+what a healthy Rust repository looks like: documented, tested, licensed, and built in CI \
+on every push so regressions are caught before they reach anyone who depends on it.\n\n## Installation\n\nInstall it from a checkout with Cargo, which builds the binary in release mode and places \
+it on your path. A recent stable toolchain is all you need; there are no system libraries \
+to install first.\n\n## Usage\n\nRun the binary without arguments to print the greeting. It reads no configuration files \
+and writes nothing to disk, so it is safe to try anywhere.\n\n## License\n\nReleased under the MIT license; see the LICENSE file for the full terms.\n",
+            ),
+            ("LICENSE", "SPDX-License-Identifier: MIT\n"),
+            (".gitignore", "/target\n"),
+            ("CHANGELOG.md", "# Changelog\n"),
+            ("CONTRIBUTING.md", "# Contributing\n"),
+            ("CODE_OF_CONDUCT.md", "# Code of Conduct\n"),
+            ("SECURITY.md", "# Security\n"),
+            (".editorconfig", "root = true\n"),
+            (
+                "Cargo.toml",
+                "[package]\nname = \"rust-healthy\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+            ),
+            ("Cargo.lock", "version = 3\n"),
+            (
+                ".github/dependabot.yml",
+                "version: 2\nupdates:\n  - package-ecosystem: cargo\n    directory: \"/\"\n    schedule:\n      interval: weekly\n",
+            ),
+            ("src/main.rs", "fn main() {}\n"),
+            ("tests/smoke.rs", "#[test]\nfn ok() {}\n"),
+            (
+                ".github/workflows/ci.yml",
+                "on: push\npermissions:\n  contents: read\njobs:\n  test:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: Swatinem/rust-cache@9d47c6ad4b02e050fd481d890b2ea34778fd09d6\n      - run: cargo tarpaulin\n",
+            ),
+        ],
+        golden: "\
+score: 100 (A)
+present: git, readme, license, gitignore, changelog, contributing, code-of-conduct, security, editorconfig, tests
+languages: Rust
+build systems: Cargo (Rust)
+ci: GitHub Actions
+license: MIT
+issues:
+",
+    },
+    Fixture {
+        name: "node-secrets",
+        files: &[
+            (".git/HEAD", "ref: refs/heads/main\n"),
+            ("README.md", "# node-secrets\n"),
+            ("package.json", "{\"name\": \"node-secrets\", \"version\": \"1.0.0\"}\n"),
+            ("index.js", "console.log('hi');\n"),
+            // repomedic:ignore potential-secret
+            (".env", "API=1\nGITHUB_TOKEN=ghp_example_not_a_real_token\n"),
+        ],
+        golden: "\
+score: 14 (F)
+present: git, readme
+languages: JavaScript
+build systems: npm (Node.js)
+ci: -
+license: -
+issues:
+  error missing-license: Missing LICENSE file
+  warning missing-gitignore: Missing .gitignore
+  warning missing-changelog: Missing CHANGELOG
+  warning missing-contributing: Missing CONTRIBUTING.md
+  warning missing-code-of-conduct: Missing CODE_OF_CONDUCT.md
+  warning missing-security: Missing SECURITY.md
+  warning missing-editorconfig: Missing .editorconfig
+  warning no-ci: No CI/CD configuration detected
+  info no-tests: No test directory detected
+  warning no-dependency-updates: Dependencies declared but no Dependabot or Renovate config
+  warning missing-lock-file: Missing npm/yarn/pnpm lock file: found package.json but no lock file (reproducible builds)
+  warning readme-incomplete: README is missing sections: description, installation, usage, license, badges (quality 30/100)
+  warning readme-short: README is short: 0 words (aim for at least 100)
+  error secret-github-token: Potential GitHub token in .env at line 2
+",
+    },
+];
+
+/// Deterministic, path-independent summary of a scan used for golden reports.
+pub fn snapshot(result: &ScanResult) -> String {
+    let join = |items: Vec<&str>| {
+        if items.is_empty() {
+            "-".to_string()
+        } else {
+            items.join(", ")
+        }
+    };
+    let score = calculate_score(result);
+    let present = [
+        (result.has_git, "git"),
+        (result.has_readme, "readme"),
+        (result.has_license, "license"),
+        (result.has_gitignore, "gitignore"),
+        (result.has_changelog, "changelog"),
+        (result.has_contributing, "contributing"),
+        (result.has_code_of_conduct, "code-of-conduct"),
+        (result.has_security, "security"),
+        (result.has_editorconfig, "editorconfig"),
+        (result.has_tests, "tests"),
+        (result.has_docs, "docs"),
+    ];
+    let mut out = format!("score: {} ({})\n", score, score_grade(score));
+    out.push_str(&format!(
+        "present: {}\n",
+        join(present.iter().filter(|p| p.0).map(|p| p.1).collect())
+    ));
+    out.push_str(&format!(
+        "languages: {}\n",
+        join(result.languages.iter().map(|l| l.name()).collect())
+    ));
+    out.push_str(&format!(
+        "build systems: {}\n",
+        join(result.build_systems.iter().map(|b| b.name()).collect())
+    ));
+    out.push_str(&format!(
+        "ci: {}\n",
+        join(result.ci_systems.iter().map(|c| c.name()).collect())
+    ));
+    out.push_str(&format!(
+        "license: {}\n",
+        license::expression(&result.licenses).unwrap_or_else(|| "-".to_string())
+    ));
+    out.push_str("issues:\n");
+    for issue in &result.issues {
+        out.push_str(&format!(
+            "  {} {}: {}\n",
+            issue.severity.name(),
+            issue.id(),
+            issue.message
+        ));
+    }
+    out
+}
+
+/// Properties every report must satisfy regardless of the repository.
+fn check_invariants(result: &ScanResult) -> Result<(), String> {
+    let score = calculate_score(result);
+    if score > 100 {
+        return Err(format!("score {} out of range", score));
+    }
+    let issues = &result.issues;
+    if let Some(issue) = issues.iter().find(|i| crate::rules::find(i.id()).is_none()) {
+        return Err(format!("issue with an unknown check ID: {}", issue.message));
+    }
+    if issues.is_empty() && score < 100 {
+        return Err(format!("no issues but score is {}", score));
+    }
+    if collect_passed(result).is_empty() && issues.is_empty() {
+        return Err("report has neither issues nor passed checks".into());
+    }
+    Ok(())
+}
+
+/// Scan `root` twice, check invariants and determinism, then compare against `golden`.
+fn run_case(name: &str, root: &Path, golden: Option<&str>) -> io::Result<(Outcome, String)> {
+    let scan = |root: &Path| scanner::scan(root).map_err(|e| io::Error::other(e.to_string()));
+    let result = scan(root)?;
+    let actual = snapshot(&result);
+    if let Err(msg) = check_invariants(&result) {
+        return Ok((Outcome::Violation(msg), actual));
+    }
+    if snapshot(&scan(root)?) != actual {
+        return Ok((
+            Outcome::Violation(format!("{}: rescanning changed the report", name)),
+            actual,
+        ));
+    }
+    let outcome = match golden {
+        Some(expected) if expected == actual => Outcome::Pass,
+        Some(expected) => Outcome::Mismatch {
+            expected: expected.to_string(),
+            actual: actual.clone(),
+        },
+        None => Outcome::Recorded(PathBuf::new()),
+    };
+    Ok((outcome, actual))
+}
+
+/// Run the bundled fixtures in a scratch directory.
+pub fn run_builtin() -> io::Result<Vec<CaseResult>> {
+    let base = std::env::temp_dir().join(format!("repomedic-selftest-{}", std::process::id()));
+    let mut results = Vec::new();
+    for fixture in FIXTURES {
+        let root = base.join(fixture.name);
+        for (rel, content) in fixture.files {
+            let file = root.join(rel);
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(file, content)?;
+        }
+        let (outcome, _) = run_case(fixture.name, &root, Some(fixture.golden))?;
+        results.push(CaseResult {
+            name: fixture.name.to_string(),
+            outcome,
+        });
+    }
+    let _ = fs::remove_dir_all(&base);
+    Ok(results)
+}
+
+/// Run every repository under `dir` against its sibling `<name>.golden` file.
+///
+/// Missing golden files are recorded from the current scan.
+pub fn run_dir(dir: &Path) -> io::Result<Vec<CaseResult>> {
+    let mut cases: Vec<PathBuf> = fs::read_dir(dir)?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    cases.sort();
+    let mut results = Vec::new();
+    for root in cases {
+        let name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let golden_path = dir.join(format!("{}.golden", name));
+        let golden = match fs::read_to_string(&golden_path) {
+            Ok(g) => Some(g),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        let (mut outcome, actual) = run_case(&name, &root, golden.as_deref())?;
+        if let Outcome::Recorded(_) = outcome {
+            fs::write(&golden_path, actual)?;
+            outcome = Outcome::Recorded(golden_path);
+        }
+        results.push(CaseResult { name, outcome });
+    }
+    Ok(results)
+}
@@ -0,0 +1,106 @@
+use serde::Serialize;
+use std::io::Read;
+use std::path::Path;
+
+/// Project SOPS config listing which paths must be encrypted.
+pub const CONFIG_FILE: &str = ".sops.yaml";
+
+/// A file matching a `.sops.yaml` creation rule that is not encrypted.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnencryptedFile {
+    pub file: String,
+    /// The `path_regex` it matches.
+    pub path_regex: String,
+}
+
+/// Whether a file is SOPS- or age-encrypted.
+///
+/// SOPS keeps the document structure and encrypts values as `ENC[...]`,
+/// recording its metadata under a top-level `sops` key (YAML, JSON) or
+/// `sops_*` entries (dotenv, INI). age files carry a fixed header, binary
+/// or ASCII-armored.
+pub fn is_encrypted(content: &str) -> bool {
+    let start = content.trim_start();
+    if start.starts_with("age-encryption.org/v1")
+        || start.starts_with("-----BEGIN AGE ENCRYPTED FILE-----")
+    {
+        return true;
+    }
+    if !content.contains("ENC[AES256_GCM,") {
+        return false;
+    }
+    content.lines().any(|l| {
+        l.starts_with("sops:")
+            || l.trim_start().starts_with("\"sops\":")
+            || l.starts_with("sops_version=")
+            || l.starts_with("sops_mac=")
+            || l.starts_with("[sops]")
+    })
+}
+
+/// Whether a binary file starts with the age header.
+pub fn is_age_file(path: &Path) -> bool {
+    const HEADER: &[u8] = b"age-encryption.org/v1";
+    let mut buf = [0u8; HEADER.len()];
+    std::fs::File::open(path)
+        .and_then(|mut f| f.read_exact(&mut buf))
+        .is_ok_and(|_| buf == HEADER)
+}
+
+/// `path_regex` patterns of every `creation_rules` entry in `.sops.yaml`.
+pub fn path_regexes(config: &str) -> Vec<String> {
+    config
+        .lines()
+        .filter_map(|l| {
+            let l = l.trim_start().trim_start_matches("- ").trim_start();
+            let value = l.strip_prefix("path_regex:")?.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .map(|v| v.replace("\\\\", "\\"))
+                .or_else(|| {
+                    value
+                        .strip_prefix('\'')
+                        .and_then(|v| v.strip_suffix('\''))
+                        .map(String::from)
+                })
+                .unwrap_or_else(|| value.to_string());
+            (!value.is_empty()).then_some(value)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_encrypted, path_regexes};
+
+    #[test]
+    fn encrypted_documents() {
+        assert!(is_encrypted(
+            "password: ENC[AES256_GCM,data:abc,iv:def,tag:ghi,type:str]\nsops:\n    version: 3.8.1\n"
+        ));
+        assert!(is_encrypted(
+            "{\n  \"key\": \"ENC[AES256_GCM,data:x]\",\n  \"sops\": {}\n}\n"
+        ));
+        assert!(is_encrypted(
+            "KEY=ENC[AES256_GCM,data:x]\nsops_version=3.8.1\n"
+        ));
+        assert!(is_encrypted("-----BEGIN AGE ENCRYPTED FILE-----\nabc\n"));
+    }
+
+    #[test]
+    fn plaintext_documents() {
+        assert!(!is_encrypted("password: hunter2\n"));
+        // Values alone, without SOPS metadata, don't count
+        assert!(!is_encrypted("password: ENC[AES256_GCM,data:abc]\n"));
+    }
+
+    #[test]
+    fn creation_rule_paths() {
+        let config = "creation_rules:\n  - path_regex: \"secrets/.*\\\\.yaml$\"\n    age: age1xyz\n  - path_regex: '\\.env$'\n  - path_regex: prod/.*\n  - age: age1abc\n";
+        assert_eq!(
+            path_regexes(config),
+            ["secrets/.*\\.yaml$", "\\.env$", "prod/.*"]
+        );
+    }
+}
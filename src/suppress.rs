@@ -0,0 +1,319 @@
+use crate::glob;
+use crate::report::check_matches;
+use crate::scanner::{Issue, ScanError};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const IGNORE_FILE: &str = ".repomedicignore";
+
+/// Marker recognised in source comments, e.g. `# repomedic:ignore` or
+/// `// repomedic:ignore potential-secret`.
+pub const INLINE_MARKER: &str = "repomedic:ignore";
+
+/// A single suppression rule.
+///
+/// Every part is optional: `check` alone silences a check everywhere, a
+/// `path` narrows it to matching files, and a `line` to one location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub check: Option<String>,
+    pub path: Option<String>,
+    pub line: Option<usize>,
+    /// Where the rule came from, e.g. `.repomedicignore:3` or `config.json:41`.
+    pub origin: String,
+    /// Last day the rule applies, `YYYY-MM-DD`; afterwards its findings
+    /// are reported again.
+    pub until: Option<String>,
+    /// Who owns removing the suppression.
+    pub owner: Option<String>,
+    /// Why the findings are accepted (the line's `#` comment).
+    pub reason: Option<String>,
+}
+
+impl Rule {
+    pub fn matches(&self, issue: &Issue) -> bool {
+        if let Some(check) = &self.check {
+            if !check_matches(check, issue.id()) {
+                return false;
+            }
+        }
+        if let Some(pattern) = &self.path {
+            match &issue.file {
+                Some(file) if glob::path_match(pattern, file) => {}
+                _ => return false,
+            }
+        }
+        match self.line {
+            Some(line) => issue.line == Some(line),
+            None => true,
+        }
+    }
+
+    /// Whether the rule's `until` date is before `today` (`YYYY-MM-DD`).
+    pub fn expired(&self, today: &str) -> bool {
+        self.until.as_deref().is_some_and(|until| until < today)
+    }
+
+    /// What the rule silences, as written: `check [path[:line]]`.
+    pub fn target(&self) -> String {
+        let mut target = self.check.clone().unwrap_or_else(|| "*".to_string());
+        if let Some(path) = &self.path {
+            target.push(' ');
+            target.push_str(path);
+            if let Some(line) = self.line {
+                target.push_str(&format!(":{}", line));
+            }
+        }
+        target
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    pub rules: Vec<Rule>,
+    /// Rules past their `until` date, which no longer silence anything.
+    pub expired: Vec<Rule>,
+}
+
+/// A suppression or baseline entry past its `until` date.
+#[derive(Debug, Clone, Serialize)]
+pub struct Debt {
+    /// Where it is written, e.g. `.repomedicignore:3` or the baseline file.
+    pub origin: String,
+    /// What it silenced: the ignore-file rule or the baseline fingerprint.
+    pub target: String,
+    pub until: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Current findings it would still silence, now reported again.
+    pub findings: usize,
+}
+
+impl Suppressions {
+    /// Load `.repomedicignore` from the repository root.
+    ///
+    /// A missing file means no suppressions; malformed lines are skipped and
+    /// returned as warnings.
+    pub fn load(root: &Path) -> (Self, Vec<ScanError>) {
+        match fs::read_to_string(root.join(IGNORE_FILE)) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => (Self::default(), Vec::new()),
+            Err(e) => (Self::default(), vec![ScanError::io(IGNORE_FILE, &e)]),
+        }
+    }
+
+    /// Parse ignore-file lines of the form
+    /// `<check-id|*> [path[:line]] [until=YYYY-MM-DD] [owner=NAME] [# reason]`.
+    pub fn parse(content: &str) -> (Self, Vec<ScanError>) {
+        let mut rules = Vec::new();
+        let mut warnings = Vec::new();
+        for (n, raw) in content.lines().enumerate() {
+            let (line, comment) = raw.split_once('#').unwrap_or((raw, ""));
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let check = parts.next().unwrap_or("*");
+            let mut location = None;
+            let (mut until, mut owner) = (None, None);
+            let mut problem = None;
+            for part in parts {
+                match part.split_once('=') {
+                    Some(("until", date)) if is_date(date) => until = Some(date.to_string()),
+                    Some(("until", date)) => {
+                        problem = Some(format!("until '{}' is not a YYYY-MM-DD date", date))
+                    }
+                    Some(("owner", name)) if !name.is_empty() => owner = Some(name.to_string()),
+                    _ if location.is_none() && !part.contains('=') => location = Some(part),
+                    _ => problem = Some("expected `<check> [path[:line]]`".to_string()),
+                }
+            }
+            if let Some(problem) = problem {
+                warnings.push(ScanError::parse(
+                    IGNORE_FILE,
+                    format!("line {}: {}", n + 1, problem),
+                ));
+                continue;
+            }
+            let (path, line_no) = match location.map(split_location) {
+                Some((p, l)) => (Some(p.to_string()), l),
+                None => (None, None),
+            };
+            let reason = comment.trim();
+            rules.push(Rule {
+                check: (check != "*").then(|| check.to_string()),
+                path,
+                line: line_no,
+                origin: format!("{}:{}", IGNORE_FILE, n + 1),
+                until,
+                owner,
+                reason: (!reason.is_empty()).then(|| reason.to_string()),
+            });
+        }
+        (
+            Self {
+                rules,
+                expired: Vec::new(),
+            },
+            warnings,
+        )
+    }
+
+    /// Move rules whose `until` date is before `today` to `expired`.
+    pub fn expire(&mut self, today: &str) {
+        let (expired, rules) = self.rules.drain(..).partition(|r| r.expired(today));
+        self.rules = rules;
+        self.expired = expired;
+    }
+
+    pub fn push(&mut self, rule: Rule) {
+        self.rules.push(rule);
+    }
+
+    /// First rule that suppresses the issue, if any.
+    pub fn find(&self, issue: &Issue) -> Option<&Rule> {
+        self.rules.iter().find(|r| r.matches(issue))
+    }
+}
+
+/// Inline suppression for line `line` (1-based) of a file's `lines`.
+///
+/// The marker may sit on the flagged line itself or on the line above. An
+/// optional check ID after the marker limits it to that check.
+pub fn inline_rule(file: &str, lines: &[&str], line: usize) -> Option<Rule> {
+    for at in [line, line.saturating_sub(1)] {
+        let Some(text) = at.checked_sub(1).and_then(|i| lines.get(i)) else {
+            continue;
+        };
+        let Some(idx) = text.find(INLINE_MARKER) else {
+            continue;
+        };
+        let check = text[idx + INLINE_MARKER.len()..]
+            .split_whitespace()
+            .next()
+            .filter(|c| {
+                c.chars()
+                    .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '-')
+            });
+        return Some(Rule {
+            check: check.map(|c| c.to_string()),
+            path: Some(file.to_string()),
+            line: Some(line),
+            origin: format!("{}:{}", file, at),
+            until: None,
+            owner: None,
+            reason: None,
+        });
+    }
+    None
+}
+
+/// Whether `s` looks like a `YYYY-MM-DD` date.
+pub fn is_date(s: &str) -> bool {
+    s.len() == 10
+        && s.char_indices().all(|(i, c)| match i {
+            4 | 7 => c == '-',
+            _ => c.is_ascii_digit(),
+        })
+}
+
+/// Split `path:line` into its parts; anything else is a bare path.
+fn split_location(location: &str) -> (&str, Option<usize>) {
+    match location.rsplit_once(':') {
+        Some((path, line)) => match line.parse() {
+            Ok(n) => (path, Some(n)),
+            Err(_) => (location, None),
+        },
+        None => (location, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{inline_rule, is_date, Suppressions};
+    use crate::scanner::Issue;
+
+    fn large_file(path: &str, line: Option<usize>) -> Issue {
+        Issue::warning(
+            "large-file",
+            format!("Large file detected (>10MB): {}", path),
+        )
+        .at(path, line)
+    }
+
+    #[test]
+    fn parses_rules_and_metadata() {
+        let (ignore, warnings) = Suppressions::parse(
+            "# header\nlarge-file assets/*.bin until=2030-01-01 owner=@ops # design assets\n\
+             * vendor/lib.js:12\nmissing-changelog\n",
+        );
+        assert!(warnings.is_empty());
+        let rule = &ignore.rules[0];
+        assert_eq!(rule.check.as_deref(), Some("large-file"));
+        assert_eq!(rule.path.as_deref(), Some("assets/*.bin"));
+        assert_eq!(rule.until.as_deref(), Some("2030-01-01"));
+        assert_eq!(rule.owner.as_deref(), Some("@ops"));
+        assert_eq!(rule.reason.as_deref(), Some("design assets"));
+        assert_eq!(rule.origin, ".repomedicignore:2");
+        assert_eq!(ignore.rules[1].check, None);
+        assert_eq!(ignore.rules[1].line, Some(12));
+        assert_eq!(ignore.rules[1].target(), "* vendor/lib.js:12");
+    }
+
+    #[test]
+    fn malformed_lines_warn() {
+        let (ignore, warnings) =
+            Suppressions::parse("large-file a b\nlarge-file until=soon\nmissing-readme\n");
+        assert_eq!(ignore.rules.len(), 1);
+        let messages: Vec<_> = warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "line 1: expected `<check> [path[:line]]`",
+                "line 2: until 'soon' is not a YYYY-MM-DD date",
+            ]
+        );
+    }
+
+    #[test]
+    fn matching_and_expiry() {
+        let (mut ignore, _) = Suppressions::parse(
+            "large-file assets/*.bin\nlarge-file docs/big.pdf:3 until=2020-01-01\n",
+        );
+        assert!(ignore.find(&large_file("assets/a.bin", None)).is_some());
+        assert!(ignore.find(&large_file("src/a.bin", None)).is_none());
+        assert!(ignore.find(&large_file("docs/big.pdf", Some(3))).is_some());
+        assert!(ignore.find(&large_file("docs/big.pdf", Some(4))).is_none());
+        ignore.expire("2024-06-01");
+        assert_eq!(ignore.expired.len(), 1);
+        assert!(ignore.find(&large_file("docs/big.pdf", Some(3))).is_none());
+    }
+
+    #[test]
+    fn inline_markers() {
+        let lines = [
+            "let a = 1;",
+            "// repomedic:ignore potential-secret",
+            "let key = \"...\";",
+            "let b = 2; # repomedic:ignore",
+        ];
+        let above = inline_rule("src/a.rs", &lines, 3).expect("marker on the line above");
+        assert_eq!(above.check.as_deref(), Some("potential-secret"));
+        assert_eq!(above.line, Some(3));
+        assert_eq!(above.origin, "src/a.rs:2");
+        let same = inline_rule("src/a.rs", &lines, 4).expect("marker on the line");
+        assert_eq!(same.check, None);
+        assert!(inline_rule("src/a.rs", &lines, 1).is_none());
+    }
+
+    #[test]
+    fn helpers() {
+        assert!(is_date("2025-09-01"));
+        assert!(!is_date("2025-9-01"));
+    }
+}
@@ -0,0 +1,326 @@
+use crate::yaml::{self, Node};
+use serde::Serialize;
+
+/// Directory holding issue templates and forms.
+pub const ISSUE_DIR: &str = ".github/ISSUE_TEMPLATE";
+
+/// Directory holding multiple pull request templates.
+pub const PR_DIR: &str = ".github/PULL_REQUEST_TEMPLATE";
+
+/// Single-file issue templates.
+pub const ISSUE_FILES: &[&str] = &[".github/ISSUE_TEMPLATE.md", "ISSUE_TEMPLATE.md"];
+
+/// Single-file pull request templates. GitHub also accepts lowercase names.
+pub const PR_FILES: &[&str] = &[
+    ".github/PULL_REQUEST_TEMPLATE.md",
+    ".github/pull_request_template.md",
+    "PULL_REQUEST_TEMPLATE.md",
+    "pull_request_template.md",
+    "docs/PULL_REQUEST_TEMPLATE.md",
+    "docs/pull_request_template.md",
+];
+
+/// Body element types allowed in an issue form.
+const FORM_TYPES: &[&str] = &["markdown", "textarea", "input", "dropdown", "checkboxes"];
+
+/// A problem in an issue form or template.
+#[derive(Debug, Clone, Serialize)]
+pub struct Problem {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Validate an issue form (`.github/ISSUE_TEMPLATE/*.yml`) against GitHub's
+/// schema: top-level `name`, `description`, and `body`; body elements with
+/// a known `type`, the attributes it needs, and unique `id`s. Required
+/// fields without a `description` are flagged too, as are placeholders.
+pub fn validate_form(file: &str, content: &str) -> Vec<Problem> {
+    let mut problems = placeholders(file, content);
+    let mut report = |line: usize, message: String| {
+        problems.push(Problem {
+            file: file.to_string(),
+            line: Some(line),
+            message,
+        })
+    };
+    let root = match yaml::parse(content) {
+        Ok(root) => root,
+        Err(e) => {
+            let (line, message) = split_line(&e);
+            report(line.unwrap_or(1), format!("invalid YAML: {}", message));
+            return problems;
+        }
+    };
+    for key in ["name", "description"] {
+        if !root.get(key).is_some_and(Node::is_text) {
+            report(root.line, format!("missing top-level `{}`", key));
+        }
+    }
+    let Some(body) = root.get("body") else {
+        report(root.line, "missing top-level `body`".to_string());
+        return problems;
+    };
+    let Some(elements) = body.as_seq().filter(|b| !b.is_empty()) else {
+        report(body.line, "`body` must be a non-empty list".to_string());
+        return problems;
+    };
+    let mut ids: Vec<&str> = Vec::new();
+    for (i, element) in elements.iter().enumerate() {
+        let at = format!("body[{}]", i);
+        let Some(kind) = element.get("type").and_then(Node::as_str) else {
+            report(element.line, format!("{} has no `type`", at));
+            continue;
+        };
+        if !FORM_TYPES.contains(&kind) {
+            report(element.line, format!("{} has unknown type `{}`", at, kind));
+            continue;
+        }
+        if let Some(id) = element.get("id") {
+            match id.as_str() {
+                Some(s) if !is_valid_id(s) => report(
+                    id.line,
+                    format!("{} id `{}` may only use letters, digits, `-`, `_`", at, s),
+                ),
+                Some(s) if ids.contains(&s) => {
+                    report(id.line, format!("{} repeats id `{}`", at, s))
+                }
+                Some(s) => ids.push(s),
+                None => report(id.line, format!("{} id must be a string", at)),
+            }
+        }
+        let required = element
+            .get("validations")
+            .and_then(|v| v.get("required"))
+            .is_some_and(Node::is_true);
+        let Some(attributes) = element.get("attributes") else {
+            report(
+                element.line,
+                format!("{} ({}) has no `attributes`", at, kind),
+            );
+            continue;
+        };
+        if kind == "markdown" {
+            if !attributes.get("value").is_some_and(Node::is_text) {
+                report(attributes.line, format!("{} (markdown) needs `value`", at));
+            }
+            if required {
+                report(
+                    element.line,
+                    format!("{} (markdown) can't have `validations`", at),
+                );
+            }
+            continue;
+        }
+        let label = attributes.get("label").and_then(Node::as_str);
+        if label.is_none_or(|l| l.trim().is_empty()) {
+            report(attributes.line, format!("{} ({}) needs `label`", at, kind));
+        }
+        if matches!(kind, "dropdown" | "checkboxes")
+            && attributes
+                .get("options")
+                .and_then(Node::as_seq)
+                .is_none_or(<[Node]>::is_empty)
+        {
+            report(
+                attributes.line,
+                format!("{} ({}) needs a non-empty `options` list", at, kind),
+            );
+        }
+        if required && !attributes.get("description").is_some_and(Node::is_text) {
+            report(
+                attributes.line,
+                format!(
+                    "required field `{}` has no description",
+                    label.unwrap_or(&at)
+                ),
+            );
+        }
+    }
+    problems
+}
+
+/// Validate a Markdown issue or pull request template. Issue templates in
+/// `ISSUE_TEMPLATE/` need `name` and `about` front matter to show up in the
+/// template chooser.
+pub fn validate_markdown(file: &str, content: &str, chooser: bool) -> Vec<Problem> {
+    let mut problems = placeholders(file, content);
+    if content.trim().is_empty() {
+        problems.push(Problem {
+            file: file.to_string(),
+            line: None,
+            message: "template is empty".to_string(),
+        });
+        return problems;
+    }
+    if chooser {
+        let front = front_matter(content);
+        for key in ["name", "about"] {
+            let present = front.as_ref().is_some_and(|f| {
+                f.lines()
+                    .any(|l| l.strip_prefix(key).is_some_and(|r| r.starts_with(':')))
+            });
+            if !present {
+                problems.push(Problem {
+                    file: file.to_string(),
+                    line: Some(1),
+                    message: format!("front matter is missing `{}`", key),
+                });
+            }
+        }
+    }
+    problems
+}
+
+/// `<!-- TODO ... -->` comments left in a template.
+fn placeholders(file: &str, content: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let mut rest = line;
+        while let Some(start) = rest.find("<!--") {
+            let comment = &rest[start + 4..];
+            let end = comment.find("-->").unwrap_or(comment.len());
+            let text = comment[..end].trim();
+            if text.to_uppercase().starts_with("TODO") || text.contains("FIXME") {
+                problems.push(Problem {
+                    file: file.to_string(),
+                    line: Some(i + 1),
+                    message: format!("placeholder left in template: <!-- {} -->", text),
+                });
+            }
+            rest = &comment[end..];
+        }
+    }
+    problems
+}
+
+/// YAML front matter between leading `---` lines.
+fn front_matter(content: &str) -> Option<String> {
+    let rest = content.trim_start().strip_prefix("---")?;
+    let end = rest.find("\n---")?;
+    Some(rest[..end].to_string())
+}
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Split a `line N: message` parse error.
+fn split_line(error: &str) -> (Option<usize>, &str) {
+    error
+        .strip_prefix("line ")
+        .and_then(|r| r.split_once(": "))
+        .and_then(|(n, m)| Some((Some(n.parse().ok()?), m)))
+        .unwrap_or((None, error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_form, validate_markdown, Problem};
+
+    fn messages(problems: Vec<Problem>) -> Vec<(Option<usize>, String)> {
+        problems.into_iter().map(|p| (p.line, p.message)).collect()
+    }
+
+    #[test]
+    fn valid_form() {
+        let form = "\
+name: Bug report
+description: Something broke
+body:
+  - type: markdown
+    attributes:
+      value: Thanks for reporting!
+  - type: textarea
+    id: what-happened
+    attributes:
+      label: What happened?
+      description: Include the output.
+    validations:
+      required: true
+  - type: dropdown
+    id: os
+    attributes:
+      label: OS
+      options: [Linux, macOS]
+";
+        assert!(validate_form("bug.yml", form).is_empty());
+    }
+
+    #[test]
+    fn invalid_form() {
+        let form = "\
+name: Bug report
+body:
+  - type: textbox
+  - type: input
+    id: version
+    attributes:
+      label: Version
+      description: <!-- TODO: explain -->
+  - type: checkboxes
+    id: version
+    attributes:
+      label: Terms
+  - type: input
+    id: has space
+    attributes:
+      label: ''
+    validations:
+      required: true
+";
+        assert_eq!(
+            messages(validate_form("bug.yml", form)),
+            [
+                (
+                    Some(8),
+                    "placeholder left in template: <!-- TODO: explain -->".to_string()
+                ),
+                (Some(1), "missing top-level `description`".to_string()),
+                (Some(3), "body[0] has unknown type `textbox`".to_string()),
+                (Some(10), "body[2] repeats id `version`".to_string()),
+                (
+                    Some(12),
+                    "body[2] (checkboxes) needs a non-empty `options` list".to_string()
+                ),
+                (
+                    Some(14),
+                    "body[3] id `has space` may only use letters, digits, `-`, `_`".to_string()
+                ),
+                (Some(16), "body[3] (input) needs `label`".to_string()),
+                (Some(16), "required field `` has no description".to_string()),
+            ]
+        );
+        assert_eq!(
+            messages(validate_form("bad.yml", "name: x\n\tbody:\n")),
+            [(
+                Some(2),
+                "invalid YAML: tabs are not allowed in indentation".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn markdown_templates() {
+        let chooser = "---\nname: Feature\nabout: Suggest an idea\n---\n\n## Idea\n";
+        assert!(validate_markdown("feature.md", chooser, true).is_empty());
+        assert_eq!(
+            messages(validate_markdown(
+                "bug.md",
+                "---\nname: Bug\n---\nSteps\n",
+                true
+            )),
+            [(Some(1), "front matter is missing `about`".to_string())]
+        );
+        // Pull request templates have no chooser front matter
+        assert!(validate_markdown("PULL_REQUEST_TEMPLATE.md", "## Summary\n", false).is_empty());
+        assert_eq!(
+            messages(validate_markdown("empty.md", "  \n", false)),
+            [(None, "template is empty".to_string())]
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use crate::docker;
+use serde::Serialize;
+
+/// Files besides CI configs where a `terraform fmt` check usually lives.
+pub const FMT_CHECK_FILES: &[&str] = &[
+    ".pre-commit-config.yaml",
+    "Makefile",
+    "justfile",
+    "Taskfile.yml",
+    ".tflint.hcl",
+];
+
+/// What a `terraform fmt` check looks like in those files: the command
+/// (also OpenTofu's) or the pre-commit-terraform hook.
+const FMT_MARKERS: &[&str] = &["terraform fmt", "tofu fmt", "terraform_fmt", "tofu_fmt"];
+
+/// A Terraform setup problem.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum TerraformProblem {
+    /// A `.tfstate` file, with every resource attribute in plaintext.
+    StateFile { file: String },
+    /// A root module using providers without `.terraform.lock.hcl`.
+    MissingLockFile { dir: String },
+    /// Providers used without a version constraint.
+    UnpinnedProviders { dir: String, providers: Vec<String> },
+    /// A `.tfvars` assignment to a credential-looking variable.
+    SecretInTfvars {
+        file: String,
+        line: usize,
+        key: String,
+    },
+    /// Neither CI nor a pre-commit hook runs `terraform fmt`.
+    NoFmtCheck,
+}
+
+impl TerraformProblem {
+    pub fn file(&self) -> Option<&str> {
+        match self {
+            TerraformProblem::StateFile { file }
+            | TerraformProblem::SecretInTfvars { file, .. } => Some(file),
+            _ => None,
+        }
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            TerraformProblem::SecretInTfvars { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+}
+
+/// `terraform.tfstate`, `prod.tfstate`, and their `.backup` copies.
+pub fn is_state_file(rel: &str) -> bool {
+    rel.ends_with(".tfstate") || rel.ends_with(".tfstate.backup")
+}
+
+/// `terraform.tfvars`, `*.auto.tfvars`, and their JSON forms; examples
+/// (`*.tfvars.example`) don't count.
+pub fn is_tfvars(rel: &str) -> bool {
+    rel.ends_with(".tfvars") || rel.ends_with(".tfvars.json")
+}
+
+/// Whether a module directory is a root module, one that gets `terraform
+/// init` and a lock file, rather than a reusable one under `modules/`.
+pub fn is_root_module(dir: &str) -> bool {
+    !dir.split('/').any(|part| part == "modules")
+}
+
+/// Whether `content` runs `terraform fmt`.
+pub fn has_fmt_check(content: &str) -> bool {
+    FMT_MARKERS.iter().any(|m| content.contains(m))
+}
+
+/// Providers a module's `.tf` files use, and whether each is pinned.
+#[derive(Debug, Default)]
+pub struct Providers {
+    /// `(name, pinned)`: `required_providers` entries and `provider`
+    /// blocks (pinned by a legacy `version` argument).
+    declared: Vec<(String, bool)>,
+    /// Providers implied by `resource` and `data` types (`aws_instance`).
+    implied: Vec<String>,
+}
+
+impl Providers {
+    /// Add what one `.tf` file declares and uses.
+    pub fn read(&mut self, content: &str) {
+        let mut depth = 0usize;
+        // Depth inside `required_providers { ... }`
+        let mut required: Option<usize> = None;
+        // The provider whose entry or block we're in, and its depth
+        let mut current: Option<(usize, usize)> = None;
+        for raw in content.lines() {
+            let line = raw.trim();
+            if line.starts_with('#') || line.starts_with("//") {
+                continue;
+            }
+            let opens = line.matches('{').count();
+            let closes = line.matches('}').count();
+            if line.starts_with("required_providers") && opens > 0 {
+                required = Some(depth + 1);
+            } else if required == Some(depth) && current.is_none() {
+                if let Some((name, value)) = line.split_once('=') {
+                    let value = value.trim();
+                    // `aws = "~> 2.0"` is the pre-0.13 version-only form
+                    let pinned = value.starts_with('"') || value.contains("version");
+                    self.declare(name.trim(), pinned);
+                    if opens > closes {
+                        current = Some((self.declared.len() - 1, depth + 1));
+                    }
+                }
+            } else if let Some(rest) = line.strip_prefix("provider ") {
+                if depth == 0 {
+                    let name = rest.split('"').nth(1).unwrap_or_default();
+                    let pinned = line.contains("version");
+                    self.declare(name, pinned);
+                    if opens > closes {
+                        current = Some((self.declared.len() - 1, depth + 1));
+                    }
+                }
+            } else if depth == 0 && (line.starts_with("resource ") || line.starts_with("data ")) {
+                let kind = line.split('"').nth(1).unwrap_or_default();
+                if let Some((provider, _)) = kind.split_once('_') {
+                    if !self.implied.iter().any(|p| p == provider) {
+                        self.implied.push(provider.to_string());
+                    }
+                }
+            } else if let Some((index, _)) = current {
+                if line.starts_with("version") {
+                    self.declared[index].1 = true;
+                }
+            }
+            depth = (depth + opens).saturating_sub(closes);
+            if current.is_some_and(|(_, at)| depth < at) {
+                current = None;
+            }
+            if required.is_some_and(|at| depth < at) {
+                required = None;
+            }
+        }
+    }
+
+    fn declare(&mut self, name: &str, pinned: bool) {
+        if name.is_empty() {
+            return;
+        }
+        match self.declared.iter_mut().find(|(n, _)| n == name) {
+            Some((_, p)) => *p |= pinned,
+            None => self.declared.push((name.to_string(), pinned)),
+        }
+    }
+
+    /// Whether the module uses any provider.
+    pub fn any(&self) -> bool {
+        !self.declared.is_empty() || !self.implied.is_empty()
+    }
+
+    /// Providers no version constraint covers, sorted.
+    pub fn unpinned(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .declared
+            .iter()
+            .filter(|(_, pinned)| !pinned)
+            .map(|(n, _)| n.clone())
+            .chain(
+                self.implied
+                    .iter()
+                    .filter(|p| !self.declared.iter().any(|(n, _)| n == *p))
+                    // Built into Terraform, nothing to pin
+                    .filter(|p| *p != "terraform")
+                    .cloned(),
+            )
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// Assignments in a `.tfvars` (or `.tfvars.json`) file to credential-looking
+/// variables with a literal value. The value is never kept.
+pub fn tfvars_secrets(file: &str, content: &str) -> Vec<TerraformProblem> {
+    let mut problems = Vec::new();
+    for (i, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.starts_with('#') || line.starts_with("//") {
+            continue;
+        }
+        let pair = if file.ends_with(".json") {
+            line.split_once(':')
+        } else {
+            line.split_once('=')
+        };
+        let Some((key, value)) = pair else {
+            continue;
+        };
+        let key = key.trim().trim_matches('"');
+        let value = value.trim().trim_end_matches(',').trim();
+        let quoted = value.len() > 2 && value.starts_with('"') && value.ends_with('"');
+        if quoted && docker::is_secret_key(key) && docker::is_literal(value.trim_matches('"')) {
+            problems.push(TerraformProblem::SecretInTfvars {
+                file: file.to_string(),
+                line: i + 1,
+                key: key.to_string(),
+            });
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        has_fmt_check, is_root_module, is_state_file, is_tfvars, tfvars_secrets, Providers,
+    };
+
+    fn unpinned(files: &[&str]) -> Vec<String> {
+        let mut providers = Providers::default();
+        for content in files {
+            providers.read(content);
+        }
+        providers.unpinned()
+    }
+
+    #[test]
+    fn file_kinds() {
+        assert!(is_state_file("envs/prod/terraform.tfstate"));
+        assert!(is_state_file("terraform.tfstate.backup"));
+        assert!(is_tfvars("prod.auto.tfvars"));
+        assert!(is_tfvars("terraform.tfvars.json"));
+        assert!(!is_tfvars("terraform.tfvars.example"));
+        assert!(is_root_module("infra/prod"));
+        assert!(!is_root_module("infra/modules/vpc"));
+        assert!(has_fmt_check("- id: terraform_fmt\n"));
+        assert!(!has_fmt_check("terraform plan\n"));
+    }
+
+    #[test]
+    fn required_providers() {
+        let versions = r#"
+terraform {
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+    random = {
+      source = "hashicorp/random"
+    }
+    google = "~> 4.0"
+  }
+}
+"#;
+        assert_eq!(unpinned(&[versions]), ["random"]);
+    }
+
+    #[test]
+    fn implied_and_legacy_providers() {
+        let main = r#"
+provider "azurerm" {
+  features {}
+}
+
+provider "null" {
+  version = "3.2.0"
+}
+
+resource "aws_s3_bucket" "logs" {
+  bucket = "logs"
+}
+
+data "terraform_remote_state" "net" {}
+"#;
+        assert_eq!(unpinned(&[main]), ["aws", "azurerm"]);
+        let mut providers = Providers::default();
+        assert!(!providers.any());
+        providers.read(main);
+        assert!(providers.any());
+    }
+
+    #[test]
+    fn tfvars_credentials() {
+        let tfvars = "region = \"eu-west-1\"\ndb_password = \"hunter2\"\napi_token = \"\"\n# admin_password = \"old\"\nsecret_name = \"prod/db\"\n";
+        let lines: Vec<_> = tfvars_secrets("prod.tfvars", tfvars)
+            .iter()
+            .filter_map(|p| p.line())
+            .collect();
+        assert_eq!(lines, [2]);
+        let json = "{\n  \"db_password\": \"hunter2\",\n  \"region\": \"eu-west-1\"\n}\n";
+        let problems = tfvars_secrets("prod.tfvars.json", json);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].file(), Some("prod.tfvars.json"));
+    }
+}
@@ -0,0 +1,515 @@
+use crate::detector::Category;
+use crate::fix::{self, Fix, FixOptions};
+use crate::report::{self, check_category};
+use crate::scanner::{Issue, ScanError, ScanResult, Severity};
+use crate::suppress::IGNORE_FILE;
+use std::fs::OpenOptions;
+use std::io::{self, IsTerminal, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Keys listed in the footer.
+const HELP: &str = "1-4/Tab pane  j/k move  Enter apply  a apply all  s suppress  r rescan  q quit";
+
+/// Order issues are grouped in on the Issues pane.
+const CATEGORIES: [Category; 6] = [
+    Category::Security,
+    Category::Ci,
+    Category::Documentation,
+    Category::Community,
+    Category::Project,
+    Category::Files,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pane {
+    Score,
+    Issues,
+    Actions,
+    Secrets,
+}
+
+impl Pane {
+    pub const ALL: [Pane; 4] = [Pane::Score, Pane::Issues, Pane::Actions, Pane::Secrets];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Pane::Score => "Score",
+            Pane::Issues => "Issues",
+            Pane::Actions => "Actions",
+            Pane::Secrets => "Secrets",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Pane::ALL.iter().position(|p| p == self).unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Up,
+    Down,
+    Left,
+    Right,
+    Enter,
+    Tab,
+    BackTab,
+    Other,
+}
+
+/// What a key press asks the dashboard to do outside of redrawing.
+enum Action {
+    None,
+    Quit,
+    Rescan,
+    /// Apply these fixes, then rescan.
+    Apply(Vec<Fix>),
+    /// Append a suppression line to `.repomedicignore`, then rescan.
+    Suppress(String),
+}
+
+/// State of the dashboard between key presses.
+pub struct Dashboard {
+    result: ScanResult,
+    /// Issues ordered by `CATEGORIES`.
+    issues: Vec<(Category, Issue)>,
+    fixes: Vec<Fix>,
+    pane: Pane,
+    selected: [usize; 4],
+    status: String,
+}
+
+impl Dashboard {
+    pub fn new(root: &Path, result: ScanResult) -> Self {
+        let mut dashboard = Dashboard {
+            result: ScanResult::default(),
+            issues: Vec::new(),
+            fixes: Vec::new(),
+            pane: Pane::Score,
+            selected: [0; 4],
+            status: String::new(),
+        };
+        dashboard.update(root, result);
+        dashboard
+    }
+
+    /// Replace the scan result, keeping the pane and clamping selections.
+    pub fn update(&mut self, root: &Path, result: ScanResult) {
+        let mut issues: Vec<(Category, Issue)> = report::collect_issues(&result)
+            .into_iter()
+            .map(|i| (check_category(i.id()), i))
+            .collect();
+        issues.sort_by_key(|(c, _)| CATEGORIES.iter().position(|x| x == c));
+        self.fixes = fix::plan(root, &result);
+        self.issues = issues;
+        self.result = result;
+        for pane in Pane::ALL {
+            let len = self.len(pane);
+            let sel = &mut self.selected[pane.index()];
+            *sel = (*sel).min(len.saturating_sub(1));
+        }
+    }
+
+    fn secrets(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().map(|(_, i)| i).filter(|i| is_secret(i))
+    }
+
+    /// Number of selectable rows on a pane.
+    fn len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Score => 0,
+            Pane::Issues => self.issues.len(),
+            Pane::Actions => self.fixes.len(),
+            Pane::Secrets => self.secrets().count(),
+        }
+    }
+
+    fn handle(&mut self, key: Key) -> Action {
+        let sel = self.selected[self.pane.index()];
+        let len = self.len(self.pane);
+        let switch = |pane: usize| Pane::ALL[pane % Pane::ALL.len()];
+        self.status.clear();
+        match key {
+            Key::Char('q') => return Action::Quit,
+            Key::Char('r') => return Action::Rescan,
+            Key::Char(c @ '1'..='4') => self.pane = switch(c as usize - '1' as usize),
+            Key::Tab | Key::Right | Key::Char('l') => self.pane = switch(self.pane.index() + 1),
+            Key::BackTab | Key::Left | Key::Char('h') => {
+                self.pane = switch(self.pane.index() + Pane::ALL.len() - 1)
+            }
+            Key::Down | Key::Char('j') if sel + 1 < len => self.selected[self.pane.index()] += 1,
+            Key::Up | Key::Char('k') => self.selected[self.pane.index()] = sel.saturating_sub(1),
+            Key::Char('a') if !self.fixes.is_empty() => {
+                return Action::Apply(self.fixes.clone());
+            }
+            Key::Char('a') => self.status = "Nothing to fix.".to_string(),
+            Key::Enter if self.pane == Pane::Actions && sel < len => {
+                return Action::Apply(vec![self.fixes[sel].clone()]);
+            }
+            Key::Enter if self.pane == Pane::Issues && sel < len => {
+                self.status = describe(&self.issues[sel].1);
+            }
+            Key::Enter if self.pane == Pane::Secrets && sel < len => {
+                let status = self.secrets().nth(sel).map(describe);
+                self.status = status.unwrap_or_default();
+            }
+            Key::Char('s') if matches!(self.pane, Pane::Issues | Pane::Secrets) && sel < len => {
+                let issue = match self.pane {
+                    Pane::Issues => Some(&self.issues[sel].1),
+                    _ => self.secrets().nth(sel),
+                };
+                if let Some(issue) = issue {
+                    return Action::Suppress(suppression_line(issue));
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    /// Render the whole screen as lines no wider than `cols`.
+    fn render(&self, rows: usize, cols: usize, color: bool) -> String {
+        let score = report::calculate_score(&self.result);
+        let mut header = vec![(
+            format!(
+                "RepoMedic  {}/100 ({})  {} issue(s)  {} fix(es) available",
+                score,
+                report::score_grade(score),
+                self.issues.len(),
+                self.fixes.len()
+            ),
+            "1",
+        )];
+        let tabs: Vec<String> = Pane::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let label = match p {
+                    Pane::Score => format!("{} {}", i + 1, p.title()),
+                    _ => format!("{} {} ({})", i + 1, p.title(), self.len(*p)),
+                };
+                if *p == self.pane {
+                    format!("[{}]", label)
+                } else {
+                    format!(" {} ", label)
+                }
+            })
+            .collect();
+        header.push((tabs.join(" "), ""));
+        header.push((String::new(), ""));
+
+        let (body, cursor) = self.body(color);
+        let height = rows.saturating_sub(header.len() + 2).max(1);
+        let start = cursor.map_or(0, |c| (c + 1).saturating_sub(height));
+
+        let mut out = String::from("\x1b[H\x1b[2J");
+        let footer = if self.status.is_empty() {
+            HELP.to_string()
+        } else {
+            self.status.clone()
+        };
+        let lines = header
+            .iter()
+            .map(|(t, s)| (t.as_str(), *s))
+            .chain(
+                body.iter()
+                    .skip(start)
+                    .take(height)
+                    .map(|(t, s)| (t.as_str(), *s)),
+            )
+            .chain(std::iter::once(("", "")))
+            .chain(std::iter::once((footer.as_str(), "2")));
+        let lines: Vec<String> = lines
+            .map(|(text, style)| {
+                let text: String = text.chars().take(cols).collect();
+                if color && !style.is_empty() {
+                    format!("\x1b[{}m{}\x1b[0m", style, text)
+                } else {
+                    text
+                }
+            })
+            .collect();
+        // No newline after the last line, which would scroll the screen
+        out.push_str(&lines.join("\r\n"));
+        out
+    }
+
+    /// Lines of the current pane with their SGR style, and the selected line.
+    fn body(&self, color: bool) -> (Vec<(String, &'static str)>, Option<usize>) {
+        let sel = self.selected[self.pane.index()];
+        let marker = |i: usize| if i == sel { "> " } else { "  " };
+        let style = |i: usize, base: &'static str| if i == sel { "7" } else { base };
+        let mut lines: Vec<(String, &'static str)> = Vec::new();
+        let mut cursor = None;
+        match self.pane {
+            Pane::Score => {
+                let score = report::calculate_score(&self.result);
+                lines.push((format!("Health score: {}/100", score), ""));
+                lines.push((String::new(), ""));
+                for c in report::category_scores(&self.result) {
+                    let filled = c.score as usize / 5;
+                    let bar = format!("{}{}", "#".repeat(filled), ".".repeat(20 - filled));
+                    let code = if color {
+                        report::score_color(c.score)
+                    } else {
+                        ""
+                    };
+                    lines.push((format!("  {:18} {:>3}  {}", c.name, c.score, bar), code));
+                }
+                lines.push((String::new(), ""));
+                lines.push((
+                    format!(
+                        "{} file(s) scanned, {} dir(s) traversed in {}ms",
+                        self.result.scan_stats.files_scanned,
+                        self.result.scan_stats.dirs_traversed,
+                        self.result.scan_stats.scan_duration_ms
+                    ),
+                    "2",
+                ));
+            }
+            Pane::Issues => {
+                if self.issues.is_empty() {
+                    lines.push(("No issues found.".to_string(), "32"));
+                }
+                let mut current = None;
+                for (i, (category, issue)) in self.issues.iter().enumerate() {
+                    if current != Some(*category) {
+                        current = Some(*category);
+                        lines.push((category.name().to_uppercase(), "1"));
+                    }
+                    if i == sel {
+                        cursor = Some(lines.len());
+                    }
+                    lines.push((
+                        format!("{}{}", marker(i), issue_line(issue)),
+                        style(i, severity_style(issue.severity)),
+                    ));
+                }
+            }
+            Pane::Actions => {
+                if self.fixes.is_empty() {
+                    lines.push(("Nothing to fix.".to_string(), "32"));
+                }
+                for (i, fix) in self.fixes.iter().enumerate() {
+                    if i == sel {
+                        cursor = Some(lines.len());
+                    }
+                    let note = if fix.needs_confirmation() {
+                        "  (changes git state)"
+                    } else {
+                        ""
+                    };
+                    lines.push((
+                        format!("{}{}{}", marker(i), fix.description, note),
+                        style(i, ""),
+                    ));
+                }
+            }
+            Pane::Secrets => {
+                if self.len(Pane::Secrets) == 0 {
+                    lines.push(("No secret findings.".to_string(), "32"));
+                }
+                for (i, issue) in self.secrets().enumerate() {
+                    if i == sel {
+                        cursor = Some(lines.len());
+                    }
+                    lines.push((
+                        format!("{}{}", marker(i), issue_line(issue)),
+                        style(i, severity_style(issue.severity)),
+                    ));
+                }
+            }
+        }
+        (lines, cursor)
+    }
+}
+
+/// Run the dashboard until the user quits. `rescan` is called after every
+/// applied fix or suppression and on `r`.
+pub fn run<F>(root: &Path, author: Option<String>, color: bool, mut rescan: F) -> io::Result<()>
+where
+    F: FnMut() -> Result<ScanResult, ScanError>,
+{
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(io::Error::other("tui needs an interactive terminal"));
+    }
+    let scan = |rescan: &mut F| rescan().map_err(|e| io::Error::other(e.to_string()));
+    let mut dashboard = Dashboard::new(root, scan(&mut rescan)?);
+    let mut terminal = Terminal::enter()?;
+    loop {
+        let (rows, cols) = terminal_size();
+        let mut stdout = io::stdout();
+        stdout.write_all(dashboard.render(rows, cols, color).as_bytes())?;
+        stdout.flush()?;
+        match dashboard.handle(read_key()?) {
+            Action::None => {}
+            Action::Quit => return Ok(()),
+            Action::Rescan => {
+                dashboard.update(root, scan(&mut rescan)?);
+                dashboard.status = "Rescanned.".to_string();
+            }
+            Action::Apply(fixes) => {
+                // Fixes print their progress; show it on the normal screen
+                terminal.leave();
+                let opts = FixOptions {
+                    yes: true,
+                    author: author.clone(),
+                    license: dashboard.result.config.license_type,
+                    ..FixOptions::default()
+                };
+                let outcome = fix::apply(root, &dashboard.result, &fixes, &opts);
+                terminal = Terminal::enter()?;
+                dashboard.update(root, scan(&mut rescan)?);
+                dashboard.status = match outcome {
+                    Ok(1) if fixes.len() == 1 => format!("Applied: {}", fixes[0].description),
+                    Ok(n) => format!("{} of {} fix(es) applied.", n, fixes.len()),
+                    Err(e) => format!("Error applying fixes: {}", e),
+                };
+            }
+            Action::Suppress(line) => {
+                let outcome = append_suppression(root, &line);
+                dashboard.update(root, scan(&mut rescan)?);
+                dashboard.status = match outcome {
+                    Ok(()) => format!("Added `{}` to {}.", line, IGNORE_FILE),
+                    Err(e) => format!("Error writing {}: {}", IGNORE_FILE, e),
+                };
+            }
+        }
+    }
+}
+
+fn is_secret(issue: &Issue) -> bool {
+    report::is_secret_check(issue.id())
+}
+
+fn issue_line(issue: &Issue) -> String {
+    let tag = match issue.severity {
+        Severity::Error => "ERR ",
+        Severity::Warning => "WARN",
+        Severity::Info => "INFO",
+    };
+    format!("{} {}", tag, issue.message)
+}
+
+fn severity_style(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "31",
+        Severity::Warning => "33",
+        Severity::Info => "",
+    }
+}
+
+/// One-line detail shown in the footer for a selected issue.
+fn describe(issue: &Issue) -> String {
+    let location = match (&issue.file, issue.line) {
+        (Some(f), Some(l)) => format!(" at {}:{}", f, l),
+        (Some(f), None) => format!(" in {}", f),
+        _ => String::new(),
+    };
+    let id = issue.id();
+    format!("[{}]{} - `repomedic rules explain {}`", id, location, id)
+}
+
+/// `.repomedicignore` line silencing one issue at its location.
+fn suppression_line(issue: &Issue) -> String {
+    let id = issue.id();
+    match (&issue.file, issue.line) {
+        (Some(f), Some(l)) => format!("{} {}:{}", id, f, l),
+        (Some(f), None) => format!("{} {}", id, f),
+        _ => id.to_string(),
+    }
+}
+
+fn append_suppression(root: &Path, line: &str) -> io::Result<()> {
+    let path = root.join(IGNORE_FILE);
+    let existing = std::fs::read(&path).unwrap_or_default();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if !existing.is_empty() && !existing.ends_with(b"\n") {
+        file.write_all(b"\n")?;
+    }
+    writeln!(file, "{}", line)
+}
+
+/// Raw-mode alternate screen, restored when dropped or on `leave`.
+struct Terminal {
+    /// `stty -g` settings to restore.
+    saved: Option<String>,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        let saved = stty(&["-g"])?;
+        // Ctrl+C arrives as a key so the terminal is always restored
+        stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Terminal {
+            saved: Some(saved.trim().to_string()),
+        })
+    }
+
+    fn leave(&mut self) {
+        if let Some(saved) = self.saved.take() {
+            print!("\x1b[?25h\x1b[?1049l");
+            let _ = io::stdout().flush();
+            let _ = stty(&[&saved]);
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        self.leave();
+    }
+}
+
+/// Run `stty` against the controlling terminal, returning its output.
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| io::Error::other(format!("stty unavailable: {}", e)))?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty could not configure the terminal"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `(rows, cols)` of the terminal, 24x80 when unknown.
+fn terminal_size() -> (usize, usize) {
+    let size = stty(&["size"]).unwrap_or_default();
+    let mut parts = size.split_whitespace().map(|p| p.parse().ok());
+    match (parts.next().flatten(), parts.next().flatten()) {
+        (Some(rows), Some(cols)) if rows > 0 && cols > 0 => (rows, cols),
+        _ => (24, 80),
+    }
+}
+
+fn read_key() -> io::Result<Key> {
+    let mut stdin = io::stdin().lock();
+    let mut byte = [0u8; 1];
+    stdin.read_exact(&mut byte)?;
+    Ok(match byte[0] {
+        b'\r' | b'\n' => Key::Enter,
+        b'\t' => Key::Tab,
+        0x03 => Key::Char('q'),
+        0x1b => {
+            let mut seq = [0u8; 2];
+            stdin.read_exact(&mut seq)?;
+            match seq {
+                [b'[', b'A'] => Key::Up,
+                [b'[', b'B'] => Key::Down,
+                [b'[', b'C'] => Key::Right,
+                [b'[', b'D'] => Key::Left,
+                [b'[', b'Z'] => Key::BackTab,
+                _ => Key::Other,
+            }
+        }
+        b if b.is_ascii_graphic() => Key::Char(b as char),
+        _ => Key::Other,
+    })
+}
@@ -0,0 +1,91 @@
+use crate::ignore::GitIgnore;
+use crate::scanner::is_ignored_dir;
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+/// How often the tree is checked for changes.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long the tree must stay unchanged before a change is reported, so a
+/// save that touches several files (or an editor's write-then-rename)
+/// triggers one re-scan.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Fingerprint of every file's path, size, and modification time.
+///
+/// Covers hidden files and directories (config, workflows) except `.git`,
+/// and skips gitignored and vendored paths, so build output doesn't
+/// trigger re-scans.
+pub fn fingerprint(root: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    let mut ignore = GitIgnore::load(root);
+    hash_dir(root, "", &mut ignore, &mut hasher, 0);
+    hasher.finish()
+}
+
+fn hash_dir(
+    root: &Path,
+    rel_dir: &str,
+    ignore: &mut GitIgnore,
+    hasher: &mut DefaultHasher,
+    depth: usize,
+) {
+    if depth > 10 {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(root.join(rel_dir)) else {
+        return;
+    };
+    let mark = (depth > 0).then(|| ignore.enter(root, rel_dir));
+    let mut entries: Vec<_> = entries.flatten().collect();
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == ".git" || is_ignored_dir(&name) {
+            continue;
+        }
+        let rel = if rel_dir.is_empty() {
+            name
+        } else {
+            format!("{}/{}", rel_dir, name)
+        };
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if ignore.is_ignored(&rel, meta.is_dir()) {
+            continue;
+        }
+        if meta.is_dir() {
+            hash_dir(root, &rel, ignore, hasher, depth + 1);
+        } else {
+            rel.hash(hasher);
+            meta.len().hash(hasher);
+            meta.modified().ok().hash(hasher);
+        }
+    }
+    if let Some(mark) = mark {
+        ignore.leave(mark);
+    }
+}
+
+/// Block until the tree differs from `last` and has settled for `DEBOUNCE`.
+/// Returns the new fingerprint.
+pub fn wait_for_change(root: &Path, last: u64) -> u64 {
+    let mut current = last;
+    while current == last {
+        thread::sleep(POLL_INTERVAL);
+        current = fingerprint(root);
+    }
+    loop {
+        thread::sleep(DEBOUNCE);
+        let settled = fingerprint(root);
+        if settled == current {
+            return settled;
+        }
+        current = settled;
+    }
+}
@@ -0,0 +1,331 @@
+use crate::yaml::{self, Node, Value};
+use serde::Serialize;
+
+/// Steps and commands that publish something outside the run: artifacts,
+/// releases, packages, or images. Matched against the lowercased workflow.
+const PUBLISH_MARKERS: &[&str] = &[
+    "actions/upload-artifact",
+    "actions/upload-release-asset",
+    "softprops/action-gh-release",
+    "gh release create",
+    "gh release upload",
+    "cargo publish",
+    "npm publish",
+    "yarn publish",
+    "pnpm publish",
+    "twine upload",
+    "pypa/gh-action-pypi-publish",
+    "docker push",
+    "docker/build-push-action",
+    "goreleaser",
+];
+
+/// One GitHub Actions workflow: what triggers it and what its token may do.
+#[derive(Debug, Clone, Serialize)]
+pub struct Workflow {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Events under `on:`, in file order.
+    pub triggers: Vec<String>,
+    /// `cron` expressions under `on.schedule`.
+    pub schedules: Vec<String>,
+    /// Top-level `permissions`, summarized (`default` when absent).
+    pub permissions: String,
+    /// Jobs that set their own `permissions`.
+    pub job_permissions: Vec<JobPermissions>,
+    /// The token can write everything: `write-all` is set, or a job has no
+    /// `permissions` block at either level, so the repository default
+    /// (write-all on repositories created before 2023) applies.
+    pub write_all: bool,
+    /// Line granting the write-all token: `permissions: write-all`, or the
+    /// first job without a `permissions` block.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_all_line: Option<usize>,
+    /// Runs on `push` to every branch (no `branches` filter).
+    pub push_all_branches: bool,
+    /// Line of the `push` trigger.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_line: Option<usize>,
+    /// Publishing steps found, such as `actions/upload-artifact`.
+    pub publishes: Vec<String>,
+    /// Actions and reusable workflows used by tag or branch instead of a
+    /// commit SHA.
+    pub unpinned_actions: Vec<ActionRef>,
+}
+
+/// A `uses:` reference and the line it's on.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionRef {
+    pub action: String,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobPermissions {
+    pub job: String,
+    pub permissions: String,
+}
+
+impl Workflow {
+    /// Summarize the workflow in `content`, or `None` if it isn't valid YAML.
+    pub fn parse(file: &str, content: &str) -> Option<Self> {
+        let root = yaml::parse(content).ok()?;
+        let on = root.get("on");
+        let triggers = on.map(events).unwrap_or_default();
+        let schedules = on
+            .and_then(|on| on.get("schedule"))
+            .and_then(Node::as_seq)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|s| s.get("cron").and_then(Node::as_str))
+            .map(String::from)
+            .collect();
+        let push_line = triggers
+            .iter()
+            .any(|t| t == "push")
+            .then(|| on.and_then(|on| on.get("push")).or(on).map(|n| n.line))
+            .flatten();
+        let push_all_branches = push_line.is_some()
+            && match on.and_then(|on| on.get("push")) {
+                Some(push) => match (push.get("branches"), push.get("branches-ignore")) {
+                    (Some(branches), _) => matches_every_branch(branches),
+                    (None, Some(_)) => false,
+                    // A tags-only filter never runs for branch pushes
+                    (None, None) => push.get("tags").is_none() && push.get("tags-ignore").is_none(),
+                },
+                None => true,
+            };
+        let mut job_permissions = Vec::new();
+        let mut write_all_job = None;
+        let mut default_job = None;
+        let mut unpinned_actions = Vec::new();
+        let mut jobs = 0;
+        if let Some(Value::Map(entries)) = root.get("jobs").map(|j| &j.value) {
+            jobs = entries.len();
+            for (job, node) in entries {
+                match node.get("permissions") {
+                    Some(p) => {
+                        let permissions = summarize(p);
+                        if permissions == "write-all" {
+                            write_all_job.get_or_insert(p.line);
+                        }
+                        job_permissions.push(JobPermissions {
+                            job: job.clone(),
+                            permissions,
+                        });
+                    }
+                    None => {
+                        default_job.get_or_insert(node.line);
+                    }
+                }
+                // Reusable workflows are called with `uses` on the job
+                let steps = node.get("steps").and_then(Node::as_seq).unwrap_or_default();
+                let uses = std::iter::once(node)
+                    .chain(steps)
+                    .filter_map(|n| n.get("uses"));
+                for u in uses {
+                    if let Some(action) = u.as_str().filter(|a| !is_pinned(a)) {
+                        unpinned_actions.push(ActionRef {
+                            action: action.to_string(),
+                            line: u.line,
+                        });
+                    }
+                }
+            }
+        }
+        let top = root.get("permissions");
+        let write_all_line = match top {
+            Some(p) if p.as_str() == Some("write-all") => Some(p.line),
+            Some(_) => write_all_job,
+            // Jobs without their own block fall back to the default
+            None if jobs == 0 => Some(1),
+            None => write_all_job.or(default_job),
+        };
+        let text = content.to_lowercase();
+        let publishes = PUBLISH_MARKERS
+            .iter()
+            .filter(|m| text.contains(*m))
+            .map(|m| m.to_string())
+            .collect();
+        Some(Workflow {
+            file: file.to_string(),
+            name: root.get("name").and_then(Node::as_str).map(String::from),
+            triggers,
+            schedules,
+            permissions: top.map_or_else(|| "default".to_string(), summarize),
+            job_permissions,
+            write_all: write_all_line.is_some(),
+            write_all_line,
+            push_all_branches,
+            push_line,
+            publishes,
+            unpinned_actions,
+        })
+    }
+
+    /// Effective permissions for display: the top level, then job overrides.
+    pub fn permissions_summary(&self) -> String {
+        let mut summary = self.permissions.clone();
+        for j in &self.job_permissions {
+            summary.push_str(&format!("; {}: {}", j.job, j.permissions));
+        }
+        summary
+    }
+}
+
+/// Event names under `on:` (a string, a list, or a mapping).
+fn events(on: &Node) -> Vec<String> {
+    match &on.value {
+        Value::Scalar(s) => vec![s.clone()],
+        Value::Seq(items) => items
+            .iter()
+            .filter_map(Node::as_str)
+            .map(String::from)
+            .collect(),
+        Value::Map(entries) => entries.iter().map(|(k, _)| k.clone()).collect(),
+        Value::Null => Vec::new(),
+    }
+}
+
+/// `permissions` as written: `read-all`, `none` for `{}`, or `contents: read, ...`.
+fn summarize(permissions: &Node) -> String {
+    match &permissions.value {
+        Value::Scalar(s) => s.clone(),
+        Value::Map(entries) if entries.is_empty() => "none".to_string(),
+        Value::Map(entries) => entries
+            .iter()
+            .map(|(scope, level)| format!("{}: {}", scope, level.as_str().unwrap_or("?")))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Value::Seq(_) | Value::Null => "none".to_string(),
+    }
+}
+
+/// A `uses` reference pinned to a full commit SHA (or a digest for Docker
+/// images). Local actions (`./path`) are part of the repository.
+fn is_pinned(uses: &str) -> bool {
+    if uses.starts_with("./") {
+        return true;
+    }
+    if let Some(image) = uses.strip_prefix("docker://") {
+        return image.contains("@sha256:");
+    }
+    uses.rsplit_once('@')
+        .is_some_and(|(_, r)| r.len() == 40 && r.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+/// A `branches` filter that matches every branch (`**`).
+fn matches_every_branch(branches: &Node) -> bool {
+    match &branches.value {
+        Value::Scalar(s) => s == "**",
+        Value::Seq(items) => items.iter().any(|b| b.as_str() == Some("**")),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Workflow;
+
+    fn parse(content: &str) -> Workflow {
+        Workflow::parse(".github/workflows/ci.yml", content).unwrap()
+    }
+
+    #[test]
+    fn triggers_and_schedules() {
+        let wf = parse(
+            "name: CI\non:\n  push:\n    branches: [main]\n  pull_request:\n  schedule:\n    - cron: '0 3 * * 1'\njobs:\n  test:\n    runs-on: ubuntu-latest\n",
+        );
+        assert_eq!(wf.name.as_deref(), Some("CI"));
+        assert_eq!(wf.triggers, ["push", "pull_request", "schedule"]);
+        assert_eq!(wf.schedules, ["0 3 * * 1"]);
+        assert!(!wf.push_all_branches);
+        assert_eq!(
+            parse("on: [push, pull_request]\n").triggers,
+            ["push", "pull_request"]
+        );
+        assert_eq!(parse("on: push\n").triggers, ["push"]);
+    }
+
+    #[test]
+    fn push_branch_filters() {
+        assert!(parse("on: push\n").push_all_branches);
+        assert!(parse("on:\n  push:\n    branches: ['**']\n").push_all_branches);
+        assert!(!parse("on:\n  push:\n    tags: ['v*']\n").push_all_branches);
+        assert!(!parse("on:\n  push:\n    branches-ignore: [wip]\n").push_all_branches);
+        assert!(!parse("on: pull_request\n").push_all_branches);
+    }
+
+    #[test]
+    fn permissions() {
+        let wf = parse(
+            "on: push\npermissions:\n  contents: read\njobs:\n  release:\n    permissions:\n      contents: write\n      packages: write\n",
+        );
+        assert_eq!(wf.permissions, "contents: read");
+        assert!(!wf.write_all);
+        assert_eq!(
+            wf.permissions_summary(),
+            "contents: read; release: contents: write, packages: write"
+        );
+        assert_eq!(parse("on: push\npermissions: {}\n").permissions, "none");
+        assert!(parse("on: push\npermissions: write-all\n").write_all);
+    }
+
+    #[test]
+    fn default_token_permissions() {
+        // One job without its own block inherits the repository default
+        let wf = parse(
+            "on: push\njobs:\n  a:\n    permissions: read-all\n  b:\n    runs-on: ubuntu-latest\n",
+        );
+        assert_eq!(wf.permissions, "default");
+        assert!(wf.write_all);
+        let wf = parse("on: push\njobs:\n  a:\n    permissions: read-all\n");
+        assert!(!wf.write_all);
+    }
+
+    #[test]
+    fn publishing_steps() {
+        let wf = parse(
+            "on: push\njobs:\n  build:\n    steps:\n      - uses: actions/upload-artifact@v4\n      - run: Cargo Publish --locked\n",
+        );
+        assert_eq!(wf.publishes, ["actions/upload-artifact", "cargo publish"]);
+        assert!(Workflow::parse("ci.yml", "on:\n\tpush:\n").is_none());
+    }
+
+    #[test]
+    fn unpinned_actions() {
+        let wf = parse(
+            "on: push\njobs:\n  test:\n    steps:\n      - uses: actions/checkout@v4\n      - uses: actions/cache@0c45773b623bea8c8e75f6c82b208c3cf94ea4f9\n      - uses: ./.github/actions/setup\n      - uses: docker://alpine:3.20\n  call:\n    uses: octo/ci/.github/workflows/lint.yml@main\n",
+        );
+        let unpinned: Vec<(&str, usize)> = wf
+            .unpinned_actions
+            .iter()
+            .map(|a| (a.action.as_str(), a.line))
+            .collect();
+        assert_eq!(
+            unpinned,
+            [
+                ("actions/checkout@v4", 5),
+                ("docker://alpine:3.20", 8),
+                ("octo/ci/.github/workflows/lint.yml@main", 10)
+            ]
+        );
+    }
+
+    #[test]
+    fn finding_lines() {
+        let wf = parse("name: CI\non: push\npermissions: write-all\n");
+        assert_eq!((wf.push_line, wf.write_all_line), (Some(2), Some(3)));
+        let wf = parse(
+            "on:\n  pull_request:\npermissions:\n  contents: read\njobs:\n  a:\n    permissions: write-all\n",
+        );
+        assert_eq!((wf.push_line, wf.write_all_line), (None, Some(7)));
+        let wf = parse("on: push\njobs:\n  a:\n    runs-on: ubuntu-latest\n");
+        assert_eq!(wf.write_all_line, Some(4));
+        assert_eq!(
+            parse("on: pull_request\npermissions: {}\n").write_all_line,
+            None
+        );
+    }
+}
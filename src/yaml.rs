@@ -0,0 +1,414 @@
+use crate::ci::yaml_key;
+
+/// A parsed YAML value with the line it starts on.
+///
+/// Covers the block subset used by GitHub config files: mappings,
+/// sequences (including `- key: value` items), plain and quoted scalars,
+/// block scalars (`|`, `>`), single-line flow sequences (`[a, b]`), and
+/// empty flow mappings (`{}`).
+/// Anchors, tags, multi-line flow collections, and multiple documents are
+/// not supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    /// 1-based line number.
+    pub line: usize,
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Scalar(String),
+    Seq(Vec<Node>),
+    Map(Vec<(String, Node)>),
+}
+
+impl Node {
+    /// Value of `key` in a mapping.
+    pub fn get(&self, key: &str) -> Option<&Node> {
+        match &self.value {
+            Value::Map(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.value {
+            Value::Scalar(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_seq(&self) -> Option<&[Node]> {
+        match &self.value {
+            Value::Seq(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    /// Whether the node is a non-empty scalar.
+    pub fn is_text(&self) -> bool {
+        self.as_str().is_some_and(|s| !s.trim().is_empty())
+    }
+
+    pub fn is_true(&self) -> bool {
+        self.as_str() == Some("true")
+    }
+}
+
+/// One non-blank, non-comment line.
+struct Line<'a> {
+    number: usize,
+    indent: usize,
+    text: &'a str,
+}
+
+/// Parse a YAML document. Errors read `line N: ...`.
+pub fn parse(content: &str) -> Result<Node, String> {
+    let raw: Vec<&str> = content.lines().collect();
+    let mut lines = Vec::new();
+    for (i, l) in raw.iter().enumerate() {
+        let text = l.trim_start_matches(' ');
+        if text.starts_with('\t') {
+            return Err(format!(
+                "line {}: tabs are not allowed in indentation",
+                i + 1
+            ));
+        }
+        let text = strip_comment(text).trim_end();
+        if text.is_empty() || (i == 0 && text == "---") {
+            continue;
+        }
+        lines.push(Line {
+            number: i + 1,
+            indent: l.len() - l.trim_start_matches(' ').len(),
+            text,
+        });
+    }
+    let mut parser = Parser {
+        raw: &raw,
+        lines,
+        pos: 0,
+    };
+    let Some(first) = parser.lines.first() else {
+        return Ok(Node {
+            line: 1,
+            value: Value::Null,
+        });
+    };
+    let node = parser.block(first.indent)?;
+    match parser.lines.get(parser.pos) {
+        Some(extra) => Err(format!("line {}: unexpected indentation", extra.number)),
+        None => Ok(node),
+    }
+}
+
+struct Parser<'a> {
+    raw: &'a [&'a str],
+    lines: Vec<Line<'a>>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn block(&mut self, indent: usize) -> Result<Node, String> {
+        let line = &self.lines[self.pos];
+        if is_item(line.text) {
+            self.seq(indent)
+        } else if yaml_key(line.text).is_some() {
+            self.map(indent)
+        } else {
+            let node = scalar(line.number, line.text)?;
+            self.pos += 1;
+            Ok(node)
+        }
+    }
+
+    fn seq(&mut self, indent: usize) -> Result<Node, String> {
+        let start = self.lines[self.pos].number;
+        let mut items = Vec::new();
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.indent != indent || !is_item(line.text) {
+                break;
+            }
+            let rest = line.text[1..].trim_start();
+            let number = line.number;
+            if rest.is_empty() {
+                self.pos += 1;
+                items.push(self.nested(indent, number)?);
+            } else if yaml_key(rest).is_some() || is_item(rest) {
+                // `- key: value` opens a mapping indented to the key
+                let offset = line.text.len() - rest.len();
+                self.lines[self.pos].indent = indent + offset;
+                self.lines[self.pos].text = rest;
+                items.push(self.block(indent + offset)?);
+            } else {
+                self.pos += 1;
+                items.push(self.value(number, indent, rest)?);
+            }
+        }
+        Ok(Node {
+            line: start,
+            value: Value::Seq(items),
+        })
+    }
+
+    fn map(&mut self, indent: usize) -> Result<Node, String> {
+        let start = self.lines[self.pos].number;
+        let mut entries: Vec<(String, Node)> = Vec::new();
+        while let Some(line) = self.lines.get(self.pos) {
+            if line.indent < indent {
+                break;
+            }
+            if line.indent > indent {
+                return Err(format!("line {}: unexpected indentation", line.number));
+            }
+            if is_item(line.text) {
+                break;
+            }
+            let number = line.number;
+            let Some((key, value)) = yaml_key(line.text) else {
+                return Err(format!("line {}: expected `key: value`", number));
+            };
+            if entries.iter().any(|(k, _)| k == key) {
+                return Err(format!("line {}: duplicate key `{}`", number, key));
+            }
+            self.pos += 1;
+            let node = if value.is_empty() {
+                // A sequence may sit at the key's own indentation
+                match self.lines.get(self.pos) {
+                    Some(next) if next.indent == indent && is_item(next.text) => {
+                        self.seq(indent)?
+                    }
+                    _ => self.nested(indent, number)?,
+                }
+            } else {
+                self.value(number, indent, value)?
+            };
+            entries.push((key.to_string(), node));
+        }
+        Ok(Node {
+            line: start,
+            value: Value::Map(entries),
+        })
+    }
+
+    /// The block below a line with no inline value, or null.
+    fn nested(&mut self, indent: usize, number: usize) -> Result<Node, String> {
+        match self.lines.get(self.pos) {
+            Some(next) if next.indent > indent => self.block(next.indent),
+            _ => Ok(Node {
+                line: number,
+                value: Value::Null,
+            }),
+        }
+    }
+
+    /// An inline value on line `number`, reading block scalar lines after it.
+    fn value(&mut self, number: usize, indent: usize, text: &str) -> Result<Node, String> {
+        if !text.starts_with(['|', '>']) {
+            return scalar(number, text);
+        }
+        let folded = text.starts_with('>');
+        let mut body = Vec::new();
+        while self.lines.get(self.pos).is_some_and(|l| l.indent > indent) {
+            self.pos += 1;
+        }
+        // Take the raw lines so `#` inside the block is kept
+        let end = self
+            .lines
+            .get(self.pos)
+            .map_or(self.raw.len(), |l| l.number - 1);
+        let mut block = &self.raw[number..end];
+        // Comment lines between the block and the next key aren't part of it
+        while let Some((last, rest)) = block.split_last() {
+            if last.trim().is_empty() || last.len() - last.trim_start().len() > indent {
+                break;
+            }
+            block = rest;
+        }
+        let margin = block
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        for l in block {
+            body.push(l.get(margin..).unwrap_or("").trim_end());
+        }
+        while body.last().is_some_and(|l| l.is_empty()) {
+            body.pop();
+        }
+        let joined = body.join(if folded { " " } else { "\n" });
+        Ok(Node {
+            line: number,
+            value: Value::Scalar(joined),
+        })
+    }
+}
+
+fn is_item(text: &str) -> bool {
+    text == "-" || text.starts_with("- ")
+}
+
+/// A single-line scalar or flow sequence.
+fn scalar(number: usize, text: &str) -> Result<Node, String> {
+    let value = if let Some(inner) = text.strip_prefix('[') {
+        let inner = inner
+            .strip_suffix(']')
+            .ok_or_else(|| format!("line {}: unterminated `[`", number))?;
+        let items = inner
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| scalar(number, s))
+            .collect::<Result<_, _>>()?;
+        Value::Seq(items)
+    } else if text == "{}" {
+        Value::Map(Vec::new())
+    } else if text.starts_with('{') {
+        return Err(format!("line {}: flow mappings are not supported", number));
+    } else if text == "~" || text == "null" {
+        Value::Null
+    } else {
+        Value::Scalar(unquote(number, text)?)
+    };
+    Ok(Node {
+        line: number,
+        value,
+    })
+}
+
+fn unquote(number: usize, text: &str) -> Result<String, String> {
+    for quote in ['"', '\''] {
+        if let Some(rest) = text.strip_prefix(quote) {
+            return match rest.strip_suffix(quote) {
+                Some(inner) if quote == '\'' => Ok(inner.replace("''", "'")),
+                Some(inner) => Ok(inner.replace("\\\"", "\"")),
+                None => Err(format!("line {}: unterminated {} quote", number, quote)),
+            };
+        }
+    }
+    Ok(text.to_string())
+}
+
+/// Drop a trailing ` # comment` outside quotes.
+fn strip_comment(text: &str) -> &str {
+    if text.starts_with('#') {
+        return "";
+    }
+    let mut quote = None;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            // Quotes only open a scalar, not an apostrophe inside one
+            None if (c == '"' || c == '\'') && (prev.is_whitespace() || prev == '[') => {
+                quote = Some(c)
+            }
+            None if c == '#' && prev.is_whitespace() => return &text[..i],
+            None => {}
+        }
+        prev = c;
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, Node, Value};
+
+    fn doc(text: &str) -> Node {
+        parse(text).expect("document should parse")
+    }
+
+    #[test]
+    fn mappings_and_sequences() {
+        let root =
+            doc("name: CI\non:\n  push:\n    branches: [main, dev]\njobs:\n  - build\n  - test\n");
+        assert_eq!(root.get("name").and_then(Node::as_str), Some("CI"));
+        let branches = root
+            .get("on")
+            .and_then(|n| n.get("push"))
+            .and_then(|n| n.get("branches"))
+            .and_then(Node::as_seq)
+            .expect("flow sequence");
+        let names: Vec<_> = branches.iter().filter_map(Node::as_str).collect();
+        assert_eq!(names, ["main", "dev"]);
+        let jobs = root.get("jobs").and_then(Node::as_seq).expect("sequence");
+        assert_eq!(jobs.len(), 2);
+        assert_eq!(jobs[1].line, 7);
+    }
+
+    #[test]
+    fn sequence_of_mappings() {
+        let root =
+            doc("steps:\n- uses: actions/checkout@v4\n  with:\n    fetch-depth: 0\n- run: make\n");
+        let steps = root.get("steps").and_then(Node::as_seq).expect("steps");
+        assert_eq!(steps.len(), 2);
+        assert_eq!(
+            steps[0].get("uses").and_then(Node::as_str),
+            Some("actions/checkout@v4")
+        );
+        assert_eq!(
+            steps[0]
+                .get("with")
+                .and_then(|w| w.get("fetch-depth"))
+                .and_then(Node::as_str),
+            Some("0")
+        );
+        assert_eq!(steps[1].get("run").and_then(Node::as_str), Some("make"));
+    }
+
+    #[test]
+    fn scalars_and_comments() {
+        let root = doc("a: \"x # y\"\nb: 'it''s'\nc: value # note\nd: ~\ne: {}\nf: true\n");
+        assert_eq!(root.get("a").and_then(Node::as_str), Some("x # y"));
+        assert_eq!(root.get("b").and_then(Node::as_str), Some("it's"));
+        assert_eq!(root.get("c").and_then(Node::as_str), Some("value"));
+        assert_eq!(root.get("d").map(|n| &n.value), Some(&Value::Null));
+        assert_eq!(
+            root.get("e").map(|n| &n.value),
+            Some(&Value::Map(Vec::new()))
+        );
+        assert!(root.get("f").is_some_and(Node::is_true));
+    }
+
+    #[test]
+    fn block_scalars() {
+        let root = doc("run: |\n  echo one\n  # kept\n  echo two\nfolded: >\n  a\n  b\nnext: x\n");
+        assert_eq!(
+            root.get("run").and_then(Node::as_str),
+            Some("echo one\n# kept\necho two")
+        );
+        assert_eq!(root.get("folded").and_then(Node::as_str), Some("a b"));
+        assert_eq!(root.get("next").and_then(Node::as_str), Some("x"));
+    }
+
+    #[test]
+    fn empty_document_is_null() {
+        assert_eq!(doc("# only a comment\n").value, Value::Null);
+        assert_eq!(doc("---\nkey: 1\n").get("key").map(|n| n.line), Some(2));
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        assert_eq!(
+            parse("a: 1\na: 2\n").unwrap_err(),
+            "line 2: duplicate key `a`"
+        );
+        assert_eq!(
+            parse("a:\n\tb: 1\n").unwrap_err(),
+            "line 2: tabs are not allowed in indentation"
+        );
+        assert_eq!(parse("a: [1, 2\n").unwrap_err(), "line 1: unterminated `[`");
+        assert_eq!(
+            parse("a: {b: 1}\n").unwrap_err(),
+            "line 1: flow mappings are not supported"
+        );
+        assert_eq!(
+            parse("a: 1\n  b: 2\n").unwrap_err(),
+            "line 2: unexpected indentation"
+        );
+    }
+}